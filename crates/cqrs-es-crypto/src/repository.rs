@@ -318,6 +318,22 @@ impl<R: PersistedEventRepository> CryptoShreddingEventRepository<R> {
     pub const fn inner(&self) -> &R {
         &self.inner
     }
+
+    /// Decrypt `events` read from the store other than through this
+    /// repository — by a query across aggregates, say — exactly as
+    /// [`get_events`](PersistedEventRepository::get_events) would: PII is
+    /// restored, or redacted for subjects whose key has been deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PersistenceError`] if a key cannot be read from the key
+    /// store or a payload cannot be decrypted.
+    pub async fn decrypt(
+        &self,
+        events: Vec<SerializedEvent>,
+    ) -> Result<Vec<SerializedEvent>, PersistenceError> {
+        self.decrypt_events(events).await
+    }
 }
 
 // ── Builder methods (postgres feature) ───────────────────────────────────
@@ -1385,6 +1401,24 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_decrypt_restores_events_read_from_the_inner_store() {
+        let repo = make_repo();
+        let subject_id = Uuid::new_v4();
+
+        repo.persist::<TestAggregate>(&[pii_event("agg-pii-raw", 1, subject_id)], None)
+            .await
+            .unwrap();
+
+        let raw = repo.inner().all_events();
+        assert_ne!(raw[0].payload["TestPii"]["secret"], "hunter2");
+        let events = repo.decrypt(raw).await.unwrap();
+        assert_eq!(
+            events[0].payload["TestPii"]["secret"].as_str().unwrap(),
+            "hunter2"
+        );
+    }
+
     // ── Redaction on key deletion ─────────────────────────────────────────────
 
     #[tokio::test]
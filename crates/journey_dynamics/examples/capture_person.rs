@@ -10,12 +10,8 @@
 use cqrs_es::{CqrsFramework, EventStore, mem_store::MemStore};
 use journey_dynamics::SimpleLoggingQuery;
 use journey_dynamics::domain::commands::JourneyCommand;
-use journey_dynamics::domain::{
-    AttributeSchema,
-    journey::{Journey, JourneyServices},
-};
+use journey_dynamics::domain::journey::{Journey, JourneyServices};
 use journey_dynamics::services::decision_engine::SimpleDecisionEngine;
-use journey_dynamics::services::schema_validator::NoOpValidator;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -26,13 +22,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Setup event store and decision engine
     let event_store = MemStore::<Journey>::default();
     let query = SimpleLoggingQuery {};
-    let decision_engine = Arc::new(SimpleDecisionEngine);
-    let schema_validator = Arc::new(NoOpValidator);
-    let services = JourneyServices::new(
-        decision_engine,
-        schema_validator,
-        Arc::new(AttributeSchema::permissive()),
-    );
+    let services = JourneyServices::without_validation(Arc::new(SimpleDecisionEngine));
     let cqrs = CqrsFramework::new(event_store.clone(), vec![Box::new(query)], services);
 
     // Create a new journey
@@ -42,7 +32,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Start the journey
     cqrs.execute(
         &journey_id.to_string(),
-        JourneyCommand::Start { id: journey_id },
+        JourneyCommand::Start {
+            id: journey_id,
+            journey_type: None,
+        },
     )
     .await?;
 
@@ -86,7 +79,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     cqrs.execute(
         &journey_id_2.to_string(),
-        JourneyCommand::Start { id: journey_id_2 },
+        JourneyCommand::Start {
+            id: journey_id_2,
+            journey_type: None,
+        },
     )
     .await?;
 
@@ -0,0 +1,344 @@
+//! A [`Query`] decorator that coalesces view writes.
+//!
+//! Every command dispatches its events on its own, and the view repository
+//! applies each dispatch in a transaction of its own, so a busy journey costs
+//! one small write per command. [`BatchingQuery`] instead queues dispatched
+//! events and hands them to the query it wraps once [`BatchingOptions::window`]
+//! has passed since the first was queued, or once
+//! [`BatchingOptions::max_events`] are waiting. Each journey's queued events
+//! are then dispatched together, as one transaction.
+//!
+//! A single background task owns the queue, so each journey's events reach
+//! the wrapped query in the order they were dispatched. The price is that the
+//! view lags the event store by up to the window: a read straight after a
+//! command may not see it yet. Call [`BatchingQuery::flush`] at shutdown,
+//! once no more commands can run, so nothing queued is lost.
+
+use std::time::Duration;
+
+use cqrs_es::{EventEnvelope, Query};
+use tokio::{
+    sync::{mpsc, oneshot},
+    time::Instant,
+};
+
+use crate::domain::journey::Journey;
+
+/// When a [`BatchingQuery`] flushes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchingOptions {
+    /// How long the first queued event may wait. Defaults to 50ms.
+    pub window: Duration,
+    /// How many queued events flush at once, without waiting out the window.
+    /// Defaults to 100.
+    pub max_events: usize,
+}
+
+impl Default for BatchingOptions {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_millis(50),
+            max_events: 100,
+        }
+    }
+}
+
+enum Message {
+    Dispatch {
+        aggregate_id: String,
+        events: Vec<EventEnvelope<Journey>>,
+    },
+    Flush(oneshot::Sender<()>),
+}
+
+/// Queues dispatched events and passes them on in batches; see the
+/// [module documentation](self).
+///
+/// Clones share the same queue.
+#[derive(Clone)]
+pub struct BatchingQuery {
+    sender: mpsc::UnboundedSender<Message>,
+}
+
+impl BatchingQuery {
+    /// Batch the dispatches to `inner` as `options` say.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside a Tokio runtime, which runs the flush task.
+    #[must_use]
+    pub fn new(inner: impl Query<Journey> + 'static, options: BatchingOptions) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(run(inner, options, receiver));
+        Self { sender }
+    }
+
+    /// Dispatch everything queued so far, returning once it has been applied.
+    pub async fn flush(&self) {
+        let (done, flushed) = oneshot::channel();
+        if self.sender.send(Message::Flush(done)).is_ok() {
+            flushed.await.ok();
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Query<Journey> for BatchingQuery {
+    async fn dispatch(&self, aggregate_id: &str, events: &[EventEnvelope<Journey>]) {
+        if events.is_empty() {
+            return;
+        }
+        let message = Message::Dispatch {
+            aggregate_id: aggregate_id.to_string(),
+            events: events.to_vec(),
+        };
+        if self.sender.send(message).is_err() {
+            eprintln!("Error queueing events for journey '{aggregate_id}': flush task has stopped");
+        }
+    }
+}
+
+/// Events waiting to be dispatched, grouped by journey in the order each
+/// journey was first seen.
+#[derive(Default)]
+struct Batch {
+    journeys: Vec<(String, Vec<EventEnvelope<Journey>>)>,
+    events: usize,
+}
+
+impl Batch {
+    fn push(&mut self, aggregate_id: String, events: Vec<EventEnvelope<Journey>>) {
+        self.events += events.len();
+        match self.journeys.iter_mut().find(|(id, _)| *id == aggregate_id) {
+            Some((_, queued)) => queued.extend(events),
+            None => self.journeys.push((aggregate_id, events)),
+        }
+    }
+
+    async fn flush(&mut self, inner: &impl Query<Journey>) {
+        self.events = 0;
+        for (aggregate_id, events) in std::mem::take(&mut self.journeys) {
+            inner.dispatch(&aggregate_id, &events).await;
+        }
+    }
+}
+
+/// The flush task: queue what arrives and flush when the window closes, the
+/// batch fills, a flush is asked for, or every [`BatchingQuery`] is dropped.
+async fn run(
+    inner: impl Query<Journey>,
+    options: BatchingOptions,
+    mut receiver: mpsc::UnboundedReceiver<Message>,
+) {
+    let mut batch = Batch::default();
+    let mut deadline = Instant::now();
+    loop {
+        let message = if batch.events == 0 {
+            receiver.recv().await
+        } else if let Ok(message) = tokio::time::timeout_at(deadline, receiver.recv()).await {
+            message
+        } else {
+            batch.flush(&inner).await;
+            continue;
+        };
+        match message {
+            Some(Message::Dispatch {
+                aggregate_id,
+                events,
+            }) => {
+                if batch.events == 0 {
+                    deadline = Instant::now() + options.window;
+                }
+                batch.push(aggregate_id, events);
+                if batch.events >= options.max_events {
+                    batch.flush(&inner).await;
+                }
+            }
+            Some(Message::Flush(done)) => {
+                batch.flush(&inner).await;
+                done.send(()).ok();
+            }
+            None => {
+                batch.flush(&inner).await;
+                return;
+            }
+        }
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+#[allow(deprecated)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex, PoisonError},
+    };
+
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::{
+        domain::events::JourneyEvent, memory_view_repository::InMemoryJourneyViewRepository,
+        view_repository::JourneyViewStore,
+    };
+
+    /// The journey and event sequences of each dispatch.
+    type Dispatches = Vec<(String, Vec<usize>)>;
+
+    /// An in-memory view that also records every dispatch it receives, each
+    /// standing for one transaction.
+    #[derive(Clone, Default)]
+    struct RecordingViews {
+        views: InMemoryJourneyViewRepository,
+        dispatches: Arc<Mutex<Dispatches>>,
+    }
+
+    impl RecordingViews {
+        fn dispatches(&self) -> Dispatches {
+            self.dispatches
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Query<Journey> for RecordingViews {
+        async fn dispatch(&self, aggregate_id: &str, events: &[EventEnvelope<Journey>]) {
+            self.dispatches
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push((
+                    aggregate_id.to_string(),
+                    events.iter().map(|event| event.sequence).collect(),
+                ));
+            self.views.dispatch(aggregate_id, events).await;
+        }
+    }
+
+    fn envelope(
+        journey_id: Uuid,
+        sequence: usize,
+        payload: JourneyEvent,
+    ) -> EventEnvelope<Journey> {
+        EventEnvelope {
+            aggregate_id: journey_id.to_string(),
+            sequence,
+            payload,
+            metadata: HashMap::default(),
+        }
+    }
+
+    /// A journey started and then captured at `origins`, one event each.
+    fn journey_events(journey_id: Uuid, origins: &[&str]) -> Vec<EventEnvelope<Journey>> {
+        let started = envelope(
+            journey_id,
+            1,
+            JourneyEvent::Started {
+                id: journey_id,
+                journey_type: None,
+            },
+        );
+        let captures = origins.iter().enumerate().map(|(i, origin)| {
+            envelope(
+                journey_id,
+                i + 2,
+                JourneyEvent::Modified {
+                    step: "search".to_string(),
+                    data: json!({ "origin": origin }),
+                },
+            )
+        });
+        std::iter::once(started).chain(captures).collect()
+    }
+
+    /// Wait for `views` to receive a dispatch, without asking for a flush.
+    async fn until_dispatched(views: &RecordingViews) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while views.dispatches().is_empty() {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("nothing was dispatched");
+    }
+
+    fn options(window: Duration, max_events: usize) -> BatchingOptions {
+        BatchingOptions { window, max_events }
+    }
+
+    #[tokio::test]
+    async fn rapid_events_are_applied_in_one_batch() {
+        let views = RecordingViews::default();
+        let query = BatchingQuery::new(views.clone(), options(Duration::from_mins(1), 100));
+        let journey_id = Uuid::new_v4();
+        let id = journey_id.to_string();
+
+        for event in journey_events(journey_id, &["LHR", "LGW", "STN", "LTN"]) {
+            query.dispatch(&id, &[event]).await;
+        }
+        assert!(views.dispatches().is_empty());
+        query.flush().await;
+
+        assert_eq!(views.dispatches(), vec![(id, vec![1, 2, 3, 4, 5])]);
+        let view = views.views.load(&journey_id).await.unwrap().unwrap();
+        assert_eq!(view.shared_data, json!({ "origin": "LTN" }));
+    }
+
+    #[tokio::test]
+    async fn journeys_are_dispatched_separately_and_in_order() {
+        let views = RecordingViews::default();
+        let query = BatchingQuery::new(views.clone(), options(Duration::from_mins(1), 100));
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        let first_events = journey_events(first, &["LHR"]);
+        let second_events = journey_events(second, &["JFK"]);
+
+        for (a, b) in first_events.into_iter().zip(second_events) {
+            query.dispatch(&first.to_string(), &[a]).await;
+            query.dispatch(&second.to_string(), &[b]).await;
+        }
+        query.flush().await;
+
+        assert_eq!(
+            views.dispatches(),
+            vec![
+                (first.to_string(), vec![1, 2]),
+                (second.to_string(), vec![1, 2])
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn a_full_batch_flushes_without_waiting() {
+        let views = RecordingViews::default();
+        let query = BatchingQuery::new(views.clone(), options(Duration::from_mins(1), 3));
+        let journey_id = Uuid::new_v4();
+        let events = journey_events(journey_id, &["LHR", "LGW"]);
+
+        query.dispatch(&journey_id.to_string(), &events[..2]).await;
+        query.dispatch(&journey_id.to_string(), &events[2..]).await;
+        until_dispatched(&views).await;
+
+        assert_eq!(
+            views.dispatches(),
+            vec![(journey_id.to_string(), vec![1, 2, 3])]
+        );
+    }
+
+    #[tokio::test]
+    async fn the_window_closing_flushes() {
+        let views = RecordingViews::default();
+        let query = BatchingQuery::new(views.clone(), options(Duration::from_millis(20), 100));
+        let journey_id = Uuid::new_v4();
+
+        query
+            .dispatch(&journey_id.to_string(), &journey_events(journey_id, &[]))
+            .await;
+        until_dispatched(&views).await;
+
+        assert_eq!(views.dispatches(), vec![(journey_id.to_string(), vec![1])]);
+    }
+}
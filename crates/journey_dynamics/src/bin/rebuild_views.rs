@@ -0,0 +1,130 @@
+//! One-shot projection rebuild CLI.
+//!
+//! Connects to the database, replays every journey's events — or only those of
+//! the journeys named on the command line — into the view tables, prints
+//! statistics, and exits.
+//!
+//! # Usage
+//!
+//! ```text
+//! cargo run --bin rebuild_views                       # every journey
+//! cargo run --bin rebuild_views -- <journey_id> ...   # selected journeys
+//! ```
+//!
+//! Set the same environment variables used by the main server:
+//!
+//! ```text
+//! DATABASE_URL=postgres://...
+//!
+//! # Multi-version schema (for rotation):
+//! JOURNEY_KEK_PRIMARY=v2
+//! JOURNEY_KEK_v1=<base64>
+//! JOURNEY_KEK_v2=<base64>
+//!
+//! # Legacy single-variable schema (backwards-compatible):
+//! JOURNEY_KEK=<base64>
+//!
+//! # With the `encryption` feature, if the service encrypts view PII:
+//! JOURNEY_VIEW_ENCRYPTION_KEY=<base64>
+//! ```
+//!
+//! The KEK is needed to decrypt PII while replaying; subjects that have been
+//! forgotten replay as redacted, just as they do for the live service. Safe to
+//! run against a live service: each journey is rebuilt in its own transaction.
+//!
+//! Exits with code 0 on success, or 1 if an argument is not a UUID or any
+//! journey failed to rebuild.
+
+use std::sync::Arc;
+
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use cqrs_es_crypto::{
+    KekProvider, KeyStore, PostgresKeyStore, PostgresKeyStoreOptions, StaticKekProvider,
+};
+use journey_dynamics::{
+    config::event_history_store, rebuild::rebuild_views,
+    view_repository::StructuredJourneyViewRepository,
+};
+use postgres_es::default_postgress_pool;
+use uuid::Uuid;
+
+#[tokio::main]
+async fn main() {
+    // Load .env file if present — same behaviour as the main server.
+    dotenv::dotenv().ok();
+
+    let journey_ids = match std::env::args()
+        .skip(1)
+        .map(|arg| Uuid::parse_str(&arg).map_err(|e| format!("'{arg}': {e}")))
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(ids) if ids.is_empty() => None,
+        Ok(ids) => Some(ids),
+        Err(e) => {
+            eprintln!("ERROR: invalid journey id {e}");
+            std::process::exit(1);
+        }
+    };
+
+    let database_url =
+        std::env::var("DATABASE_URL").expect("DATABASE_URL environment variable must be set");
+
+    println!("Connecting to database…");
+    let pool = default_postgress_pool(&database_url).await;
+
+    println!("Running migrations…");
+    sqlx::migrate!("../../migrations")
+        .run(&pool)
+        .await
+        .expect("Database migrations failed");
+
+    // Build the KEK provider — mirrors the logic in state.rs.
+    let provider: Arc<dyn KekProvider> = if std::env::var("JOURNEY_KEK_PRIMARY").is_ok() {
+        Arc::new(
+            StaticKekProvider::from_env("JOURNEY_KEK")
+                .expect("JOURNEY_KEK_PRIMARY / JOURNEY_KEK_<id> env config is invalid"),
+        )
+    } else {
+        let kek_b64 = std::env::var("JOURNEY_KEK")
+            .expect("Either JOURNEY_KEK_PRIMARY or JOURNEY_KEK must be set");
+        let kek = BASE64
+            .decode(kek_b64.trim())
+            .expect("JOURNEY_KEK must be valid base64");
+        Arc::new(
+            StaticKekProvider::single("legacy:v1", kek)
+                .expect("JOURNEY_KEK must decode to exactly 32 bytes"),
+        )
+    };
+
+    // Read-only use: leave re-wrapping to the server and the rewrap CLI.
+    let key_store: Arc<dyn KeyStore> = Arc::new(PostgresKeyStore::new_with_options(
+        pool.clone(),
+        provider,
+        &PostgresKeyStoreOptions { lazy_rewrap: false },
+    ));
+    let event_store = event_history_store(pool.clone(), key_store);
+    let view_repo = StructuredJourneyViewRepository::new(pool);
+    // Rebuilt rows must be encrypted exactly as the live service writes them.
+    #[cfg(feature = "encryption")]
+    let view_repo = match journey_dynamics::state::view_pii_cipher() {
+        Some(pii_cipher) => view_repo.with_pii_cipher(pii_cipher),
+        None => view_repo,
+    };
+
+    match &journey_ids {
+        Some(ids) => println!("Rebuilding {} journey view(s)…", ids.len()),
+        None => println!("Rebuilding every journey view…"),
+    }
+    match rebuild_views(event_store.as_ref(), &view_repo, journey_ids).await {
+        Ok(stats) => {
+            println!(
+                "Rebuild complete — journeys: {}, events replayed: {}",
+                stats.journeys, stats.events
+            );
+        }
+        Err(e) => {
+            eprintln!("ERROR: {e}");
+            std::process::exit(1);
+        }
+    }
+}
@@ -1,18 +1,24 @@
 use axum::{
     body::{Body, Bytes},
-    extract::FromRequest,
-    http::{Request, StatusCode},
+    extract::{FromRequest, OptionalFromRequestParts, Path},
+    http::{HeaderMap, Request, StatusCode, Uri},
     response::{IntoResponse, Response},
 };
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt};
+use uuid::Uuid;
 
-use crate::domain::{commands::JourneyCommand, flatten};
+use crate::domain::{
+    commands::{JOURNEY_KEY_NAMESPACE, JourneyCommand},
+    events::{ACTOR_METADATA_KEY, COMMAND_METADATA_KEY, EVENT_TIME_METADATA_KEY},
+    flatten,
+};
 
 // This is a custom Axum extension that builds metadata from the inbound request
 // and parses and deserializes the body as the command payload.
 pub struct CommandExtractor(pub HashMap<String, String>, pub JourneyCommand);
 
 const USER_AGENT_HDR: &str = "User-Agent";
+const ACTOR_HDR: &str = "X-Actor";
 
 impl<S> FromRequest<S> for CommandExtractor
 where
@@ -21,40 +27,111 @@ where
     type Rejection = CommandExtractionError;
 
     async fn from_request(req: Request<Body>, state: &S) -> Result<Self, Self::Rejection> {
-        let uri_path = req.uri().path().to_string();
-
-        // Here we are including the current date/time, the uri that was called and the user-agent
-        // in a HashMap that we will submit as metadata with the command.
-        let mut metadata = HashMap::default();
-        metadata.insert("time".to_string(), chrono::Utc::now().to_rfc3339());
-        metadata.insert("uri".to_string(), req.uri().to_string());
-        if let Some(user_agent) = req.headers().get(USER_AGENT_HDR)
-            && let Ok(value) = user_agent.to_str()
-        {
-            metadata.insert(USER_AGENT_HDR.to_string(), value.to_string());
-        }
+        let (mut parts, body) = req.into_parts();
+
+        // `None` for `POST /journeys`, the journey id for `POST /journeys/{journey_id}`.
+        let path_id =
+            <Path<Uuid> as OptionalFromRequestParts<S>>::from_request_parts(&mut parts, state)
+                .await
+                .map_err(|rejection| CommandExtractionError::new(rejection.body_text()))?
+                .map(|Path(id)| id);
+
+        let mut metadata = command_metadata(&parts.uri, &parts.headers);
+        let key = creation_key(&parts.uri)?;
 
         // Parse and deserialize the request body as the command payload.
-        let body = Bytes::from_request(req, state).await?;
+        let body = Bytes::from_request(Request::from_parts(parts, body), state).await?;
         let command: JourneyCommand = if body.is_empty() {
             // Only generate a Start command for journey creation (POST /journeys)
             // If posting to a specific journey (POST /journeys/{id}), empty body is invalid
-            if uri_path == "/journeys" {
-                let id = uuid::Uuid::new_v4();
-                JourneyCommand::Start { id }
-            } else {
-                return Err(CommandExtractionError);
+            if path_id.is_some() {
+                return Err(CommandExtractionError::new(
+                    "request body is empty; expected a journey command",
+                ));
             }
+            // A client-supplied key names the journey, so a retried create
+            // is rejected as already started rather than duplicated.
+            key.map_or_else(
+                || JourneyCommand::Start {
+                    id: Uuid::new_v4(),
+                    journey_type: None,
+                },
+                |name| JourneyCommand::StartDeterministic {
+                    namespace: JOURNEY_KEY_NAMESPACE,
+                    name,
+                    journey_type: None,
+                },
+            )
         } else {
-            let mut raw: serde_json::Value = serde_json::from_slice(&body)?;
-            normalize_set_attributes(&mut raw);
-            serde_json::from_value(raw)?
+            parse_command(&body, path_id)?
         };
 
+        metadata.insert(COMMAND_METADATA_KEY.to_string(), command.name().to_string());
         Ok(Self(metadata, command))
     }
 }
 
+/// The `key` query parameter of `uri`, if given.
+fn creation_key(uri: &Uri) -> Result<Option<String>, CommandExtractionError> {
+    let key = url::form_urlencoded::parse(uri.query().unwrap_or_default().as_bytes())
+        .find(|(name, _)| name == "key")
+        .map(|(_, key)| key.into_owned());
+    if key.as_deref() == Some("") {
+        return Err(CommandExtractionError::new("key must not be empty"));
+    }
+    Ok(key)
+}
+
+/// The metadata submitted with a command received at `uri`: the current
+/// date/time, the uri that was called, the user-agent and the actor named by
+/// the `X-Actor` header.
+pub(crate) fn command_metadata(uri: &Uri, headers: &HeaderMap) -> HashMap<String, String> {
+    let mut metadata = HashMap::default();
+    metadata.insert(
+        EVENT_TIME_METADATA_KEY.to_string(),
+        chrono::Utc::now().to_rfc3339(),
+    );
+    metadata.insert("uri".to_string(), uri.to_string());
+    if let Some(user_agent) = headers.get(USER_AGENT_HDR)
+        && let Ok(value) = user_agent.to_str()
+    {
+        metadata.insert(USER_AGENT_HDR.to_string(), value.to_string());
+    }
+    if let Some(actor) = headers.get(ACTOR_HDR)
+        && let Ok(value) = actor.to_str()
+    {
+        metadata.insert(ACTOR_METADATA_KEY.to_string(), value.to_string());
+    }
+    metadata
+}
+
+/// Deserialize a JSON command for the journey named by `path_id`, if any.
+pub(crate) fn parse_command(
+    json: &[u8],
+    path_id: Option<Uuid>,
+) -> Result<JourneyCommand, CommandExtractionError> {
+    let mut raw: serde_json::Value = serde_json::from_slice(json)?;
+    normalize_set_attributes(&mut raw);
+    let command = serde_json::from_value(raw)?;
+    check_journey_id(path_id, &command)?;
+    Ok(command)
+}
+
+/// Reject a command whose body names a different journey than the request path.
+///
+/// The start commands are the only ones that name the journey in their body.
+fn check_journey_id(
+    path_id: Option<Uuid>,
+    command: &JourneyCommand,
+) -> Result<(), CommandExtractionError> {
+    match (path_id, command.started_id()) {
+        (Some(path_id), Some(id)) if id != path_id => Err(CommandExtractionError::new(format!(
+            "journey id in body ({id}) does not match journey id in path ({path_id})"
+        ))),
+        _ => Ok(()),
+    }
+}
+
 /// Normalise the nested-form sugar for `SetAttributes` into the canonical
 /// `{ "changes": { ... } }` form before deserialisation.
 ///
@@ -109,38 +186,252 @@ fn normalize_set_attributes(value: &mut serde_json::Value) {
     }
 }
 
-pub struct CommandExtractionError;
+/// Rejection returned when a request cannot be turned into a [`JourneyCommand`].
+///
+/// Rendered as `400 Bad Request` with a message describing what was wrong.
+#[derive(Debug)]
+pub struct CommandExtractionError {
+    message: String,
+}
+
+impl CommandExtractionError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for CommandExtractionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "command could not be read: {}", self.message)
+    }
+}
 
 impl IntoResponse for CommandExtractionError {
     fn into_response(self) -> Response {
-        (
-            StatusCode::BAD_REQUEST,
-            "command could not be read".to_string(),
-        )
-            .into_response()
+        (StatusCode::BAD_REQUEST, self.to_string()).into_response()
     }
 }
 
 impl From<axum::extract::rejection::BytesRejection> for CommandExtractionError {
-    fn from(_: axum::extract::rejection::BytesRejection) -> Self {
-        Self
+    fn from(rejection: axum::extract::rejection::BytesRejection) -> Self {
+        Self::new(rejection.body_text())
     }
 }
 
 impl From<serde_json::Error> for CommandExtractionError {
-    fn from(_: serde_json::Error) -> Self {
-        Self
+    fn from(err: serde_json::Error) -> Self {
+        Self::new(err.to_string())
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, StatusCode},
+        routing::post,
+    };
     use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
 
-    use crate::domain::commands::JourneyCommand;
+    use crate::domain::commands::{JourneyCommand, deterministic_journey_id};
     use jsonptr::PointerBuf;
 
-    use super::normalize_set_attributes;
+    use super::{CommandExtractor, normalize_set_attributes};
+
+    /// Mounts the extractor on the same routes as `main.rs` and echoes the
+    /// extracted command's `Debug` form.
+    async fn extract(uri: &str, body: &str) -> (StatusCode, String) {
+        async fn echo(CommandExtractor(_, command): CommandExtractor) -> String {
+            format!("{command:?}")
+        }
+        let router = Router::new()
+            .route("/journeys", post(echo))
+            .route("/journeys/{journey_id}", post(echo));
+
+        let request = Request::post(uri)
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_owned()))
+            .unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    fn journey_uri() -> String {
+        format!("/journeys/{}", Uuid::new_v4())
+    }
+
+    // ── extraction through the router ────────────────────────────────────────
+
+    #[tokio::test]
+    async fn empty_body_on_create_route_starts_a_journey() {
+        let (status, command) = extract("/journeys", "").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(command.starts_with("Start {"), "{command}");
+    }
+
+    #[tokio::test]
+    async fn key_on_create_route_starts_a_deterministic_journey() {
+        let (status, command) = extract("/journeys?key=order%201234", "").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(
+            command.starts_with("StartDeterministic {")
+                && command.contains(r#"name: "order 1234""#),
+            "{command}"
+        );
+
+        let (status, message) = extract("/journeys?key=", "").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(message.contains("key must not be empty"), "{message}");
+    }
+
+    #[tokio::test]
+    async fn start_deterministic_must_name_the_path_journey() {
+        let body = json!({ "StartDeterministic": { "namespace": Uuid::nil(), "name": "a" } });
+        let id = deterministic_journey_id(&Uuid::nil(), "a");
+
+        let (status, _) = extract(&format!("/journeys/{id}"), &body.to_string()).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, message) = extract(&journey_uri(), &body.to_string()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(message.contains("does not match"), "{message}");
+    }
+
+    #[tokio::test]
+    async fn empty_body_on_journey_route_is_rejected() {
+        let (status, message) = extract(&journey_uri(), "").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(message.contains("request body is empty"), "{message}");
+    }
+
+    #[tokio::test]
+    async fn start_with_matching_path_id_is_extracted() {
+        let id = Uuid::new_v4();
+        let (status, command) = extract(
+            &format!("/journeys/{id}"),
+            &json!({ "Start": { "id": id } }).to_string(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(command, format!("Start {{ id: {id}, journey_type: None }}"));
+    }
+
+    #[tokio::test]
+    async fn start_with_mismatched_path_id_is_rejected() {
+        let body_id = Uuid::new_v4();
+        let (status, message) = extract(
+            &journey_uri(),
+            &json!({ "Start": { "id": body_id } }).to_string(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(message.contains(&body_id.to_string()), "{message}");
+        assert!(message.contains("does not match"), "{message}");
+    }
+
+    #[tokio::test]
+    async fn capture_is_extracted() {
+        let (status, command) = extract(
+            &journey_uri(),
+            r#"{"Capture":{"step":"search","data":{"origin":"LHR"}}}"#,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(command.starts_with("Capture {"), "{command}");
+    }
+
+    #[tokio::test]
+    async fn set_attributes_sugar_is_extracted() {
+        let (status, command) = extract(
+            &journey_uri(),
+            r#"{"SetAttributes":{"search":{"origin":"LHR"}}}"#,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(command.starts_with("SetAttributes {"), "{command}");
+        assert!(command.contains("/search/origin"), "{command}");
+    }
+
+    #[tokio::test]
+    async fn capture_person_is_extracted() {
+        let body = json!({
+            "CapturePerson": {
+                "person_ref": "lead_booker",
+                "subject_id": Uuid::new_v4(),
+                "name": "Alice Smith",
+                "email": "alice@example.com",
+                "phone": null
+            }
+        });
+        let (status, command) = extract(&journey_uri(), &body.to_string()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(command.starts_with("CapturePerson {"), "{command}");
+    }
+
+    #[tokio::test]
+    async fn capture_person_details_is_extracted() {
+        let (status, command) = extract(
+            &journey_uri(),
+            r#"{"CapturePersonDetails":{"person_ref":"lead_booker","data":{"passportNumber":"GB1"}}}"#,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(command.starts_with("CapturePersonDetails {"), "{command}");
+    }
+
+    #[tokio::test]
+    async fn complete_is_extracted() {
+        let (status, command) = extract(&journey_uri(), r#""Complete""#).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(command, "Complete");
+    }
+
+    #[tokio::test]
+    async fn forget_subject_is_extracted() {
+        let subject_id = Uuid::new_v4();
+        let (status, command) = extract(
+            &journey_uri(),
+            &json!({ "ForgetSubject": { "subject_id": subject_id } }).to_string(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            command,
+            format!("ForgetSubject {{ subject_id: {subject_id} }}")
+        );
+    }
+
+    #[tokio::test]
+    async fn malformed_json_is_rejected_with_details() {
+        let (status, message) = extract(&journey_uri(), "{not json").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(
+            message.starts_with("command could not be read: "),
+            "{message}"
+        );
+        assert!(message.contains("line 1"), "{message}");
+    }
+
+    #[tokio::test]
+    async fn unknown_command_is_rejected_with_details() {
+        let (status, message) = extract(&journey_uri(), r#"{"Teleport":{}}"#).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(message.contains("unknown variant `Teleport`"), "{message}");
+    }
+
+    #[tokio::test]
+    async fn invalid_path_id_is_rejected() {
+        let (status, _) = extract("/journeys/not-a-uuid", r#""Complete""#).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
 
     // ── canonical form ────────────────────────────────────────────────────────
 
@@ -0,0 +1,253 @@
+//! An audit log of the commands the journey aggregate accepted.
+//!
+//! [`CommandLog`] is a projection: every successful dispatch is one command,
+//! recorded as one row of `journey_command_log` with the command's name, the
+//! step it acted on, who submitted it and the sequences of the events it
+//! produced. The log is kept apart from the event store and indexed by
+//! journey, so answering "who did what" needs no event decoding.
+//!
+//! The command's name and actor come from the envelope metadata under
+//! [`COMMAND_METADATA_KEY`] and [`ACTOR_METADATA_KEY`]; events committed
+//! without them are logged with neither. Rejected commands produce no events
+//! and are recorded by [`command_rejections`](crate::command_rejections)
+//! instead.
+
+use chrono::{DateTime, Utc};
+use cqrs_es::{EventEnvelope, Query};
+use sqlx::{Pool, Postgres, Row, postgres::PgRow};
+use uuid::Uuid;
+
+use crate::domain::{
+    events::{ACTOR_METADATA_KEY, COMMAND_METADATA_KEY, EventTime, JourneyEvent},
+    journey::Journey,
+};
+
+/// One accepted command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandLogEntry {
+    pub journey_id: Uuid,
+    /// The command's name, such as `"Capture"`; `None` if its events were
+    /// committed without one.
+    pub command_type: Option<String>,
+    /// The step the command captured or skipped to; `None` for commands that
+    /// act on no step, such as `Complete`.
+    pub step: Option<String>,
+    /// Who submitted the command, if they were named.
+    pub actor: Option<String>,
+    /// When the command was accepted.
+    pub at: DateTime<Utc>,
+    /// The sequences of the events the command produced, in order.
+    pub sequences: Vec<i64>,
+}
+
+impl CommandLogEntry {
+    /// The entry for the command that produced `events`, or `None` if it
+    /// produced none.
+    #[must_use]
+    pub fn from_events(journey_id: Uuid, events: &[EventEnvelope<Journey>]) -> Option<Self> {
+        let first = events.first()?;
+        Some(Self {
+            journey_id,
+            command_type: first.metadata.get(COMMAND_METADATA_KEY).cloned(),
+            step: events
+                .iter()
+                .find_map(|event| step(&event.payload))
+                .map(str::to_string),
+            actor: first.metadata.get(ACTOR_METADATA_KEY).cloned(),
+            at: first.occurred_at().unwrap_or_else(Utc::now),
+            sequences: events
+                .iter()
+                .map(|event| i64::try_from(event.sequence).unwrap_or(i64::MAX))
+                .collect(),
+        })
+    }
+
+    fn from_row(row: &PgRow) -> Self {
+        Self {
+            journey_id: row.get("journey_id"),
+            command_type: row.get("command_type"),
+            step: row.get("step"),
+            actor: row.get("actor"),
+            at: row.get("at"),
+            sequences: row.get("sequences"),
+        }
+    }
+}
+
+/// The step `event` names, if any.
+#[allow(deprecated)]
+fn step(event: &JourneyEvent) -> Option<&str> {
+    match event {
+        JourneyEvent::Modified { step, .. }
+        | JourneyEvent::DraftSaved { step, .. }
+        | JourneyEvent::StepSkipped { step, .. } => Some(step),
+        JourneyEvent::StepProgressed { to_step, .. } => Some(to_step),
+        _ => None,
+    }
+}
+
+/// The `journey_command_log` table, and the projection that fills it.
+#[derive(Clone)]
+pub struct CommandLog {
+    pool: Pool<Postgres>,
+}
+
+impl CommandLog {
+    #[must_use]
+    pub const fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+
+    /// Record `entry`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the row cannot be inserted.
+    pub async fn record(&self, entry: &CommandLogEntry) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r"
+            INSERT INTO journey_command_log (journey_id, command_type, step, actor, at, sequences)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ",
+        )
+        .bind(entry.journey_id)
+        .bind(&entry.command_type)
+        .bind(&entry.step)
+        .bind(&entry.actor)
+        .bind(entry.at)
+        .bind(&entry.sequences)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Every command accepted for `journey_id`, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log cannot be read.
+    pub async fn load_command_log(
+        &self,
+        journey_id: &Uuid,
+    ) -> Result<Vec<CommandLogEntry>, sqlx::Error> {
+        let rows = sqlx::query(
+            r"
+            SELECT journey_id, command_type, step, actor, at, sequences
+            FROM journey_command_log
+            WHERE journey_id = $1
+            ORDER BY id
+            ",
+        )
+        .bind(journey_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.iter().map(CommandLogEntry::from_row).collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl Query<Journey> for CommandLog {
+    async fn dispatch(&self, aggregate_id: &str, events: &[EventEnvelope<Journey>]) {
+        let journey_id = match Uuid::parse_str(aggregate_id) {
+            Ok(id) => id,
+            Err(e) => {
+                eprintln!("Invalid journey ID '{aggregate_id}': {e:?}");
+                return;
+            }
+        };
+        let Some(entry) = CommandLogEntry::from_events(journey_id, events) else {
+            return;
+        };
+        if let Err(e) = self.record(&entry).await {
+            eprintln!("Error recording command for journey '{aggregate_id}': {e:?}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::domain::events::EVENT_TIME_METADATA_KEY;
+
+    fn envelope(
+        sequence: usize,
+        payload: JourneyEvent,
+        metadata: &HashMap<String, String>,
+    ) -> EventEnvelope<Journey> {
+        EventEnvelope {
+            aggregate_id: String::new(),
+            sequence,
+            payload,
+            metadata: metadata.clone(),
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn entry_records_the_command_step_actor_and_sequences() {
+        let journey_id = Uuid::new_v4();
+        let metadata = HashMap::from([
+            (
+                EVENT_TIME_METADATA_KEY.to_string(),
+                "2026-06-15T10:00:00Z".to_string(),
+            ),
+            (COMMAND_METADATA_KEY.to_string(), "Capture".to_string()),
+            (ACTOR_METADATA_KEY.to_string(), "agent-7".to_string()),
+        ]);
+        let events = [
+            envelope(
+                4,
+                JourneyEvent::Modified {
+                    step: "search".to_string(),
+                    data: json!({}),
+                },
+                &metadata,
+            ),
+            envelope(
+                5,
+                JourneyEvent::WorkflowEvaluated {
+                    suggested_actions: vec![],
+                    phase: None,
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec![],
+                },
+                &metadata,
+            ),
+        ];
+
+        let entry = CommandLogEntry::from_events(journey_id, &events).unwrap();
+
+        assert_eq!(
+            entry,
+            CommandLogEntry {
+                journey_id,
+                command_type: Some("Capture".to_string()),
+                step: Some("search".to_string()),
+                actor: Some("agent-7".to_string()),
+                at: "2026-06-15T10:00:00Z".parse().unwrap(),
+                sequences: vec![4, 5],
+            }
+        );
+    }
+
+    #[test]
+    fn entry_without_metadata_names_no_command_or_actor() {
+        let events = [envelope(1, JourneyEvent::Completed, &HashMap::new())];
+
+        let entry = CommandLogEntry::from_events(Uuid::new_v4(), &events).unwrap();
+
+        assert_eq!(entry.command_type, None);
+        assert_eq!(entry.step, None);
+        assert_eq!(entry.actor, None);
+        assert_eq!(entry.sequences, vec![1]);
+    }
+
+    #[test]
+    fn no_events_is_no_entry() {
+        assert_eq!(CommandLogEntry::from_events(Uuid::new_v4(), &[]), None);
+    }
+}
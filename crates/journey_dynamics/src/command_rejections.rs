@@ -0,0 +1,205 @@
+//! A record of commands the journey aggregate rejected.
+//!
+//! A rejected command records no events, so without this log there is no
+//! trace of it. Every [`JourneyError`] a command fails with is recorded with
+//! the command's name and the error's stable [`JourneyError::code`], so a
+//! dashboard can ask, for instance, how often passenger details are
+//! submitted invalid. Optimistic-lock conflicts and infrastructure failures
+//! are not rejections and are not recorded.
+
+use std::{
+    collections::BTreeMap,
+    sync::{Mutex, PoisonError},
+};
+
+use sqlx::{Pool, Postgres, Row};
+use uuid::Uuid;
+
+use crate::domain::journey::JourneyError;
+
+/// One rejected command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandRejection {
+    pub journey_id: Uuid,
+    /// The command's name, such as `"SetAttributes"`.
+    pub command_type: &'static str,
+    /// The error's [`JourneyError::code`], such as `"invalid_data"`.
+    pub error_code: &'static str,
+    /// The error message, as returned to the client.
+    pub detail: String,
+}
+
+impl CommandRejection {
+    /// The rejection of a command named `command_type`, as given by
+    /// [`JourneyCommand::name`](crate::domain::commands::JourneyCommand::name),
+    /// with `err`.
+    #[must_use]
+    pub fn new(journey_id: Uuid, command_type: &'static str, err: &JourneyError) -> Self {
+        Self {
+            journey_id,
+            command_type,
+            error_code: err.code(),
+            detail: err.to_string(),
+        }
+    }
+}
+
+/// Where rejected commands are recorded.
+#[async_trait::async_trait]
+pub trait RejectionLog: Send + Sync {
+    /// Record `rejection`.
+    async fn record(&self, rejection: &CommandRejection) -> Result<(), sqlx::Error>;
+
+    /// How many rejections have been recorded with each error code.
+    async fn count_rejections_by_code(&self) -> Result<BTreeMap<String, u64>, sqlx::Error>;
+}
+
+/// Rejections in the `journey_command_rejections` table.
+#[derive(Clone)]
+pub struct PostgresRejectionLog {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresRejectionLog {
+    #[must_use]
+    pub const fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl RejectionLog for PostgresRejectionLog {
+    async fn record(&self, rejection: &CommandRejection) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r"
+            INSERT INTO journey_command_rejections (journey_id, command_type, error_code, detail)
+            VALUES ($1, $2, $3, $4)
+            ",
+        )
+        .bind(rejection.journey_id)
+        .bind(rejection.command_type)
+        .bind(rejection.error_code)
+        .bind(&rejection.detail)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn count_rejections_by_code(&self) -> Result<BTreeMap<String, u64>, sqlx::Error> {
+        let rows = sqlx::query(
+            r"
+            SELECT error_code, COUNT(*) AS rejections
+            FROM journey_command_rejections
+            GROUP BY error_code
+            ",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let count: i64 = row.get("rejections");
+                (row.get("error_code"), count.unsigned_abs())
+            })
+            .collect())
+    }
+}
+
+/// Rejections held in process memory, for tests and single-instance
+/// development. They are lost when the process exits.
+#[derive(Default)]
+pub struct InMemoryRejectionLog {
+    rejections: Mutex<Vec<CommandRejection>>,
+}
+
+impl InMemoryRejectionLog {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every rejection recorded so far, oldest first.
+    #[must_use]
+    pub fn rejections(&self) -> Vec<CommandRejection> {
+        self.rejections
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl RejectionLog for InMemoryRejectionLog {
+    async fn record(&self, rejection: &CommandRejection) -> Result<(), sqlx::Error> {
+        self.rejections
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(rejection.clone());
+        Ok(())
+    }
+
+    async fn count_rejections_by_code(&self) -> Result<BTreeMap<String, u64>, sqlx::Error> {
+        let mut counts = BTreeMap::new();
+        for rejection in self.rejections() {
+            *counts.entry(rejection.error_code.to_string()).or_default() += 1;
+        }
+        Ok(counts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::commands::JourneyCommand;
+
+    #[test]
+    fn rejection_names_the_command_and_classifies_the_error() {
+        let journey_id = Uuid::new_v4();
+
+        let rejection = CommandRejection::new(
+            journey_id,
+            JourneyCommand::Complete.name(),
+            &JourneyError::CannotComplete {
+                missing: vec!["payment".to_string()],
+            },
+        );
+
+        assert_eq!(
+            rejection,
+            CommandRejection {
+                journey_id,
+                command_type: "Complete",
+                error_code: "cannot_complete",
+                detail: r#"Journey cannot be completed yet; outstanding steps: ["payment"]"#
+                    .to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn rejections_are_counted_by_code() {
+        let log = InMemoryRejectionLog::new();
+        let journey_id = Uuid::new_v4();
+        for err in [
+            JourneyError::Paused,
+            JourneyError::InvalidData(vec![]),
+            JourneyError::Paused,
+        ] {
+            log.record(&CommandRejection::new(
+                journey_id,
+                JourneyCommand::Pause.name(),
+                &err,
+            ))
+            .await
+            .unwrap();
+        }
+
+        let counts = log.count_rejections_by_code().await.unwrap();
+
+        assert_eq!(
+            counts,
+            BTreeMap::from([("invalid_data".to_string(), 1), ("paused".to_string(), 2)])
+        );
+        assert_eq!(log.rejections().len(), 3);
+    }
+}
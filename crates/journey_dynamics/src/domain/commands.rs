@@ -1,25 +1,82 @@
 use std::collections::BTreeMap;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use uuid::Uuid;
 
 use jsonptr::PointerBuf;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum JourneyCommand {
-    /// Create a new journey.
-    Start { id: Uuid },
+    /// Create a new journey, optionally of a named type (e.g.
+    /// `"flight-booking"`) that selects its decision model.
+    Start {
+        id: Uuid,
+        #[serde(default)]
+        journey_type: Option<String>,
+    },
+
+    /// Create a new journey whose id is derived from `namespace` and `name`
+    /// (a UUID v5), so retrying the same logical start names the same
+    /// journey and is rejected with `JourneyError::AlreadyStarted` instead of
+    /// creating a duplicate. See [`deterministic_journey_id`].
+    StartDeterministic {
+        namespace: Uuid,
+        name: String,
+        #[serde(default)]
+        journey_type: Option<String>,
+    },
+
+    /// Create a new journey seeded with the data captured by journey
+    /// `source_id`, e.g. to rebook. The new journey takes the source's type
+    /// and data but starts afresh: in progress, on no step, with no decision.
+    ///
+    /// An aggregate cannot load another, so `POST /journeys/from/{source_id}`
+    /// reads the source's view into `source`; sent any other way, the journey
+    /// starts empty. The `persons` subtree of the source's data is left out
+    /// unless `include_person` is set, in which case every person slot is
+    /// captured again, under the same subject, and its attributes set as a
+    /// `SetAttributes` would, so secret ones stay encrypted.
+    StartFrom {
+        id: Uuid,
+        source_id: Uuid,
+        #[serde(default)]
+        include_person: bool,
+        #[serde(skip)]
+        source: JourneySeed,
+    },
 
     /// Capture non-PII shared data for a step.
     /// The `data` field MUST NOT contain PII — use `CapturePerson` or
     /// `CapturePersonDetails` for any personally identifiable information.
     ///
+    /// The recorded `WorkflowEvaluated` reflects every capture committed
+    /// before it, including one for another step that raced this command: the
+    /// loser of the race conflicts and is re-handled on the merged data.
+    ///
     /// # Deprecated
     /// Use [`JourneyCommand::SetAttributes`] instead.
     #[deprecated(since = "0.3.0", note = "use SetAttributes (path-keyed attributes)")]
     Capture { step: String, data: Value },
 
+    /// Capture several steps' shared data as one unit, in order.
+    ///
+    /// Every entry is validated before anything is recorded, so one invalid
+    /// entry fails the whole command. Emits a `Modified` per entry, then a
+    /// single `WorkflowEvaluated` for the final merged state and, when the
+    /// last step differs from the current one, a `StepProgressed` to it.
+    /// Like `Capture`, `data` MUST NOT contain PII.
+    CaptureMany { captures: Vec<(String, Value)> },
+
+    /// Save an unfinished form for `step` without committing it.
+    ///
+    /// `data` is merged into the step's draft, kept apart from
+    /// `shared_data`, and validated partially: fields present must be valid,
+    /// but required fields may be missing. The workflow is not re-evaluated
+    /// and the journey stays on its current step. Capturing the step later
+    /// discards its draft. Like `Capture`, `data` MUST NOT contain PII.
+    SaveDraft { step: String, data: Value },
+
     /// Set one or more journey attributes in a single command.
     ///
     /// `changes` is a flat map of [`PointerBuf`] to value. A single
@@ -32,6 +89,16 @@ pub enum JourneyCommand {
         changes: BTreeMap<PointerBuf, Value>,
     },
 
+    /// Replace a value captured earlier, such as a misspelled name, without
+    /// re-evaluating the workflow or moving the journey to another step.
+    ///
+    /// `path` is a JSON Pointer (`/search/origin`) or a dot-notation path
+    /// (`passengerDetails[0].firstName`) to a value already in
+    /// `shared_data`. The corrected data is validated against the JSON
+    /// Schema. Paths the attribute schema classifies as secret are rejected;
+    /// correct those with `SetAttributes`.
+    Correct { path: String, value: Value },
+
     /// Register or update a person's identity fields in a named slot.
     ///
     /// `person_ref` is a client-assigned, journey-local slot name
@@ -65,6 +132,42 @@ pub enum JourneyCommand {
     /// Mark the journey as complete.
     Complete,
 
+    /// Return a completed journey to `InProgress` so it can be corrected,
+    /// keeping all its data. Rejected with `JourneyError::NotCompleted` for a
+    /// journey that is not complete, and with `JourneyError::Forgotten` for
+    /// one that has been forgotten.
+    Reopen,
+
+    /// Re-run the decision engine on the journey's data as it stands and
+    /// record the fresh decision, capturing nothing. Used to refresh
+    /// in-progress journeys after the decision model changes.
+    Reevaluate,
+
+    /// Record that the user has stepped away. While paused, commands that
+    /// capture data or complete the journey are rejected with
+    /// `JourneyError::Paused`. Pausing an already-paused journey is a no-op.
+    Pause,
+
+    /// Record that the user has returned to a paused journey. Resuming a
+    /// journey that is not paused is a no-op.
+    Resume,
+
+    /// Set the tag `key` to `value`, replacing any earlier value, to segment
+    /// journeys (e.g. `channel=mobile`) without touching their data. Allowed
+    /// in every state, since tags are not data capture. Tagging a key with
+    /// the value it already has is a no-op; an empty key is rejected with
+    /// `JourneyError::InvalidTag`.
+    Tag { key: String, value: String },
+
+    /// Redact every person slot and the `persons` subtree of `shared_data`,
+    /// keeping the skeletal journey for audit counts. Further data capture is
+    /// rejected with `JourneyError::Forgotten`. Forgetting an already
+    /// forgotten journey is a no-op.
+    ///
+    /// This does not delete any subject's DEK; use `ForgetSubject` (via
+    /// `DELETE /subjects/{id}`) for crypto-shredding.
+    Forget,
+
     /// Emit a `SubjectForgotten` audit event.
     ///
     /// Called by the shredding route handler after the subject's DEK has
@@ -72,3 +175,105 @@ pub enum JourneyCommand {
     /// and triggers the read-model projection to null out the person slot.
     ForgetSubject { subject_id: Uuid },
 }
+
+impl JourneyCommand {
+    /// The command's name as submitted, such as `"SetAttributes"`.
+    #[must_use]
+    #[allow(deprecated)]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Start { .. } => "Start",
+            Self::StartDeterministic { .. } => "StartDeterministic",
+            Self::StartFrom { .. } => "StartFrom",
+            Self::Capture { .. } => "Capture",
+            Self::CaptureMany { .. } => "CaptureMany",
+            Self::SaveDraft { .. } => "SaveDraft",
+            Self::SetAttributes { .. } => "SetAttributes",
+            Self::Correct { .. } => "Correct",
+            Self::CapturePerson { .. } => "CapturePerson",
+            Self::CapturePersonDetails { .. } => "CapturePersonDetails",
+            Self::Complete => "Complete",
+            Self::Reopen => "Reopen",
+            Self::Reevaluate => "Reevaluate",
+            Self::Pause => "Pause",
+            Self::Resume => "Resume",
+            Self::Tag { .. } => "Tag",
+            Self::Forget => "Forget",
+            Self::ForgetSubject { .. } => "ForgetSubject",
+        }
+    }
+
+    /// The id of the journey a start command creates; `None` for every other
+    /// command.
+    #[must_use]
+    pub fn started_id(&self) -> Option<Uuid> {
+        match self {
+            Self::Start { id, .. } | Self::StartFrom { id, .. } => Some(*id),
+            Self::StartDeterministic {
+                namespace, name, ..
+            } => Some(deterministic_journey_id(namespace, name)),
+            _ => None,
+        }
+    }
+}
+
+/// What [`JourneyCommand::StartFrom`] copies from its source journey.
+#[derive(Debug, Clone, Default)]
+pub struct JourneySeed {
+    pub journey_type: Option<String>,
+    /// The source's `shared_data`.
+    pub shared_data: Value,
+    /// The source's person slots that have not been forgotten.
+    pub persons: Vec<SeedPerson>,
+}
+
+/// A person slot copied by [`JourneyCommand::StartFrom`], with the identity
+/// fields `CapturePerson` takes.
+#[derive(Debug, Clone)]
+pub struct SeedPerson {
+    pub person_ref: String,
+    pub subject_id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub phone: Option<String>,
+}
+
+/// Namespace of the journey ids derived from a client's `key` on
+/// `POST /journeys?key=…`.
+pub const JOURNEY_KEY_NAMESPACE: Uuid = Uuid::from_u128(0x0663_1131_2044_4952_ba7a_c639_3649_0c80);
+
+/// The UUID v5 (RFC 9562) of `name` in `namespace`: the same inputs always
+/// give the same id.
+#[must_use]
+pub fn deterministic_journey_id(namespace: &Uuid, name: &str) -> Uuid {
+    Uuid::new_v5(namespace, name.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deterministic_ids_are_uuid_v5() {
+        // The RFC 9562 DNS namespace example.
+        let dns: Uuid = "6ba7b810-9dad-11d1-80b4-00c04fd430c8".parse().unwrap();
+
+        let id = deterministic_journey_id(&dns, "www.example.com");
+
+        assert_eq!(id.to_string(), "2ed6657d-e927-568b-95e1-2665a8aea6a2");
+        assert_eq!(id.get_version_num(), 5);
+    }
+
+    #[test]
+    fn same_namespace_and_name_give_the_same_id() {
+        let start = |name: &str| JourneyCommand::StartDeterministic {
+            namespace: JOURNEY_KEY_NAMESPACE,
+            name: name.to_string(),
+            journey_type: None,
+        };
+
+        assert_eq!(start("order-1").started_id(), start("order-1").started_id());
+        assert_ne!(start("order-1").started_id(), start("order-2").started_id());
+        assert_eq!(JourneyCommand::Complete.started_id(), None);
+    }
+}
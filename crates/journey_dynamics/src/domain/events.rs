@@ -1,12 +1,18 @@
 use std::collections::BTreeMap;
 
-use cqrs_es::DomainEvent;
+use chrono::{DateTime, Utc};
+use cqrs_es::{
+    Aggregate, DomainEvent, EventEnvelope,
+    persist::{EventUpcaster, SemanticVersionEventUpcaster},
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use uuid::Uuid;
 
 use jsonptr::PointerBuf;
 
+use crate::services::decision_engine::RankedAction;
+
 /// Per-subject secret data carried by an [`JourneyEvent::AttributesSet`] event.
 ///
 /// Each entry corresponds to one person slot whose secret attributes were
@@ -23,16 +29,27 @@ pub struct SecretPartitionData {
     pub changes: BTreeMap<PointerBuf, Value>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum JourneyEvent {
     Started {
         id: Uuid,
+        /// Which kind of journey this is, used to pick its decision model.
+        /// `None` for untyped journeys and for events written before schema
+        /// version 1.1.
+        #[serde(default)]
+        journey_type: Option<String>,
     },
     #[deprecated(since = "0.3.0", note = "use SetAttributes (path-keyed attributes)")]
     Modified {
         step: String,
         data: Value,
     },
+    /// Unfinished data for `step`, merged into its draft by `SaveDraft`.
+    /// Never touches `shared_data`.
+    DraftSaved {
+        step: String,
+        data: Value,
+    },
     PersonCaptured {
         person_ref: String,
         subject_id: Uuid,
@@ -52,6 +69,15 @@ pub enum JourneyEvent {
         /// before schema version 1.1 (legacy `Capture` arm always writes `None`).
         #[serde(default)]
         phase: Option<String>,
+        /// Actions with the model's weights, highest first; empty for events
+        /// written before schema version 1.2 or by models that do not rank.
+        #[serde(default)]
+        ranked_actions: Vec<RankedAction>,
+        /// Steps the model requires before the journey may complete; empty
+        /// for events written before schema version 1.3 or by models that
+        /// declare none.
+        #[serde(default)]
+        completion_blocked_by: Vec<String>,
     },
     #[deprecated(
         since = "0.3.0",
@@ -61,10 +87,43 @@ pub enum JourneyEvent {
         from_step: Option<String>,
         to_step: String,
     },
+    /// A `Capture` moved to a step the latest decision did not suggest.
+    /// Precedes the capture's own events; `allowed` is what was suggested.
+    StepSkipped {
+        step: String,
+        allowed: Vec<String>,
+    },
+    /// The decision engine suggested no actions for the first time since
+    /// it last suggested some: every step is satisfied. Follows that
+    /// `WorkflowEvaluated` and is recorded at most once per journey.
+    WorkflowSatisfied,
     Completed,
+    /// A completed journey was returned to `InProgress` by `Reopen`.
+    Reopened,
+    /// The user stepped away; `at` is when the `Pause` command was handled.
+    Paused {
+        at: DateTime<Utc>,
+    },
+    /// The user returned to a paused journey.
+    Resumed {
+        at: DateTime<Utc>,
+    },
     SubjectForgotten {
         subject_id: Uuid,
     },
+    /// Every person's PII in the journey was redacted by `Forget`.
+    Forgotten,
+    /// Ops metadata set by a `Tag` command; `value` replaces any earlier
+    /// value for `key`.
+    Tagged {
+        key: String,
+        value: String,
+    },
+    /// A plaintext value in `shared_data` replaced by a `Correct` command.
+    Corrected {
+        path: PointerBuf,
+        value: Value,
+    },
     /// Path-keyed attribute changes produced by a `SetAttributes` command.
     ///
     /// `plaintext` contains all changes that the attribute schema classified
@@ -87,30 +146,153 @@ impl DomainEvent for JourneyEvent {
         let event_type: &str = match self {
             Self::Started { .. } => "JourneyOpened",
             Self::Modified { .. } => "JourneyModified",
+            Self::DraftSaved { .. } => "JourneyDraftSaved",
             Self::PersonCaptured { .. } => "PersonCaptured",
             Self::PersonDetailsUpdated { .. } => "PersonDetailsUpdated",
             Self::WorkflowEvaluated { .. } => "WorkflowEvaluated",
             Self::StepProgressed { .. } => "StepProgressed",
+            Self::StepSkipped { .. } => "StepSkipped",
+            Self::WorkflowSatisfied => "WorkflowSatisfied",
             Self::Completed => "JourneyClosed",
+            Self::Reopened => "JourneyReopened",
+            Self::Paused { .. } => "JourneyPaused",
+            Self::Resumed { .. } => "JourneyResumed",
             Self::SubjectForgotten { .. } => "SubjectForgotten",
+            Self::Forgotten => "JourneyForgotten",
             Self::AttributesSet { .. } => "AttributesSet",
+            Self::Corrected { .. } => "JourneyCorrected",
+            Self::Tagged { .. } => "JourneyTagged",
         };
         event_type.to_string()
     }
 
     fn event_version(&self) -> String {
-        match self {
-            // Bumped to 1.1 when `phase` was added (step B1).
-            // Old 1.0 payloads (no `phase`) deserialise to `phase: None`
-            // via `#[serde(default)]` on the field.
-            Self::WorkflowEvaluated { .. } => "1.1".to_string(),
-            _ => "1.0".to_string(),
-        }
+        let event_version = match self {
+            Self::WorkflowEvaluated { .. } => WORKFLOW_EVALUATED_VERSION,
+            Self::Started { .. } => JOURNEY_OPENED_VERSION,
+            _ => INITIAL_EVENT_VERSION,
+        };
+        event_version.to_string()
+    }
+}
+
+// ── Versioning ────────────────────────────────────────────────────────────────
+//
+// Every event is persisted with its type and schema version alongside the
+// payload. Adding a field to a variant bumps that variant's version here and
+// adds an upcaster to `journey_event_upcasters` that fills the field in on
+// payloads written at the previous version.
+
+/// Schema version of every event type that has never changed shape.
+pub const INITIAL_EVENT_VERSION: &str = "1.0";
+
+/// Schema version of `JourneyOpened` ([`JourneyEvent::Started`]); 1.1 added
+/// `journey_type`.
+pub const JOURNEY_OPENED_VERSION: &str = "1.1";
+
+/// Schema version of [`JourneyEvent::WorkflowEvaluated`]; 1.1 added `phase`
+/// (step B1), 1.2 added `ranked_actions` and 1.3 `completion_blocked_by`.
+pub const WORKFLOW_EVALUATED_VERSION: &str = "1.3";
+
+/// Upcasters that bring persisted [`JourneyEvent`] payloads up to the current
+/// schema version.
+///
+/// They are ordered oldest first, as
+/// [`PersistedEventStore::with_upcasters`](cqrs_es::persist::PersistedEventStore::with_upcasters)
+/// requires.
+///
+/// Each one only adds the new field with the value the variant's
+/// `#[serde(default)]` would give it, so upcasting never changes what an
+/// event means.
+#[must_use]
+pub fn journey_event_upcasters() -> Vec<Box<dyn EventUpcaster>> {
+    vec![
+        field_added(
+            "JourneyOpened",
+            "1.1",
+            "Started",
+            "journey_type",
+            Value::Null,
+        ),
+        field_added(
+            "WorkflowEvaluated",
+            "1.1",
+            "WorkflowEvaluated",
+            "phase",
+            Value::Null,
+        ),
+        field_added(
+            "WorkflowEvaluated",
+            "1.2",
+            "WorkflowEvaluated",
+            "ranked_actions",
+            Value::Array(vec![]),
+        ),
+        field_added(
+            "WorkflowEvaluated",
+            "1.3",
+            "WorkflowEvaluated",
+            "completion_blocked_by",
+            Value::Array(vec![]),
+        ),
+    ]
+}
+
+/// An upcaster to `event_version` of `event_type`, whose `variant` payloads
+/// gained `field`: payloads without it get `default`.
+fn field_added(
+    event_type: &str,
+    event_version: &str,
+    variant: &'static str,
+    field: &'static str,
+    default: Value,
+) -> Box<dyn EventUpcaster> {
+    Box::new(SemanticVersionEventUpcaster::new(
+        event_type,
+        event_version,
+        Box::new(move |mut payload: Value| {
+            if let Some(fields) = payload.get_mut(variant).and_then(Value::as_object_mut) {
+                fields.entry(field).or_insert_with(|| default.clone());
+            }
+            payload
+        }),
+    ))
+}
+
+/// Envelope metadata key holding the RFC 3339 time the originating command
+/// was accepted. Every event a command produces shares the same time.
+pub const EVENT_TIME_METADATA_KEY: &str = "time";
+
+/// Envelope metadata key holding the name of the originating command, as given
+/// by [`JourneyCommand::name`](crate::domain::commands::JourneyCommand::name).
+pub const COMMAND_METADATA_KEY: &str = "command";
+
+/// Envelope metadata key holding who submitted the originating command, taken
+/// from the request's `X-Actor` header. Absent when the header was not sent.
+pub const ACTOR_METADATA_KEY: &str = "actor";
+
+/// Event time, carried in the [`EventEnvelope`] metadata rather than in each
+/// payload so that every variant — and every event already in the store —
+/// gets one without a schema change.
+pub trait EventTime {
+    /// When the event occurred, or `None` if it was persisted without a
+    /// parseable [`EVENT_TIME_METADATA_KEY`] entry.
+    fn occurred_at(&self) -> Option<DateTime<Utc>>;
+}
+
+impl<A: Aggregate> EventTime for EventEnvelope<A> {
+    fn occurred_at(&self) -> Option<DateTime<Utc>> {
+        self.metadata
+            .get(EVENT_TIME_METADATA_KEY)
+            .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+            .map(|time| time.with_timezone(&Utc))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use cqrs_es::persist::{SemanticVersion, SerializedEvent};
+
     use super::*;
 
     /// Verify that a v1.0 `WorkflowEvaluated` payload (no `phase` field)
@@ -123,24 +305,202 @@ mod tests {
             JourneyEvent::WorkflowEvaluated {
                 suggested_actions,
                 phase,
+                ranked_actions,
+                completion_blocked_by,
             } => {
                 assert_eq!(suggested_actions, vec!["next".to_string()]);
                 assert!(phase.is_none(), "phase must be None for v1.0 payload");
+                assert!(ranked_actions.is_empty());
+                assert!(completion_blocked_by.is_empty());
             }
             other => panic!("expected WorkflowEvaluated, got {other:?}"),
         }
     }
 
-    /// Verify that a v1.1 `WorkflowEvaluated` payload (with `phase`) round-trips.
+    /// Verify that a v1.0 `Started` payload (no `journey_type` field)
+    /// deserialises to an untyped journey.
     #[test]
-    fn workflow_evaluated_v1_1_round_trips_phase() {
+    fn started_v1_0_fixture_deserialises_to_no_journey_type() {
+        let id = Uuid::new_v4();
+        let json = format!(r#"{{"Started": {{"id": "{id}"}}}}"#);
+        let event: JourneyEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            event,
+            JourneyEvent::Started {
+                id,
+                journey_type: None
+            }
+        );
+    }
+
+    /// Verify that a v1.3 `WorkflowEvaluated` payload (with `phase`,
+    /// `ranked_actions` and `completion_blocked_by`) round-trips.
+    #[test]
+    fn workflow_evaluated_v1_3_round_trips_phase_ranking_and_blocks() {
         let event = JourneyEvent::WorkflowEvaluated {
             suggested_actions: vec!["confirm".to_string()],
             phase: Some("collecting_passengers".to_string()),
+            ranked_actions: vec![RankedAction {
+                action: "confirm".to_string(),
+                weight: 0.75,
+            }],
+            completion_blocked_by: vec!["payment".to_string()],
         };
         let json = serde_json::to_string(&event).unwrap();
         let decoded: JourneyEvent = serde_json::from_str(&json).unwrap();
         assert_eq!(event, decoded);
-        assert_eq!(event.event_version(), "1.1");
+        assert_eq!(event.event_version(), "1.3");
+    }
+
+    // ── Upcasting ────────────────────────────────────────────────────────────
+
+    /// A hand-written persisted event, run through the upcasters as the
+    /// event store runs it on load.
+    fn load(event_type: &str, event_version: &str, payload: Value) -> SerializedEvent {
+        let event = SerializedEvent::new(
+            "journey".to_string(),
+            1,
+            "Journey".to_string(),
+            event_type.to_string(),
+            event_version.to_string(),
+            payload,
+            serde_json::json!({}),
+        );
+        journey_event_upcasters()
+            .iter()
+            .fold(event, |event, upcaster| {
+                if upcaster.can_upcast(&event.event_type, &event.event_version) {
+                    upcaster.upcast(event)
+                } else {
+                    event
+                }
+            })
+    }
+
+    fn version(version: &str) -> SemanticVersion {
+        version.parse().unwrap()
+    }
+
+    #[test]
+    fn started_v1_0_upcasts_to_an_untyped_journey() {
+        let id = Uuid::new_v4();
+        let v1 = serde_json::json!({ "Started": { "id": id } });
+
+        let loaded = load("JourneyOpened", "1.0", v1);
+
+        assert_eq!(
+            version(&loaded.event_version),
+            version(JOURNEY_OPENED_VERSION)
+        );
+        assert_eq!(
+            loaded.payload,
+            serde_json::json!({ "Started": { "id": id, "journey_type": null } })
+        );
+        let envelope = EventEnvelope::<crate::domain::journey::Journey>::try_from(loaded).unwrap();
+        assert_eq!(
+            envelope.payload,
+            JourneyEvent::Started {
+                id,
+                journey_type: None
+            }
+        );
+    }
+
+    #[test]
+    fn workflow_evaluated_v1_0_upcasts_through_every_version() {
+        let v1 = serde_json::json!({ "WorkflowEvaluated": { "suggested_actions": ["next"] } });
+
+        let loaded = load("WorkflowEvaluated", "1.0", v1);
+
+        assert_eq!(
+            version(&loaded.event_version),
+            version(WORKFLOW_EVALUATED_VERSION)
+        );
+        assert_eq!(
+            loaded.payload,
+            serde_json::json!({ "WorkflowEvaluated": {
+                "suggested_actions": ["next"],
+                "phase": null,
+                "ranked_actions": [],
+                "completion_blocked_by": [],
+            } })
+        );
+    }
+
+    #[test]
+    fn current_events_are_loaded_unchanged() {
+        let event = JourneyEvent::Started {
+            id: Uuid::new_v4(),
+            journey_type: Some("flight".to_string()),
+        };
+        let payload = serde_json::to_value(&event).unwrap();
+
+        let loaded = load(&event.event_type(), &event.event_version(), payload.clone());
+
+        assert_eq!(loaded.event_version, event.event_version());
+        assert_eq!(loaded.payload, payload);
+    }
+
+    /// Guards against bumping a variant's version without an upcaster that
+    /// reaches it: a payload persisted at 1.0 must load at the current version.
+    #[test]
+    fn upcasters_reach_the_current_version_of_every_event() {
+        let current = [
+            JourneyEvent::Started {
+                id: Uuid::new_v4(),
+                journey_type: None,
+            },
+            JourneyEvent::WorkflowEvaluated {
+                suggested_actions: vec![],
+                phase: None,
+                ranked_actions: vec![],
+                completion_blocked_by: vec![],
+            },
+            JourneyEvent::Completed,
+        ];
+        for event in current {
+            let loaded = load(
+                &event.event_type(),
+                INITIAL_EVENT_VERSION,
+                serde_json::to_value(&event).unwrap(),
+            );
+            assert_eq!(
+                version(&loaded.event_version),
+                version(&event.event_version()),
+                "{}",
+                event.event_type()
+            );
+        }
+    }
+
+    fn envelope(metadata: &[(&str, &str)]) -> EventEnvelope<crate::domain::journey::Journey> {
+        EventEnvelope {
+            aggregate_id: "journey".to_string(),
+            sequence: 1,
+            payload: JourneyEvent::Completed,
+            metadata: metadata
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn occurred_at_reads_time_metadata() {
+        let event = envelope(&[(EVENT_TIME_METADATA_KEY, "2025-08-15T11:00:00+01:00")]);
+
+        assert_eq!(
+            event.occurred_at(),
+            Some("2025-08-15T10:00:00Z".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn occurred_at_is_none_when_missing_or_malformed() {
+        assert_eq!(envelope(&[]).occurred_at(), None);
+        assert_eq!(
+            envelope(&[(EVENT_TIME_METADATA_KEY, "yesterday")]).occurred_at(),
+            None
+        );
     }
 }
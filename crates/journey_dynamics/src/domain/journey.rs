@@ -1,5 +1,10 @@
-use std::{collections::BTreeMap, sync::Arc};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
 
+use chrono::{DateTime, Utc};
 use cqrs_es::{Aggregate, event_sink::EventSink};
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
@@ -10,24 +15,61 @@ use crate::{
     domain::{
         AttributeSchema, assign_all,
         attribute_schema::{PiiClass, classify_changes},
-        commands::JourneyCommand,
+        commands::{JourneyCommand, JourneySeed, deterministic_journey_id},
+        dotted_pointer,
         events::{JourneyEvent, SecretPartitionData},
+        flatten,
+        merge::{
+            MergeStrategy, NullHandling, StepMergeMode, replace_subtrees, resolve_patch,
+            split_nulls, step_patch,
+        },
+    },
+    services::{
+        decision_engine::{DecisionEngine, DecisionEngineError, RankedAction, WorkflowDecision},
+        schema_validator::{CompletenessReport, NoOpValidator, SchemaValidator, ValidationIssue},
     },
-    services::{decision_engine::DecisionEngine, schema_validator::SchemaValidator},
 };
 use jsonptr::PointerBuf;
 
+/// The step under which [`JourneyCommand::StartFrom`] records the data it
+/// copies.
+pub const START_FROM_STEP: &str = "start_from";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Journey {
     id: Uuid,
+    /// Set at `Start`; selects the decision model for engines that route
+    /// by type.
+    #[serde(default)]
+    #[allow(clippy::struct_field_names)]
+    journey_type: Option<String>,
     state: JourneyState,
     /// Shared, non-PII data accumulated from `Capture` commands.
     /// Never encrypted. Fully intact after any shredding operation.
     shared_data: Value,
+    /// Uncommitted data saved by `SaveDraft`, keyed by step. A step's draft
+    /// is discarded once the step is captured.
+    #[serde(default = "empty_object")]
+    draft_data: Value,
     /// Per-person slots, keyed by client-assigned `person_ref`.
     persons: BTreeMap<String, PersonSlot>,
     current_step: Option<String>,
     latest_workflow_decision: Option<WorkflowDecisionState>,
+    /// When the current pause began; `None` unless the journey is paused.
+    #[serde(default)]
+    paused_at: Option<DateTime<Utc>>,
+    /// Total time spent in completed pauses.
+    #[serde(default)]
+    paused_duration: Duration,
+    /// Set by a journey-level `Forget`; no further data may be captured.
+    #[serde(default)]
+    forgotten: bool,
+    /// Set once `WorkflowSatisfied` is recorded, so it is never repeated.
+    #[serde(default)]
+    workflow_satisfied: bool,
+    /// Ops metadata set by `Tag`, kept apart from `shared_data`.
+    #[serde(default)]
+    tags: BTreeMap<String, String>,
 }
 
 /// One data subject's slot within a journey.
@@ -64,12 +106,20 @@ pub struct WorkflowDecisionState {
     /// Phase label from the decision engine.
     /// `None` until the `WorkflowEvaluated` event carries `phase` (step B1).
     pub phase: Option<String>,
+    /// Suggested actions with the model's weights, highest first. Empty when
+    /// the model does not rank its suggestions.
+    #[serde(default)]
+    pub ranked_actions: Vec<RankedAction>,
+    /// Steps the model requires before the journey may complete.
+    #[serde(default)]
+    pub completion_blocked_by: Vec<String>,
 }
 
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum JourneyState {
     #[default]
     InProgress,
+    Paused,
     Complete,
 }
 
@@ -89,13 +139,45 @@ impl Aggregate for Journey {
         sink: &EventSink<Self>,
     ) -> Result<(), Self::Error> {
         match command {
-            JourneyCommand::Start { id } => {
-                if self.id == id {
-                    Err(JourneyError::AlreadyStarted)
-                } else {
-                    sink.write(JourneyEvent::Started { id }, self).await;
-                    Ok(())
+            JourneyCommand::Start { id, journey_type } => self.start(id, journey_type, sink).await,
+
+            JourneyCommand::StartDeterministic {
+                namespace,
+                name,
+                journey_type,
+            } => {
+                let id = deterministic_journey_id(&namespace, &name);
+                self.start(id, journey_type, sink).await
+            }
+
+            JourneyCommand::StartFrom {
+                id,
+                source_id: _,
+                include_person,
+                source,
+            } => {
+                self.start_from(id, include_person, source, services, sink)
+                    .await
+            }
+
+            JourneyCommand::SaveDraft { step, data } => {
+                self.ensure_capturable()?;
+
+                if let Err(e) = services.schema_validator().validate_partial(&step, &data) {
+                    return Err(JourneyError::InvalidData(e.into_issues()));
                 }
+                let mut drafts = self.draft_data.clone();
+                if let Some(drafts) = drafts.as_object_mut() {
+                    json_patch::merge(
+                        drafts.entry(step.clone()).or_insert_with(|| json!({})),
+                        &data,
+                    );
+                }
+                services.check_data_size(&drafts)?;
+
+                sink.write(JourneyEvent::DraftSaved { step, data }, self)
+                    .await;
+                Ok(())
             }
 
             JourneyCommand::CapturePerson {
@@ -105,18 +187,14 @@ impl Aggregate for Journey {
                 email,
                 phone,
             } => {
-                if self.id == Uuid::default() {
-                    return Err(JourneyError::NotFound);
-                }
-                if JourneyState::Complete == self.state {
-                    return Err(JourneyError::AlreadyCompleted);
-                }
+                self.ensure_capturable()?;
                 // If the slot already exists, the subject_id must match.
                 if let Some(slot) = self.persons.get(&person_ref)
                     && slot.subject_id != subject_id
                 {
                     return Err(JourneyError::PersonRefConflict(person_ref));
                 }
+                let email = validate_person(&name, &email)?;
                 sink.write(
                     JourneyEvent::PersonCaptured {
                         person_ref,
@@ -132,12 +210,7 @@ impl Aggregate for Journey {
             }
 
             JourneyCommand::CapturePersonDetails { person_ref, data } => {
-                if self.id == Uuid::default() {
-                    return Err(JourneyError::NotFound);
-                }
-                if JourneyState::Complete == self.state {
-                    return Err(JourneyError::AlreadyCompleted);
-                }
+                self.ensure_capturable()?;
                 // The slot must already exist so we know which subject_id to use.
                 let subject_id = match self.persons.get(&person_ref) {
                     Some(slot) => slot.subject_id,
@@ -156,32 +229,76 @@ impl Aggregate for Journey {
             }
 
             JourneyCommand::Capture { step, data } => {
-                if self.id == Uuid::default() {
-                    return Err(JourneyError::NotFound);
-                }
-                if JourneyState::Complete == self.state {
-                    return Err(JourneyError::AlreadyCompleted);
-                }
+                self.ensure_capturable()?;
 
                 if let Err(e) = services.schema_validator().validate(&data) {
-                    return Err(JourneyError::InvalidData(e.to_string()));
+                    return Err(JourneyError::InvalidData(e.into_issues()));
                 }
 
+                // Combine arrays and split out stored nulls according to the
+                // configuration up front so the recorded events replay
+                // identically under any configuration.
+                let (data, nulls, merged) =
+                    services.resolve_capture(&self.shared_data, &step, &data);
+
+                services.check_data_size(&merged)?;
+
                 let is_step_transition = self.current_step.as_ref() != Some(&step);
+                let skipped = is_step_transition
+                    .then(|| self.skipped_step(&step))
+                    .flatten();
+                if let Some(allowed) = &skipped
+                    && services.strict_step_order()
+                {
+                    return Err(JourneyError::StepNotAllowed {
+                        step,
+                        allowed: allowed.clone(),
+                    });
+                }
 
+                // The decision sees every capture committed before this journey
+                // was loaded. One committed since makes our commit conflict, and
+                // `execute_with_retry` re-handles the command on the merged data.
+                // Stored nulls are already in place, since the engine merges
+                // `data` as a plain merge patch.
+                let evaluate = self.completion_blocked()
+                    || services
+                        .capture_evaluation()
+                        .evaluates(is_step_transition, merged != self.shared_data);
                 let mut journey_for_eval = self.clone();
+                if !nulls.is_empty() {
+                    journey_for_eval.shared_data = merged;
+                }
                 if is_step_transition {
                     journey_for_eval.current_step = Some(step.clone());
                 }
 
-                let decision = services
-                    .decision_engine()
-                    .evaluate_next_steps(&journey_for_eval, &step, &data)
-                    .await
-                    .map_err(|e| JourneyError::DecisionEngineError(e.to_string()))?;
+                let decision = if evaluate {
+                    Some(
+                        services
+                            .decide(services.decision_engine().evaluate_next_steps(
+                                &journey_for_eval,
+                                &step,
+                                &data,
+                            ))
+                            .await?,
+                    )
+                } else {
+                    None
+                };
 
                 let from_step = self.current_step.clone();
 
+                if let Some(allowed) = skipped {
+                    sink.write(
+                        JourneyEvent::StepSkipped {
+                            step: step.clone(),
+                            allowed,
+                        },
+                        self,
+                    )
+                    .await;
+                }
                 sink.write(
                     JourneyEvent::Modified {
                         step: step.clone(),
@@ -190,16 +307,22 @@ impl Aggregate for Journey {
                     self,
                 )
                 .await;
-
-                sink.write(
-                    JourneyEvent::WorkflowEvaluated {
-                        suggested_actions: decision.suggested_actions,
-                        // The legacy `Capture` arm never carries a phase label.
-                        phase: None,
-                    },
-                    self,
-                )
-                .await;
+                write_nulls(nulls, sink, self).await;
+
+                if let Some(decision) = decision {
+                    write_evaluation(
+                        JourneyEvent::WorkflowEvaluated {
+                            suggested_actions: decision.suggested_actions,
+                            // The legacy `Capture` arm never carries a phase label.
+                            phase: None,
+                            ranked_actions: decision.ranked_actions,
+                            completion_blocked_by: decision.completion_blocked_by,
+                        },
+                        sink,
+                        self,
+                    )
+                    .await;
+                }
 
                 if is_step_transition {
                     sink.write(
@@ -215,117 +338,176 @@ impl Aggregate for Journey {
                 Ok(())
             }
 
-            JourneyCommand::SetAttributes { changes } => {
-                if self.id == Uuid::default() {
-                    return Err(JourneyError::NotFound);
+            JourneyCommand::CaptureMany { captures } => {
+                self.ensure_capturable()?;
+                let Some((last_step, _)) = captures.last() else {
+                    return Err(JourneyError::InvalidData(vec![ValidationIssue::new(
+                        "",
+                        "no captures",
+                    )]));
+                };
+                let last_step = last_step.clone();
+
+                for (_, data) in &captures {
+                    if let Err(e) = services.schema_validator().validate(data) {
+                        return Err(JourneyError::InvalidData(e.into_issues()));
+                    }
                 }
-                if JourneyState::Complete == self.state {
-                    return Err(JourneyError::AlreadyCompleted);
+
+                // Resolve each patch against the data merged so far, exactly as
+                // a sequence of `Capture`s would. The decision is made once, as
+                // a `Capture` of the last entry on top of the others.
+                let mut merged = self.shared_data.clone();
+                let mut merged_before_last = merged.clone();
+                let mut modifications = Vec::with_capacity(captures.len());
+                for (step, data) in captures {
+                    let (data, nulls, merged_now) = services.resolve_capture(&merged, &step, &data);
+                    merged_before_last = std::mem::replace(&mut merged, merged_now);
+                    modifications.push((step, data, nulls));
                 }
-                if changes.is_empty() {
-                    return Err(JourneyError::InvalidData("no changes".to_string()));
+
+                services.check_data_size(&merged)?;
+
+                // Step order is checked as for a `Capture` of the last entry,
+                // the step the journey ends up at.
+                let is_step_transition = self.current_step.as_ref() != Some(&last_step);
+                let skipped = is_step_transition
+                    .then(|| self.skipped_step(&last_step))
+                    .flatten();
+                if let Some(allowed) = &skipped
+                    && services.strict_step_order()
+                {
+                    return Err(JourneyError::StepNotAllowed {
+                        step: last_step,
+                        allowed: allowed.clone(),
+                    });
                 }
 
-                // Classify every path against the attribute schema.
-                // The subject_lookup resolves "/persons/<ref>" → slot UUID.
-                let schema = services.attribute_schema();
-                let classification = {
-                    let persons = &self.persons;
-                    classify_changes(schema, &changes, |subject_path| {
-                        subject_path
-                            .as_str()
-                            .strip_prefix("/persons/")
-                            .and_then(|person_ref| persons.get(person_ref))
-                            .map(|slot| slot.subject_id)
-                    })
+                let evaluate = self.completion_blocked()
+                    || services
+                        .capture_evaluation()
+                        .evaluates(is_step_transition, merged != self.shared_data);
+                let (_, last_data, last_nulls) = &modifications[modifications.len() - 1];
+                let mut journey_for_eval = self.clone();
+                journey_for_eval.shared_data = if last_nulls.is_empty() {
+                    merged_before_last
+                } else {
+                    merged
                 };
+                if is_step_transition {
+                    journey_for_eval.current_step = Some(last_step.clone());
+                }
 
-                // Reject paths that are not registered in the schema at all.
-                let truly_unknown: Vec<PointerBuf> = classification
-                    .unknown
-                    .iter()
-                    .filter(|p| schema.classify(p).is_none())
-                    .cloned()
-                    .collect();
-                if !truly_unknown.is_empty() {
-                    return Err(JourneyError::UnknownAttributePath(truly_unknown));
+                let decision = if evaluate {
+                    Some(
+                        services
+                            .decide(services.decision_engine().evaluate_next_steps(
+                                &journey_for_eval,
+                                &last_step,
+                                last_data,
+                            ))
+                            .await?,
+                    )
+                } else {
+                    None
+                };
+
+                let from_step = self.current_step.clone();
+
+                if let Some(allowed) = skipped {
+                    sink.write(
+                        JourneyEvent::StepSkipped {
+                            step: last_step.clone(),
+                            allowed,
+                        },
+                        self,
+                    )
+                    .await;
+                }
+                for (step, data, nulls) in modifications {
+                    sink.write(JourneyEvent::Modified { step, data }, self)
+                        .await;
+                    write_nulls(nulls, sink, self).await;
                 }
 
-                // Reject secret paths whose person slot hasn't been created yet.
-                for path in &classification.unknown {
-                    let Some(cls) = schema.classify(path) else {
-                        continue;
-                    };
-                    let PiiClass::Secret { subject } = cls.as_ref() else {
-                        continue;
-                    };
-                    let person_ref = subject
-                        .as_str()
-                        .strip_prefix("/persons/")
-                        .unwrap_or(subject.as_str())
-                        .to_string();
-                    return Err(JourneyError::PersonNotFound(person_ref));
+                if let Some(decision) = decision {
+                    write_evaluation(
+                        JourneyEvent::WorkflowEvaluated {
+                            suggested_actions: decision.suggested_actions,
+                            phase: None,
+                            ranked_actions: decision.ranked_actions,
+                            completion_blocked_by: decision.completion_blocked_by,
+                        },
+                        sink,
+                        self,
+                    )
+                    .await;
                 }
 
-                // Build one SecretPartitionData per subject, sorted by person_ref
-                // for deterministic event ordering.
-                // Reverse map: subject_id → person_ref (1-to-1: PersonRefConflict
-                // prevents two slots sharing the same subject_id).
-                let subject_to_ref: BTreeMap<Uuid, String> = self
-                    .persons
-                    .iter()
-                    .map(|(person_ref, slot)| (slot.subject_id, person_ref.clone()))
-                    .collect();
-
-                let mut secret_partitions: Vec<SecretPartitionData> = classification
-                    .secret_by_subject
-                    .into_iter()
-                    .map(|(subject_id, secret_changes)| {
-                        let person_ref = subject_to_ref
-                            .get(&subject_id)
-                            .cloned()
-                            .unwrap_or_else(|| subject_id.to_string());
-                        SecretPartitionData {
-                            person_ref,
-                            subject_id,
-                            changes: secret_changes,
-                        }
-                    })
-                    .collect();
-                secret_partitions.sort_by(|a, b| a.person_ref.cmp(&b.person_ref));
+                if is_step_transition {
+                    sink.write(
+                        JourneyEvent::StepProgressed {
+                            from_step,
+                            to_step: last_step,
+                        },
+                        self,
+                    )
+                    .await;
+                }
+
+                Ok(())
+            }
+
+            JourneyCommand::SetAttributes { changes } => {
+                self.ensure_capturable()?;
+                if changes.is_empty() {
+                    return Err(JourneyError::InvalidData(vec![ValidationIssue::new(
+                        "",
+                        "no changes",
+                    )]));
+                }
+
+                let (plaintext, secret_partitions) =
+                    self.partition_attributes(services.attribute_schema(), &changes)?;
 
                 // Validate plaintext changes merged with current shared_data.
-                if !classification.plaintext.is_empty() {
+                if !plaintext.is_empty() {
                     let mut merged_data = self.shared_data.clone();
 
-                    assign_all(&mut merged_data, &classification.plaintext)?;
+                    assign_all(&mut merged_data, &plaintext)?;
 
                     if let Err(e) = services.schema_validator().validate(&merged_data) {
-                        return Err(JourneyError::InvalidData(e.to_string()));
+                        return Err(JourneyError::InvalidData(e.into_issues()));
                     }
+                    services.check_data_size(&merged_data)?;
                 }
 
                 // Evaluate the workflow with the full (plaintext + secret) change set.
                 let decision = services
-                    .decision_engine()
-                    .evaluate_attributes(self, &changes)
-                    .await
-                    .map_err(|e| JourneyError::DecisionEngineError(e.to_string()))?;
+                    .decide(
+                        services
+                            .decision_engine()
+                            .evaluate_attributes(self, &changes),
+                    )
+                    .await?;
 
                 sink.write(
                     JourneyEvent::AttributesSet {
-                        plaintext: classification.plaintext,
+                        plaintext,
                         secret_partitions,
                     },
                     self,
                 )
                 .await;
 
-                sink.write(
+                write_evaluation(
                     JourneyEvent::WorkflowEvaluated {
                         suggested_actions: decision.suggested_actions,
                         phase: decision.phase,
+                        ranked_actions: decision.ranked_actions,
+                        completion_blocked_by: decision.completion_blocked_by,
                     },
+                    sink,
                     self,
                 )
                 .await;
@@ -333,21 +515,172 @@ impl Aggregate for Journey {
                 Ok(())
             }
 
-            JourneyCommand::Complete => {
-                if self.id == Uuid::default() {
-                    Err(JourneyError::NotFound)
-                } else if JourneyState::Complete == self.state {
-                    Err(JourneyError::AlreadyCompleted)
+            JourneyCommand::Correct { path, value } => {
+                self.ensure_capturable()?;
+
+                // Only a value that is already there can be corrected.
+                let pointer = if path.starts_with('/') {
+                    PointerBuf::parse(&path).ok()
                 } else {
-                    sink.write(JourneyEvent::Completed, self).await;
-                    Ok(())
+                    dotted_pointer(&path)
+                };
+                let Some(pointer) = pointer.filter(|pointer| {
+                    !pointer.is_root() && pointer.resolve(&self.shared_data).is_ok()
+                }) else {
+                    return Err(JourneyError::NothingToCorrect(path));
+                };
+
+                match services.attribute_schema().classify(&pointer).as_deref() {
+                    Some(PiiClass::Plaintext) => {}
+                    Some(PiiClass::Secret { .. }) => {
+                        return Err(JourneyError::SecretCorrection(pointer));
+                    }
+                    None => return Err(JourneyError::UnknownAttributePath(vec![pointer])),
+                }
+
+                let mut corrected = self.shared_data.clone();
+                pointer.assign(&mut corrected, value.clone())?;
+                if let Err(e) = services.schema_validator().validate(&corrected) {
+                    return Err(JourneyError::InvalidData(e.into_issues()));
                 }
+                services.check_data_size(&corrected)?;
+
+                sink.write(
+                    JourneyEvent::Corrected {
+                        path: pointer,
+                        value,
+                    },
+                    self,
+                )
+                .await;
+                Ok(())
             }
 
-            JourneyCommand::ForgetSubject { subject_id } => {
-                if self.id == Uuid::default() {
-                    return Err(JourneyError::NotFound);
+            JourneyCommand::Complete => {
+                self.ensure_mutable()?;
+
+                if services.can_complete_check() {
+                    // Ask the engine about the journey as it stands now, as
+                    // `Reevaluate` does: at the current step, with nothing new.
+                    let step = self.current_step.clone().unwrap_or_default();
+                    let decision = services
+                        .decide(services.decision_engine().evaluate_next_steps(
+                            self,
+                            &step,
+                            &json!({}),
+                        ))
+                        .await?;
+                    if !decision.completion_blocked_by.is_empty() {
+                        return Err(JourneyError::CannotComplete {
+                            missing: decision.completion_blocked_by,
+                        });
+                    }
+                    // A model that names no blocking steps still leaves the
+                    // ones it suggests next.
+                    if !decision.can_complete {
+                        return Err(JourneyError::CannotComplete {
+                            missing: decision.suggested_actions,
+                        });
+                    }
+                } else if let Some(decision) = &self.latest_workflow_decision
+                    && !decision.completion_blocked_by.is_empty()
+                {
+                    // The model's own completion rules, as of the latest
+                    // decision.
+                    return Err(JourneyError::CannotComplete {
+                        missing: decision.completion_blocked_by.clone(),
+                    });
+                }
+
+                sink.write(JourneyEvent::Completed, self).await;
+                Ok(())
+            }
+
+            JourneyCommand::Reevaluate => {
+                self.ensure_capturable()?;
+
+                // Evaluate as the latest capture was, at the current step, but
+                // with nothing new.
+                let step = self.current_step.clone().unwrap_or_default();
+                let decision = services
+                    .decide(
+                        services
+                            .decision_engine()
+                            .evaluate_next_steps(self, &step, &json!({})),
+                    )
+                    .await?;
+
+                write_evaluation(
+                    JourneyEvent::WorkflowEvaluated {
+                        suggested_actions: decision.suggested_actions,
+                        phase: decision.phase,
+                        ranked_actions: decision.ranked_actions,
+                        completion_blocked_by: decision.completion_blocked_by,
+                    },
+                    sink,
+                    self,
+                )
+                .await;
+                Ok(())
+            }
+
+            JourneyCommand::Reopen => {
+                self.ensure_started()?;
+                if self.forgotten {
+                    return Err(JourneyError::Forgotten);
+                }
+                if self.state != JourneyState::Complete {
+                    return Err(JourneyError::NotCompleted);
+                }
+                sink.write(JourneyEvent::Reopened, self).await;
+                Ok(())
+            }
+
+            JourneyCommand::Pause => {
+                self.ensure_started()?;
+                match self.state {
+                    JourneyState::Complete => return Err(JourneyError::AlreadyCompleted),
+                    JourneyState::Paused => {}
+                    JourneyState::InProgress => {
+                        sink.write(JourneyEvent::Paused { at: Utc::now() }, self)
+                            .await;
+                    }
+                }
+                Ok(())
+            }
+
+            JourneyCommand::Resume => {
+                self.ensure_started()?;
+                if JourneyState::Paused == self.state {
+                    sink.write(JourneyEvent::Resumed { at: Utc::now() }, self)
+                        .await;
+                }
+                Ok(())
+            }
+
+            JourneyCommand::Tag { key, value } => {
+                self.ensure_started()?;
+                if key.trim().is_empty() {
+                    return Err(JourneyError::InvalidTag(
+                        "key must not be empty".to_string(),
+                    ));
+                }
+                if self.tags.get(&key) != Some(&value) {
+                    sink.write(JourneyEvent::Tagged { key, value }, self).await;
+                }
+                Ok(())
+            }
+
+            JourneyCommand::Forget => {
+                self.ensure_started()?;
+                if !self.forgotten {
+                    sink.write(JourneyEvent::Forgotten, self).await;
                 }
+                Ok(())
+            }
+
+            JourneyCommand::ForgetSubject { subject_id } => {
+                self.ensure_started()?;
                 // Only emit SubjectForgotten if the subject has at least one
                 // non-forgotten slot in this journey.  This makes the shredding
                 // endpoint idempotent: a second erasure request for the same
@@ -366,15 +699,24 @@ impl Aggregate for Journey {
         }
     }
 
-    #[allow(deprecated)]
+    #[allow(clippy::too_many_lines, deprecated)]
     fn apply(&mut self, event: Self::Event) {
         match event {
-            JourneyEvent::Started { id } => {
+            JourneyEvent::Started { id, journey_type } => {
                 self.id = id;
+                self.journey_type = journey_type;
                 self.state = JourneyState::InProgress;
             }
-            JourneyEvent::Modified { data, .. } => {
-                json_patch::merge(&mut self.shared_data, &data);
+            JourneyEvent::Modified { step, data } => {
+                json_patch::merge(&mut self.shared_data, &step_patch(&step, &data));
+                if let Some(drafts) = self.draft_data.as_object_mut() {
+                    drafts.remove(&step);
+                }
+            }
+            JourneyEvent::DraftSaved { step, data } => {
+                if let Some(drafts) = self.draft_data.as_object_mut() {
+                    json_patch::merge(drafts.entry(step).or_insert_with(|| json!({})), &data);
+                }
             }
             JourneyEvent::PersonCaptured {
                 person_ref,
@@ -443,21 +785,51 @@ impl Aggregate for Journey {
                 }
             }
 
+            JourneyEvent::Corrected { path, value } => {
+                path.assign(&mut self.shared_data, value)
+                    .expect("events should have valid JSON pointers");
+            }
+
             JourneyEvent::WorkflowEvaluated {
                 suggested_actions,
                 phase,
+                ranked_actions,
+                completion_blocked_by,
             } => {
                 self.latest_workflow_decision = Some(WorkflowDecisionState {
                     suggested_actions,
                     phase,
+                    ranked_actions,
+                    completion_blocked_by,
                 });
             }
             JourneyEvent::StepProgressed { to_step, .. } => {
                 self.current_step = Some(to_step);
             }
+            // Recorded for analytics; the `StepProgressed` that follows moves
+            // the journey.
+            JourneyEvent::StepSkipped { .. } => {}
+            JourneyEvent::WorkflowSatisfied => {
+                self.workflow_satisfied = true;
+            }
             JourneyEvent::Completed => {
                 self.state = JourneyState::Complete;
             }
+            // `current_step` and the accumulated data are kept.
+            JourneyEvent::Reopened => {
+                self.state = JourneyState::InProgress;
+            }
+            JourneyEvent::Paused { at } => {
+                self.state = JourneyState::Paused;
+                self.paused_at = Some(at);
+            }
+            JourneyEvent::Resumed { at } => {
+                self.state = JourneyState::InProgress;
+                if let Some(paused_at) = self.paused_at.take() {
+                    // A clock that stepped backwards counts as no time paused.
+                    self.paused_duration += (at - paused_at).to_std().unwrap_or_default();
+                }
+            }
             JourneyEvent::SubjectForgotten { subject_id } => {
                 for slot in self.persons.values_mut() {
                     if slot.subject_id == subject_id {
@@ -465,10 +837,36 @@ impl Aggregate for Journey {
                     }
                 }
             }
+            JourneyEvent::Forgotten => {
+                self.forgotten = true;
+                self.draft_data = json!({});
+                for slot in self.persons.values_mut() {
+                    slot.name = None;
+                    slot.email = None;
+                    slot.phone = None;
+                    slot.details = json!({});
+                    slot.forgotten = true;
+                }
+                if let Some(shared_data) = self.shared_data.as_object_mut() {
+                    shared_data.remove("persons");
+                }
+            }
+            JourneyEvent::Tagged { key, value } => {
+                self.tags.insert(key, value);
+            }
         }
     }
 }
 
+/// The outcome of [`JourneyServices::preview_capture`].
+#[derive(Debug, Clone, Serialize)]
+pub struct CapturePreview {
+    /// Why the data would be rejected; empty when it is valid.
+    pub validation_errors: Vec<ValidationIssue>,
+    /// What the workflow would suggest next; `None` when the data is invalid.
+    pub decision: Option<WorkflowDecision>,
+}
+
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum JourneyError {
     #[error("Journey not found")]
@@ -477,24 +875,184 @@ pub enum JourneyError {
     AlreadyStarted,
     #[error("Journey already closed")]
     AlreadyCompleted,
+    #[error("Journey is not closed")]
+    NotCompleted,
+    #[error("Journey is paused — resume it first")]
+    Paused,
+    #[error("Journey has been forgotten")]
+    Forgotten,
+    #[error("Step '{step}' was not suggested; suggested steps: {allowed:?}")]
+    StepNotAllowed { step: String, allowed: Vec<String> },
+    #[error("Journey cannot be completed yet; outstanding steps: {missing:?}")]
+    CannotComplete { missing: Vec<String> },
     #[error("Decision engine error: {0}")]
-    DecisionEngineError(String),
-    #[error("Invalid data: {0}")]
-    InvalidData(String),
+    DecisionEngineError(DecisionEngineError),
+    #[error("Invalid data: {}", ValidationIssue::join(.0))]
+    InvalidData(Vec<ValidationIssue>),
     #[error("Person slot '{0}' is already bound to a different subject")]
     PersonRefConflict(String),
     #[error("Person slot '{0}' does not exist — call CapturePerson first")]
     PersonNotFound(String),
+    #[error("Invalid person: {0}")]
+    InvalidPerson(String),
+    #[error("Invalid tag: {0}")]
+    InvalidTag(String),
     #[error("Unknown attribute paths: {0:?}")]
     UnknownAttributePath(Vec<PointerBuf>),
+    #[error("Nothing has been captured at '{0}' to correct")]
+    NothingToCorrect(String),
+    #[error("Attribute '{0}' is secret — correct it with SetAttributes")]
+    SecretCorrection(PointerBuf),
     #[error("Invalid JSON pointer: {0}")]
     InvalidJsonPointer(#[from] jsonptr::assign::Error),
+    #[error("Journey data would grow to {size} bytes, over the {limit} byte limit")]
+    DataTooLarge { size: usize, limit: usize },
+}
+
+impl JourneyError {
+    /// A stable, machine-readable name for the kind of error, such as
+    /// `"invalid_data"`, for counting rejections without parsing messages.
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::NotFound => "not_found",
+            Self::AlreadyStarted => "already_started",
+            Self::AlreadyCompleted => "already_completed",
+            Self::NotCompleted => "not_completed",
+            Self::Paused => "paused",
+            Self::Forgotten => "forgotten",
+            Self::StepNotAllowed { .. } => "step_not_allowed",
+            Self::CannotComplete { .. } => "cannot_complete",
+            Self::DecisionEngineError(_) => "decision_engine_error",
+            Self::InvalidData(_) => "invalid_data",
+            Self::PersonRefConflict(_) => "person_ref_conflict",
+            Self::PersonNotFound(_) => "person_not_found",
+            Self::InvalidPerson(_) => "invalid_person",
+            Self::InvalidTag(_) => "invalid_tag",
+            Self::UnknownAttributePath(_) => "unknown_attribute_path",
+            Self::NothingToCorrect(_) => "nothing_to_correct",
+            Self::SecretCorrection(_) => "secret_correction",
+            Self::InvalidJsonPointer(_) => "invalid_json_pointer",
+            Self::DataTooLarge { .. } => "data_too_large",
+        }
+    }
+}
+
+/// Record `evaluation`, a `WorkflowEvaluated`, followed by `WorkflowSatisfied`
+/// the first time it leaves a journey that had suggested actions with none.
+async fn write_evaluation(
+    evaluation: JourneyEvent,
+    sink: &EventSink<Journey>,
+    journey: &mut Journey,
+) {
+    let was_pending = has_suggested_actions(journey);
+    sink.write(evaluation, journey).await;
+    if was_pending && !has_suggested_actions(journey) && !journey.workflow_satisfied {
+        sink.write(JourneyEvent::WorkflowSatisfied, journey).await;
+    }
+}
+
+/// Whether `journey`'s latest decision suggested any actions.
+fn has_suggested_actions(journey: &Journey) -> bool {
+    journey
+        .latest_workflow_decision
+        .as_ref()
+        .is_some_and(|decision| !decision.suggested_actions.is_empty())
+}
+
+/// Record the nulls a `Capture` stores under [`NullHandling::Store`], which
+/// its `Modified` merge patch cannot express, as plaintext path assignments.
+async fn write_nulls(
+    nulls: BTreeMap<PointerBuf, Value>,
+    sink: &EventSink<Journey>,
+    journey: &mut Journey,
+) {
+    if nulls.is_empty() {
+        return;
+    }
+    sink.write(
+        JourneyEvent::AttributesSet {
+            plaintext: nulls,
+            secret_partitions: vec![],
+        },
+        journey,
+    )
+    .await;
+}
+
+/// Check the identity fields of a `CapturePerson` command, returning the
+/// email trimmed and lowercased.
+///
+/// Emails are checked with the same `format: email` logic the attribute
+/// schemas use.
+fn validate_person(name: &str, email: &str) -> Result<String, JourneyError> {
+    static EMAIL_VALIDATOR: OnceLock<jsonschema::Validator> = OnceLock::new();
+
+    if name.trim().is_empty() {
+        return Err(JourneyError::InvalidPerson(
+            "name must not be empty".to_string(),
+        ));
+    }
+    let email = email.trim().to_lowercase();
+    let validator = EMAIL_VALIDATOR.get_or_init(|| {
+        jsonschema::options()
+            .should_validate_formats(true)
+            .build(&json!({ "type": "string", "format": "email" }))
+            .expect("email schema is valid")
+    });
+    if !validator.is_valid(&json!(email)) {
+        return Err(JourneyError::InvalidPerson(format!(
+            "'{email}' is not a valid email address"
+        )));
+    }
+    Ok(email)
+}
+
+/// Which captures re-evaluate the workflow and record a `WorkflowEvaluated`.
+///
+/// A capture that is not evaluated leaves the latest decision in place, so
+/// its suggestions can go stale until the next evaluated command. While the
+/// latest decision blocks completion, every capture is evaluated, so that a
+/// capture filling a blocking step lifts the block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CaptureEvaluation {
+    /// Every capture.
+    #[default]
+    Always,
+    /// Only a capture that moves the journey to another step.
+    OnStepTransition,
+    /// A capture that moves the journey to another step or changes its data,
+    /// skipping same-step saves of data already captured.
+    OnChange,
+}
+
+impl CaptureEvaluation {
+    /// Whether a capture that does (`is_step_transition`) or doesn't move
+    /// the journey, and does (`changed`) or doesn't change its data, is
+    /// evaluated.
+    #[must_use]
+    pub const fn evaluates(self, is_step_transition: bool, changed: bool) -> bool {
+        match self {
+            Self::Always => true,
+            Self::OnStepTransition => is_step_transition,
+            Self::OnChange => is_step_transition || changed,
+        }
+    }
 }
 
+#[derive(Clone)]
 pub struct JourneyServices {
     decision_engine: Arc<dyn DecisionEngine>,
     schema_validator: Arc<dyn SchemaValidator>,
     attribute_schema: Arc<AttributeSchema>,
+    merge_strategy: MergeStrategy,
+    null_handling: NullHandling,
+    step_merge_modes: BTreeMap<String, StepMergeMode>,
+    can_complete_check: bool,
+    strict_step_order: bool,
+    decision_timeout: Option<Duration>,
+    max_shared_data_size: Option<usize>,
+    capture_evaluation: CaptureEvaluation,
 }
 
 impl JourneyServices {
@@ -507,74 +1065,599 @@ impl JourneyServices {
             decision_engine,
             schema_validator,
             attribute_schema,
+            merge_strategy: MergeStrategy::default(),
+            null_handling: NullHandling::default(),
+            step_merge_modes: BTreeMap::new(),
+            can_complete_check: false,
+            strict_step_order: false,
+            decision_timeout: None,
+            max_shared_data_size: None,
+            capture_evaluation: CaptureEvaluation::default(),
         }
     }
 
+    /// Services that accept any captured data: a [`NoOpValidator`] and a
+    /// permissive attribute schema that stores every path as plaintext.
+    pub fn without_validation(decision_engine: Arc<dyn DecisionEngine>) -> Self {
+        Self::new(
+            decision_engine,
+            Arc::new(NoOpValidator),
+            Arc::new(AttributeSchema::permissive()),
+        )
+    }
+
+    /// Set how arrays in `Capture` data are combined with arrays already in
+    /// `shared_data`. Defaults to [`MergeStrategy::Replace`].
     #[must_use]
-    pub fn decision_engine(&self) -> &Arc<dyn DecisionEngine> {
-        &self.decision_engine
+    pub fn with_merge_strategy(mut self, merge_strategy: MergeStrategy) -> Self {
+        self.merge_strategy = merge_strategy;
+        self
     }
 
+    /// Set whether a `null` in `Capture` data deletes its key from
+    /// `shared_data` or is stored there. Defaults to
+    /// [`NullHandling::Delete`], as RFC 7386 merge patch does.
     #[must_use]
-    pub fn schema_validator(&self) -> &Arc<dyn SchemaValidator> {
-        &self.schema_validator
+    pub const fn with_null_handling(mut self, null_handling: NullHandling) -> Self {
+        self.null_handling = null_handling;
+        self
     }
 
+    /// Set how a `Capture` at `step` combines its data with `shared_data`.
+    /// Steps not configured use [`StepMergeMode::Merge`].
     #[must_use]
-    pub const fn attribute_schema(&self) -> &Arc<AttributeSchema> {
-        &self.attribute_schema
+    pub fn with_step_merge_mode(mut self, step: impl Into<String>, mode: StepMergeMode) -> Self {
+        self.step_merge_modes.insert(step.into(), mode);
+        self
     }
-}
 
-impl Journey {
+    /// Reject `Complete` unless the decision engine reports `can_complete`
+    /// for the journey as it stands. Off by default, so `Complete` is
+    /// accepted at any time.
     #[must_use]
-    pub const fn id(&self) -> Uuid {
-        self.id
+    pub const fn with_can_complete_check(mut self, enabled: bool) -> Self {
+        self.can_complete_check = enabled;
+        self
     }
 
+    /// Reject a `Capture` that moves to a step the latest decision did not
+    /// suggest, or a `CaptureMany` whose last entry does, with
+    /// [`JourneyError::StepNotAllowed`]. Off by default, so the capture is
+    /// accepted and a `StepSkipped` event records the jump.
     #[must_use]
-    pub const fn state(&self) -> JourneyState {
-        self.state
+    pub const fn with_strict_step_order(mut self, enabled: bool) -> Self {
+        self.strict_step_order = enabled;
+        self
     }
 
+    /// Fail a command with a transient `DecisionEngineError` when the decision
+    /// engine takes longer than `timeout`. Unbounded by default.
+    ///
+    /// The command returns promptly, but an evaluation already running on an
+    /// engine worker thread cannot be interrupted and runs to completion in
+    /// the background.
     #[must_use]
-    pub const fn shared_data(&self) -> &Value {
-        &self.shared_data
+    pub const fn with_decision_timeout(mut self, timeout: Duration) -> Self {
+        self.decision_timeout = Some(timeout);
+        self
     }
 
+    /// Reject a `Capture` whose data would take the journey's `shared_data`
+    /// past `limit` bytes of serialized JSON, measured after the merge.
+    /// Unbounded by default.
     #[must_use]
-    #[deprecated(
-        since = "0.3.0",
-        note = "read WorkflowEvaluated.phase from shared_data instead"
-    )]
-    pub const fn current_step(&self) -> Option<&String> {
-        self.current_step.as_ref()
+    pub const fn with_max_shared_data_size(mut self, limit: usize) -> Self {
+        self.max_shared_data_size = Some(limit);
+        self
     }
 
+    /// Set which `Capture` and `CaptureMany` commands re-evaluate the
+    /// workflow. Defaults to [`CaptureEvaluation::Always`].
     #[must_use]
-    pub const fn latest_workflow_decision(&self) -> Option<&WorkflowDecisionState> {
-        self.latest_workflow_decision.as_ref()
+    pub const fn with_capture_evaluation(mut self, capture_evaluation: CaptureEvaluation) -> Self {
+        self.capture_evaluation = capture_evaluation;
+        self
     }
 
     #[must_use]
-    pub const fn persons(&self) -> &BTreeMap<String, PersonSlot> {
-        &self.persons
+    pub fn decision_engine(&self) -> &Arc<dyn DecisionEngine> {
+        &self.decision_engine
     }
-}
 
-impl Default for Journey {
-    fn default() -> Self {
-        Self {
-            id: Uuid::default(),
+    #[must_use]
+    pub fn schema_validator(&self) -> &Arc<dyn SchemaValidator> {
+        &self.schema_validator
+    }
+
+    #[must_use]
+    pub const fn attribute_schema(&self) -> &Arc<AttributeSchema> {
+        &self.attribute_schema
+    }
+
+    #[must_use]
+    pub const fn merge_strategy(&self) -> &MergeStrategy {
+        &self.merge_strategy
+    }
+
+    #[must_use]
+    pub const fn null_handling(&self) -> NullHandling {
+        self.null_handling
+    }
+
+    /// How a `Capture` at `step` combines its data with `shared_data`.
+    #[must_use]
+    pub fn step_merge_mode(&self, step: &str) -> StepMergeMode {
+        self.step_merge_modes.get(step).copied().unwrap_or_default()
+    }
+
+    /// Resolve `data` captured at `step` against `shared_data` as a `Capture`
+    /// records it: filed under the step unless it is an object (see
+    /// [`step_patch`]), arrays combined per the merge strategy, the values it
+    /// sets replaced wholesale if the step says so and, when nulls are stored,
+    /// the nulls split out as path assignments. Returns the merge patch, the
+    /// null assignments and the data as merged.
+    fn resolve_capture(
+        &self,
+        shared_data: &Value,
+        step: &str,
+        data: &Value,
+    ) -> (Value, BTreeMap<PointerBuf, Value>, Value) {
+        let data = &*step_patch(step, data);
+        let replace = self.step_merge_mode(step) == StepMergeMode::Replace;
+        let data = if replace {
+            data.clone()
+        } else {
+            resolve_patch(shared_data, data, self.merge_strategy())
+        };
+        let (data, nulls) = match self.null_handling {
+            NullHandling::Delete => (data, BTreeMap::new()),
+            NullHandling::Store => split_nulls(&data),
+        };
+        // Replacing after the nulls are split out, so the removals it adds
+        // delete their keys whatever the null handling.
+        let data = if replace {
+            replace_subtrees(shared_data, &data)
+        } else {
+            data
+        };
+        let mut merged = shared_data.clone();
+        json_patch::merge(&mut merged, &data);
+        // The patch already created every parent, so assigning cannot fail.
+        let _ = assign_all(&mut merged, &nulls);
+        (data, nulls, merged)
+    }
+
+    #[must_use]
+    pub const fn can_complete_check(&self) -> bool {
+        self.can_complete_check
+    }
+
+    #[must_use]
+    pub const fn strict_step_order(&self) -> bool {
+        self.strict_step_order
+    }
+
+    #[must_use]
+    pub const fn decision_timeout(&self) -> Option<Duration> {
+        self.decision_timeout
+    }
+
+    #[must_use]
+    pub const fn max_shared_data_size(&self) -> Option<usize> {
+        self.max_shared_data_size
+    }
+
+    /// Check `data` serialises to no more than
+    /// [`Self::max_shared_data_size`] bytes, when a limit is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JourneyError::DataTooLarge`] when it is larger.
+    pub fn check_data_size(&self, data: &Value) -> Result<(), JourneyError> {
+        let Some(limit) = self.max_shared_data_size else {
+            return Ok(());
+        };
+        let size = serde_json::to_vec(data).map_or(0, |bytes| bytes.len());
+        if size > limit {
+            return Err(JourneyError::DataTooLarge { size, limit });
+        }
+        Ok(())
+    }
+
+    #[must_use]
+    pub const fn capture_evaluation(&self) -> CaptureEvaluation {
+        self.capture_evaluation
+    }
+
+    /// Preview a `Capture` of `data` at `step` without emitting any events:
+    /// validate the data, then evaluate the workflow against a copy of
+    /// `journey` as if it had been captured.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error `Capture` would for a journey that cannot take data
+    /// (not started, forgotten, complete or paused), or when the decision
+    /// engine fails. Invalid data is reported in the preview instead.
+    pub async fn preview_capture(
+        &self,
+        journey: &Journey,
+        step: &str,
+        data: &Value,
+    ) -> Result<CapturePreview, JourneyError> {
+        journey.ensure_capturable()?;
+
+        if let Err(e) = self.schema_validator().validate(data) {
+            return Ok(CapturePreview {
+                validation_errors: e.into_issues(),
+                decision: None,
+            });
+        }
+
+        let (data, nulls, merged) = self.resolve_capture(&journey.shared_data, step, data);
+        let mut journey_for_eval = journey.clone();
+        if !nulls.is_empty() {
+            journey_for_eval.shared_data = merged;
+        }
+        journey_for_eval.current_step = Some(step.to_string());
+
+        let decision = self
+            .decide(
+                self.decision_engine()
+                    .evaluate_next_steps(&journey_for_eval, step, &data),
+            )
+            .await?;
+        Ok(CapturePreview {
+            validation_errors: Vec::new(),
+            decision: Some(decision),
+        })
+    }
+
+    /// Await a decision engine call, bounded by the decision timeout.
+    pub(crate) async fn decide(
+        &self,
+        evaluation: impl Future<Output = Result<WorkflowDecision, DecisionEngineError>>,
+    ) -> Result<WorkflowDecision, JourneyError> {
+        let decision = match self.decision_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, evaluation)
+                .await
+                .unwrap_or_else(|_| {
+                    Err(DecisionEngineError::new(format!(
+                        "decision engine timed out after {} ms",
+                        timeout.as_millis()
+                    ))
+                    .with_transient(true))
+                }),
+            None => evaluation.await,
+        };
+        decision.map_err(JourneyError::DecisionEngineError)
+    }
+}
+
+impl Journey {
+    #[must_use]
+    pub const fn id(&self) -> Uuid {
+        self.id
+    }
+
+    #[must_use]
+    pub const fn state(&self) -> JourneyState {
+        self.state
+    }
+
+    #[must_use]
+    pub const fn shared_data(&self) -> &Value {
+        &self.shared_data
+    }
+
+    /// Uncommitted drafts saved by `SaveDraft`, as an object keyed by step.
+    #[must_use]
+    pub const fn draft_data(&self) -> &Value {
+        &self.draft_data
+    }
+
+    /// Ops metadata set by `Tag`, by key.
+    #[must_use]
+    pub const fn tags(&self) -> &BTreeMap<String, String> {
+        &self.tags
+    }
+
+    #[must_use]
+    #[deprecated(
+        since = "0.3.0",
+        note = "read WorkflowEvaluated.phase from shared_data instead"
+    )]
+    pub const fn current_step(&self) -> Option<&String> {
+        self.current_step.as_ref()
+    }
+
+    #[must_use]
+    pub const fn latest_workflow_decision(&self) -> Option<&WorkflowDecisionState> {
+        self.latest_workflow_decision.as_ref()
+    }
+
+    #[must_use]
+    pub const fn persons(&self) -> &BTreeMap<String, PersonSlot> {
+        &self.persons
+    }
+
+    /// When the current pause began, or `None` if the journey is not paused.
+    #[must_use]
+    pub const fn paused_at(&self) -> Option<DateTime<Utc>> {
+        self.paused_at
+    }
+
+    /// Total time the journey has spent paused, excluding any pause still
+    /// in progress.
+    #[must_use]
+    pub const fn paused_duration(&self) -> Duration {
+        self.paused_duration
+    }
+
+    /// The type given at `Start`, if any.
+    #[must_use]
+    pub fn journey_type(&self) -> Option<&str> {
+        self.journey_type.as_deref()
+    }
+
+    /// Whether the journey's personal data has been redacted by `Forget`.
+    #[must_use]
+    pub const fn forgotten(&self) -> bool {
+        self.forgotten
+    }
+
+    /// Record the start of journey `id`, unless it has already started.
+    async fn start(
+        &mut self,
+        id: Uuid,
+        journey_type: Option<String>,
+        sink: &EventSink<Self>,
+    ) -> Result<(), JourneyError> {
+        if self.id == id {
+            return Err(JourneyError::AlreadyStarted);
+        }
+        sink.write(JourneyEvent::Started { id, journey_type }, self)
+            .await;
+        Ok(())
+    }
+
+    /// Split attribute `changes` into plaintext ones and one secret
+    /// partition per person, as `SetAttributes` records them.
+    ///
+    /// Rejects paths `schema` does not know and secret paths of persons with
+    /// no slot.
+    fn partition_attributes(
+        &self,
+        schema: &AttributeSchema,
+        changes: &BTreeMap<PointerBuf, Value>,
+    ) -> Result<(BTreeMap<PointerBuf, Value>, Vec<SecretPartitionData>), JourneyError> {
+        // Classify every path against the attribute schema.
+        // The subject_lookup resolves "/persons/<ref>" → slot UUID.
+        let classification = {
+            let persons = &self.persons;
+            classify_changes(schema, changes, |subject_path| {
+                subject_path
+                    .as_str()
+                    .strip_prefix("/persons/")
+                    .and_then(|person_ref| persons.get(person_ref))
+                    .map(|slot| slot.subject_id)
+            })
+        };
+
+        // Reject paths that are not registered in the schema at all.
+        let truly_unknown: Vec<PointerBuf> = classification
+            .unknown
+            .iter()
+            .filter(|p| schema.classify(p).is_none())
+            .cloned()
+            .collect();
+        if !truly_unknown.is_empty() {
+            return Err(JourneyError::UnknownAttributePath(truly_unknown));
+        }
+
+        // Reject secret paths whose person slot hasn't been created yet.
+        for path in &classification.unknown {
+            let Some(cls) = schema.classify(path) else {
+                continue;
+            };
+            let PiiClass::Secret { subject } = cls.as_ref() else {
+                continue;
+            };
+            let person_ref = subject
+                .as_str()
+                .strip_prefix("/persons/")
+                .unwrap_or(subject.as_str())
+                .to_string();
+            return Err(JourneyError::PersonNotFound(person_ref));
+        }
+
+        // Build one SecretPartitionData per subject, sorted by person_ref
+        // for deterministic event ordering.
+        // Reverse map: subject_id → person_ref (1-to-1: PersonRefConflict
+        // prevents two slots sharing the same subject_id).
+        let subject_to_ref: BTreeMap<Uuid, String> = self
+            .persons
+            .iter()
+            .map(|(person_ref, slot)| (slot.subject_id, person_ref.clone()))
+            .collect();
+
+        let mut secret_partitions: Vec<SecretPartitionData> = classification
+            .secret_by_subject
+            .into_iter()
+            .map(|(subject_id, secret_changes)| {
+                let person_ref = subject_to_ref
+                    .get(&subject_id)
+                    .cloned()
+                    .unwrap_or_else(|| subject_id.to_string());
+                SecretPartitionData {
+                    person_ref,
+                    subject_id,
+                    changes: secret_changes,
+                }
+            })
+            .collect();
+        secret_partitions.sort_by(|a, b| a.person_ref.cmp(&b.person_ref));
+
+        Ok((classification.plaintext, secret_partitions))
+    }
+
+    /// Start journey `id` with `seed`'s type and data; see
+    /// [`JourneyCommand::StartFrom`].
+    #[allow(deprecated)]
+    async fn start_from(
+        &mut self,
+        id: Uuid,
+        include_person: bool,
+        seed: JourneySeed,
+        services: &JourneyServices,
+        sink: &EventSink<Self>,
+    ) -> Result<(), JourneyError> {
+        let JourneySeed {
+            journey_type,
+            mut shared_data,
+            persons,
+        } = seed;
+        // Person attributes may be secret, so they never travel in a
+        // plaintext `Modified`.
+        let person_data = shared_data
+            .as_object_mut()
+            .and_then(|data| data.remove("persons"));
+        let seeded = shared_data.as_object().is_some_and(|data| !data.is_empty());
+        if seeded && let Err(e) = services.schema_validator().validate(&shared_data) {
+            return Err(JourneyError::InvalidData(e.into_issues()));
+        }
+        if seeded {
+            services.check_data_size(&shared_data)?;
+        }
+
+        self.start(id, journey_type, sink).await?;
+        if seeded {
+            sink.write(
+                JourneyEvent::Modified {
+                    step: START_FROM_STEP.to_string(),
+                    data: shared_data,
+                },
+                self,
+            )
+            .await;
+        }
+        if !include_person {
+            return Ok(());
+        }
+
+        for person in persons {
+            sink.write(
+                JourneyEvent::PersonCaptured {
+                    person_ref: person.person_ref,
+                    subject_id: person.subject_id,
+                    name: person.name,
+                    email: person.email,
+                    phone: person.phone,
+                },
+                self,
+            )
+            .await;
+        }
+        let changes = person_data
+            .map(|persons| flatten(&json!({ "persons": persons })))
+            .unwrap_or_default();
+        if !changes.is_empty() {
+            // The slots were captured above, so secret attributes find their
+            // subjects.
+            let (plaintext, secret_partitions) =
+                self.partition_attributes(services.attribute_schema(), &changes)?;
+            sink.write(
+                JourneyEvent::AttributesSet {
+                    plaintext,
+                    secret_partitions,
+                },
+                self,
+            )
+            .await;
+        }
+        Ok(())
+    }
+
+    /// Check the journey has been started.
+    fn ensure_started(&self) -> Result<(), JourneyError> {
+        if self.id == Uuid::default() {
+            return Err(JourneyError::NotFound);
+        }
+        Ok(())
+    }
+
+    /// Check the journey exists and may still change: it is neither complete
+    /// nor paused.
+    fn ensure_mutable(&self) -> Result<(), JourneyError> {
+        self.ensure_started()?;
+        match self.state {
+            JourneyState::Complete => Err(JourneyError::AlreadyCompleted),
+            JourneyState::Paused => Err(JourneyError::Paused),
+            JourneyState::InProgress => Ok(()),
+        }
+    }
+
+    /// Whether the latest decision names steps that block completion.
+    fn completion_blocked(&self) -> bool {
+        self.latest_workflow_decision
+            .as_ref()
+            .is_some_and(|decision| !decision.completion_blocked_by.is_empty())
+    }
+
+    /// Check the journey may take more data: it may change and has not been
+    /// forgotten.
+    fn ensure_capturable(&self) -> Result<(), JourneyError> {
+        if self.forgotten {
+            return Err(JourneyError::Forgotten);
+        }
+        self.ensure_mutable()
+    }
+
+    /// The steps the latest decision suggested, if moving to `step` skips
+    /// them. A journey with no decision yet, or whose latest decision
+    /// suggested nothing, may move to any step.
+    fn skipped_step(&self, step: &str) -> Option<Vec<String>> {
+        let allowed = &self.latest_workflow_decision.as_ref()?.suggested_actions;
+        (!allowed.is_empty() && !allowed.iter().any(|action| action == step))
+            .then(|| allowed.clone())
+    }
+
+    /// Which of the fields `step` requires are filled in the journey's
+    /// accumulated data, according to `validator`'s schema.
+    #[must_use]
+    pub fn completeness(
+        &self,
+        validator: &dyn SchemaValidator,
+        step: &str,
+    ) -> Option<CompletenessReport> {
+        validator.completeness(step, &self.shared_data)
+    }
+}
+
+impl Default for Journey {
+    fn default() -> Self {
+        Self {
+            id: Uuid::default(),
+            journey_type: None,
             state: JourneyState::default(),
             shared_data: json!({}),
+            draft_data: json!({}),
             persons: BTreeMap::new(),
             current_step: None,
             latest_workflow_decision: None,
+            paused_at: None,
+            paused_duration: Duration::ZERO,
+            forgotten: false,
+            workflow_satisfied: false,
+            tags: BTreeMap::new(),
         }
     }
 }
 
+/// `draft_data` of journeys snapshotted before drafts existed.
+fn empty_object() -> Value {
+    json!({})
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::too_many_lines)]
@@ -587,8 +1670,16 @@ mod tests {
     use uuid::Uuid;
 
     use super::*;
-    use crate::domain::{AttributeSchema, attribute_schema::PiiClass, events::SecretPartitionData};
-    use crate::services::decision_engine::SimpleDecisionEngine;
+    use crate::domain::{
+        AttributeSchema,
+        attribute_schema::PiiClass,
+        commands::{JOURNEY_KEY_NAMESPACE, SeedPerson},
+        events::SecretPartitionData,
+        test_support::JourneyEventsBuilder,
+    };
+    use crate::services::decision_engine::{
+        MockDecisionEngine, SimpleDecisionEngine, TableDecisionEngine,
+    };
     use crate::services::schema_validator::JsonSchemaValidator;
 
     type JourneyTester = TestFramework<Journey>;
@@ -653,6 +1744,14 @@ mod tests {
         AttributeSchema::new(paths, None)
     }
 
+    fn services_with_decision_engine(engine: MockDecisionEngine) -> JourneyServices {
+        JourneyServices::new(
+            Arc::new(engine),
+            create_test_schema_validator(),
+            Arc::new(AttributeSchema::permissive()),
+        )
+    }
+
     fn services_with_attribute_schema(schema: AttributeSchema) -> JourneyServices {
         JourneyServices::new(
             Arc::new(SimpleDecisionEngine),
@@ -668,15 +1767,24 @@ mod tests {
         let id = Uuid::new_v4();
         JourneyTester::with(services())
             .given_no_previous_events()
-            .when(JourneyCommand::Start { id })
-            .then_expect_events(vec![JourneyEvent::Started { id }]);
+            .when(JourneyCommand::Start {
+                id,
+                journey_type: None,
+            })
+            .then_expect_events(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }]);
     }
 
     #[test]
     fn modify_journey() {
         let id = Uuid::new_v4();
         JourneyTester::with(services())
-            .given(vec![JourneyEvent::Started { id }])
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
             .when(JourneyCommand::Capture {
                 step: "first_name".to_string(),
                 data: json!("Joe"),
@@ -684,11 +1792,13 @@ mod tests {
             .then_expect_events(vec![
                 JourneyEvent::Modified {
                     step: "first_name".to_string(),
-                    data: json!("Joe"),
+                    data: json!({ "first_name": "Joe" }),
                 },
                 JourneyEvent::WorkflowEvaluated {
-                    suggested_actions: vec![],
+                    suggested_actions: vec!["form_3".to_string()],
                     phase: None,
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec![],
                 },
                 JourneyEvent::StepProgressed {
                     from_step: None,
@@ -697,11 +1807,53 @@ mod tests {
             ]);
     }
 
+    #[test]
+    fn a_scalar_capture_is_decided_under_its_step() {
+        let id = Uuid::new_v4();
+        let engine = MockDecisionEngine::with_fn(|journey, _, new_data| {
+            let mut captured_data = journey.shared_data().clone();
+            json_patch::merge(&mut captured_data, new_data);
+            assert_eq!(
+                captured_data,
+                json!({ "search": { "origin": "LHR" }, "first_name": "Joe" })
+            );
+            Ok(WorkflowDecision {
+                suggested_actions: vec![],
+                phase: None,
+                ranked_actions: vec![],
+                can_complete: true,
+                completion_blocked_by: vec![],
+            })
+        });
+        let events = JourneyTester::with(services_with_decision_engine(engine))
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::Modified {
+                    step: "search".to_string(),
+                    data: json!({ "search": { "origin": "LHR" } }),
+                },
+            ])
+            .when(JourneyCommand::Capture {
+                step: "first_name".to_string(),
+                data: json!("Joe"),
+            })
+            .inspect_result()
+            .unwrap();
+
+        assert_eq!(events.len(), 3);
+    }
+
     #[test]
     fn complete_unmodified_journey() {
         let id = Uuid::new_v4();
         JourneyTester::with(services())
-            .given(vec![JourneyEvent::Started { id }])
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
             .when(JourneyCommand::Complete)
             .then_expect_events(vec![JourneyEvent::Completed]);
     }
@@ -711,7 +1863,10 @@ mod tests {
         let id = Uuid::new_v4();
         JourneyTester::with(services())
             .given(vec![
-                JourneyEvent::Started { id },
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
                 JourneyEvent::Modified {
                     step: "first_name".to_string(),
                     data: json!("Joe"),
@@ -725,46 +1880,31 @@ mod tests {
     fn capture_empty_form_data() {
         let id = Uuid::new_v4();
         JourneyTester::with(services())
-            .given(vec![JourneyEvent::Started { id }])
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
             .when(JourneyCommand::Capture {
                 step: "form_data".to_string(),
                 data: json!({}),
             })
-            .then_expect_events(vec![
-                JourneyEvent::Modified {
-                    step: "form_data".to_string(),
-                    data: json!({}),
-                },
-                JourneyEvent::WorkflowEvaluated {
-                    suggested_actions: vec![],
-                    phase: None,
-                },
-                JourneyEvent::StepProgressed {
-                    from_step: None,
-                    to_step: "form_data".to_string(),
-                },
-            ]);
+            .then_expect_events(
+                JourneyEventsBuilder::new()
+                    .step("form_data", json!({}), &[])
+                    .build(),
+            );
     }
 
     #[test]
     fn capture_form_data_with_values() {
         let id = Uuid::new_v4();
         JourneyTester::with(services())
-            .given(vec![
-                JourneyEvent::Started { id },
-                JourneyEvent::Modified {
-                    step: "form_data".to_string(),
-                    data: json!({}),
-                },
-                JourneyEvent::WorkflowEvaluated {
-                    suggested_actions: vec![],
-                    phase: None,
-                },
-                JourneyEvent::StepProgressed {
-                    from_step: None,
-                    to_step: "form_data".to_string(),
-                },
-            ])
+            .given(
+                JourneyEventsBuilder::new()
+                    .started(id)
+                    .step("form_data", json!({}), &[])
+                    .build(),
+            )
             .when(JourneyCommand::Capture {
                 step: "alpha".to_string(),
                 data: json!({ "alpha": 42, "beta": "hello" }),
@@ -777,6 +1917,8 @@ mod tests {
                 JourneyEvent::WorkflowEvaluated {
                     suggested_actions: vec![],
                     phase: None,
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec![],
                 },
                 JourneyEvent::StepProgressed {
                     from_step: Some("form_data".to_string()),
@@ -785,12 +1927,101 @@ mod tests {
             ]);
     }
 
+    #[test]
+    fn capture_many_evaluates_once_on_the_merged_data() {
+        let id = Uuid::new_v4();
+        // Evaluated as a capture of the last step on top of the earlier ones.
+        let engine = MockDecisionEngine::with_fn(|journey, step, data| {
+            assert_eq!(journey.shared_data(), &json!({ "name": "Joe" }));
+            assert_eq!((step, data), ("section_2", &json!({ "alpha": 42 })));
+            Ok(WorkflowDecision {
+                suggested_actions: vec!["form_4".to_string()],
+                phase: None,
+                ranked_actions: vec![],
+                can_complete: false,
+                completion_blocked_by: vec![],
+            })
+        });
+        JourneyTester::with(services_with_decision_engine(engine))
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
+            .when(JourneyCommand::CaptureMany {
+                captures: vec![
+                    ("section_1".to_string(), json!({ "name": "Joe" })),
+                    ("section_2".to_string(), json!({ "alpha": 42 })),
+                ],
+            })
+            .then_expect_events(vec![
+                JourneyEvent::Modified {
+                    step: "section_1".to_string(),
+                    data: json!({ "name": "Joe" }),
+                },
+                JourneyEvent::Modified {
+                    step: "section_2".to_string(),
+                    data: json!({ "alpha": 42 }),
+                },
+                JourneyEvent::WorkflowEvaluated {
+                    suggested_actions: vec!["form_4".to_string()],
+                    phase: None,
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec![],
+                },
+                JourneyEvent::StepProgressed {
+                    from_step: None,
+                    to_step: "section_2".to_string(),
+                },
+            ]);
+    }
+
+    #[test]
+    fn capture_many_records_nothing_when_an_entry_is_invalid() {
+        let id = Uuid::new_v4();
+        JourneyTester::with(services())
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
+            .when(JourneyCommand::CaptureMany {
+                captures: vec![
+                    ("section_1".to_string(), json!({ "name": "Joe" })),
+                    ("section_2".to_string(), json!({ "alpha": "not a number" })),
+                    ("section_3".to_string(), json!({ "beta": "fine" })),
+                ],
+            })
+            .then_expect_error(JourneyError::InvalidData(vec![
+                ValidationIssue::new("/alpha", "\"not a number\" is not of type \"number\"")
+                    .with_code("type_mismatch")
+                    .with_param("expected", "number")
+                    .with_param("actual", "string"),
+            ]));
+    }
+
+    #[test]
+    fn capture_many_rejects_an_empty_batch() {
+        let id = Uuid::new_v4();
+        JourneyTester::with(services())
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
+            .when(JourneyCommand::CaptureMany { captures: vec![] })
+            .then_expect_error(JourneyError::InvalidData(vec![ValidationIssue::new(
+                "",
+                "no captures",
+            )]));
+    }
+
     #[test]
     fn complete_journey_with_form_data() {
         let id = Uuid::new_v4();
         JourneyTester::with(services())
             .given(vec![
-                JourneyEvent::Started { id },
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
                 JourneyEvent::Modified {
                     step: "alpha".to_string(),
                     data: json!({ "alpha": 42, "beta": "hello" }),
@@ -798,6 +2029,8 @@ mod tests {
                 JourneyEvent::WorkflowEvaluated {
                     suggested_actions: vec![],
                     phase: None,
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec![],
                 },
                 JourneyEvent::StepProgressed {
                     from_step: Some("form_data".to_string()),
@@ -812,589 +2045,2804 @@ mod tests {
     fn open_already_opened() {
         let id = Uuid::new_v4();
         JourneyTester::with(services())
-            .given(vec![JourneyEvent::Started { id }])
-            .when(JourneyCommand::Start { id })
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
+            .when(JourneyCommand::Start {
+                id,
+                journey_type: None,
+            })
             .then_expect_error(JourneyError::AlreadyStarted);
     }
 
     #[test]
-    fn complete_not_started() {
+    fn start_deterministic_derives_the_id_and_rejects_a_retry() {
+        let start = || JourneyCommand::StartDeterministic {
+            namespace: JOURNEY_KEY_NAMESPACE,
+            name: "order-1234".to_string(),
+            journey_type: Some("flight-booking".to_string()),
+        };
+        let id = deterministic_journey_id(&JOURNEY_KEY_NAMESPACE, "order-1234");
+        let started = JourneyEvent::Started {
+            id,
+            journey_type: Some("flight-booking".to_string()),
+        };
+
         JourneyTester::with(services())
             .given_no_previous_events()
-            .when(JourneyCommand::Complete)
-            .then_expect_error(JourneyError::NotFound);
+            .when(start())
+            .then_expect_events(vec![started.clone()]);
+        JourneyTester::with(services())
+            .given(vec![started])
+            .when(start())
+            .then_expect_error(JourneyError::AlreadyStarted);
     }
 
-    #[test]
-    fn complete_already_completed() {
-        let id = Uuid::new_v4();
-        JourneyTester::with(services())
-            .given(vec![JourneyEvent::Started { id }, JourneyEvent::Completed])
-            .when(JourneyCommand::Complete)
-            .then_expect_error(JourneyError::AlreadyCompleted);
-    }
-
-    #[test]
-    fn modify_not_started() {
-        JourneyTester::with(services())
-            .given_no_previous_events()
-            .when(JourneyCommand::Capture {
-                step: "first_name".to_string(),
-                data: json!("Joe"),
-            })
-            .then_expect_error(JourneyError::NotFound);
+    // ── StartFrom ────────────────────────────────────────────────────────────
+
+    /// A source journey that captured a search and one passenger's passport.
+    fn rebooking_seed(subject_id: Uuid) -> JourneySeed {
+        JourneySeed {
+            journey_type: Some("flight-booking".to_string()),
+            shared_data: json!({
+                "search": { "origin": "LHR" },
+                "persons": { "passenger_0": { "passport": "AB123456" } },
+            }),
+            persons: vec![SeedPerson {
+                person_ref: "passenger_0".to_string(),
+                subject_id,
+                name: "Alice Smith".to_string(),
+                email: "alice@example.com".to_string(),
+                phone: None,
+            }],
+        }
     }
 
     #[test]
-    fn modify_already_completed() {
+    fn start_from_copies_the_data_without_the_people() {
         let id = Uuid::new_v4();
-        JourneyTester::with(services())
-            .given(vec![JourneyEvent::Started { id }, JourneyEvent::Completed])
-            .when(JourneyCommand::Capture {
-                step: "first_name".to_string(),
-                data: json!("Joe"),
-            })
-            .then_expect_error(JourneyError::AlreadyCompleted);
-    }
-
-    // ── Workflow evaluation ──────────────────────────────────────────────────
 
-    #[test]
-    fn automatic_workflow_evaluation_after_every_event() {
-        let id = Uuid::new_v4();
-        JourneyTester::with(services())
-            .given(vec![JourneyEvent::Started { id }])
-            .when(JourneyCommand::Capture {
-                step: "step-1".to_string(),
-                data: json!({
-                    "step": "personal_info",
-                    "email": "user@example.com",
-                    "name": "Alice"
-                }),
+        JourneyTester::with(services_with_attribute_schema(explicit_attribute_schema()))
+            .given_no_previous_events()
+            .when(JourneyCommand::StartFrom {
+                id,
+                source_id: Uuid::new_v4(),
+                include_person: false,
+                source: rebooking_seed(Uuid::new_v4()),
             })
             .then_expect_events(vec![
-                JourneyEvent::Modified {
-                    step: "step-1".to_string(),
-                    data: json!({
-                        "step": "personal_info",
-                        "email": "user@example.com",
-                        "name": "Alice"
-                    }),
-                },
-                JourneyEvent::WorkflowEvaluated {
-                    suggested_actions: vec![],
-                    phase: None,
+                JourneyEvent::Started {
+                    id,
+                    journey_type: Some("flight-booking".to_string()),
                 },
-                JourneyEvent::StepProgressed {
-                    from_step: None,
-                    to_step: "step-1".to_string(),
+                JourneyEvent::Modified {
+                    step: START_FROM_STEP.to_string(),
+                    data: json!({ "search": { "origin": "LHR" } }),
                 },
             ]);
     }
 
     #[test]
-    fn automatic_workflow_evaluation_for_specific_data() {
+    fn start_from_with_people_keeps_their_secrets_encrypted() {
         let id = Uuid::new_v4();
-        JourneyTester::with(services())
-            .given(vec![JourneyEvent::Started { id }])
-            .when(JourneyCommand::Capture {
-                step: "step-1".to_string(),
-                data: json!({
-                    "step": "personal_info",
-                    "email": "user@example.com",
-                    "first_name": "Alice"
-                }),
+        let subject_id = Uuid::new_v4();
+
+        JourneyTester::with(services_with_attribute_schema(explicit_attribute_schema()))
+            .given_no_previous_events()
+            .when(JourneyCommand::StartFrom {
+                id,
+                source_id: Uuid::new_v4(),
+                include_person: true,
+                source: rebooking_seed(subject_id),
             })
             .then_expect_events(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: Some("flight-booking".to_string()),
+                },
                 JourneyEvent::Modified {
-                    step: "step-1".to_string(),
-                    data: json!({
-                        "step": "personal_info",
-                        "email": "user@example.com",
-                        "first_name": "Alice"
-                    }),
+                    step: START_FROM_STEP.to_string(),
+                    data: json!({ "search": { "origin": "LHR" } }),
                 },
-                JourneyEvent::WorkflowEvaluated {
-                    suggested_actions: vec!["form_3".to_string()],
-                    phase: None,
+                JourneyEvent::PersonCaptured {
+                    person_ref: "passenger_0".to_string(),
+                    subject_id,
+                    name: "Alice Smith".to_string(),
+                    email: "alice@example.com".to_string(),
+                    phone: None,
                 },
-                JourneyEvent::StepProgressed {
-                    from_step: None,
-                    to_step: "step-1".to_string(),
+                JourneyEvent::AttributesSet {
+                    plaintext: BTreeMap::new(),
+                    secret_partitions: vec![SecretPartitionData {
+                        person_ref: "passenger_0".to_string(),
+                        subject_id,
+                        changes: BTreeMap::from([(
+                            "/persons/passenger_0/passport".parse().unwrap(),
+                            json!("AB123456"),
+                        )]),
+                    }],
                 },
             ]);
     }
 
-    // ── CapturePerson ────────────────────────────────────────────────────────
-
     #[test]
-    fn test_capture_person() {
+    fn start_from_an_empty_source_only_starts() {
         let id = Uuid::new_v4();
-        let subject_id = Uuid::new_v4();
 
         JourneyTester::with(services())
-            .given(vec![JourneyEvent::Started { id }])
-            .when(JourneyCommand::CapturePerson {
-                person_ref: "passenger_0".to_string(),
-                subject_id,
-                name: "Alice Smith".to_string(),
-                email: "alice@example.com".to_string(),
-                phone: Some("+44-7700-900000".to_string()),
+            .given_no_previous_events()
+            .when(JourneyCommand::StartFrom {
+                id,
+                source_id: Uuid::new_v4(),
+                include_person: true,
+                source: JourneySeed::default(),
             })
-            .then_expect_events(vec![JourneyEvent::PersonCaptured {
-                person_ref: "passenger_0".to_string(),
-                subject_id,
-                name: "Alice Smith".to_string(),
-                email: "alice@example.com".to_string(),
-                phone: Some("+44-7700-900000".to_string()),
+            .then_expect_events(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
             }]);
     }
 
     #[test]
-    fn test_capture_person_updates_identity_fields_for_same_subject() {
-        // Calling CapturePerson again with the same person_ref and subject_id
-        // is allowed — it updates the identity fields in place.
+    fn start_from_rejects_an_existing_journey() {
         let id = Uuid::new_v4();
-        let subject_id = Uuid::new_v4();
 
         JourneyTester::with(services())
-            .given(vec![
-                JourneyEvent::Started { id },
-                JourneyEvent::PersonCaptured {
-                    person_ref: "passenger_0".to_string(),
-                    subject_id,
-                    name: "Alice Smith".to_string(),
-                    email: "alice@example.com".to_string(),
-                    phone: None,
-                },
-            ])
-            .when(JourneyCommand::CapturePerson {
-                person_ref: "passenger_0".to_string(),
-                subject_id, // same subject_id — update allowed
-                name: "Alice J. Smith".to_string(),
-                email: "alice.new@example.com".to_string(),
-                phone: Some("+44-7700-900001".to_string()),
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
+            .when(JourneyCommand::StartFrom {
+                id,
+                source_id: Uuid::new_v4(),
+                include_person: false,
+                source: rebooking_seed(Uuid::new_v4()),
             })
-            .then_expect_events(vec![JourneyEvent::PersonCaptured {
-                person_ref: "passenger_0".to_string(),
-                subject_id,
-                name: "Alice J. Smith".to_string(),
-                email: "alice.new@example.com".to_string(),
-                phone: Some("+44-7700-900001".to_string()),
-            }]);
+            .then_expect_error(JourneyError::AlreadyStarted);
     }
 
     #[test]
-    fn test_capture_person_conflict_rejects_different_subject_for_same_ref() {
-        // Reusing a person_ref with a different subject_id is an error.
-        let id = Uuid::new_v4();
-        let subject_id_a = Uuid::new_v4();
-        let subject_id_b = Uuid::new_v4();
+    fn start_from_rejects_a_seed_over_the_size_limit() {
+        let result = JourneyTester::with(services().with_max_shared_data_size(1))
+            .given_no_previous_events()
+            .when(JourneyCommand::StartFrom {
+                id: Uuid::new_v4(),
+                source_id: Uuid::new_v4(),
+                include_person: false,
+                source: rebooking_seed(Uuid::new_v4()),
+            })
+            .inspect_result();
+        assert_matches!(result, Err(JourneyError::DataTooLarge { limit: 1, .. }));
+    }
+
+    #[test]
+    fn complete_not_started() {
+        JourneyTester::with(services())
+            .given_no_previous_events()
+            .when(JourneyCommand::Complete)
+            .then_expect_error(JourneyError::NotFound);
+    }
 
+    #[test]
+    fn complete_already_completed() {
+        let id = Uuid::new_v4();
         JourneyTester::with(services())
             .given(vec![
-                JourneyEvent::Started { id },
-                JourneyEvent::PersonCaptured {
-                    person_ref: "passenger_0".to_string(),
-                    subject_id: subject_id_a,
-                    name: "Alice Smith".to_string(),
-                    email: "alice@example.com".to_string(),
-                    phone: None,
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
                 },
+                JourneyEvent::Completed,
             ])
-            .when(JourneyCommand::CapturePerson {
-                person_ref: "passenger_0".to_string(),
-                subject_id: subject_id_b, // different subject — must be rejected
-                name: "Bob Jones".to_string(),
-                email: "bob@example.com".to_string(),
-                phone: None,
-            })
-            .then_expect_error(JourneyError::PersonRefConflict("passenger_0".to_string()));
+            .when(JourneyCommand::Complete)
+            .then_expect_error(JourneyError::AlreadyCompleted);
     }
 
     #[test]
-    fn test_capture_multiple_persons_independently() {
-        // Two different passengers in the same journey.
+    fn complete_ignores_outstanding_steps_by_default() {
         let id = Uuid::new_v4();
-        let subject_a = Uuid::new_v4();
-        let subject_b = Uuid::new_v4();
-
         JourneyTester::with(services())
             .given(vec![
-                JourneyEvent::Started { id },
-                JourneyEvent::PersonCaptured {
-                    person_ref: "passenger_0".to_string(),
-                    subject_id: subject_a,
-                    name: "Alice Smith".to_string(),
-                    email: "alice@example.com".to_string(),
-                    phone: None,
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::Modified {
+                    step: "form_1".to_string(),
+                    data: json!({ "form_1": { "first_name": "Joe" } }),
                 },
             ])
-            .when(JourneyCommand::CapturePerson {
-                person_ref: "passenger_1".to_string(),
-                subject_id: subject_b,
-                name: "Bob Jones".to_string(),
-                email: "bob@example.com".to_string(),
-                phone: None,
-            })
-            .then_expect_events(vec![JourneyEvent::PersonCaptured {
-                person_ref: "passenger_1".to_string(),
-                subject_id: subject_b,
-                name: "Bob Jones".to_string(),
-                email: "bob@example.com".to_string(),
-                phone: None,
-            }]);
+            .when(JourneyCommand::Complete)
+            .then_expect_events(vec![JourneyEvent::Completed]);
     }
 
     #[test]
-    fn test_capture_person_journey_not_started() {
-        JourneyTester::with(services())
-            .given_no_previous_events()
-            .when(JourneyCommand::CapturePerson {
-                person_ref: "passenger_0".to_string(),
-                subject_id: Uuid::new_v4(),
-                name: "Alice Smith".to_string(),
-                email: "alice@example.com".to_string(),
-                phone: None,
-            })
-            .then_expect_error(JourneyError::NotFound);
+    fn complete_rejected_when_engine_says_cannot_complete() {
+        let id = Uuid::new_v4();
+        let engine = MockDecisionEngine::with_actions(vec![vec!["form_3".to_string()]]);
+        JourneyTester::with(services_with_decision_engine(engine).with_can_complete_check(true))
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
+            .when(JourneyCommand::Complete)
+            .then_expect_error(JourneyError::CannotComplete {
+                missing: vec!["form_3".to_string()],
+            });
     }
 
     #[test]
-    fn test_capture_person_journey_completed() {
+    fn complete_evaluates_the_current_step_with_nothing_new() {
         let id = Uuid::new_v4();
-        JourneyTester::with(services())
-            .given(vec![JourneyEvent::Started { id }, JourneyEvent::Completed])
-            .when(JourneyCommand::CapturePerson {
-                person_ref: "passenger_0".to_string(),
-                subject_id: Uuid::new_v4(),
-                name: "Alice Smith".to_string(),
-                email: "alice@example.com".to_string(),
-                phone: None,
+        let engine = MockDecisionEngine::with_fn(|_, step, new_data| {
+            assert_eq!(step, "form_2");
+            assert_eq!(new_data, &json!({}));
+            Ok(WorkflowDecision {
+                suggested_actions: vec!["form_3".to_string()],
+                phase: None,
+                ranked_actions: vec![],
+                can_complete: false,
+                completion_blocked_by: vec!["payment".to_string()],
             })
-            .then_expect_error(JourneyError::AlreadyCompleted);
+        });
+        JourneyTester::with(services_with_decision_engine(engine).with_can_complete_check(true))
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::StepProgressed {
+                    from_step: None,
+                    to_step: "form_2".to_string(),
+                },
+            ])
+            .when(JourneyCommand::Complete)
+            .then_expect_error(JourneyError::CannotComplete {
+                missing: vec!["payment".to_string()],
+            });
     }
 
-    // ── CapturePersonDetails ─────────────────────────────────────────────────
-
     #[test]
-    fn test_capture_person_details() {
+    fn complete_accepted_when_engine_says_can_complete() {
         let id = Uuid::new_v4();
-        let subject_id = Uuid::new_v4();
+        JourneyTester::with(services().with_can_complete_check(true))
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
+            .when(JourneyCommand::Complete)
+            .then_expect_events(vec![JourneyEvent::Completed]);
+    }
 
-        JourneyTester::with(services())
+    #[test]
+    fn complete_accepted_by_a_table_once_the_last_step_is_captured() {
+        let id = Uuid::new_v4();
+        let table = TableDecisionEngine::from_json(
+            r#"{
+                "search": { "requires": ["search.origin"], "next": ["payment"] },
+                "payment": { "requires": ["payment.card"] }
+            }"#,
+        )
+        .unwrap();
+        let services = JourneyServices::new(
+            Arc::new(table),
+            create_test_schema_validator(),
+            Arc::new(AttributeSchema::permissive()),
+        )
+        .with_can_complete_check(true);
+        JourneyTester::with(services)
             .given(vec![
-                JourneyEvent::Started { id },
-                JourneyEvent::PersonCaptured {
-                    person_ref: "passenger_0".to_string(),
-                    subject_id,
-                    name: "Alice Smith".to_string(),
-                    email: "alice@example.com".to_string(),
-                    phone: None,
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
                 },
-            ])
-            .when(JourneyCommand::CapturePersonDetails {
-                person_ref: "passenger_0".to_string(),
-                data: json!({
-                    "passportNumber": "GB123456789",
-                    "dateOfBirth":    "1990-05-15",
-                    "nationality":    "GB"
-                }),
-            })
-            .then_expect_events(vec![JourneyEvent::PersonDetailsUpdated {
-                person_ref: "passenger_0".to_string(),
-                subject_id, // copied from the slot by the aggregate
-                data: json!({
-                    "passportNumber": "GB123456789",
-                    "dateOfBirth":    "1990-05-15",
-                    "nationality":    "GB"
-                }),
-            }]);
+                JourneyEvent::Modified {
+                    step: "payment".to_string(),
+                    data: json!({ "payment": { "card": "4111" } }),
+                },
+                JourneyEvent::WorkflowEvaluated {
+                    suggested_actions: vec![],
+                    phase: None,
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec![],
+                },
+                JourneyEvent::StepProgressed {
+                    from_step: None,
+                    to_step: "payment".to_string(),
+                },
+            ])
+            .when(JourneyCommand::Complete)
+            .then_expect_events(vec![JourneyEvent::Completed]);
     }
 
     #[test]
-    fn test_capture_person_details_uses_subject_id_from_slot() {
-        // The emitted event carries the subject_id from the existing slot, not
-        // one supplied by the caller — CapturePersonDetails has no subject_id field.
+    fn complete_rejected_while_latest_decision_blocks_completion() {
         let id = Uuid::new_v4();
-        let subject_id = Uuid::new_v4();
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::WorkflowEvaluated {
+                    suggested_actions: vec!["payment".to_string()],
+                    phase: None,
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec!["payment".to_string()],
+                },
+            ])
+            .when(JourneyCommand::Complete)
+            .then_expect_error(JourneyError::CannotComplete {
+                missing: vec!["payment".to_string()],
+            });
+    }
 
+    #[test]
+    fn complete_accepted_once_a_later_decision_clears_the_block() {
+        let id = Uuid::new_v4();
         JourneyTester::with(services())
             .given(vec![
-                JourneyEvent::Started { id },
-                JourneyEvent::PersonCaptured {
-                    person_ref: "lead_booker".to_string(),
-                    subject_id,
-                    name: "Alice Smith".to_string(),
-                    email: "alice@example.com".to_string(),
-                    phone: None,
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::WorkflowEvaluated {
+                    suggested_actions: vec!["payment".to_string()],
+                    phase: None,
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec!["payment".to_string()],
+                },
+                JourneyEvent::WorkflowEvaluated {
+                    suggested_actions: vec![],
+                    phase: Some("done".to_string()),
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec![],
                 },
             ])
-            .when(JourneyCommand::CapturePersonDetails {
-                person_ref: "lead_booker".to_string(),
-                data: json!({ "dateOfBirth": "1990-01-01" }),
-            })
-            .then_expect_events(vec![JourneyEvent::PersonDetailsUpdated {
-                person_ref: "lead_booker".to_string(),
-                subject_id, // must match the subject captured above
-                data: json!({ "dateOfBirth": "1990-01-01" }),
-            }]);
+            .when(JourneyCommand::Complete)
+            .then_expect_events(vec![JourneyEvent::Completed]);
     }
 
     #[test]
-    fn test_capture_person_details_not_started() {
+    fn modify_not_started() {
         JourneyTester::with(services())
             .given_no_previous_events()
-            .when(JourneyCommand::CapturePersonDetails {
-                person_ref: "passenger_0".to_string(),
-                data: json!({ "passportNumber": "GB123456789" }),
+            .when(JourneyCommand::Capture {
+                step: "first_name".to_string(),
+                data: json!("Joe"),
             })
             .then_expect_error(JourneyError::NotFound);
     }
 
     #[test]
-    fn test_capture_person_details_journey_completed() {
+    fn modify_already_completed() {
         let id = Uuid::new_v4();
-        let subject_id = Uuid::new_v4();
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::Completed,
+            ])
+            .when(JourneyCommand::Capture {
+                step: "first_name".to_string(),
+                data: json!("Joe"),
+            })
+            .then_expect_error(JourneyError::AlreadyCompleted);
+    }
+
+    #[test]
+    fn every_mutating_command_is_rejected_once_completed() {
+        let id = Uuid::new_v4();
+        let commands = vec![
+            JourneyCommand::Capture {
+                step: "search".to_string(),
+                data: json!({ "origin": "LHR" }),
+            },
+            JourneyCommand::CaptureMany {
+                captures: vec![("search".to_string(), json!({ "origin": "LHR" }))],
+            },
+            JourneyCommand::SetAttributes {
+                changes: BTreeMap::from([("/origin".parse().unwrap(), json!("LHR"))]),
+            },
+            JourneyCommand::Correct {
+                path: "origin".to_string(),
+                value: json!("LHR"),
+            },
+            JourneyCommand::CapturePerson {
+                person_ref: "lead_booker".to_string(),
+                subject_id: Uuid::new_v4(),
+                name: "Alice Smith".to_string(),
+                email: "alice@example.com".to_string(),
+                phone: None,
+            },
+            JourneyCommand::CapturePersonDetails {
+                person_ref: "lead_booker".to_string(),
+                data: json!({ "nationality": "GB" }),
+            },
+            JourneyCommand::Complete,
+            JourneyCommand::Reevaluate,
+            JourneyCommand::Pause,
+        ];
+        for command in commands {
+            JourneyTester::with(services())
+                .given(vec![
+                    JourneyEvent::Started {
+                        id,
+                        journey_type: None,
+                    },
+                    JourneyEvent::Modified {
+                        step: "search".to_string(),
+                        data: json!({ "origin": "LHX" }),
+                    },
+                    JourneyEvent::Completed,
+                ])
+                .when(command)
+                .then_expect_error(JourneyError::AlreadyCompleted);
+        }
+    }
+
+    // ── Reopen ───────────────────────────────────────────────────────────────
 
+    #[test]
+    fn reopen_completed_journey() {
+        let id = Uuid::new_v4();
         JourneyTester::with(services())
             .given(vec![
-                JourneyEvent::Started { id },
-                JourneyEvent::PersonCaptured {
-                    person_ref: "passenger_0".to_string(),
-                    subject_id,
-                    name: "Alice Smith".to_string(),
-                    email: "alice@example.com".to_string(),
-                    phone: None,
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
                 },
                 JourneyEvent::Completed,
             ])
-            .when(JourneyCommand::CapturePersonDetails {
-                person_ref: "passenger_0".to_string(),
-                data: json!({ "passportNumber": "GB123456789" }),
+            .when(JourneyCommand::Reopen)
+            .then_expect_events(vec![JourneyEvent::Reopened]);
+    }
+
+    #[test]
+    fn reopen_in_progress_journey_is_rejected() {
+        let id = Uuid::new_v4();
+        JourneyTester::with(services())
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
+            .when(JourneyCommand::Reopen)
+            .then_expect_error(JourneyError::NotCompleted);
+    }
+
+    #[test]
+    fn reopen_forgotten_journey_is_rejected() {
+        let id = Uuid::new_v4();
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::Completed,
+                JourneyEvent::Forgotten,
+            ])
+            .when(JourneyCommand::Reopen)
+            .then_expect_error(JourneyError::Forgotten);
+    }
+
+    #[test]
+    fn reopen_not_started() {
+        JourneyTester::with(services())
+            .given_no_previous_events()
+            .when(JourneyCommand::Reopen)
+            .then_expect_error(JourneyError::NotFound);
+    }
+
+    #[test]
+    fn reopened_journey_keeps_its_step_and_data() {
+        let id = Uuid::new_v4();
+        let mut journey = Journey::default();
+        for event in [
+            JourneyEvent::Started {
+                id,
+                journey_type: None,
+            },
+            JourneyEvent::Modified {
+                step: "first_name".to_string(),
+                data: json!({ "first_name": "Joe" }),
+            },
+            JourneyEvent::StepProgressed {
+                from_step: None,
+                to_step: "first_name".to_string(),
+            },
+            JourneyEvent::Completed,
+            JourneyEvent::Reopened,
+        ] {
+            journey.apply(event);
+        }
+
+        assert_eq!(journey.state, JourneyState::InProgress);
+        assert_eq!(journey.current_step.as_deref(), Some("first_name"));
+        assert_eq!(journey.shared_data, json!({ "first_name": "Joe" }));
+    }
+
+    #[test]
+    fn capture_accepted_after_reopen() {
+        let id = Uuid::new_v4();
+        let result = JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::Completed,
+                JourneyEvent::Reopened,
+            ])
+            .when(JourneyCommand::Capture {
+                step: "first_name".to_string(),
+                data: json!("Joe"),
             })
+            .inspect_result();
+
+        assert!(result.is_ok());
+    }
+
+    // ── Pause / Resume ───────────────────────────────────────────────────────
+
+    fn paused_at(at: &str) -> JourneyEvent {
+        JourneyEvent::Paused {
+            at: at.parse().unwrap(),
+        }
+    }
+
+    fn resumed_at(at: &str) -> JourneyEvent {
+        JourneyEvent::Resumed {
+            at: at.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn pause_in_progress_journey() {
+        let id = Uuid::new_v4();
+        let before = Utc::now();
+        let events = JourneyTester::with(services())
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
+            .when(JourneyCommand::Pause)
+            .inspect_result()
+            .unwrap();
+
+        assert_matches!(events.as_slice(), [JourneyEvent::Paused { at }] if *at >= before);
+    }
+
+    #[test]
+    fn pause_not_started() {
+        JourneyTester::with(services())
+            .given_no_previous_events()
+            .when(JourneyCommand::Pause)
+            .then_expect_error(JourneyError::NotFound);
+    }
+
+    #[test]
+    fn resume_not_started() {
+        JourneyTester::with(services())
+            .given_no_previous_events()
+            .when(JourneyCommand::Resume)
+            .then_expect_error(JourneyError::NotFound);
+    }
+
+    #[test]
+    fn pause_already_completed() {
+        let id = Uuid::new_v4();
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::Completed,
+            ])
+            .when(JourneyCommand::Pause)
             .then_expect_error(JourneyError::AlreadyCompleted);
     }
 
     #[test]
-    fn test_capture_person_details_slot_not_found() {
-        // CapturePersonDetails requires CapturePerson to have been called first.
+    fn pause_already_paused_is_noop() {
+        let id = Uuid::new_v4();
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                paused_at("2025-08-15T10:00:00Z"),
+            ])
+            .when(JourneyCommand::Pause)
+            .then_expect_events(vec![]);
+    }
+
+    #[test]
+    fn capture_rejected_while_paused() {
+        let id = Uuid::new_v4();
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                paused_at("2025-08-15T10:00:00Z"),
+            ])
+            .when(JourneyCommand::Capture {
+                step: "first_name".to_string(),
+                data: json!("Joe"),
+            })
+            .then_expect_error(JourneyError::Paused);
+    }
+
+    #[test]
+    fn set_attributes_rejected_while_paused() {
+        let id = Uuid::new_v4();
+        let mut changes = BTreeMap::new();
+        changes.insert(
+            "/search/origin".parse::<PointerBuf>().unwrap(),
+            json!("LHR"),
+        );
+
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                paused_at("2025-08-15T10:00:00Z"),
+            ])
+            .when(JourneyCommand::SetAttributes { changes })
+            .then_expect_error(JourneyError::Paused);
+    }
+
+    #[test]
+    fn complete_rejected_while_paused() {
+        let id = Uuid::new_v4();
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                paused_at("2025-08-15T10:00:00Z"),
+            ])
+            .when(JourneyCommand::Complete)
+            .then_expect_error(JourneyError::Paused);
+    }
+
+    #[test]
+    fn resume_paused_journey() {
+        let id = Uuid::new_v4();
+        let events = JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                paused_at("2025-08-15T10:00:00Z"),
+            ])
+            .when(JourneyCommand::Resume)
+            .inspect_result()
+            .unwrap();
+
+        assert_matches!(events.as_slice(), [JourneyEvent::Resumed { .. }]);
+    }
+
+    #[test]
+    fn resume_not_paused_is_noop() {
+        let id = Uuid::new_v4();
+        JourneyTester::with(services())
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
+            .when(JourneyCommand::Resume)
+            .then_expect_events(vec![]);
+    }
+
+    #[test]
+    fn capture_accepted_after_resume() {
+        let id = Uuid::new_v4();
+        let result = JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                paused_at("2025-08-15T10:00:00Z"),
+                resumed_at("2025-08-15T10:05:00Z"),
+            ])
+            .when(JourneyCommand::Capture {
+                step: "first_name".to_string(),
+                data: json!("Joe"),
+            })
+            .inspect_result();
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_apply_accumulates_paused_duration() {
+        let id = Uuid::new_v4();
+        let mut journey = Journey::default();
+        journey.apply(JourneyEvent::Started {
+            id,
+            journey_type: None,
+        });
+
+        journey.apply(paused_at("2025-08-15T10:00:00Z"));
+        assert_eq!(journey.state(), JourneyState::Paused);
+        assert_eq!(
+            journey.paused_at(),
+            Some("2025-08-15T10:00:00Z".parse().unwrap())
+        );
+        assert_eq!(journey.paused_duration(), Duration::ZERO);
+
+        journey.apply(resumed_at("2025-08-15T10:01:30Z"));
+        journey.apply(paused_at("2025-08-15T11:00:00Z"));
+        journey.apply(resumed_at("2025-08-15T11:00:45Z"));
+
+        assert_eq!(journey.state(), JourneyState::InProgress);
+        assert_eq!(journey.paused_at(), None);
+        assert_eq!(journey.paused_duration(), Duration::from_secs(135));
+    }
+
+    // ── Workflow evaluation ──────────────────────────────────────────────────
+
+    #[test]
+    fn automatic_workflow_evaluation_after_every_event() {
+        let id = Uuid::new_v4();
+        JourneyTester::with(services())
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
+            .when(JourneyCommand::Capture {
+                step: "step-1".to_string(),
+                data: json!({
+                    "step": "personal_info",
+                    "email": "user@example.com",
+                    "name": "Alice"
+                }),
+            })
+            .then_expect_events(vec![
+                JourneyEvent::Modified {
+                    step: "step-1".to_string(),
+                    data: json!({
+                        "step": "personal_info",
+                        "email": "user@example.com",
+                        "name": "Alice"
+                    }),
+                },
+                JourneyEvent::WorkflowEvaluated {
+                    suggested_actions: vec![],
+                    phase: None,
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec![],
+                },
+                JourneyEvent::StepProgressed {
+                    from_step: None,
+                    to_step: "step-1".to_string(),
+                },
+            ]);
+    }
+
+    #[test]
+    fn automatic_workflow_evaluation_for_specific_data() {
+        let id = Uuid::new_v4();
+        JourneyTester::with(services())
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
+            .when(JourneyCommand::Capture {
+                step: "step-1".to_string(),
+                data: json!({
+                    "step": "personal_info",
+                    "email": "user@example.com",
+                    "first_name": "Alice"
+                }),
+            })
+            .then_expect_events(vec![
+                JourneyEvent::Modified {
+                    step: "step-1".to_string(),
+                    data: json!({
+                        "step": "personal_info",
+                        "email": "user@example.com",
+                        "first_name": "Alice"
+                    }),
+                },
+                JourneyEvent::WorkflowEvaluated {
+                    suggested_actions: vec!["form_3".to_string()],
+                    phase: None,
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec![],
+                },
+                JourneyEvent::StepProgressed {
+                    from_step: None,
+                    to_step: "step-1".to_string(),
+                },
+            ]);
+    }
+
+    #[test]
+    fn reevaluate_records_a_fresh_decision_without_capturing() {
+        let id = Uuid::new_v4();
+        // The model changed since the journey's last capture.
+        JourneyTester::with(services())
+            .given(
+                JourneyEventsBuilder::new()
+                    .started(id)
+                    .captured("name", json!({ "name": { "first_name": "Alice" } }))
+                    .evaluated(&["retired_step"])
+                    .build(),
+            )
+            .when(JourneyCommand::Reevaluate)
+            .then_expect_events(vec![JourneyEvent::WorkflowEvaluated {
+                suggested_actions: vec!["form_3".to_string()],
+                phase: None,
+                ranked_actions: vec![],
+                completion_blocked_by: vec![],
+            }]);
+    }
+
+    #[test]
+    fn reevaluate_rejects_a_forgotten_journey() {
+        let id = Uuid::new_v4();
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::Forgotten,
+            ])
+            .when(JourneyCommand::Reevaluate)
+            .then_expect_error(JourneyError::Forgotten);
+    }
+
+    /// A journey whose last decision suggested `form_3`, at step `step-1`.
+    fn with_pending_action(id: Uuid) -> Vec<JourneyEvent> {
+        JourneyEventsBuilder::new()
+            .started(id)
+            .evaluated(&["form_3"])
+            .progressed(None, "step-1")
+            .build()
+    }
+
+    #[test]
+    fn workflow_satisfied_when_suggested_actions_run_out() {
+        JourneyTester::with(services())
+            .given(with_pending_action(Uuid::new_v4()))
+            .when(JourneyCommand::Capture {
+                step: "step-1".to_string(),
+                data: json!({ "name": "Alice" }),
+            })
+            .then_expect_events(vec![
+                JourneyEvent::Modified {
+                    step: "step-1".to_string(),
+                    data: json!({ "name": "Alice" }),
+                },
+                JourneyEvent::WorkflowEvaluated {
+                    suggested_actions: vec![],
+                    phase: None,
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec![],
+                },
+                JourneyEvent::WorkflowSatisfied,
+            ]);
+    }
+
+    #[test]
+    fn workflow_satisfied_is_recorded_only_once() {
+        let mut given = with_pending_action(Uuid::new_v4());
+        given.extend([
+            JourneyEvent::WorkflowEvaluated {
+                suggested_actions: vec![],
+                phase: None,
+                ranked_actions: vec![],
+                completion_blocked_by: vec![],
+            },
+            JourneyEvent::WorkflowSatisfied,
+            // The model asked for more, and the user supplied it.
+            JourneyEvent::WorkflowEvaluated {
+                suggested_actions: vec!["form_3".to_string()],
+                phase: None,
+                ranked_actions: vec![],
+                completion_blocked_by: vec![],
+            },
+        ]);
+
+        JourneyTester::with(services())
+            .given(given)
+            .when(JourneyCommand::Reevaluate)
+            .then_expect_events(vec![JourneyEvent::WorkflowEvaluated {
+                suggested_actions: vec![],
+                phase: None,
+                ranked_actions: vec![],
+                completion_blocked_by: vec![],
+            }]);
+    }
+
+    #[test]
+    fn workflow_not_satisfied_without_earlier_suggestions() {
+        let id = Uuid::new_v4();
+        JourneyTester::with(services())
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
+            .when(JourneyCommand::Reevaluate)
+            .then_expect_events(vec![JourneyEvent::WorkflowEvaluated {
+                suggested_actions: vec![],
+                phase: None,
+                ranked_actions: vec![],
+                completion_blocked_by: vec![],
+            }]);
+    }
+
+    struct SlowDecisionEngine;
+
+    #[async_trait::async_trait]
+    impl DecisionEngine for SlowDecisionEngine {
+        async fn evaluate_next_steps(
+            &self,
+            _journey: &Journey,
+            _current_step: &str,
+            _new_data: &Value,
+        ) -> Result<WorkflowDecision, DecisionEngineError> {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            unreachable!("the decision timeout fires first")
+        }
+    }
+
+    #[tokio::test]
+    async fn decision_engine_timeout_fails_the_command() {
+        let services = JourneyServices::new(
+            Arc::new(SlowDecisionEngine),
+            create_test_schema_validator(),
+            Arc::new(AttributeSchema::permissive()),
+        )
+        .with_decision_timeout(Duration::from_millis(20));
+        let mut journey = Journey::default();
+        journey.apply(JourneyEvent::Started {
+            id: Uuid::new_v4(),
+            journey_type: None,
+        });
+
+        let err = journey
+            .handle(
+                JourneyCommand::Capture {
+                    step: "step-1".to_string(),
+                    data: json!({ "name": "Alice" }),
+                },
+                &services,
+                &EventSink::default(),
+            )
+            .await
+            .unwrap_err();
+
+        let JourneyError::DecisionEngineError(err) = err else {
+            panic!("expected a decision engine error, got {err:?}");
+        };
+        assert_eq!(err.message, "decision engine timed out after 20 ms");
+        assert!(err.transient);
+    }
+
+    // ── CapturePerson ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_capture_person() {
+        let id = Uuid::new_v4();
+        let subject_id = Uuid::new_v4();
+
+        JourneyTester::with(services())
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
+            .when(JourneyCommand::CapturePerson {
+                person_ref: "passenger_0".to_string(),
+                subject_id,
+                name: "Alice Smith".to_string(),
+                email: "alice@example.com".to_string(),
+                phone: Some("+44-7700-900000".to_string()),
+            })
+            .then_expect_events(vec![JourneyEvent::PersonCaptured {
+                person_ref: "passenger_0".to_string(),
+                subject_id,
+                name: "Alice Smith".to_string(),
+                email: "alice@example.com".to_string(),
+                phone: Some("+44-7700-900000".to_string()),
+            }]);
+    }
+
+    #[test]
+    fn test_capture_person_updates_identity_fields_for_same_subject() {
+        // Calling CapturePerson again with the same person_ref and subject_id
+        // is allowed — it updates the identity fields in place.
+        let id = Uuid::new_v4();
+        let subject_id = Uuid::new_v4();
+
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::PersonCaptured {
+                    person_ref: "passenger_0".to_string(),
+                    subject_id,
+                    name: "Alice Smith".to_string(),
+                    email: "alice@example.com".to_string(),
+                    phone: None,
+                },
+            ])
+            .when(JourneyCommand::CapturePerson {
+                person_ref: "passenger_0".to_string(),
+                subject_id, // same subject_id — update allowed
+                name: "Alice J. Smith".to_string(),
+                email: "alice.new@example.com".to_string(),
+                phone: Some("+44-7700-900001".to_string()),
+            })
+            .then_expect_events(vec![JourneyEvent::PersonCaptured {
+                person_ref: "passenger_0".to_string(),
+                subject_id,
+                name: "Alice J. Smith".to_string(),
+                email: "alice.new@example.com".to_string(),
+                phone: Some("+44-7700-900001".to_string()),
+            }]);
+    }
+
+    #[test]
+    fn test_capture_person_normalizes_email() {
+        let id = Uuid::new_v4();
+        let subject_id = Uuid::new_v4();
+
+        JourneyTester::with(services())
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
+            .when(JourneyCommand::CapturePerson {
+                person_ref: "passenger_0".to_string(),
+                subject_id,
+                name: "Alice Smith".to_string(),
+                email: " Alice.Smith@Example.COM ".to_string(),
+                phone: None,
+            })
+            .then_expect_events(vec![JourneyEvent::PersonCaptured {
+                person_ref: "passenger_0".to_string(),
+                subject_id,
+                name: "Alice Smith".to_string(),
+                email: "alice.smith@example.com".to_string(),
+                phone: None,
+            }]);
+    }
+
+    #[test]
+    fn test_capture_person_rejects_empty_name() {
+        let id = Uuid::new_v4();
+
+        JourneyTester::with(services())
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
+            .when(JourneyCommand::CapturePerson {
+                person_ref: "passenger_0".to_string(),
+                subject_id: Uuid::new_v4(),
+                name: "  ".to_string(),
+                email: "alice@example.com".to_string(),
+                phone: None,
+            })
+            .then_expect_error(JourneyError::InvalidPerson(
+                "name must not be empty".to_string(),
+            ));
+    }
+
+    #[test]
+    fn test_capture_person_rejects_malformed_email() {
+        let id = Uuid::new_v4();
+
+        for email in ["", "alice", "alice@", "@example.com", "alice@@example.com"] {
+            JourneyTester::with(services())
+                .given(vec![JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                }])
+                .when(JourneyCommand::CapturePerson {
+                    person_ref: "passenger_0".to_string(),
+                    subject_id: Uuid::new_v4(),
+                    name: "Alice Smith".to_string(),
+                    email: email.to_string(),
+                    phone: None,
+                })
+                .then_expect_error(JourneyError::InvalidPerson(format!(
+                    "'{email}' is not a valid email address"
+                )));
+        }
+    }
+
+    #[test]
+    fn test_capture_person_conflict_rejects_different_subject_for_same_ref() {
+        // Reusing a person_ref with a different subject_id is an error.
+        let id = Uuid::new_v4();
+        let subject_id_a = Uuid::new_v4();
+        let subject_id_b = Uuid::new_v4();
+
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::PersonCaptured {
+                    person_ref: "passenger_0".to_string(),
+                    subject_id: subject_id_a,
+                    name: "Alice Smith".to_string(),
+                    email: "alice@example.com".to_string(),
+                    phone: None,
+                },
+            ])
+            .when(JourneyCommand::CapturePerson {
+                person_ref: "passenger_0".to_string(),
+                subject_id: subject_id_b, // different subject — must be rejected
+                name: "Bob Jones".to_string(),
+                email: "bob@example.com".to_string(),
+                phone: None,
+            })
+            .then_expect_error(JourneyError::PersonRefConflict("passenger_0".to_string()));
+    }
+
+    #[test]
+    fn test_capture_multiple_persons_independently() {
+        // Two different passengers in the same journey.
+        let id = Uuid::new_v4();
+        let subject_a = Uuid::new_v4();
+        let subject_b = Uuid::new_v4();
+
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::PersonCaptured {
+                    person_ref: "passenger_0".to_string(),
+                    subject_id: subject_a,
+                    name: "Alice Smith".to_string(),
+                    email: "alice@example.com".to_string(),
+                    phone: None,
+                },
+            ])
+            .when(JourneyCommand::CapturePerson {
+                person_ref: "passenger_1".to_string(),
+                subject_id: subject_b,
+                name: "Bob Jones".to_string(),
+                email: "bob@example.com".to_string(),
+                phone: None,
+            })
+            .then_expect_events(vec![JourneyEvent::PersonCaptured {
+                person_ref: "passenger_1".to_string(),
+                subject_id: subject_b,
+                name: "Bob Jones".to_string(),
+                email: "bob@example.com".to_string(),
+                phone: None,
+            }]);
+    }
+
+    #[test]
+    fn test_capture_person_journey_not_started() {
+        JourneyTester::with(services())
+            .given_no_previous_events()
+            .when(JourneyCommand::CapturePerson {
+                person_ref: "passenger_0".to_string(),
+                subject_id: Uuid::new_v4(),
+                name: "Alice Smith".to_string(),
+                email: "alice@example.com".to_string(),
+                phone: None,
+            })
+            .then_expect_error(JourneyError::NotFound);
+    }
+
+    #[test]
+    fn test_capture_person_journey_completed() {
+        let id = Uuid::new_v4();
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::Completed,
+            ])
+            .when(JourneyCommand::CapturePerson {
+                person_ref: "passenger_0".to_string(),
+                subject_id: Uuid::new_v4(),
+                name: "Alice Smith".to_string(),
+                email: "alice@example.com".to_string(),
+                phone: None,
+            })
+            .then_expect_error(JourneyError::AlreadyCompleted);
+    }
+
+    // ── CapturePersonDetails ─────────────────────────────────────────────────
+
+    #[test]
+    fn test_capture_person_details() {
+        let id = Uuid::new_v4();
+        let subject_id = Uuid::new_v4();
+
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::PersonCaptured {
+                    person_ref: "passenger_0".to_string(),
+                    subject_id,
+                    name: "Alice Smith".to_string(),
+                    email: "alice@example.com".to_string(),
+                    phone: None,
+                },
+            ])
+            .when(JourneyCommand::CapturePersonDetails {
+                person_ref: "passenger_0".to_string(),
+                data: json!({
+                    "passportNumber": "GB123456789",
+                    "dateOfBirth":    "1990-05-15",
+                    "nationality":    "GB"
+                }),
+            })
+            .then_expect_events(vec![JourneyEvent::PersonDetailsUpdated {
+                person_ref: "passenger_0".to_string(),
+                subject_id, // copied from the slot by the aggregate
+                data: json!({
+                    "passportNumber": "GB123456789",
+                    "dateOfBirth":    "1990-05-15",
+                    "nationality":    "GB"
+                }),
+            }]);
+    }
+
+    #[test]
+    fn test_capture_person_details_uses_subject_id_from_slot() {
+        // The emitted event carries the subject_id from the existing slot, not
+        // one supplied by the caller — CapturePersonDetails has no subject_id field.
+        let id = Uuid::new_v4();
+        let subject_id = Uuid::new_v4();
+
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::PersonCaptured {
+                    person_ref: "lead_booker".to_string(),
+                    subject_id,
+                    name: "Alice Smith".to_string(),
+                    email: "alice@example.com".to_string(),
+                    phone: None,
+                },
+            ])
+            .when(JourneyCommand::CapturePersonDetails {
+                person_ref: "lead_booker".to_string(),
+                data: json!({ "dateOfBirth": "1990-01-01" }),
+            })
+            .then_expect_events(vec![JourneyEvent::PersonDetailsUpdated {
+                person_ref: "lead_booker".to_string(),
+                subject_id, // must match the subject captured above
+                data: json!({ "dateOfBirth": "1990-01-01" }),
+            }]);
+    }
+
+    #[test]
+    fn test_capture_person_details_not_started() {
+        JourneyTester::with(services())
+            .given_no_previous_events()
+            .when(JourneyCommand::CapturePersonDetails {
+                person_ref: "passenger_0".to_string(),
+                data: json!({ "passportNumber": "GB123456789" }),
+            })
+            .then_expect_error(JourneyError::NotFound);
+    }
+
+    #[test]
+    fn test_capture_person_details_journey_completed() {
+        let id = Uuid::new_v4();
+        let subject_id = Uuid::new_v4();
+
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::PersonCaptured {
+                    person_ref: "passenger_0".to_string(),
+                    subject_id,
+                    name: "Alice Smith".to_string(),
+                    email: "alice@example.com".to_string(),
+                    phone: None,
+                },
+                JourneyEvent::Completed,
+            ])
+            .when(JourneyCommand::CapturePersonDetails {
+                person_ref: "passenger_0".to_string(),
+                data: json!({ "passportNumber": "GB123456789" }),
+            })
+            .then_expect_error(JourneyError::AlreadyCompleted);
+    }
+
+    #[test]
+    fn test_capture_person_details_slot_not_found() {
+        // CapturePersonDetails requires CapturePerson to have been called first.
+        let id = Uuid::new_v4();
+
+        JourneyTester::with(services())
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
+            .when(JourneyCommand::CapturePersonDetails {
+                person_ref: "passenger_0".to_string(),
+                data: json!({ "passportNumber": "GB123456789" }),
+            })
+            .then_expect_error(JourneyError::PersonNotFound("passenger_0".to_string()));
+    }
+
+    #[test]
+    fn test_capture_person_details_multiple_calls_merge() {
+        // Successive CapturePersonDetails calls for the same slot each produce
+        // their own PersonDetailsUpdated event; the aggregate merges them via
+        // json_patch::merge in apply().
+        let id = Uuid::new_v4();
+        let subject_id = Uuid::new_v4();
+
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::PersonCaptured {
+                    person_ref: "passenger_0".to_string(),
+                    subject_id,
+                    name: "Alice Smith".to_string(),
+                    email: "alice@example.com".to_string(),
+                    phone: None,
+                },
+                JourneyEvent::PersonDetailsUpdated {
+                    person_ref: "passenger_0".to_string(),
+                    subject_id,
+                    data: json!({ "passportNumber": "GB123456789" }),
+                },
+            ])
+            .when(JourneyCommand::CapturePersonDetails {
+                person_ref: "passenger_0".to_string(),
+                data: json!({ "nationality": "GB", "dateOfBirth": "1990-05-15" }),
+            })
+            .then_expect_events(vec![JourneyEvent::PersonDetailsUpdated {
+                person_ref: "passenger_0".to_string(),
+                subject_id,
+                data: json!({ "nationality": "GB", "dateOfBirth": "1990-05-15" }),
+            }]);
+    }
+
+    // ── ForgetSubject ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_forget_subject() {
+        let id = Uuid::new_v4();
+        let subject_id = Uuid::new_v4();
+
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::PersonCaptured {
+                    person_ref: "passenger_0".to_string(),
+                    subject_id,
+                    name: "Alice Smith".to_string(),
+                    email: "alice@example.com".to_string(),
+                    phone: None,
+                },
+            ])
+            .when(JourneyCommand::ForgetSubject { subject_id })
+            .then_expect_events(vec![JourneyEvent::SubjectForgotten { subject_id }]);
+    }
+
+    #[test]
+    fn test_forget_subject_already_forgotten_is_noop() {
+        // A second ForgetSubject for the same subject must not emit another
+        // SubjectForgotten event — shredding is idempotent.
+        let id = Uuid::new_v4();
+        let subject_id = Uuid::new_v4();
+
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::PersonCaptured {
+                    person_ref: "passenger_0".to_string(),
+                    subject_id,
+                    name: "Alice Smith".to_string(),
+                    email: "alice@example.com".to_string(),
+                    phone: None,
+                },
+                // The subject was already forgotten in a prior shredding call.
+                JourneyEvent::SubjectForgotten { subject_id },
+            ])
+            .when(JourneyCommand::ForgetSubject { subject_id })
+            .then_expect_events(vec![]);
+    }
+
+    #[test]
+    fn test_forget_subject_for_subject_not_in_journey_is_noop() {
+        // ForgetSubject for a subject that never appeared in this journey
+        // must not emit SubjectForgotten.
+        let id = Uuid::new_v4();
+        let subject_a = Uuid::new_v4();
+        let subject_b = Uuid::new_v4();
+
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::PersonCaptured {
+                    person_ref: "passenger_0".to_string(),
+                    subject_id: subject_a,
+                    name: "Alice Smith".to_string(),
+                    email: "alice@example.com".to_string(),
+                    phone: None,
+                },
+            ])
+            // subject_b has no slot in this journey.
+            .when(JourneyCommand::ForgetSubject {
+                subject_id: subject_b,
+            })
+            .then_expect_events(vec![]);
+    }
+
+    #[test]
+    fn test_forget_subject_journey_not_found() {
+        JourneyTester::with(services())
+            .given_no_previous_events()
+            .when(JourneyCommand::ForgetSubject {
+                subject_id: Uuid::new_v4(),
+            })
+            .then_expect_error(JourneyError::NotFound);
+    }
+
+    #[test]
+    fn test_forget_subject_only_affects_target_slot() {
+        // After forgetting passenger_0, the aggregate should mark only that
+        // slot as forgotten; passenger_1's slot must be unaffected.
+        let id = Uuid::new_v4();
+        let subject_a = Uuid::new_v4();
+        let subject_b = Uuid::new_v4();
+
+        // Build the aggregate state by replaying events directly via apply().
+        let mut journey = Journey::default();
+        for event in [
+            JourneyEvent::Started {
+                id,
+                journey_type: None,
+            },
+            JourneyEvent::PersonCaptured {
+                person_ref: "passenger_0".to_string(),
+                subject_id: subject_a,
+                name: "Alice Smith".to_string(),
+                email: "alice@example.com".to_string(),
+                phone: None,
+            },
+            JourneyEvent::PersonCaptured {
+                person_ref: "passenger_1".to_string(),
+                subject_id: subject_b,
+                name: "Bob Jones".to_string(),
+                email: "bob@example.com".to_string(),
+                phone: None,
+            },
+            JourneyEvent::SubjectForgotten {
+                subject_id: subject_a,
+            },
+        ] {
+            journey.apply(event);
+        }
+
+        let p0 = journey.persons().get("passenger_0").unwrap();
+        assert!(p0.forgotten, "passenger_0 should be forgotten");
+
+        let p1 = journey.persons().get("passenger_1").unwrap();
+        assert!(!p1.forgotten, "passenger_1 should NOT be forgotten");
+    }
+
+    // ── Forget ───────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_forget() {
+        let id = Uuid::new_v4();
+
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::Completed,
+            ])
+            .when(JourneyCommand::Forget)
+            .then_expect_events(vec![JourneyEvent::Forgotten]);
+    }
+
+    #[test]
+    fn test_forget_already_forgotten_is_noop() {
+        let id = Uuid::new_v4();
+
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::Forgotten,
+            ])
+            .when(JourneyCommand::Forget)
+            .then_expect_events(vec![]);
+    }
+
+    #[test]
+    fn test_forget_journey_not_found() {
+        JourneyTester::with(services())
+            .given_no_previous_events()
+            .when(JourneyCommand::Forget)
+            .then_expect_error(JourneyError::NotFound);
+    }
+
+    #[test]
+    fn test_capture_after_forget_is_rejected() {
+        let id = Uuid::new_v4();
+
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::Forgotten,
+            ])
+            .when(JourneyCommand::Capture {
+                step: "first_name".to_string(),
+                data: json!("Joe"),
+            })
+            .then_expect_error(JourneyError::Forgotten);
+    }
+
+    #[test]
+    fn test_capture_person_after_forget_is_rejected() {
+        let id = Uuid::new_v4();
+
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::Forgotten,
+            ])
+            .when(JourneyCommand::CapturePerson {
+                person_ref: "passenger_0".to_string(),
+                subject_id: Uuid::new_v4(),
+                name: "Alice Smith".to_string(),
+                email: "alice@example.com".to_string(),
+                phone: None,
+            })
+            .then_expect_error(JourneyError::Forgotten);
+    }
+
+    #[test]
+    fn test_forgotten_redacts_persons_and_shared_data() {
+        let id = Uuid::new_v4();
+
+        let mut journey = Journey::default();
+        for event in [
+            JourneyEvent::Started {
+                id,
+                journey_type: None,
+            },
+            JourneyEvent::Modified {
+                step: "search".to_string(),
+                data: json!({ "origin": "LHR", "persons": { "passenger_0": { "dob": "1990-01-01" } } }),
+            },
+            JourneyEvent::PersonCaptured {
+                person_ref: "passenger_0".to_string(),
+                subject_id: Uuid::new_v4(),
+                name: "Alice Smith".to_string(),
+                email: "alice@example.com".to_string(),
+                phone: Some("+44 20 7946 0000".to_string()),
+            },
+            JourneyEvent::Forgotten,
+        ] {
+            journey.apply(event);
+        }
+
+        assert!(journey.forgotten());
+        assert_eq!(journey.shared_data(), &json!({ "origin": "LHR" }));
+        let slot = journey.persons().get("passenger_0").unwrap();
+        assert!(slot.forgotten);
+        assert!(slot.name.is_none() && slot.email.is_none() && slot.phone.is_none());
+        assert_eq!(slot.details, json!({}));
+    }
+
+    // ── Tag ──────────────────────────────────────────────────────────────────
+
+    fn tag(key: &str, value: &str) -> JourneyCommand {
+        JourneyCommand::Tag {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    fn tagged(key: &str, value: &str) -> JourneyEvent {
+        JourneyEvent::Tagged {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn tag_records_the_tag() {
+        JourneyTester::with(services())
+            .given(vec![JourneyEvent::Started {
+                id: Uuid::new_v4(),
+                journey_type: None,
+            }])
+            .when(tag("channel", "mobile"))
+            .then_expect_events(vec![tagged("channel", "mobile")]);
+    }
+
+    #[test]
+    fn tag_replaces_an_earlier_value() {
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id: Uuid::new_v4(),
+                    journey_type: None,
+                },
+                tagged("channel", "web"),
+            ])
+            .when(tag("channel", "mobile"))
+            .then_expect_events(vec![tagged("channel", "mobile")]);
+    }
+
+    #[test]
+    fn tag_with_the_same_value_is_noop() {
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id: Uuid::new_v4(),
+                    journey_type: None,
+                },
+                tagged("channel", "mobile"),
+            ])
+            .when(tag("channel", "mobile"))
+            .then_expect_events(vec![]);
+    }
+
+    #[test]
+    fn tag_completed_and_forgotten_journey() {
+        JourneyTester::with(services())
+            .given(vec![
+                JourneyEvent::Started {
+                    id: Uuid::new_v4(),
+                    journey_type: None,
+                },
+                JourneyEvent::Completed,
+                JourneyEvent::Forgotten,
+            ])
+            .when(tag("campaign", "summer"))
+            .then_expect_events(vec![tagged("campaign", "summer")]);
+    }
+
+    #[test]
+    fn tag_not_started() {
+        JourneyTester::with(services())
+            .given_no_previous_events()
+            .when(tag("channel", "mobile"))
+            .then_expect_error(JourneyError::NotFound);
+    }
+
+    #[test]
+    fn tag_rejects_an_empty_key() {
+        JourneyTester::with(services())
+            .given(vec![JourneyEvent::Started {
+                id: Uuid::new_v4(),
+                journey_type: None,
+            }])
+            .when(tag(" ", "mobile"))
+            .then_expect_error(JourneyError::InvalidTag(
+                "key must not be empty".to_string(),
+            ));
+    }
+
+    #[test]
+    fn tags_are_kept_apart_from_shared_data() {
+        let mut journey = Journey::default();
+        journey.apply(JourneyEvent::Started {
+            id: Uuid::new_v4(),
+            journey_type: None,
+        });
+        journey.apply(tagged("channel", "web"));
+        journey.apply(tagged("campaign", "summer"));
+        journey.apply(tagged("channel", "mobile"));
+
+        assert_eq!(
+            journey.tags(),
+            &BTreeMap::from([
+                ("campaign".to_string(), "summer".to_string()),
+                ("channel".to_string(), "mobile".to_string()),
+            ])
+        );
+        assert_eq!(journey.shared_data(), &json!({}));
+    }
+
+    // ── Correct ──────────────────────────────────────────────────────────────
+
+    fn captured(id: Uuid, data: Value) -> Vec<JourneyEvent> {
+        vec![
+            JourneyEvent::Started {
+                id,
+                journey_type: None,
+            },
+            JourneyEvent::Modified {
+                step: "traveller".to_string(),
+                data,
+            },
+        ]
+    }
+
+    #[test]
+    fn correct_replaces_a_nested_field_without_evaluating() {
+        let id = Uuid::new_v4();
+        let given = captured(
+            id,
+            json!({ "traveller": { "names": [{ "first": "Jhon", "last": "Smith" }] } }),
+        );
+        let events = JourneyTester::with(services())
+            .given(given.clone())
+            .when(JourneyCommand::Correct {
+                path: "traveller.names[0].first".to_string(),
+                value: json!("John"),
+            })
+            .inspect_result()
+            .unwrap();
+
+        assert_eq!(
+            events,
+            vec![JourneyEvent::Corrected {
+                path: "/traveller/names/0/first".parse().unwrap(),
+                value: json!("John"),
+            }]
+        );
+        let mut journey = Journey::default();
+        for event in given.into_iter().chain(events) {
+            journey.apply(event);
+        }
+        assert_eq!(
+            journey.shared_data(),
+            &json!({ "traveller": { "names": [{ "first": "John", "last": "Smith" }] } })
+        );
+    }
+
+    #[test]
+    fn correct_accepts_a_json_pointer() {
+        let id = Uuid::new_v4();
+        JourneyTester::with(services())
+            .given(captured(id, json!({ "name": "Jo" })))
+            .when(JourneyCommand::Correct {
+                path: "/name".to_string(),
+                value: json!("Joe"),
+            })
+            .then_expect_events(vec![JourneyEvent::Corrected {
+                path: "/name".parse().unwrap(),
+                value: json!("Joe"),
+            }]);
+    }
+
+    #[test]
+    fn correct_rejects_a_path_with_nothing_captured() {
+        let id = Uuid::new_v4();
+        for path in [
+            "traveller.email",
+            "traveller..name",
+            "/traveller/name/x",
+            "",
+        ] {
+            JourneyTester::with(services())
+                .given(captured(id, json!({ "traveller": { "name": "Jhon" } })))
+                .when(JourneyCommand::Correct {
+                    path: path.to_string(),
+                    value: json!("John"),
+                })
+                .then_expect_error(JourneyError::NothingToCorrect(path.to_string()));
+        }
+    }
+
+    #[test]
+    fn correct_rejects_a_secret_path() {
+        let id = Uuid::new_v4();
+        let path: PointerBuf = "/persons/passenger_0/passport".parse().unwrap();
+        JourneyTester::with(services_with_attribute_schema(explicit_attribute_schema()))
+            .given(captured(
+                id,
+                json!({ "persons": { "passenger_0": { "passport": "X1" } } }),
+            ))
+            .when(JourneyCommand::Correct {
+                path: path.to_string(),
+                value: json!("X2"),
+            })
+            .then_expect_error(JourneyError::SecretCorrection(path));
+    }
+
+    #[test]
+    fn correct_validates_the_corrected_data() {
+        let id = Uuid::new_v4();
+        JourneyTester::with(services())
+            .given(captured(id, json!({ "alpha": 1 })))
+            .when(JourneyCommand::Correct {
+                path: "alpha".to_string(),
+                value: json!("not a number"),
+            })
+            .then_expect_error(JourneyError::InvalidData(vec![
+                ValidationIssue::new("/alpha", "\"not a number\" is not of type \"number\"")
+                    .with_code("type_mismatch")
+                    .with_param("expected", "number")
+                    .with_param("actual", "string"),
+            ]));
+    }
+
+    // ── SaveDraft ────────────────────────────────────────────────────────────
+
+    /// Services whose schema requires a traveller's first and last names.
+    fn draft_services() -> JourneyServices {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "traveller": {
+                    "type": "object",
+                    "properties": {
+                        "first": { "type": "string" },
+                        "last":  { "type": "string" },
+                        "age":   { "type": "integer" }
+                    },
+                    "required": ["first", "last"]
+                }
+            }
+        });
+        JourneyServices::new(
+            Arc::new(SimpleDecisionEngine),
+            Arc::new(JsonSchemaValidator::new(&schema).unwrap()),
+            Arc::new(AttributeSchema::permissive()),
+        )
+    }
+
+    fn started(id: Uuid) -> Vec<JourneyEvent> {
+        vec![JourneyEvent::Started {
+            id,
+            journey_type: None,
+        }]
+    }
+
+    #[test]
+    fn save_draft_accepts_missing_required_fields() {
+        let id = Uuid::new_v4();
+        let events = JourneyTester::with(draft_services())
+            .given(started(id))
+            .when(JourneyCommand::SaveDraft {
+                step: "traveller".to_string(),
+                data: json!({ "traveller": { "first": "Jo" } }),
+            })
+            .inspect_result()
+            .unwrap();
+
+        assert_eq!(
+            events,
+            vec![JourneyEvent::DraftSaved {
+                step: "traveller".to_string(),
+                data: json!({ "traveller": { "first": "Jo" } }),
+            }]
+        );
+        let mut journey = Journey::default();
+        for event in started(id).into_iter().chain(events) {
+            journey.apply(event);
+        }
+        assert_eq!(journey.shared_data(), &json!({}));
+        assert_eq!(
+            journey.draft_data(),
+            &json!({ "traveller": { "traveller": { "first": "Jo" } } })
+        );
+        assert_eq!(journey.current_step(), None);
+    }
+
+    #[test]
+    fn save_draft_rejects_fields_of_the_wrong_type() {
+        let id = Uuid::new_v4();
+        JourneyTester::with(draft_services())
+            .given(started(id))
+            .when(JourneyCommand::SaveDraft {
+                step: "traveller".to_string(),
+                data: json!({ "traveller": { "age": "forty" } }),
+            })
+            .then_expect_error(JourneyError::InvalidData(vec![
+                ValidationIssue::new("/traveller/age", "\"forty\" is not of type \"integer\"")
+                    .with_code("type_mismatch")
+                    .with_param("expected", "integer")
+                    .with_param("actual", "string"),
+            ]));
+    }
+
+    #[test]
+    fn capturing_an_incomplete_draft_is_still_rejected() {
+        let id = Uuid::new_v4();
+        let mut given = started(id);
+        given.push(JourneyEvent::DraftSaved {
+            step: "traveller".to_string(),
+            data: json!({ "traveller": { "first": "Jo" } }),
+        });
+        JourneyTester::with(draft_services())
+            .given(given)
+            .when(JourneyCommand::Capture {
+                step: "traveller".to_string(),
+                data: json!({ "traveller": { "first": "Jo" } }),
+            })
+            .then_expect_error(JourneyError::InvalidData(vec![
+                ValidationIssue::new("/traveller/last", "required").with_code("required"),
+            ]));
+    }
+
+    #[test]
+    fn save_draft_is_rejected_unless_the_journey_can_capture() {
+        let id = Uuid::new_v4();
+        let draft = || JourneyCommand::SaveDraft {
+            step: "traveller".to_string(),
+            data: json!({ "traveller": { "first": "Jo" } }),
+        };
+        JourneyTester::with(draft_services())
+            .given_no_previous_events()
+            .when(draft())
+            .then_expect_error(JourneyError::NotFound);
+        for (event, error) in [
+            (JourneyEvent::Completed, JourneyError::AlreadyCompleted),
+            (
+                JourneyEvent::Paused { at: Utc::now() },
+                JourneyError::Paused,
+            ),
+            (JourneyEvent::Forgotten, JourneyError::Forgotten),
+        ] {
+            let mut given = started(id);
+            given.push(event);
+            JourneyTester::with(draft_services())
+                .given(given)
+                .when(draft())
+                .then_expect_error(error);
+        }
+    }
+
+    #[test]
+    fn drafts_merge_per_step_until_the_step_is_captured() {
+        let id = Uuid::new_v4();
+        let mut journey = Journey::default();
+        for event in started(id).into_iter().chain([
+            JourneyEvent::DraftSaved {
+                step: "traveller".to_string(),
+                data: json!({ "traveller": { "first": "Jo" } }),
+            },
+            JourneyEvent::DraftSaved {
+                step: "traveller".to_string(),
+                data: json!({ "traveller": { "last": "Smith" } }),
+            },
+            JourneyEvent::DraftSaved {
+                step: "search".to_string(),
+                data: json!({ "search": { "origin": "LHR" } }),
+            },
+        ]) {
+            journey.apply(event);
+        }
+        assert_eq!(
+            journey.draft_data()["traveller"],
+            json!({ "traveller": { "first": "Jo", "last": "Smith" } })
+        );
+
+        journey.apply(JourneyEvent::Modified {
+            step: "traveller".to_string(),
+            data: json!({ "traveller": { "first": "Jo", "last": "Smith" } }),
+        });
+
+        assert_eq!(
+            journey.draft_data(),
+            &json!({ "search": { "search": { "origin": "LHR" } } })
+        );
+        assert_eq!(
+            journey.shared_data(),
+            &json!({ "traveller": { "first": "Jo", "last": "Smith" } })
+        );
+
+        journey.apply(JourneyEvent::Forgotten);
+        assert_eq!(journey.draft_data(), &json!({}));
+    }
+
+    #[test]
+    fn journeys_snapshotted_before_drafts_have_none() {
+        let mut snapshot = serde_json::to_value(Journey::default()).unwrap();
+        snapshot.as_object_mut().unwrap().remove("draft_data");
+
+        let journey: Journey = serde_json::from_value(snapshot).unwrap();
+
+        assert_eq!(journey.draft_data(), &json!({}));
+    }
+
+    // ── apply() — shared_data accumulation ───────────────────────────────────
+
+    #[test]
+    fn test_apply_merges_shared_data() {
+        let id = Uuid::new_v4();
+        let mut journey = Journey::default();
+        journey.apply(JourneyEvent::Started {
+            id,
+            journey_type: None,
+        });
+        journey.apply(JourneyEvent::Modified {
+            step: "search".to_string(),
+            data: json!({ "origin": "LHR", "destination": "JFK" }),
+        });
+        journey.apply(JourneyEvent::Modified {
+            step: "pricing".to_string(),
+            data: json!({ "totalPrice": 450.00 }),
+        });
+
+        assert_eq!(journey.shared_data()["origin"], json!("LHR"));
+        assert_eq!(journey.shared_data()["destination"], json!("JFK"));
+        assert_eq!(journey.shared_data()["totalPrice"], json!(450.00));
+    }
+
+    #[test]
+    fn test_apply_person_details_merges_into_slot() {
+        let id = Uuid::new_v4();
+        let subject_id = Uuid::new_v4();
+        let mut journey = Journey::default();
+        journey.apply(JourneyEvent::Started {
+            id,
+            journey_type: None,
+        });
+        journey.apply(JourneyEvent::PersonCaptured {
+            person_ref: "passenger_0".to_string(),
+            subject_id,
+            name: "Alice Smith".to_string(),
+            email: "alice@example.com".to_string(),
+            phone: None,
+        });
+        journey.apply(JourneyEvent::PersonDetailsUpdated {
+            person_ref: "passenger_0".to_string(),
+            subject_id,
+            data: json!({ "passportNumber": "GB123456789" }),
+        });
+        journey.apply(JourneyEvent::PersonDetailsUpdated {
+            person_ref: "passenger_0".to_string(),
+            subject_id,
+            data: json!({ "dateOfBirth": "1990-05-15" }),
+        });
+
+        let slot = journey.persons().get("passenger_0").unwrap();
+        assert_eq!(slot.details["passportNumber"], json!("GB123456789"));
+        assert_eq!(slot.details["dateOfBirth"], json!("1990-05-15"));
+    }
+
+    // ── Capture — null handling ──────────────────────────────────────────────
+
+    /// The events and resulting `shared_data` of capturing a null `returnDate`
+    /// over a round trip.
+    fn clear_return_date(services: JourneyServices) -> (Vec<JourneyEvent>, Value) {
+        let given = vec![
+            JourneyEvent::Started {
+                id: Uuid::new_v4(),
+                journey_type: None,
+            },
+            JourneyEvent::Modified {
+                step: "search".to_string(),
+                data: json!({ "search": { "tripType": "round-trip", "returnDate": "2024-06-22" } }),
+            },
+        ];
+        let events = JourneyTester::with(services)
+            .given(given.clone())
+            .when(JourneyCommand::Capture {
+                step: "search".to_string(),
+                data: json!({ "search": { "tripType": "one-way", "returnDate": null } }),
+            })
+            .inspect_result()
+            .unwrap();
+
+        let mut journey = Journey::default();
+        for event in given.into_iter().chain(events.clone()) {
+            journey.apply(event);
+        }
+        (events, journey.shared_data().clone())
+    }
+
+    #[test]
+    fn capture_deletes_null_fields_by_default() {
+        let (events, shared_data) = clear_return_date(services());
+
+        assert_eq!(shared_data, json!({ "search": { "tripType": "one-way" } }));
+        assert_matches!(
+            &events[0],
+            JourneyEvent::Modified { data, .. } if data["search"]["returnDate"].is_null()
+        );
+    }
+
+    #[test]
+    fn capture_stores_null_fields_when_configured() {
+        let (events, shared_data) =
+            clear_return_date(services().with_null_handling(NullHandling::Store));
+
+        assert_eq!(
+            shared_data,
+            json!({ "search": { "tripType": "one-way", "returnDate": null } })
+        );
+        assert_eq!(
+            events[..2],
+            [
+                JourneyEvent::Modified {
+                    step: "search".to_string(),
+                    data: json!({ "search": { "tripType": "one-way" } }),
+                },
+                JourneyEvent::AttributesSet {
+                    plaintext: BTreeMap::from([(
+                        "/search/returnDate".parse::<PointerBuf>().unwrap(),
+                        Value::Null,
+                    )]),
+                    secret_partitions: vec![],
+                },
+            ]
+        );
+    }
+
+    // ── Capture — evaluation ─────────────────────────────────────────────────
+
+    /// How many `WorkflowEvaluated` events `command` records, under
+    /// `capture_evaluation`, for a journey at `search` with `{ "origin": "LHR" }`.
+    fn evaluations(capture_evaluation: CaptureEvaluation, command: JourneyCommand) -> usize {
+        let given = JourneyEventsBuilder::new()
+            .started(Uuid::new_v4())
+            .step("search", json!({ "origin": "LHR" }), &["passengers"])
+            .build();
+        JourneyTester::with(services().with_capture_evaluation(capture_evaluation))
+            .given(given)
+            .when(command)
+            .inspect_result()
+            .unwrap()
+            .iter()
+            .filter(|event| matches!(event, JourneyEvent::WorkflowEvaluated { .. }))
+            .count()
+    }
+
+    fn capture(step: &str, origin: &str) -> JourneyCommand {
+        JourneyCommand::Capture {
+            step: step.to_string(),
+            data: json!({ "origin": origin }),
+        }
+    }
+
+    #[test]
+    fn every_capture_is_evaluated_by_default() {
+        for command in [
+            capture("search", "LHR"),
+            capture("search", "LGW"),
+            capture("passengers", "LHR"),
+        ] {
+            assert_eq!(evaluations(CaptureEvaluation::default(), command), 1);
+        }
+    }
+
+    #[test]
+    fn on_step_transition_skips_same_step_captures() {
+        let mode = CaptureEvaluation::OnStepTransition;
+
+        assert_eq!(evaluations(mode, capture("search", "LHR")), 0);
+        assert_eq!(evaluations(mode, capture("search", "LGW")), 0);
+        assert_eq!(evaluations(mode, capture("passengers", "LHR")), 1);
+    }
+
+    #[test]
+    fn on_change_skips_only_same_step_captures_that_change_nothing() {
+        let mode = CaptureEvaluation::OnChange;
+
+        assert_eq!(evaluations(mode, capture("search", "LHR")), 0);
+        assert_eq!(evaluations(mode, capture("search", "LGW")), 1);
+        assert_eq!(evaluations(mode, capture("passengers", "LHR")), 1);
+    }
+
+    #[test]
+    fn on_step_transition_applies_to_capture_many() {
+        let capture_many = |step: &str| JourneyCommand::CaptureMany {
+            captures: vec![
+                ("passengers".to_string(), json!({ "adults": 2 })),
+                (step.to_string(), json!({ "origin": "LGW" })),
+            ],
+        };
+        let mode = CaptureEvaluation::OnStepTransition;
+
+        assert_eq!(evaluations(mode, capture_many("search")), 0);
+        assert_eq!(evaluations(mode, capture_many("seats")), 1);
+    }
+
+    #[test]
+    fn an_unevaluated_capture_keeps_the_latest_decision() {
+        let id = Uuid::new_v4();
+
+        JourneyTester::with(
+            services().with_capture_evaluation(CaptureEvaluation::OnStepTransition),
+        )
+        .given(
+            JourneyEventsBuilder::new()
+                .started(id)
+                .step("search", json!({ "origin": "LHR" }), &["passengers"])
+                .build(),
+        )
+        .when(capture("search", "LGW"))
+        .then_expect_events(vec![JourneyEvent::Modified {
+            step: "search".to_string(),
+            data: json!({ "origin": "LGW" }),
+        }]);
+    }
+
+    #[test]
+    fn a_capture_is_evaluated_while_completion_is_blocked() {
+        let id = Uuid::new_v4();
+        let engine = MockDecisionEngine::with_actions(vec![vec![]]);
+
+        JourneyTester::with(
+            services_with_decision_engine(engine)
+                .with_capture_evaluation(CaptureEvaluation::OnStepTransition),
+        )
+        .given(vec![
+            JourneyEvent::Started {
+                id,
+                journey_type: None,
+            },
+            JourneyEvent::Modified {
+                step: "search".to_string(),
+                data: json!({ "search": {} }),
+            },
+            JourneyEvent::WorkflowEvaluated {
+                suggested_actions: vec![],
+                phase: None,
+                ranked_actions: vec![],
+                completion_blocked_by: vec!["search".to_string()],
+            },
+            JourneyEvent::StepProgressed {
+                from_step: None,
+                to_step: "search".to_string(),
+            },
+        ])
+        .when(capture("search", "LHR"))
+        .then_expect_events(vec![
+            JourneyEvent::Modified {
+                step: "search".to_string(),
+                data: json!({ "origin": "LHR" }),
+            },
+            JourneyEvent::WorkflowEvaluated {
+                suggested_actions: vec![],
+                phase: None,
+                ranked_actions: vec![],
+                completion_blocked_by: vec![],
+            },
+        ]);
+    }
+
+    // ── Capture — step merge mode ────────────────────────────────────────────
+
+    /// The `shared_data` after re-submitting `search_criteria` as a one-way
+    /// trip over a round trip, and the data the capture recorded.
+    fn switch_to_one_way(services: JourneyServices) -> (Value, Value) {
+        let given = vec![
+            JourneyEvent::Started {
+                id: Uuid::new_v4(),
+                journey_type: None,
+            },
+            JourneyEvent::Modified {
+                step: "search_criteria".to_string(),
+                data: json!({ "search": {
+                    "tripType": "round-trip",
+                    "origin": "LHR",
+                    "returnDate": "2024-06-22"
+                } }),
+            },
+        ];
+        let events = JourneyTester::with(services)
+            .given(given.clone())
+            .when(JourneyCommand::Capture {
+                step: "search_criteria".to_string(),
+                data: json!({ "search": { "tripType": "one-way", "origin": "LAX" } }),
+            })
+            .inspect_result()
+            .unwrap();
+
+        let JourneyEvent::Modified { data, .. } = &events[0] else {
+            panic!("expected Modified, got {:?}", events[0]);
+        };
+        let recorded = data.clone();
+        let mut journey = Journey::default();
+        for event in given.into_iter().chain(events) {
+            journey.apply(event);
+        }
+        (journey.shared_data().clone(), recorded)
+    }
+
+    #[test]
+    fn capture_keeps_fields_left_out_under_merge_mode() {
+        let (shared_data, _) = switch_to_one_way(services());
+
+        assert_eq!(
+            shared_data,
+            json!({ "search": {
+                "tripType": "one-way",
+                "origin": "LAX",
+                "returnDate": "2024-06-22"
+            } })
+        );
+    }
+
+    #[test]
+    fn capture_drops_fields_left_out_under_replace_mode() {
+        let (shared_data, recorded) = switch_to_one_way(
+            services().with_step_merge_mode("search_criteria", StepMergeMode::Replace),
+        );
+
+        assert_eq!(
+            shared_data,
+            json!({ "search": { "tripType": "one-way", "origin": "LAX" } })
+        );
+        // The removal is recorded, so replay needs no configuration.
+        assert_eq!(
+            recorded,
+            json!({ "search": { "tripType": "one-way", "origin": "LAX", "returnDate": null } })
+        );
+    }
+
+    #[test]
+    fn replace_mode_applies_only_to_its_step() {
+        let (shared_data, _) = switch_to_one_way(
+            services().with_step_merge_mode("passengers", StepMergeMode::Replace),
+        );
+
+        assert_eq!(shared_data["search"]["returnDate"], json!("2024-06-22"));
+    }
+
+    #[test]
+    fn replace_mode_still_stores_explicit_nulls() {
+        let services = services()
+            .with_step_merge_mode("search", StepMergeMode::Replace)
+            .with_null_handling(NullHandling::Store);
+
+        let (_, shared_data) = clear_return_date(services);
+
+        assert_eq!(
+            shared_data,
+            json!({ "search": { "tripType": "one-way", "returnDate": null } })
+        );
+    }
+
+    // ── Capture — step order ─────────────────────────────────────────────────
+
+    /// A journey at `search` whose latest decision suggested `suggested`.
+    fn at_search_suggesting(id: Uuid, suggested: &[&str]) -> Vec<JourneyEvent> {
+        JourneyEventsBuilder::new()
+            .started(id)
+            .step("search", json!({ "search": "LHR" }), suggested)
+            .build()
+    }
+
+    fn capture_step(step: &str) -> JourneyCommand {
+        JourneyCommand::Capture {
+            step: step.to_string(),
+            data: json!({ step: "done" }),
+        }
+    }
+
+    #[test]
+    fn capture_records_a_skipped_step_by_default() {
+        let id = Uuid::new_v4();
+        JourneyTester::with(services())
+            .given(at_search_suggesting(id, &["passengers"]))
+            .when(capture_step("payment"))
+            .then_expect_events(vec![
+                JourneyEvent::StepSkipped {
+                    step: "payment".to_string(),
+                    allowed: vec!["passengers".to_string()],
+                },
+                JourneyEvent::Modified {
+                    step: "payment".to_string(),
+                    data: json!({ "payment": "done" }),
+                },
+                JourneyEvent::WorkflowEvaluated {
+                    suggested_actions: vec![],
+                    phase: None,
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec![],
+                },
+                // The search step's suggestion was the last one outstanding.
+                JourneyEvent::WorkflowSatisfied,
+                JourneyEvent::StepProgressed {
+                    from_step: Some("search".to_string()),
+                    to_step: "payment".to_string(),
+                },
+            ]);
+    }
+
+    #[test]
+    fn capture_rejects_a_skipped_step_when_strict() {
+        let id = Uuid::new_v4();
+        JourneyTester::with(services().with_strict_step_order(true))
+            .given(at_search_suggesting(id, &["passengers", "extras"]))
+            .when(capture_step("payment"))
+            .then_expect_error(JourneyError::StepNotAllowed {
+                step: "payment".to_string(),
+                allowed: vec!["passengers".to_string(), "extras".to_string()],
+            });
+    }
+
+    #[test]
+    fn capture_accepts_a_suggested_step_when_strict() {
+        let id = Uuid::new_v4();
+        let events = JourneyTester::with(services().with_strict_step_order(true))
+            .given(at_search_suggesting(id, &["passengers"]))
+            .when(capture_step("passengers"))
+            .inspect_result()
+            .unwrap();
+
+        assert!(
+            !events
+                .iter()
+                .any(|event| matches!(event, JourneyEvent::StepSkipped { .. }))
+        );
+    }
+
+    #[test]
+    fn capture_checks_step_order_only_on_a_transition_after_a_suggestion() {
+        let id = Uuid::new_v4();
+        // Staying on the current step is not a skip.
+        JourneyTester::with(services().with_strict_step_order(true))
+            .given(at_search_suggesting(id, &["passengers"]))
+            .when(capture_step("search"))
+            .inspect_result()
+            .unwrap();
+        // Nor is moving on when nothing was suggested.
+        JourneyTester::with(services().with_strict_step_order(true))
+            .given(at_search_suggesting(id, &[]))
+            .when(capture_step("payment"))
+            .inspect_result()
+            .unwrap();
+    }
+
+    fn capture_many_steps(steps: &[&str]) -> JourneyCommand {
+        JourneyCommand::CaptureMany {
+            captures: steps
+                .iter()
+                .map(|step| (step.to_string(), json!({ *step: "done" })))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn capture_many_records_a_skipped_step_by_default() {
+        let id = Uuid::new_v4();
+        let events = JourneyTester::with(services())
+            .given(at_search_suggesting(id, &["passengers"]))
+            .when(capture_many_steps(&["payment"]))
+            .inspect_result()
+            .unwrap();
+
+        assert_eq!(
+            events[..2],
+            [
+                JourneyEvent::StepSkipped {
+                    step: "payment".to_string(),
+                    allowed: vec!["passengers".to_string()],
+                },
+                JourneyEvent::Modified {
+                    step: "payment".to_string(),
+                    data: json!({ "payment": "done" }),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn capture_many_rejects_a_skipped_step_when_strict() {
         let id = Uuid::new_v4();
+        JourneyTester::with(services().with_strict_step_order(true))
+            .given(at_search_suggesting(id, &["passengers"]))
+            .when(capture_many_steps(&["search", "payment"]))
+            .then_expect_error(JourneyError::StepNotAllowed {
+                step: "payment".to_string(),
+                allowed: vec!["passengers".to_string()],
+            });
+    }
 
-        JourneyTester::with(services())
-            .given(vec![JourneyEvent::Started { id }])
-            .when(JourneyCommand::CapturePersonDetails {
-                person_ref: "passenger_0".to_string(),
-                data: json!({ "passportNumber": "GB123456789" }),
-            })
-            .then_expect_error(JourneyError::PersonNotFound("passenger_0".to_string()));
+    #[test]
+    fn capture_many_accepts_a_suggested_step_when_strict() {
+        let id = Uuid::new_v4();
+        let events = JourneyTester::with(services().with_strict_step_order(true))
+            .given(at_search_suggesting(id, &["passengers"]))
+            .when(capture_many_steps(&["search", "passengers"]))
+            .inspect_result()
+            .unwrap();
+
+        assert!(
+            !events
+                .iter()
+                .any(|event| matches!(event, JourneyEvent::StepSkipped { .. }))
+        );
     }
 
+    // ── Capture — without validation ─────────────────────────────────────────
+
     #[test]
-    fn test_capture_person_details_multiple_calls_merge() {
-        // Successive CapturePersonDetails calls for the same slot each produce
-        // their own PersonDetailsUpdated event; the aggregate merges them via
-        // json_patch::merge in apply().
+    fn capture_without_validation_accepts_any_data() {
         let id = Uuid::new_v4();
-        let subject_id = Uuid::new_v4();
+        let data = json!({ "alpha": "not a number", "unknown": [1, { "x": null }] });
+        JourneyTester::with(JourneyServices::without_validation(Arc::new(
+            SimpleDecisionEngine,
+        )))
+        .given(vec![JourneyEvent::Started {
+            id,
+            journey_type: None,
+        }])
+        .when(JourneyCommand::Capture {
+            step: "anything".to_string(),
+            data: data.clone(),
+        })
+        .then_expect_events(vec![
+            JourneyEvent::Modified {
+                step: "anything".to_string(),
+                data,
+            },
+            JourneyEvent::WorkflowEvaluated {
+                suggested_actions: vec![],
+                phase: None,
+                ranked_actions: vec![],
+                completion_blocked_by: vec![],
+            },
+            JourneyEvent::StepProgressed {
+                from_step: None,
+                to_step: "anything".to_string(),
+            },
+        ]);
+    }
+
+    // ── Capture — array merge strategy ───────────────────────────────────────
 
+    #[test]
+    fn capture_replaces_arrays_by_default() {
+        let id = Uuid::new_v4();
         JourneyTester::with(services())
             .given(vec![
-                JourneyEvent::Started { id },
-                JourneyEvent::PersonCaptured {
-                    person_ref: "passenger_0".to_string(),
-                    subject_id,
-                    name: "Alice Smith".to_string(),
-                    email: "alice@example.com".to_string(),
-                    phone: None,
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
                 },
-                JourneyEvent::PersonDetailsUpdated {
-                    person_ref: "passenger_0".to_string(),
-                    subject_id,
-                    data: json!({ "passportNumber": "GB123456789" }),
+                JourneyEvent::Modified {
+                    step: "profile".to_string(),
+                    data: json!({ "nicknames": ["Joe"] }),
                 },
             ])
-            .when(JourneyCommand::CapturePersonDetails {
-                person_ref: "passenger_0".to_string(),
-                data: json!({ "nationality": "GB", "dateOfBirth": "1990-05-15" }),
+            .when(JourneyCommand::Capture {
+                step: "profile".to_string(),
+                data: json!({ "nicknames": ["Joey"] }),
             })
-            .then_expect_events(vec![JourneyEvent::PersonDetailsUpdated {
-                person_ref: "passenger_0".to_string(),
-                subject_id,
-                data: json!({ "nationality": "GB", "dateOfBirth": "1990-05-15" }),
-            }]);
+            .then_expect_events(vec![
+                JourneyEvent::Modified {
+                    step: "profile".to_string(),
+                    data: json!({ "nicknames": ["Joey"] }),
+                },
+                JourneyEvent::WorkflowEvaluated {
+                    suggested_actions: vec![],
+                    phase: None,
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec![],
+                },
+                JourneyEvent::StepProgressed {
+                    from_step: None,
+                    to_step: "profile".to_string(),
+                },
+            ]);
     }
 
-    // ── ForgetSubject ────────────────────────────────────────────────────────
-
     #[test]
-    fn test_forget_subject() {
+    fn capture_appends_arrays_with_append_strategy() {
         let id = Uuid::new_v4();
-        let subject_id = Uuid::new_v4();
-
-        JourneyTester::with(services())
+        JourneyTester::with(services().with_merge_strategy(MergeStrategy::Append))
             .given(vec![
-                JourneyEvent::Started { id },
-                JourneyEvent::PersonCaptured {
-                    person_ref: "passenger_0".to_string(),
-                    subject_id,
-                    name: "Alice Smith".to_string(),
-                    email: "alice@example.com".to_string(),
-                    phone: None,
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::Modified {
+                    step: "profile".to_string(),
+                    data: json!({ "nicknames": ["Joe"] }),
                 },
             ])
-            .when(JourneyCommand::ForgetSubject { subject_id })
-            .then_expect_events(vec![JourneyEvent::SubjectForgotten { subject_id }]);
+            .when(JourneyCommand::Capture {
+                step: "profile".to_string(),
+                data: json!({ "nicknames": ["Joey"] }),
+            })
+            .then_expect_events(vec![
+                JourneyEvent::Modified {
+                    step: "profile".to_string(),
+                    data: json!({ "nicknames": ["Joe", "Joey"] }),
+                },
+                JourneyEvent::WorkflowEvaluated {
+                    suggested_actions: vec![],
+                    phase: None,
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec![],
+                },
+                JourneyEvent::StepProgressed {
+                    from_step: None,
+                    to_step: "profile".to_string(),
+                },
+            ]);
     }
 
     #[test]
-    fn test_forget_subject_already_forgotten_is_noop() {
-        // A second ForgetSubject for the same subject must not emit another
-        // SubjectForgotten event — shredding is idempotent.
+    fn capture_merges_arrays_by_key() {
         let id = Uuid::new_v4();
-        let subject_id = Uuid::new_v4();
-
-        JourneyTester::with(services())
+        let services = services().with_merge_strategy(MergeStrategy::MergeByKey("id".to_string()));
+        JourneyTester::with(services)
             .given(vec![
-                JourneyEvent::Started { id },
-                JourneyEvent::PersonCaptured {
-                    person_ref: "passenger_0".to_string(),
-                    subject_id,
-                    name: "Alice Smith".to_string(),
-                    email: "alice@example.com".to_string(),
-                    phone: None,
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::Modified {
+                    step: "passengers".to_string(),
+                    data: json!({ "passengerDetails": [
+                        { "id": "p0", "first_name": "Alice" },
+                        { "id": "p1", "first_name": "Bob" }
+                    ] }),
                 },
-                // The subject was already forgotten in a prior shredding call.
-                JourneyEvent::SubjectForgotten { subject_id },
             ])
-            .when(JourneyCommand::ForgetSubject { subject_id })
-            .then_expect_events(vec![]);
+            .when(JourneyCommand::Capture {
+                step: "passengers".to_string(),
+                data: json!({ "passengerDetails": [
+                    { "id": "p1", "last_name": "Jones" },
+                    { "id": "p2", "first_name": "Carol" }
+                ] }),
+            })
+            .then_expect_events(vec![
+                JourneyEvent::Modified {
+                    step: "passengers".to_string(),
+                    data: json!({ "passengerDetails": [
+                        { "id": "p0", "first_name": "Alice" },
+                        { "id": "p1", "first_name": "Bob", "last_name": "Jones" },
+                        { "id": "p2", "first_name": "Carol" }
+                    ] }),
+                },
+                JourneyEvent::WorkflowEvaluated {
+                    suggested_actions: vec![],
+                    phase: None,
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec![],
+                },
+                JourneyEvent::StepProgressed {
+                    from_step: None,
+                    to_step: "passengers".to_string(),
+                },
+            ]);
     }
 
-    #[test]
-    fn test_forget_subject_for_subject_not_in_journey_is_noop() {
-        // ForgetSubject for a subject that never appeared in this journey
-        // must not emit SubjectForgotten.
-        let id = Uuid::new_v4();
-        let subject_a = Uuid::new_v4();
-        let subject_b = Uuid::new_v4();
+    // ── Capture — shared_data size limit ────────────────────────────────────
 
-        JourneyTester::with(services())
+    /// Captures `{"beta":"xy"}` (13 bytes) onto `{"alpha":1}`, merging to
+    /// `{"alpha":1,"beta":"xy"}` (23 bytes).
+    fn capture_with_size_limit(limit: usize) -> Result<Vec<JourneyEvent>, JourneyError> {
+        let id = Uuid::new_v4();
+        JourneyTester::with(services().with_max_shared_data_size(limit))
             .given(vec![
-                JourneyEvent::Started { id },
-                JourneyEvent::PersonCaptured {
-                    person_ref: "passenger_0".to_string(),
-                    subject_id: subject_a,
-                    name: "Alice Smith".to_string(),
-                    email: "alice@example.com".to_string(),
-                    phone: None,
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::Modified {
+                    step: "profile".to_string(),
+                    data: json!({ "alpha": 1 }),
                 },
             ])
-            // subject_b has no slot in this journey.
-            .when(JourneyCommand::ForgetSubject {
-                subject_id: subject_b,
+            .when(JourneyCommand::Capture {
+                step: "profile".to_string(),
+                data: json!({ "beta": "xy" }),
             })
-            .then_expect_events(vec![]);
+            .inspect_result()
     }
 
     #[test]
-    fn test_forget_subject_journey_not_found() {
-        JourneyTester::with(services())
-            .given_no_previous_events()
-            .when(JourneyCommand::ForgetSubject {
-                subject_id: Uuid::new_v4(),
-            })
-            .then_expect_error(JourneyError::NotFound);
+    fn capture_within_size_limit_is_accepted() {
+        let events = capture_with_size_limit(1024).unwrap();
+        assert_matches!(events[0], JourneyEvent::Modified { .. });
     }
 
     #[test]
-    fn test_forget_subject_only_affects_target_slot() {
-        // After forgetting passenger_0, the aggregate should mark only that
-        // slot as forgotten; passenger_1's slot must be unaffected.
-        let id = Uuid::new_v4();
-        let subject_a = Uuid::new_v4();
-        let subject_b = Uuid::new_v4();
-
-        // Build the aggregate state by replaying events directly via apply().
-        let mut journey = Journey::default();
-        for event in [
-            JourneyEvent::Started { id },
-            JourneyEvent::PersonCaptured {
-                person_ref: "passenger_0".to_string(),
-                subject_id: subject_a,
-                name: "Alice Smith".to_string(),
-                email: "alice@example.com".to_string(),
-                phone: None,
-            },
-            JourneyEvent::PersonCaptured {
-                person_ref: "passenger_1".to_string(),
-                subject_id: subject_b,
-                name: "Bob Jones".to_string(),
-                email: "bob@example.com".to_string(),
-                phone: None,
-            },
-            JourneyEvent::SubjectForgotten {
-                subject_id: subject_a,
-            },
-        ] {
-            journey.apply(event);
-        }
-
-        let p0 = journey.persons().get("passenger_0").unwrap();
-        assert!(p0.forgotten, "passenger_0 should be forgotten");
-
-        let p1 = journey.persons().get("passenger_1").unwrap();
-        assert!(!p1.forgotten, "passenger_1 should NOT be forgotten");
+    fn capture_at_size_limit_is_accepted() {
+        let events = capture_with_size_limit(23).unwrap();
+        assert_matches!(events[0], JourneyEvent::Modified { .. });
     }
 
-    // ── apply() — shared_data accumulation ───────────────────────────────────
+    #[test]
+    fn capture_over_size_limit_counts_the_merged_data() {
+        // The 13-byte payload fits on its own; the merged result does not.
+        assert_eq!(
+            capture_with_size_limit(22).unwrap_err(),
+            JourneyError::DataTooLarge {
+                size: 23,
+                limit: 22
+            }
+        );
+    }
 
     #[test]
-    fn test_apply_merges_shared_data() {
+    fn set_attributes_over_size_limit_is_rejected() {
         let id = Uuid::new_v4();
-        let mut journey = Journey::default();
-        journey.apply(JourneyEvent::Started { id });
-        journey.apply(JourneyEvent::Modified {
-            step: "search".to_string(),
-            data: json!({ "origin": "LHR", "destination": "JFK" }),
-        });
-        journey.apply(JourneyEvent::Modified {
-            step: "pricing".to_string(),
-            data: json!({ "totalPrice": 450.00 }),
-        });
+        let mut changes = BTreeMap::new();
+        changes.insert(
+            "/search/origin".parse::<PointerBuf>().unwrap(),
+            json!("LHR"),
+        );
 
-        assert_eq!(journey.shared_data()["origin"], json!("LHR"));
-        assert_eq!(journey.shared_data()["destination"], json!("JFK"));
-        assert_eq!(journey.shared_data()["totalPrice"], json!(450.00));
+        // `{"search":{"origin":"LHR"}}` is 27 bytes.
+        JourneyTester::with(services().with_max_shared_data_size(26))
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
+            .when(JourneyCommand::SetAttributes { changes })
+            .then_expect_error(JourneyError::DataTooLarge {
+                size: 27,
+                limit: 26,
+            });
     }
 
     #[test]
-    fn test_apply_person_details_merges_into_slot() {
+    fn save_draft_over_size_limit_is_rejected() {
         let id = Uuid::new_v4();
-        let subject_id = Uuid::new_v4();
-        let mut journey = Journey::default();
-        journey.apply(JourneyEvent::Started { id });
-        journey.apply(JourneyEvent::PersonCaptured {
-            person_ref: "passenger_0".to_string(),
-            subject_id,
-            name: "Alice Smith".to_string(),
-            email: "alice@example.com".to_string(),
-            phone: None,
-        });
-        journey.apply(JourneyEvent::PersonDetailsUpdated {
-            person_ref: "passenger_0".to_string(),
-            subject_id,
-            data: json!({ "passportNumber": "GB123456789" }),
-        });
-        journey.apply(JourneyEvent::PersonDetailsUpdated {
-            person_ref: "passenger_0".to_string(),
-            subject_id,
-            data: json!({ "dateOfBirth": "1990-05-15" }),
-        });
-
-        let slot = journey.persons().get("passenger_0").unwrap();
-        assert_eq!(slot.details["passportNumber"], json!("GB123456789"));
-        assert_eq!(slot.details["dateOfBirth"], json!("1990-05-15"));
+        // `{"profile":{"name":"Al"}}` is 25 bytes.
+        JourneyTester::with(services().with_max_shared_data_size(24))
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
+            .when(JourneyCommand::SaveDraft {
+                step: "profile".to_string(),
+                data: json!({ "name": "Al" }),
+            })
+            .then_expect_error(JourneyError::DataTooLarge {
+                size: 25,
+                limit: 24,
+            });
     }
 
     // ── Schema validation ────────────────────────────────────────────────────
@@ -1425,7 +4873,13 @@ mod tests {
         );
 
         JourneyTester::with(services())
-            .given(vec![JourneyEvent::Started { id }, JourneyEvent::Completed])
+            .given(vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::Completed,
+            ])
             .when(JourneyCommand::SetAttributes { changes })
             .then_expect_error(JourneyError::AlreadyCompleted);
     }
@@ -1435,11 +4889,17 @@ mod tests {
         let id = Uuid::new_v4();
 
         JourneyTester::with(services())
-            .given(vec![JourneyEvent::Started { id }])
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
             .when(JourneyCommand::SetAttributes {
                 changes: BTreeMap::new(),
             })
-            .then_expect_error(JourneyError::InvalidData("no changes".to_string()));
+            .then_expect_error(JourneyError::InvalidData(vec![ValidationIssue::new(
+                "",
+                "no changes",
+            )]));
     }
 
     #[test]
@@ -1451,7 +4911,10 @@ mod tests {
         changes.insert(unknown_path.clone(), json!("value"));
 
         JourneyTester::with(services_with_attribute_schema(explicit_attribute_schema()))
-            .given(vec![JourneyEvent::Started { id }])
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
             .when(JourneyCommand::SetAttributes { changes })
             .then_expect_error(JourneyError::UnknownAttributePath(vec![unknown_path]));
     }
@@ -1472,7 +4935,10 @@ mod tests {
         );
 
         let mut journey = Journey::default();
-        journey.apply(JourneyEvent::Started { id });
+        journey.apply(JourneyEvent::Started {
+            id,
+            journey_type: None,
+        });
         journey.apply(JourneyEvent::AttributesSet {
             plaintext,
             secret_partitions: vec![],
@@ -1482,6 +4948,36 @@ mod tests {
         assert_eq!(journey.shared_data()["search"]["destination"], json!("JFK"));
     }
 
+    #[test]
+    fn completeness_reads_the_accumulated_data() {
+        let validator = JsonSchemaValidator::new(&json!({
+            "type": "object",
+            "properties": {
+                "search": {
+                    "type": "object",
+                    "required": ["origin", "destination", "departureDate"]
+                }
+            }
+        }))
+        .unwrap();
+        let mut journey = Journey::default();
+        journey.apply(JourneyEvent::Started {
+            id: Uuid::new_v4(),
+            journey_type: None,
+        });
+        journey.apply(JourneyEvent::AttributesSet {
+            plaintext: BTreeMap::from([(
+                "/search/origin".parse::<PointerBuf>().unwrap(),
+                json!("LHR"),
+            )]),
+            secret_partitions: vec![],
+        });
+
+        let report = journey.completeness(&validator, "search").unwrap();
+        assert_eq!(report.present, vec!["origin"]);
+        assert_eq!(report.missing, vec!["destination", "departureDate"]);
+    }
+
     #[test]
     fn set_attributes_secret_requires_person_captured() {
         // The person slot must exist before a secret path targeting it is accepted.
@@ -1495,7 +4991,10 @@ mod tests {
         );
 
         JourneyTester::with(services_with_attribute_schema(explicit_attribute_schema()))
-            .given(vec![JourneyEvent::Started { id }])
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
             .when(JourneyCommand::SetAttributes { changes })
             .then_expect_error(JourneyError::PersonNotFound("passenger_0".to_string()));
     }
@@ -1512,7 +5011,10 @@ mod tests {
         secret_changes.insert(passport_path, json!("AB123456"));
 
         let mut journey = Journey::default();
-        journey.apply(JourneyEvent::Started { id });
+        journey.apply(JourneyEvent::Started {
+            id,
+            journey_type: None,
+        });
         journey.apply(JourneyEvent::PersonCaptured {
             person_ref: "passenger_0".to_string(),
             subject_id,
@@ -1549,7 +5051,10 @@ mod tests {
         let expected_plaintext = changes.clone();
 
         JourneyTester::with(services())
-            .given(vec![JourneyEvent::Started { id }])
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
             .when(JourneyCommand::SetAttributes { changes })
             .then_expect_events(vec![
                 JourneyEvent::AttributesSet {
@@ -1559,6 +5064,8 @@ mod tests {
                 JourneyEvent::WorkflowEvaluated {
                     suggested_actions: vec!["form_3".to_string()],
                     phase: None,
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec![],
                 },
             ]);
     }
@@ -1584,7 +5091,10 @@ mod tests {
 
         JourneyTester::with(services_with_attribute_schema(explicit_attribute_schema()))
             .given(vec![
-                JourneyEvent::Started { id },
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
                 JourneyEvent::PersonCaptured {
                     person_ref: "passenger_0".to_string(),
                     subject_id: subject_id_0,
@@ -1620,6 +5130,8 @@ mod tests {
                 JourneyEvent::WorkflowEvaluated {
                     suggested_actions: vec![],
                     phase: None,
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec![],
                 },
             ]);
     }
@@ -1638,12 +5150,17 @@ mod tests {
         );
 
         JourneyTester::with(services())
-            .given(vec![JourneyEvent::Started { id }])
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
             .when(JourneyCommand::SetAttributes { changes })
-            .then_expect_error(JourneyError::InvalidData(
-                "Schema validation failed: {\"alpha\":\"not_a_number\"} is not valid under any of the schemas listed in the 'oneOf' keyword"
-                    .to_string(),
-            ));
+            .then_expect_error(JourneyError::InvalidData(vec![
+                ValidationIssue::new("/alpha", "\"not_a_number\" is not of type \"number\"")
+                    .with_code("type_mismatch")
+                    .with_param("expected", "number")
+                    .with_param("actual", "string"),
+            ]));
     }
 
     #[test]
@@ -1657,7 +5174,10 @@ mod tests {
         );
 
         let result = JourneyTester::with(services())
-            .given(vec![JourneyEvent::Started { id }])
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
             .when(JourneyCommand::SetAttributes { changes })
             .inspect_result();
 
@@ -1680,7 +5200,10 @@ mod tests {
         );
 
         let result = JourneyTester::with(services())
-            .given(vec![JourneyEvent::Started { id }])
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
             .when(JourneyCommand::SetAttributes { changes })
             .inspect_result();
 
@@ -1701,15 +5224,26 @@ mod tests {
         });
 
         JourneyTester::with(services())
-            .given(vec![JourneyEvent::Started { id }])
+            .given(vec![JourneyEvent::Started {
+                id,
+                journey_type: None,
+            }])
             .when(JourneyCommand::Capture {
                 step: "test_step".to_string(),
                 data: invalid_data,
             })
-            .then_expect_error(JourneyError::InvalidData(
-                "Schema validation failed: {\"alpha\":\"this should be a number\",\"beta\":123} \
-                 is not valid under any of the schemas listed in the 'oneOf' keyword"
-                    .to_string(),
-            ));
+            .then_expect_error(JourneyError::InvalidData(vec![
+                ValidationIssue::new(
+                    "/alpha",
+                    "\"this should be a number\" is not of type \"number\"",
+                )
+                .with_code("type_mismatch")
+                .with_param("expected", "number")
+                .with_param("actual", "string"),
+                ValidationIssue::new("/beta", "123 is not of type \"string\"")
+                    .with_code("type_mismatch")
+                    .with_param("expected", "string")
+                    .with_param("actual", "integer"),
+            ]));
     }
 }
@@ -2,6 +2,8 @@
 //! trees using `jsonptr` pointers.
 //!
 //! Pointers are RFC6901 JSON Pointers and therefore MUST start with a leading `/`.
+//! [`get_dotted`] reads the friendlier dot-notation used by query consumers, and
+//! [`dotted_pointer`] converts it to a pointer.
 
 use std::collections::BTreeMap;
 
@@ -62,6 +64,74 @@ where
     Ok(())
 }
 
+// ── dotted paths ──────────────────────────────────────────────────────────────
+
+/// One step of a parsed dot-notation path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+/// Parse a dot-notation path into its steps, so [`get_dotted`] and
+/// [`dotted_pointer`] read the same syntax. Returns `None` if the path is
+/// malformed.
+fn parse_dotted(path: &str) -> Option<Vec<Step<'_>>> {
+    let mut steps = Vec::new();
+    for segment in path.split('.') {
+        let (key, mut indices) = segment
+            .find('[')
+            .map_or((segment, ""), |at| segment.split_at(at));
+        match key {
+            "" if indices.is_empty() => return None,
+            "" => {}
+            key => steps.push(Step::Key(key)),
+        }
+        while !indices.is_empty() {
+            let (index, rest) = indices.strip_prefix('[')?.split_once(']')?;
+            steps.push(Step::Index(index.parse().ok()?));
+            indices = rest;
+        }
+    }
+    Some(steps)
+}
+
+// ── get_dotted ────────────────────────────────────────────────────────────────
+
+/// Look up a dot-notation path such as `search.origin` or
+/// `passengerDetails[0].firstName` in `source`.
+///
+/// Each `.`-separated segment names an object member, optionally followed by
+/// one or more `[n]` array indices. Returns `None` if any step is missing, the
+/// value there has the wrong shape, or the path is malformed.
+#[must_use]
+pub fn get_dotted<'a>(source: &'a Value, path: &str) -> Option<&'a Value> {
+    parse_dotted(path)?
+        .into_iter()
+        .try_fold(source, |value, step| match step {
+            Step::Key(key) => value.as_object()?.get(key),
+            Step::Index(index) => value.as_array()?.get(index),
+        })
+}
+
+// ── dotted_pointer ────────────────────────────────────────────────────────────
+
+/// Convert a dot-notation path, as read by [`get_dotted`], to a JSON Pointer.
+///
+/// `passengerDetails[0].firstName` becomes `/passengerDetails/0/firstName`.
+/// Returns `None` if the path is malformed.
+#[must_use]
+pub fn dotted_pointer(path: &str) -> Option<PointerBuf> {
+    let mut pointer = PointerBuf::new();
+    for step in parse_dotted(path)? {
+        match step {
+            Step::Key(key) => pointer.push_back(key),
+            Step::Index(index) => pointer.push_back(index),
+        }
+    }
+    Some(pointer)
+}
+
 // ── Tests ─────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -132,6 +202,113 @@ mod tests {
         );
     }
 
+    // ── parse_dotted ──────────────────────────────────────────────────────
+
+    #[test]
+    fn parse_dotted_mixes_keys_and_indices() {
+        assert_eq!(
+            parse_dotted("passengerDetails[0].phones[1]"),
+            Some(vec![
+                Step::Key("passengerDetails"),
+                Step::Index(0),
+                Step::Key("phones"),
+                Step::Index(1),
+            ])
+        );
+        assert_eq!(
+            parse_dotted("grid[1][0]"),
+            Some(vec![Step::Key("grid"), Step::Index(1), Step::Index(0)])
+        );
+        assert_eq!(
+            parse_dotted("[2].name"),
+            Some(vec![Step::Index(2), Step::Key("name")])
+        );
+        assert_eq!(parse_dotted("grid[-1]"), None);
+    }
+
+    // ── get_dotted ────────────────────────────────────────────────────────
+
+    fn booking() -> Value {
+        json!({
+            "search": {"origin": "LHR", "passengers": {"adults": 2}},
+            "passengerDetails": [
+                {"firstName": "Alice", "phones": ["+44 1", "+44 2"]},
+                {"firstName": "Bob"}
+            ],
+            "grid": [[1, 2], [3, 4]]
+        })
+    }
+
+    #[test]
+    fn get_dotted_nested_objects() {
+        let v = booking();
+        assert_eq!(get_dotted(&v, "search.origin"), Some(&json!("LHR")));
+        assert_eq!(get_dotted(&v, "search.passengers.adults"), Some(&json!(2)));
+        assert_eq!(get_dotted(&v, "search"), v.get("search"));
+    }
+
+    #[test]
+    fn get_dotted_array_indices() {
+        let v = booking();
+        assert_eq!(
+            get_dotted(&v, "passengerDetails[1].firstName"),
+            Some(&json!("Bob"))
+        );
+        assert_eq!(
+            get_dotted(&v, "passengerDetails[0].phones[1]"),
+            Some(&json!("+44 2"))
+        );
+        assert_eq!(get_dotted(&v, "grid[1][0]"), Some(&json!(3)));
+    }
+
+    #[test]
+    fn get_dotted_missing_or_mismatched_is_none() {
+        let v = booking();
+        assert_eq!(get_dotted(&v, "search.destination"), None);
+        assert_eq!(get_dotted(&v, "passengerDetails[2].firstName"), None);
+        assert_eq!(get_dotted(&v, "passengerDetails[0].phones[2]"), None);
+        assert_eq!(get_dotted(&v, "grid[0][9]"), None);
+        assert_eq!(get_dotted(&v, "search[0]"), None);
+        assert_eq!(get_dotted(&v, "search.origin.code"), None);
+    }
+
+    #[test]
+    fn get_dotted_malformed_path_is_none() {
+        let v = booking();
+        assert_eq!(get_dotted(&v, ""), None);
+        assert_eq!(get_dotted(&v, "search..origin"), None);
+        assert_eq!(get_dotted(&v, "passengerDetails[x]"), None);
+        assert_eq!(get_dotted(&v, "passengerDetails[0"), None);
+        assert_eq!(get_dotted(&v, "passengerDetails[0]x"), None);
+    }
+
+    // ── dotted_pointer ────────────────────────────────────────────────────
+
+    #[test]
+    fn dotted_pointer_names_the_same_location_as_get_dotted() {
+        let v = booking();
+        for dotted in [
+            "search.origin",
+            "passengerDetails[0].phones[1]",
+            "grid[1][0]",
+        ] {
+            let pointer = dotted_pointer(dotted).unwrap();
+            assert_eq!(pointer.resolve(&v).ok(), get_dotted(&v, dotted));
+        }
+        assert_eq!(
+            dotted_pointer("passengerDetails[1].firstName"),
+            Some(path("/passengerDetails/1/firstName"))
+        );
+    }
+
+    #[test]
+    fn dotted_pointer_malformed_path_is_none() {
+        assert_eq!(dotted_pointer(""), None);
+        assert_eq!(dotted_pointer("search..origin"), None);
+        assert_eq!(dotted_pointer("passengerDetails[x]"), None);
+        assert_eq!(dotted_pointer("passengerDetails[0]x"), None);
+    }
+
     // ── round-trip ────────────────────────────────────────────────────────
 
     #[test]
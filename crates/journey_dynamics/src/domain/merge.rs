@@ -0,0 +1,493 @@
+//! Array-aware merge strategies for folding captured data into `shared_data`.
+//!
+//! RFC 7386 merge patch (`json_patch::merge`) replaces arrays wholesale, so
+//! re-submitting a list with one extra element discards everything that was
+//! captured before. [`MergeStrategy`] lets a deployment append or merge array
+//! elements by key instead.
+//!
+//! The strategy is resolved in `Journey::handle` via [`resolve_patch`]: the
+//! recorded `Modified` event already carries the combined arrays, so `apply`
+//! and every projection keep using plain merge patch and replay never depends
+//! on the configuration in force when the event was written.
+//!
+//! Merge patch also deletes a key whose incoming value is `null`.
+//! [`NullHandling::Store`] keeps such nulls instead: [`split_nulls`] takes them
+//! out of the patch in `Journey::handle`, and they are recorded as explicit
+//! path assignments alongside it, again leaving replay unaffected.
+//!
+//! Merge patch never removes a key the incoming data leaves out, so a step
+//! re-submitted with fewer fields keeps the old ones. A step captured with
+//! [`StepMergeMode::Replace`] has [`replace_subtrees`] add the removals to
+//! the patch, which still replays as plain merge patch.
+
+use std::{borrow::Cow, collections::BTreeMap};
+
+use jsonptr::PointerBuf;
+use serde_json::Value;
+
+// ── MergeStrategy ─────────────────────────────────────────────────────────────
+
+/// How arrays in incoming data are combined with arrays already present.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Replace the existing array wholesale (RFC 7386 behaviour).
+    #[default]
+    Replace,
+    /// Append incoming elements after the existing ones.
+    Append,
+    /// Merge object elements that carry the same value under the given key;
+    /// incoming elements with no match are appended.
+    MergeByKey(String),
+}
+
+// ── resolve_patch ─────────────────────────────────────────────────────────────
+
+/// Rewrite `patch` so that merging it into `target` with `json_patch::merge`
+/// honours `strategy` for every array that exists on both sides.
+///
+/// Objects are walked recursively; scalars, `null` deletions and arrays with
+/// no existing counterpart are passed through unchanged.
+#[must_use]
+pub fn resolve_patch(target: &Value, patch: &Value, strategy: &MergeStrategy) -> Value {
+    match (target, patch) {
+        (Value::Object(target_map), Value::Object(patch_map)) => Value::Object(
+            patch_map
+                .iter()
+                .map(|(key, value)| {
+                    let resolved = target_map.get(key).map_or_else(
+                        || value.clone(),
+                        |existing| resolve_patch(existing, value, strategy),
+                    );
+                    (key.clone(), resolved)
+                })
+                .collect(),
+        ),
+        (Value::Array(existing), Value::Array(incoming)) => {
+            merge_arrays(existing, incoming, strategy)
+        }
+        _ => patch.clone(),
+    }
+}
+
+/// Merge `patch` into `target`, combining arrays according to `strategy`.
+///
+/// With [`MergeStrategy::Replace`] this is exactly `json_patch::merge`.
+pub fn merge_with_strategy(target: &mut Value, patch: &Value, strategy: &MergeStrategy) {
+    let resolved = resolve_patch(target, patch, strategy);
+    json_patch::merge(target, &resolved);
+}
+
+fn merge_arrays(existing: &[Value], incoming: &[Value], strategy: &MergeStrategy) -> Value {
+    match strategy {
+        MergeStrategy::Replace => Value::Array(incoming.to_vec()),
+        MergeStrategy::Append => Value::Array(existing.iter().chain(incoming).cloned().collect()),
+        MergeStrategy::MergeByKey(key) => {
+            let mut merged = existing.to_vec();
+            for item in incoming {
+                let position = item
+                    .get(key)
+                    .and_then(|id| merged.iter().position(|e| e.get(key) == Some(id)));
+                match position {
+                    Some(index) => merge_with_strategy(&mut merged[index], item, strategy),
+                    None => merged.push(item.clone()),
+                }
+            }
+            Value::Array(merged)
+        }
+    }
+}
+
+// ── NullHandling ──────────────────────────────────────────────────────────────
+
+/// What a `null` in incoming data does to the key it is set on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NullHandling {
+    /// Delete the key (RFC 7386 behaviour).
+    #[default]
+    Delete,
+    /// Store `null` as the key's value.
+    Store,
+}
+
+/// Merge `patch` into `target` like `json_patch::merge`, except that a `null`
+/// in an object is stored rather than deleting its key.
+pub fn merge_storing_nulls(target: &mut Value, patch: &Value) {
+    let Value::Object(patch_map) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(serde_json::Map::new());
+    }
+    if let Value::Object(target_map) = target {
+        for (key, value) in patch_map {
+            merge_storing_nulls(target_map.entry(key.clone()).or_insert(Value::Null), value);
+        }
+    }
+}
+
+/// Split `patch` into a merge patch without its `null`s and the pointers it
+/// sets to `null`. Merging the first with `json_patch::merge` and then
+/// assigning the second is [`merge_storing_nulls`].
+///
+/// Only nulls in objects are taken out; merge patch already keeps nulls inside
+/// arrays, which it replaces wholesale.
+#[must_use]
+pub fn split_nulls(patch: &Value) -> (Value, BTreeMap<PointerBuf, Value>) {
+    let mut nulls = BTreeMap::new();
+    let stripped = strip_nulls(patch, &mut PointerBuf::new(), &mut nulls);
+    (stripped, nulls)
+}
+
+fn strip_nulls(
+    patch: &Value,
+    at: &mut PointerBuf,
+    nulls: &mut BTreeMap<PointerBuf, Value>,
+) -> Value {
+    let Value::Object(patch_map) = patch else {
+        return patch.clone();
+    };
+    let mut stripped = serde_json::Map::new();
+    for (key, value) in patch_map {
+        at.push_back(key.as_str());
+        if value.is_null() {
+            nulls.insert(at.clone(), Value::Null);
+        } else {
+            stripped.insert(key.clone(), strip_nulls(value, at, nulls));
+        }
+        at.pop_back();
+    }
+    Value::Object(stripped)
+}
+
+// ── merge_diff ────────────────────────────────────────────────────────────────
+
+/// The merge patch that turns `from` into `to`: `{}` when they are equal.
+///
+/// Merging the result into `from` with `json_patch::merge` gives `to`, except
+/// for nulls `to` holds in objects, which a merge patch cannot set.
+#[must_use]
+pub fn merge_diff(from: &Value, to: &Value) -> Value {
+    let (Value::Object(from_map), Value::Object(to_map)) = (from, to) else {
+        return to.clone();
+    };
+    let mut patch = serde_json::Map::new();
+    for key in from_map.keys().filter(|key| !to_map.contains_key(*key)) {
+        patch.insert(key.clone(), Value::Null);
+    }
+    for (key, value) in to_map {
+        match from_map.get(key) {
+            Some(old) if old == value => {}
+            Some(old) => {
+                patch.insert(key.clone(), merge_diff(old, value));
+            }
+            None => {
+                patch.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    Value::Object(patch)
+}
+
+// ── step_patch ───────────────────────────────────────────────────────────────
+
+/// The merge patch capturing `data` at `step` applies to `shared_data`.
+///
+/// Captured data is normally an object of the fields it sets. Anything else,
+/// such as a bare `"Joe"`, would replace `shared_data` outright as a merge
+/// patch, so it is filed under the step's name instead: `{ step: data }`.
+#[must_use]
+pub fn step_patch<'a>(step: &str, data: &'a Value) -> Cow<'a, Value> {
+    if data.is_object() {
+        Cow::Borrowed(data)
+    } else {
+        let mut patch = serde_json::Map::new();
+        patch.insert(step.to_string(), data.clone());
+        Cow::Owned(Value::Object(patch))
+    }
+}
+
+// ── StepMergeMode ────────────────────────────────────────────────────────────
+
+/// How a `Capture` at a step combines its data with what is already there.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StepMergeMode {
+    /// Merge the data over the existing values; keys it leaves out are kept.
+    #[default]
+    Merge,
+    /// Replace each top-level value the data sets wholesale, dropping keys it
+    /// leaves out.
+    Replace,
+}
+
+/// Rewrite `patch` so that merging it into `target` with `json_patch::merge`
+/// replaces every top-level value `patch` sets instead of merging into it.
+///
+/// Top-level keys `patch` does not name are untouched.
+#[must_use]
+pub fn replace_subtrees(target: &Value, patch: &Value) -> Value {
+    let (Value::Object(target_map), Value::Object(patch_map)) = (target, patch) else {
+        return patch.clone();
+    };
+    Value::Object(
+        patch_map
+            .iter()
+            .map(|(key, value)| {
+                let resolved = target_map
+                    .get(key)
+                    .map_or_else(|| value.clone(), |existing| merge_diff(existing, value));
+                (key.clone(), resolved)
+            })
+            .collect(),
+    )
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn passengers() -> Value {
+        json!({
+            "passengerDetails": [
+                { "id": "p0", "firstName": "Alice" },
+                { "id": "p1", "firstName": "Bob" }
+            ]
+        })
+    }
+
+    #[test]
+    fn replace_matches_json_merge_patch() {
+        let patch = json!({ "passengerDetails": [{ "id": "p2", "firstName": "Carol" }] });
+
+        let mut expected = passengers();
+        json_patch::merge(&mut expected, &patch);
+
+        let mut actual = passengers();
+        merge_with_strategy(&mut actual, &patch, &MergeStrategy::Replace);
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn append_keeps_existing_elements() {
+        let mut data = passengers();
+        merge_with_strategy(
+            &mut data,
+            &json!({ "passengerDetails": [{ "id": "p2", "firstName": "Carol" }] }),
+            &MergeStrategy::Append,
+        );
+
+        assert_eq!(
+            data["passengerDetails"],
+            json!([
+                { "id": "p0", "firstName": "Alice" },
+                { "id": "p1", "firstName": "Bob" },
+                { "id": "p2", "firstName": "Carol" }
+            ])
+        );
+    }
+
+    #[test]
+    fn merge_by_key_updates_matching_and_appends_new() {
+        let mut data = passengers();
+        merge_with_strategy(
+            &mut data,
+            &json!({
+                "passengerDetails": [
+                    { "id": "p1", "lastName": "Jones" },
+                    { "id": "p2", "firstName": "Carol" }
+                ]
+            }),
+            &MergeStrategy::MergeByKey("id".to_string()),
+        );
+
+        assert_eq!(
+            data["passengerDetails"],
+            json!([
+                { "id": "p0", "firstName": "Alice" },
+                { "id": "p1", "firstName": "Bob", "lastName": "Jones" },
+                { "id": "p2", "firstName": "Carol" }
+            ])
+        );
+    }
+
+    #[test]
+    fn merge_by_key_appends_elements_without_the_key() {
+        let mut data = json!({ "tags": ["a"] });
+        merge_with_strategy(
+            &mut data,
+            &json!({ "tags": ["b"] }),
+            &MergeStrategy::MergeByKey("id".to_string()),
+        );
+
+        assert_eq!(data["tags"], json!(["a", "b"]));
+    }
+
+    #[test]
+    fn arrays_without_an_existing_counterpart_pass_through() {
+        let resolved = resolve_patch(
+            &json!({ "search": { "origin": "LHR" } }),
+            &json!({ "search": { "stops": ["CDG"] }, "nicknames": ["Joe"] }),
+            &MergeStrategy::Append,
+        );
+
+        assert_eq!(
+            resolved,
+            json!({ "search": { "stops": ["CDG"] }, "nicknames": ["Joe"] })
+        );
+    }
+
+    #[test]
+    fn null_still_deletes_under_every_strategy() {
+        let mut data = json!({ "returnDate": "2024-06-22", "tags": ["a"] });
+        merge_with_strategy(
+            &mut data,
+            &json!({ "returnDate": null }),
+            &MergeStrategy::Append,
+        );
+
+        assert_eq!(data, json!({ "tags": ["a"] }));
+    }
+
+    // ── NullHandling ─────────────────────────────────────────────────────────
+
+    fn trip() -> Value {
+        json!({ "search": { "origin": "LHR", "returnDate": "2024-06-22" } })
+    }
+
+    #[test]
+    fn merge_storing_nulls_keeps_an_explicit_null() {
+        let mut data = trip();
+        merge_storing_nulls(&mut data, &json!({ "search": { "returnDate": null } }));
+
+        assert_eq!(
+            data,
+            json!({ "search": { "origin": "LHR", "returnDate": null } })
+        );
+    }
+
+    #[test]
+    fn split_nulls_replays_as_merge_storing_nulls() {
+        let patch = json!({
+            "search": { "returnDate": null, "destination": "JFK", "stops": [null] },
+            "booking": { "insurance": null },
+            "pricing": null
+        });
+
+        let (stripped, nulls) = split_nulls(&patch);
+        assert_eq!(
+            stripped,
+            json!({ "search": { "destination": "JFK", "stops": [null] }, "booking": {} })
+        );
+        let pointers: Vec<&str> = nulls.keys().map(|p| p.as_str()).collect();
+        assert_eq!(
+            pointers,
+            vec!["/booking/insurance", "/pricing", "/search/returnDate"]
+        );
+
+        let mut replayed = trip();
+        json_patch::merge(&mut replayed, &stripped);
+        crate::domain::assign_all(&mut replayed, &nulls).unwrap();
+
+        let mut expected = trip();
+        merge_storing_nulls(&mut expected, &patch);
+        assert_eq!(replayed, expected);
+    }
+
+    // ── merge_diff ───────────────────────────────────────────────────────────
+
+    #[test]
+    fn merge_diff_of_equal_values_is_empty() {
+        assert_eq!(merge_diff(&trip(), &trip()), json!({}));
+    }
+
+    #[test]
+    fn merge_diff_records_additions_changes_and_removals() {
+        let from = json!({
+            "search": { "origin": "LHR", "destination": "JFK", "returnDate": "2025-07-01" },
+            "extras": { "bags": 1 }
+        });
+        let to = json!({
+            "search": { "origin": "LHR", "destination": "CDG" },
+            "passengers": { "adults": 2 }
+        });
+
+        let patch = merge_diff(&from, &to);
+
+        assert_eq!(
+            patch,
+            json!({
+                "search": { "destination": "CDG", "returnDate": null },
+                "extras": null,
+                "passengers": { "adults": 2 }
+            })
+        );
+        let mut replayed = from;
+        json_patch::merge(&mut replayed, &patch);
+        assert_eq!(replayed, to);
+    }
+
+    #[test]
+    fn merge_diff_replaces_arrays_and_scalars_wholesale() {
+        let from = json!({ "stops": ["DUB"], "class": { "cabin": "economy" } });
+        let to = json!({ "stops": ["DUB", "KEF"], "class": "business" });
+
+        assert_eq!(
+            merge_diff(&from, &to),
+            json!({ "stops": ["DUB", "KEF"], "class": "business" })
+        );
+    }
+
+    #[test]
+    fn step_patch_passes_objects_through() {
+        let data = json!({ "first_name": "Joe" });
+
+        assert_eq!(*step_patch("profile", &data), data);
+    }
+
+    #[test]
+    fn step_patch_files_other_data_under_the_step() {
+        for data in [json!("Joe"), json!(42), json!(["Joe"]), Value::Null] {
+            assert_eq!(
+                *step_patch("first_name", &data),
+                json!({ "first_name": data })
+            );
+        }
+    }
+
+    fn round_trip() -> Value {
+        json!({
+            "search": { "tripType": "round-trip", "returnDate": "2024-06-22" },
+            "passengers": 2
+        })
+    }
+
+    #[test]
+    fn replace_subtrees_drops_keys_the_patch_leaves_out() {
+        let target = round_trip();
+        let patch = json!({ "search": { "tripType": "one-way" } });
+
+        let resolved = replace_subtrees(&target, &patch);
+        let mut replaced = target;
+        json_patch::merge(&mut replaced, &resolved);
+
+        assert_eq!(
+            resolved,
+            json!({ "search": { "tripType": "one-way", "returnDate": null } })
+        );
+        assert_eq!(
+            replaced,
+            json!({ "search": { "tripType": "one-way" }, "passengers": 2 })
+        );
+    }
+
+    #[test]
+    fn replace_subtrees_passes_new_keys_through() {
+        let patch = json!({ "payment": { "method": "card" } });
+
+        assert_eq!(replace_subtrees(&round_trip(), &patch), patch);
+    }
+}
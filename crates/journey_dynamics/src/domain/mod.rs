@@ -10,8 +10,12 @@ pub mod commands;
 pub mod events;
 pub mod journey;
 pub mod json_path;
+pub mod merge;
+#[cfg(test)]
+pub(crate) mod test_support;
 pub use attribute_schema::{
     AttributeSchema, AttributeSchemaConfig, Classification, NamespacePattern,
     NamespacePatternConfig, PiiClass, classify_changes,
 };
-pub use json_path::{assign_all, flatten};
+pub use json_path::{assign_all, dotted_pointer, flatten, get_dotted};
+pub use merge::{MergeStrategy, NullHandling, StepMergeMode, merge_diff, step_patch};
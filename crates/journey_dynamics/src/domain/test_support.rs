@@ -0,0 +1,175 @@
+//! Helpers for aggregate tests.
+
+#![allow(deprecated)]
+
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::domain::events::JourneyEvent;
+
+/// Builds a journey's event history in the order the aggregate records it.
+///
+/// ```ignore
+/// let given = JourneyEventsBuilder::new()
+///     .started(id)
+///     .step("search", json!({ "origin": "LHR" }), &["passengers"])
+///     .build();
+/// ```
+#[derive(Debug, Default)]
+pub struct JourneyEventsBuilder {
+    events: Vec<JourneyEvent>,
+    current_step: Option<String>,
+    pending: bool,
+    satisfied: bool,
+}
+
+impl JourneyEventsBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An untyped journey `id` starts.
+    #[must_use]
+    pub fn started(mut self, id: Uuid) -> Self {
+        self.events.push(JourneyEvent::Started {
+            id,
+            journey_type: None,
+        });
+        self
+    }
+
+    /// `data` is captured at `step`.
+    #[must_use]
+    pub fn captured(mut self, step: &str, data: Value) -> Self {
+        self.events.push(JourneyEvent::Modified {
+            step: step.to_string(),
+            data,
+        });
+        self
+    }
+
+    /// A decision suggests `actions`, with no phase, ranking or blockers,
+    /// followed by `WorkflowSatisfied` the first time the actions run out.
+    #[must_use]
+    pub fn evaluated(mut self, actions: &[&str]) -> Self {
+        self.events.push(JourneyEvent::WorkflowEvaluated {
+            suggested_actions: actions.iter().map(ToString::to_string).collect(),
+            phase: None,
+            ranked_actions: vec![],
+            completion_blocked_by: vec![],
+        });
+        if self.pending && actions.is_empty() && !self.satisfied {
+            self.events.push(JourneyEvent::WorkflowSatisfied);
+            self.satisfied = true;
+        }
+        self.pending = !actions.is_empty();
+        self
+    }
+
+    /// The journey moves from step `from` to step `to`.
+    #[must_use]
+    pub fn progressed(mut self, from: Option<&str>, to: &str) -> Self {
+        self.events.push(JourneyEvent::StepProgressed {
+            from_step: from.map(str::to_string),
+            to_step: to.to_string(),
+        });
+        self.current_step = Some(to.to_string());
+        self
+    }
+
+    /// A `Capture` of `data` at `step` whose decision suggests `actions`:
+    /// the capture, the decision and, if `step` is not the current one, the
+    /// progression to it from wherever the journey was.
+    #[must_use]
+    pub fn step(self, step: &str, data: Value, actions: &[&str]) -> Self {
+        let from = self.current_step.clone();
+        let builder = self.captured(step, data).evaluated(actions);
+        if from.as_deref() == Some(step) {
+            builder
+        } else {
+            builder.progressed(from.as_deref(), step)
+        }
+    }
+
+    /// The journey completes.
+    #[must_use]
+    pub fn completed(mut self) -> Self {
+        self.events.push(JourneyEvent::Completed);
+        self
+    }
+
+    #[must_use]
+    pub fn build(self) -> Vec<JourneyEvent> {
+        self.events
+    }
+}
+
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn step_progresses_only_when_the_step_changes() {
+        let id = Uuid::new_v4();
+
+        let events = JourneyEventsBuilder::new()
+            .started(id)
+            .step("search", json!({ "origin": "LHR" }), &["passengers"])
+            .step("search", json!({ "origin": "LGW" }), &["passengers"])
+            .step("passengers", json!({ "adults": 1 }), &[])
+            .completed()
+            .build();
+
+        assert_eq!(
+            events,
+            vec![
+                JourneyEvent::Started {
+                    id,
+                    journey_type: None,
+                },
+                JourneyEvent::Modified {
+                    step: "search".to_string(),
+                    data: json!({ "origin": "LHR" }),
+                },
+                JourneyEvent::WorkflowEvaluated {
+                    suggested_actions: vec!["passengers".to_string()],
+                    phase: None,
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec![],
+                },
+                JourneyEvent::StepProgressed {
+                    from_step: None,
+                    to_step: "search".to_string(),
+                },
+                JourneyEvent::Modified {
+                    step: "search".to_string(),
+                    data: json!({ "origin": "LGW" }),
+                },
+                JourneyEvent::WorkflowEvaluated {
+                    suggested_actions: vec!["passengers".to_string()],
+                    phase: None,
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec![],
+                },
+                JourneyEvent::Modified {
+                    step: "passengers".to_string(),
+                    data: json!({ "adults": 1 }),
+                },
+                JourneyEvent::WorkflowEvaluated {
+                    suggested_actions: vec![],
+                    phase: None,
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec![],
+                },
+                JourneyEvent::WorkflowSatisfied,
+                JourneyEvent::StepProgressed {
+                    from_step: Some("search".to_string()),
+                    to_step: "passengers".to_string(),
+                },
+                JourneyEvent::Completed,
+            ]
+        );
+    }
+}
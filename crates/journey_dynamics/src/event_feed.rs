@@ -0,0 +1,144 @@
+//! A feed of every journey's events, for downstream consumers tailing the
+//! event log.
+//!
+//! Events are numbered across journeys by the `global_position` column of the
+//! `events` table. A consumer asks for the events after the last position it
+//! has seen and receives them with the cursor to ask from next. Events come
+//! back decrypted and upcast as they are for `GET /journeys/{id}/events`.
+//!
+//! Positions are assigned as events are inserted, so a transaction that
+//! commits after a later-numbered one can make an event appear behind a
+//! cursor that has already passed it. Consumers that must see every event
+//! should re-read the last few positions.
+
+use std::sync::Arc;
+
+use cqrs_es::{
+    EventEnvelope,
+    persist::{EventUpcaster, PersistenceError, SerializedEvent},
+};
+use cqrs_es_crypto::{CryptoShreddingEventRepository, FieldCipher, KeyStore};
+use postgres_es::PostgresEventRepository;
+use serde::Serialize;
+use sqlx::{Pool, Postgres, Row};
+
+use crate::{
+    domain::{events::journey_event_upcasters, journey::Journey},
+    pii_codec::JourneyPiiCodec,
+    queries::JourneyEventView,
+};
+
+/// One event in the feed.
+#[derive(Debug, Serialize, Clone)]
+pub struct FeedEvent {
+    /// Where the event stands in the feed.
+    pub position: i64,
+    pub journey_id: String,
+    #[serde(flatten)]
+    pub event: JourneyEventView,
+}
+
+/// A batch of the feed, as returned by `GET /events`.
+#[derive(Debug, Serialize, Clone)]
+pub struct EventPage {
+    /// The events after the requested cursor, oldest first.
+    pub events: Vec<FeedEvent>,
+    /// The cursor to ask from next: the position of the last event, or the
+    /// requested cursor when there were none.
+    pub next: i64,
+}
+
+/// Reads the feed from the Postgres event store.
+pub struct EventFeed {
+    pool: Pool<Postgres>,
+    repo: CryptoShreddingEventRepository<PostgresEventRepository>,
+    upcasters: Vec<Box<dyn EventUpcaster>>,
+}
+
+impl EventFeed {
+    /// A feed over the events in `pool`, decrypted with keys from `key_store`.
+    #[must_use]
+    pub fn new(pool: Pool<Postgres>, key_store: Arc<dyn KeyStore>) -> Self {
+        let repo = CryptoShreddingEventRepository::new(
+            PostgresEventRepository::new(pool.clone()),
+            key_store,
+            FieldCipher::new(),
+            Arc::new(JourneyPiiCodec),
+        );
+        Self {
+            pool,
+            repo,
+            upcasters: journey_event_upcasters(),
+        }
+    }
+
+    /// At most `limit` journey events after position `after`, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PersistenceError`] if the events cannot be read, decrypted
+    /// or deserialized.
+    pub async fn read_after(&self, after: i64, limit: i64) -> Result<EventPage, PersistenceError> {
+        let rows = sqlx::query(
+            r"
+            SELECT global_position, aggregate_type, aggregate_id, sequence,
+                   event_type, event_version, payload, metadata
+            FROM events
+            WHERE aggregate_type = 'Journey' AND global_position > $1
+            ORDER BY global_position
+            LIMIT $2
+            ",
+        )
+        .bind(after)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PersistenceError::UnknownError(Box::new(e)))?;
+
+        let positions: Vec<i64> = rows.iter().map(|row| row.get("global_position")).collect();
+        let serialized = rows
+            .iter()
+            .map(|row| {
+                let sequence: i64 = row.get("sequence");
+                SerializedEvent {
+                    aggregate_id: row.get("aggregate_id"),
+                    sequence: usize::try_from(sequence).unwrap_or_default(),
+                    aggregate_type: row.get("aggregate_type"),
+                    event_type: row.get("event_type"),
+                    event_version: row.get("event_version"),
+                    payload: row.get("payload"),
+                    metadata: row.get("metadata"),
+                }
+            })
+            .collect();
+
+        let events = self
+            .repo
+            .decrypt(serialized)
+            .await?
+            .into_iter()
+            .zip(positions)
+            .map(|(event, position)| {
+                let envelope = EventEnvelope::<Journey>::try_from(self.upcast(event))?;
+                Ok(FeedEvent {
+                    position,
+                    journey_id: envelope.aggregate_id.clone(),
+                    event: JourneyEventView::from(envelope),
+                })
+            })
+            .collect::<Result<Vec<_>, PersistenceError>>()?;
+
+        let next = events.last().map_or(after, |event| event.position);
+        Ok(EventPage { events, next })
+    }
+
+    fn upcast(&self, event: SerializedEvent) -> SerializedEvent {
+        self.upcasters.iter().fold(event, |event, upcaster| {
+            if upcaster.can_upcast(&event.event_type, &event.event_version) {
+                upcaster.upcast(event)
+            } else {
+                event
+            }
+        })
+    }
+}
@@ -0,0 +1,493 @@
+//! Idempotency keys for command submission.
+//!
+//! A client sends an `Idempotency-Key` header with a command so a network
+//! retry cannot apply it twice. The first request with a key claims it; once
+//! the command succeeds its response is recorded, and a repeat of the key for
+//! the same journey replays that response instead of executing again. A
+//! failed command releases its claim, so a retry runs it afresh — it recorded
+//! no events. Keys expire after a TTL, after which they can be reused.
+//!
+//! A claim whose command never finished, because the process died while it
+//! ran, is held only for a short lease; a retry after that takes the key
+//! over. Each key also remembers a [`fingerprint`] of the request that
+//! claimed it, and a repeat with a different request is refused.
+
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    sync::{Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::{Pool, Postgres, Row};
+use uuid::Uuid;
+
+/// How long a key is remembered unless configured otherwise: 24 hours.
+pub const DEFAULT_IDEMPOTENCY_TTL: Duration = Duration::from_hours(24);
+
+/// How long a claim whose command has not finished holds its key unless
+/// configured otherwise: one minute.
+pub const DEFAULT_IDEMPOTENCY_LEASE: Duration = Duration::from_mins(1);
+
+/// The hex SHA-256 digest of `request` serialized as JSON, which a repeat of
+/// an idempotency key must match.
+///
+/// # Panics
+///
+/// Panics if `request` cannot be serialized as JSON, which a request
+/// deserialized from JSON always can.
+#[must_use]
+pub fn fingerprint(request: &impl Serialize) -> String {
+    let json = serde_json::to_vec(request).expect("a request serializes as JSON");
+    Sha256::digest(json)
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+/// The response recorded for a completed command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedResponse {
+    pub status: u16,
+    /// The `Location` header, for a command that created the journey.
+    pub location: Option<String>,
+}
+
+/// The outcome of claiming a key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Claim {
+    /// The key is new or expired; the caller should execute the command.
+    Claimed,
+    /// A command with this key already succeeded.
+    Completed(RecordedResponse),
+    /// A command with this key is still executing.
+    InProgress,
+    /// The key was claimed for a request with another fingerprint.
+    Mismatch,
+}
+
+/// Where idempotency keys are kept, scoped per journey.
+#[async_trait::async_trait]
+pub trait IdempotencyStore: Send + Sync {
+    /// Claim `key` for `journey_id` and the request with `fingerprint`,
+    /// unless an unexpired claim holds it. A claim still in progress once its
+    /// lease has run out is taken over by the same request.
+    async fn claim(
+        &self,
+        journey_id: Uuid,
+        key: &str,
+        fingerprint: &str,
+    ) -> Result<Claim, sqlx::Error>;
+
+    /// Record the response of the command that claimed `key`.
+    async fn complete(
+        &self,
+        journey_id: Uuid,
+        key: &str,
+        response: &RecordedResponse,
+    ) -> Result<(), sqlx::Error>;
+
+    /// Drop the claim on `key`, so the command can be submitted again.
+    async fn release(&self, journey_id: Uuid, key: &str) -> Result<(), sqlx::Error>;
+}
+
+/// Idempotency keys in the `idempotency_key` table.
+#[derive(Clone)]
+pub struct PostgresIdempotencyStore {
+    pool: Pool<Postgres>,
+    ttl: Duration,
+    lease: Duration,
+}
+
+impl PostgresIdempotencyStore {
+    #[must_use]
+    pub const fn new(pool: Pool<Postgres>) -> Self {
+        Self {
+            pool,
+            ttl: DEFAULT_IDEMPOTENCY_TTL,
+            lease: DEFAULT_IDEMPOTENCY_LEASE,
+        }
+    }
+
+    /// Forget keys `ttl` after they were claimed.
+    #[must_use]
+    pub const fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Let a repeat take over a claim whose command has not finished `lease`
+    /// after it was claimed.
+    #[must_use]
+    pub const fn with_lease(mut self, lease: Duration) -> Self {
+        self.lease = lease;
+        self
+    }
+
+    /// Delete expired keys, returning how many were removed. Expired keys are
+    /// ignored either way; this only reclaims space.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn purge_expired(&self) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r"
+            DELETE FROM idempotency_key
+            WHERE claimed_at < CURRENT_TIMESTAMP - make_interval(secs => $1)
+            ",
+        )
+        .bind(self.ttl.as_secs_f64())
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[async_trait::async_trait]
+impl IdempotencyStore for PostgresIdempotencyStore {
+    async fn claim(
+        &self,
+        journey_id: Uuid,
+        key: &str,
+        fingerprint: &str,
+    ) -> Result<Claim, sqlx::Error> {
+        // Take the key if it is new, reclaim it once expired, or take over an
+        // unfinished claim of the same request once its lease has run out.
+        // Keys claimed before fingerprints were kept match any request.
+        let claimed = sqlx::query(
+            r"
+            INSERT INTO idempotency_key (journey_id, key, fingerprint)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (journey_id, key) DO UPDATE
+            SET status      = NULL,
+                location    = NULL,
+                fingerprint = EXCLUDED.fingerprint,
+                claimed_at  = CURRENT_TIMESTAMP
+            WHERE idempotency_key.claimed_at
+                  < CURRENT_TIMESTAMP - make_interval(secs => $4)
+               OR (idempotency_key.status IS NULL
+                   AND COALESCE(idempotency_key.fingerprint, $3) = $3
+                   AND idempotency_key.claimed_at
+                       < CURRENT_TIMESTAMP - make_interval(secs => $5))
+            RETURNING journey_id
+            ",
+        )
+        .bind(journey_id)
+        .bind(key)
+        .bind(fingerprint)
+        .bind(self.ttl.as_secs_f64())
+        .bind(self.lease.as_secs_f64())
+        .fetch_optional(&self.pool)
+        .await?;
+        if claimed.is_some() {
+            return Ok(Claim::Claimed);
+        }
+
+        let row = sqlx::query(
+            r"
+            SELECT status, location, fingerprint
+            FROM idempotency_key
+            WHERE journey_id = $1 AND key = $2
+            ",
+        )
+        .bind(journey_id)
+        .bind(key)
+        .fetch_one(&self.pool)
+        .await?;
+        let claimed_by: Option<String> = row.get("fingerprint");
+        if claimed_by.is_some_and(|claimed_by| claimed_by != fingerprint) {
+            return Ok(Claim::Mismatch);
+        }
+        let status: Option<i16> = row.get("status");
+        Ok(status.map_or(Claim::InProgress, |status| {
+            Claim::Completed(RecordedResponse {
+                status: status.unsigned_abs(),
+                location: row.get("location"),
+            })
+        }))
+    }
+
+    async fn complete(
+        &self,
+        journey_id: Uuid,
+        key: &str,
+        response: &RecordedResponse,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r"
+            UPDATE idempotency_key
+            SET status = $3, location = $4
+            WHERE journey_id = $1 AND key = $2
+            ",
+        )
+        .bind(journey_id)
+        .bind(key)
+        .bind(i16::try_from(response.status).unwrap_or(i16::MAX))
+        .bind(response.location.as_deref())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn release(&self, journey_id: Uuid, key: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM idempotency_key WHERE journey_id = $1 AND key = $2")
+            .bind(journey_id)
+            .bind(key)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+/// A key held in memory.
+struct HeldKey {
+    claimed_at: Instant,
+    fingerprint: String,
+    /// The response once the command succeeded.
+    response: Option<RecordedResponse>,
+}
+
+impl HeldKey {
+    fn claimed(fingerprint: &str) -> Self {
+        Self {
+            claimed_at: Instant::now(),
+            fingerprint: fingerprint.to_string(),
+            response: None,
+        }
+    }
+}
+
+/// Idempotency keys held in process memory, for tests and single-instance
+/// development. Keys are lost when the process exits.
+pub struct InMemoryIdempotencyStore {
+    keys: Mutex<HashMap<(Uuid, String), HeldKey>>,
+    ttl: Duration,
+    lease: Duration,
+}
+
+impl Default for InMemoryIdempotencyStore {
+    fn default() -> Self {
+        Self {
+            keys: Mutex::default(),
+            ttl: DEFAULT_IDEMPOTENCY_TTL,
+            lease: DEFAULT_IDEMPOTENCY_LEASE,
+        }
+    }
+}
+
+impl InMemoryIdempotencyStore {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget keys `ttl` after they were claimed.
+    #[must_use]
+    pub const fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Let a repeat take over a claim whose command has not finished `lease`
+    /// after it was claimed.
+    #[must_use]
+    pub const fn with_lease(mut self, lease: Duration) -> Self {
+        self.lease = lease;
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl IdempotencyStore for InMemoryIdempotencyStore {
+    async fn claim(
+        &self,
+        journey_id: Uuid,
+        key: &str,
+        fingerprint: &str,
+    ) -> Result<Claim, sqlx::Error> {
+        let mut keys = self.keys.lock().unwrap_or_else(PoisonError::into_inner);
+        let entry = keys.entry((journey_id, key.to_string()));
+        let claim = match entry {
+            std::collections::hash_map::Entry::Occupied(mut held)
+                if held.get().claimed_at.elapsed() >= self.ttl =>
+            {
+                held.insert(HeldKey::claimed(fingerprint));
+                Claim::Claimed
+            }
+            std::collections::hash_map::Entry::Occupied(held)
+                if held.get().fingerprint != fingerprint =>
+            {
+                Claim::Mismatch
+            }
+            std::collections::hash_map::Entry::Occupied(mut held) => {
+                match held.get().response.clone() {
+                    Some(response) => Claim::Completed(response),
+                    None if held.get().claimed_at.elapsed() >= self.lease => {
+                        held.insert(HeldKey::claimed(fingerprint));
+                        Claim::Claimed
+                    }
+                    None => Claim::InProgress,
+                }
+            }
+            std::collections::hash_map::Entry::Vacant(vacant) => {
+                vacant.insert(HeldKey::claimed(fingerprint));
+                Claim::Claimed
+            }
+        };
+        drop(keys);
+        Ok(claim)
+    }
+
+    async fn complete(
+        &self,
+        journey_id: Uuid,
+        key: &str,
+        response: &RecordedResponse,
+    ) -> Result<(), sqlx::Error> {
+        let mut keys = self.keys.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(held) = keys.get_mut(&(journey_id, key.to_string())) {
+            held.response = Some(response.clone());
+        }
+        drop(keys);
+        Ok(())
+    }
+
+    async fn release(&self, journey_id: Uuid, key: &str) -> Result<(), sqlx::Error> {
+        self.keys
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&(journey_id, key.to_string()));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_content() -> RecordedResponse {
+        RecordedResponse {
+            status: 204,
+            location: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn a_completed_key_replays_its_response() {
+        let store = InMemoryIdempotencyStore::new();
+        let journey_id = Uuid::new_v4();
+
+        assert_eq!(
+            store.claim(journey_id, "k1", "f1").await.unwrap(),
+            Claim::Claimed
+        );
+        assert_eq!(
+            store.claim(journey_id, "k1", "f1").await.unwrap(),
+            Claim::InProgress
+        );
+        store
+            .complete(journey_id, "k1", &no_content())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store.claim(journey_id, "k1", "f1").await.unwrap(),
+            Claim::Completed(no_content())
+        );
+    }
+
+    #[tokio::test]
+    async fn keys_are_scoped_per_journey() {
+        let store = InMemoryIdempotencyStore::new();
+        store.claim(Uuid::new_v4(), "k1", "f1").await.unwrap();
+
+        assert_eq!(
+            store.claim(Uuid::new_v4(), "k1", "f1").await.unwrap(),
+            Claim::Claimed
+        );
+    }
+
+    #[tokio::test]
+    async fn a_key_repeated_with_another_request_is_a_mismatch() {
+        let store = InMemoryIdempotencyStore::new();
+        let journey_id = Uuid::new_v4();
+        store.claim(journey_id, "k1", "f1").await.unwrap();
+
+        assert_eq!(
+            store.claim(journey_id, "k1", "f2").await.unwrap(),
+            Claim::Mismatch
+        );
+        store
+            .complete(journey_id, "k1", &no_content())
+            .await
+            .unwrap();
+        assert_eq!(
+            store.claim(journey_id, "k1", "f2").await.unwrap(),
+            Claim::Mismatch
+        );
+    }
+
+    #[tokio::test]
+    async fn an_unfinished_claim_is_taken_over_once_its_lease_runs_out() {
+        let store = InMemoryIdempotencyStore::new().with_lease(Duration::ZERO);
+        let journey_id = Uuid::new_v4();
+        store.claim(journey_id, "k1", "f1").await.unwrap();
+
+        assert_eq!(
+            store.claim(journey_id, "k1", "f2").await.unwrap(),
+            Claim::Mismatch
+        );
+        assert_eq!(
+            store.claim(journey_id, "k1", "f1").await.unwrap(),
+            Claim::Claimed
+        );
+
+        // A finished command keeps its key for the whole TTL.
+        store
+            .complete(journey_id, "k1", &no_content())
+            .await
+            .unwrap();
+        assert_eq!(
+            store.claim(journey_id, "k1", "f1").await.unwrap(),
+            Claim::Completed(no_content())
+        );
+    }
+
+    #[test]
+    fn fingerprints_tell_requests_apart() {
+        let search = serde_json::json!({ "Capture": { "step": "search", "data": {} } });
+        let other = serde_json::json!({ "Capture": { "step": "seats", "data": {} } });
+
+        assert_eq!(fingerprint(&search), fingerprint(&search.clone()));
+        assert_ne!(fingerprint(&search), fingerprint(&other));
+        assert_eq!(fingerprint(&search).len(), 64);
+    }
+
+    #[tokio::test]
+    async fn released_and_expired_keys_can_be_claimed_again() {
+        let journey_id = Uuid::new_v4();
+        let store = InMemoryIdempotencyStore::new();
+        store.claim(journey_id, "k1", "f1").await.unwrap();
+        store.release(journey_id, "k1").await.unwrap();
+        assert_eq!(
+            store.claim(journey_id, "k1", "f1").await.unwrap(),
+            Claim::Claimed
+        );
+
+        let store = InMemoryIdempotencyStore::new().with_ttl(Duration::ZERO);
+        store.claim(journey_id, "k1", "f1").await.unwrap();
+        store
+            .complete(journey_id, "k1", &no_content())
+            .await
+            .unwrap();
+        assert_eq!(
+            store.claim(journey_id, "k1", "f1").await.unwrap(),
+            Claim::Claimed
+        );
+    }
+}
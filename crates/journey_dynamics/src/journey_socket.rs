@@ -0,0 +1,420 @@
+//! Two-way WebSocket channel for one journey, served at
+//! `GET /journeys/{id}/ws`.
+//!
+//! The client sends `JourneyCommand`s as JSON text frames, exactly as it would
+//! `POST` them to `/journeys/{id}`, and the server answers each one in order
+//! with a JSON text frame tagged by `type`:
+//!
+//! - `accepted`: the command was committed; carries the updated `view` and
+//!   its latest workflow `decision`.
+//! - `rejected`: the command was not; carries the HTTP `status` the same
+//!   command would have got, and either the per-field `errors` or a
+//!   `message`.
+//!
+//! On connect, and whenever another client changes the journey, the server
+//! also pushes a `journey` frame with the current `view`.
+//!
+//! Commands are executed one at a time: the next frame is not read until the
+//! previous command has been answered, so a client sending faster than its
+//! commands commit is held back by the socket rather than buffered. Pushed
+//! views come from the same bounded broadcast as the SSE stream, and a client
+//! that lags behind skips to the latest one. The server pings every
+//! [`KEEPALIVE_INTERVAL`] and drops a connection that has sent nothing,
+//! pongs included, since the previous ping.
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use axum::{
+    body::{Bytes, to_bytes},
+    extract::{
+        Path, State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    http::{HeaderMap, StatusCode, Uri},
+    response::{IntoResponse, Response},
+};
+use cqrs_es::{CqrsFramework, EventStore};
+use serde::Serialize;
+use serde_json::Value;
+use tokio::{
+    sync::broadcast::{self, error::RecvError},
+    time::{MissedTickBehavior, interval_at},
+};
+use uuid::Uuid;
+
+use crate::{
+    command_extractor::{command_metadata, parse_command},
+    command_rejections::RejectionLog,
+    domain::{events::COMMAND_METADATA_KEY, journey::Journey},
+    queries::{JourneyView, WorkflowDecisionView},
+    route_handler::{
+        COMMAND_MAX_ATTEMPTS, command_error_response, execute_with_retry, record_rejection,
+    },
+    state::ApplicationState,
+    view_repository::JourneyViewStore,
+};
+
+/// How often the server pings an idle connection.
+pub const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Largest command frame accepted, in bytes.
+pub const MAX_FRAME_BYTES: usize = 1024 * 1024;
+
+/// A frame sent to the client.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerFrame<'a> {
+    Journey {
+        view: &'a JourneyView,
+    },
+    Accepted {
+        view: &'a JourneyView,
+        decision: Option<&'a WorkflowDecisionView>,
+    },
+    Rejected {
+        status: u16,
+        #[serde(flatten)]
+        body: Value,
+    },
+}
+
+// Upgrades to a WebSocket for commands to, and views of, one journey. Answers
+// `404` instead when the journey does not exist.
+pub async fn journey_socket_handler(
+    ws: WebSocketUpgrade,
+    Path(journey_id): Path<Uuid>,
+    State(state): State<Arc<ApplicationState>>,
+    uri: Uri,
+    headers: HeaderMap,
+) -> Response {
+    // Subscribe before loading the snapshot so no update committed in between is lost.
+    let updates = state.journey_updates.subscribe(journey_id);
+    let snapshot = match state.journey_query.load(&journey_id).await {
+        Ok(Some(journey_view)) => journey_view,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(err) => return err.into_response(),
+    };
+
+    ws.max_message_size(MAX_FRAME_BYTES)
+        .on_upgrade(move |socket| async move {
+            let session = Session {
+                cqrs: state.cqrs.as_ref(),
+                views: state.journey_query.as_ref(),
+                rejections: state.rejections.as_ref(),
+                journey_id,
+                uri,
+                headers,
+            };
+            session.run(socket, snapshot, updates).await;
+        })
+}
+
+/// One client's connection to one journey.
+pub(crate) struct Session<'a, ES: EventStore<Journey>> {
+    pub cqrs: &'a CqrsFramework<Journey, ES>,
+    pub views: &'a dyn JourneyViewStore,
+    pub rejections: &'a dyn RejectionLog,
+    pub journey_id: Uuid,
+    /// The handshake's uri and headers, recorded with every command as a
+    /// `POST` would record its own.
+    pub uri: Uri,
+    pub headers: HeaderMap,
+}
+
+impl<ES: EventStore<Journey>> Session<'_, ES> {
+    /// Serve `socket` until either side closes it: `snapshot` first, then the
+    /// answer to every command and each view received on `updates`.
+    pub async fn run(
+        &self,
+        mut socket: WebSocket,
+        snapshot: JourneyView,
+        mut updates: broadcast::Receiver<JourneyView>,
+    ) {
+        if send(&mut socket, &ServerFrame::Journey { view: &snapshot })
+            .await
+            .is_err()
+        {
+            return;
+        }
+
+        let mut keepalive = interval_at(
+            tokio::time::Instant::now() + KEEPALIVE_INTERVAL,
+            KEEPALIVE_INTERVAL,
+        );
+        keepalive.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut heard_since_ping = true;
+
+        loop {
+            let sent = tokio::select! {
+                received = socket.recv() => {
+                    heard_since_ping = true;
+                    match received {
+                        Some(Ok(Message::Text(text))) => {
+                            self.handle(&mut socket, text.as_bytes(), &mut updates).await
+                        }
+                        Some(Ok(Message::Binary(_))) => {
+                            let frame = ServerFrame::Rejected {
+                                status: StatusCode::UNSUPPORTED_MEDIA_TYPE.as_u16(),
+                                body: message_body("commands must be sent as text frames"),
+                            };
+                            send(&mut socket, &frame).await
+                        }
+                        // Pongs to our pings are queued by the socket itself.
+                        Some(Ok(Message::Ping(_) | Message::Pong(_))) => Ok(()),
+                        Some(Ok(Message::Close(_)) | Err(_)) | None => return,
+                    }
+                }
+                update = updates.recv() => match update {
+                    Ok(view) => send(&mut socket, &ServerFrame::Journey { view: &view }).await,
+                    // Every view is a full snapshot, so the next one supersedes those missed.
+                    Err(RecvError::Lagged(_)) => Ok(()),
+                    Err(RecvError::Closed) => return,
+                },
+                _ = keepalive.tick() => {
+                    if !heard_since_ping {
+                        return;
+                    }
+                    heard_since_ping = false;
+                    socket.send(Message::Ping(Bytes::new())).await
+                }
+            };
+            if sent.is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Execute the command in `frame` and answer it.
+    async fn handle(
+        &self,
+        socket: &mut WebSocket,
+        frame: &[u8],
+        updates: &mut broadcast::Receiver<JourneyView>,
+    ) -> Result<(), axum::Error> {
+        let command = match parse_command(frame, Some(self.journey_id)) {
+            Ok(command) => command,
+            Err(err) => return send(socket, &rejected(err.into_response()).await).await,
+        };
+        let command_type = command.name();
+        let mut metadata = command_metadata(&self.uri, &self.headers);
+        metadata.insert(COMMAND_METADATA_KEY.to_string(), command_type.to_string());
+        if let Err(err) = execute_with_retry(
+            self.cqrs,
+            &self.journey_id.to_string(),
+            command,
+            metadata,
+            COMMAND_MAX_ATTEMPTS,
+        )
+        .await
+        {
+            record_rejection(self.rejections, self.journey_id, command_type, &err).await;
+            return send(socket, &rejected(command_error_response(err)).await).await;
+        }
+
+        // The views published while the command ran are no newer than the one
+        // loaded below, and it is sent in their place.
+        while let Ok(_) | Err(broadcast::error::TryRecvError::Lagged(_)) = updates.try_recv() {}
+
+        match self.views.load(&self.journey_id).await {
+            Ok(Some(view)) => {
+                let frame = ServerFrame::Accepted {
+                    view: &view,
+                    decision: view.latest_workflow_decision.as_ref(),
+                };
+                send(socket, &frame).await
+            }
+            Ok(None) => {
+                let frame = ServerFrame::Rejected {
+                    status: StatusCode::NOT_FOUND.as_u16(),
+                    body: message_body("journey not found"),
+                };
+                send(socket, &frame).await
+            }
+            Err(err) => {
+                eprintln!("Error: {err:#?}");
+                let frame = ServerFrame::Rejected {
+                    status: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+                    body: message_body(&err.to_string()),
+                };
+                send(socket, &frame).await
+            }
+        }
+    }
+}
+
+/// The `rejected` frame carrying what `response` would have told a `POST`.
+///
+/// A JSON object body, such as the per-field `errors`, is kept as is; any
+/// other body becomes the `message`.
+async fn rejected(response: Response) -> ServerFrame<'static> {
+    let status = response.status().as_u16();
+    let bytes = to_bytes(response.into_body(), MAX_FRAME_BYTES)
+        .await
+        .unwrap_or_default();
+    let body = match serde_json::from_slice(&bytes) {
+        Ok(body @ Value::Object(_)) => body,
+        _ => message_body(&String::from_utf8_lossy(&bytes)),
+    };
+    ServerFrame::Rejected { status, body }
+}
+
+fn message_body(message: &str) -> Value {
+    let mut body = HashMap::new();
+    body.insert("message", message);
+    serde_json::to_value(body).unwrap_or_default()
+}
+
+async fn send(socket: &mut WebSocket, frame: &ServerFrame<'_>) -> Result<(), axum::Error> {
+    // Serializing these frames cannot fail: every map key is a string.
+    let text = serde_json::to_string(frame).unwrap_or_default();
+    socket.send(Message::Text(text.into())).await
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, extract::WebSocketUpgrade, routing::get};
+    use cqrs_es::{CqrsFramework, mem_store::MemStore};
+    use futures_util::{SinkExt, StreamExt};
+    use serde_json::{Value, json};
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async, tungstenite};
+    use uuid::Uuid;
+
+    use super::Session;
+    use crate::{
+        command_rejections::{CommandRejection, InMemoryRejectionLog},
+        domain::{
+            commands::JourneyCommand,
+            journey::{Journey, JourneyError, JourneyServices},
+        },
+        journey_updates::JourneyUpdates,
+        memory_view_repository::InMemoryJourneyViewRepository,
+        services::decision_engine::SimpleDecisionEngine,
+        view_repository::JourneyViewStore,
+    };
+
+    type Client = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
+
+    /// Serves a session for a started journey on a local port, returning the
+    /// journey ID, the socket's URL and the log its rejections go to.
+    async fn serve() -> (Uuid, String, Arc<InMemoryRejectionLog>) {
+        let views = InMemoryJourneyViewRepository::new();
+        let cqrs = Arc::new(CqrsFramework::new(
+            MemStore::<Journey>::default(),
+            vec![Box::new(views.clone())],
+            JourneyServices::without_validation(Arc::new(SimpleDecisionEngine)),
+        ));
+        let journey_id = Uuid::new_v4();
+        let start = JourneyCommand::Start {
+            id: journey_id,
+            journey_type: None,
+        };
+        cqrs.execute(&journey_id.to_string(), start).await.unwrap();
+        let updates = JourneyUpdates::default();
+        let rejections = Arc::new(InMemoryRejectionLog::new());
+
+        let router = Router::new().route(
+            "/ws",
+            get({
+                let rejections = Arc::clone(&rejections);
+                move |ws: WebSocketUpgrade| async move {
+                    let receiver = updates.subscribe(journey_id);
+                    let snapshot = views.load(&journey_id).await.unwrap().unwrap();
+                    ws.on_upgrade(move |socket| async move {
+                        let session = Session {
+                            cqrs: cqrs.as_ref(),
+                            views: &views,
+                            rejections: rejections.as_ref(),
+                            journey_id,
+                            uri: "/ws".parse().unwrap(),
+                            headers: axum::http::HeaderMap::new(),
+                        };
+                        session.run(socket, snapshot, receiver).await;
+                    })
+                }
+            }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        (journey_id, format!("ws://{address}/ws"), rejections)
+    }
+
+    async fn next_frame(client: &mut Client) -> Value {
+        loop {
+            match client.next().await.unwrap().unwrap() {
+                tungstenite::Message::Text(text) => return serde_json::from_str(&text).unwrap(),
+                tungstenite::Message::Ping(_) | tungstenite::Message::Pong(_) => {}
+                other => panic!("unexpected frame: {other:?}"),
+            }
+        }
+    }
+
+    async fn send(client: &mut Client, command: &Value) {
+        let text = command.to_string();
+        client
+            .send(tungstenite::Message::Text(text.into()))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn capture_is_answered_with_the_updated_decision() {
+        let (journey_id, url, _) = serve().await;
+        let (mut client, _) = connect_async(url).await.unwrap();
+
+        let snapshot = next_frame(&mut client).await;
+        assert_eq!(snapshot["type"], "journey");
+        assert_eq!(snapshot["view"]["id"], journey_id.to_string());
+
+        let capture = json!({ "Capture": { "step": "name", "data": { "first_name": "Joe" } } });
+        send(&mut client, &capture).await;
+
+        let answer = next_frame(&mut client).await;
+        assert_eq!(answer["type"], "accepted");
+        assert_eq!(answer["view"]["shared_data"]["first_name"], "Joe");
+        assert_eq!(answer["decision"]["suggested_actions"], json!(["form_3"]));
+    }
+
+    #[tokio::test]
+    async fn a_rejected_command_keeps_the_connection_open() {
+        let (journey_id, url, rejections) = serve().await;
+        let (mut client, _) = connect_async(url).await.unwrap();
+        next_frame(&mut client).await;
+
+        send(&mut client, &json!({ "Teleport": {} })).await;
+        let unreadable = next_frame(&mut client).await;
+        assert_eq!(unreadable["type"], "rejected");
+        assert_eq!(unreadable["status"], 400);
+        assert!(unreadable["message"].is_string());
+
+        send(&mut client, &json!({ "Start": { "id": Uuid::new_v4() } })).await;
+        let other_journey = next_frame(&mut client).await;
+        assert_eq!(other_journey["status"], 400);
+
+        let correct = json!({ "Correct": { "path": "first_name", "value": "Jo" } });
+        send(&mut client, &correct).await;
+        let nothing_captured = next_frame(&mut client).await;
+        assert_eq!(nothing_captured["type"], "rejected");
+        assert_eq!(nothing_captured["status"], 422);
+
+        let capture = json!({ "Capture": { "step": "name", "data": { "first_name": "Joe" } } });
+        send(&mut client, &capture).await;
+        assert_eq!(next_frame(&mut client).await["type"], "accepted");
+
+        // Only the command the aggregate rejected is recorded; the others
+        // never reached it.
+        assert_eq!(
+            rejections.rejections(),
+            vec![CommandRejection::new(
+                journey_id,
+                "Correct",
+                &JourneyError::NothingToCorrect("first_name".to_string())
+            )]
+        );
+    }
+}
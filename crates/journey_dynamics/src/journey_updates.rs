@@ -0,0 +1,195 @@
+//! Per-journey broadcast of updated [`JourneyView`]s.
+//!
+//! [`StructuredJourneyViewRepository`](crate::view_repository::StructuredJourneyViewRepository)
+//! publishes the freshly committed view after every successful dispatch; the
+//! `GET /journeys/{id}/stream` SSE endpoint and the `GET /journeys/{id}/ws`
+//! WebSocket subscribe to it.
+//!
+//! Each journey with at least one live subscriber owns a bounded
+//! [`broadcast`] channel. A subscriber that falls more than `capacity`
+//! messages behind skips the oldest ones instead of buffering them, and a
+//! journey's channel is dropped once its last subscriber disconnects.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::queries::JourneyView;
+
+/// Default number of views buffered per journey for a lagging subscriber.
+pub const DEFAULT_CAPACITY: usize = 16;
+
+/// Registry of per-journey view update channels.
+///
+/// Cheap to clone; clones share the same registry.
+#[derive(Clone)]
+pub struct JourneyUpdates {
+    senders: Arc<Mutex<HashMap<Uuid, broadcast::Sender<JourneyView>>>>,
+    capacity: usize,
+}
+
+impl Default for JourneyUpdates {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl JourneyUpdates {
+    /// Create a registry that buffers at most `capacity` views per journey.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "JourneyUpdates capacity must be non-zero");
+        Self {
+            senders: Arc::new(Mutex::new(HashMap::new())),
+            capacity,
+        }
+    }
+
+    /// Subscribe to view updates for `journey_id`.
+    #[must_use]
+    pub fn subscribe(&self, journey_id: Uuid) -> broadcast::Receiver<JourneyView> {
+        let mut senders = self.senders();
+        senders.retain(|_, sender| sender.receiver_count() > 0);
+        senders
+            .entry(journey_id)
+            .or_insert_with(|| broadcast::channel(self.capacity).0)
+            .subscribe()
+    }
+
+    /// Whether anyone is currently subscribed to `journey_id`.
+    ///
+    /// Lets publishers skip loading a view nobody is waiting for.
+    #[must_use]
+    pub fn has_subscribers(&self, journey_id: &Uuid) -> bool {
+        self.senders()
+            .get(journey_id)
+            .is_some_and(|sender| sender.receiver_count() > 0)
+    }
+
+    /// Send `view` to every current subscriber of `journey_id`.
+    pub fn publish(&self, journey_id: Uuid, view: JourneyView) {
+        let mut senders = self.senders();
+        if let Some(sender) = senders.get(&journey_id)
+            && sender.send(view).is_err()
+        {
+            // Every receiver has been dropped.
+            senders.remove(&journey_id);
+        }
+    }
+
+    /// Drop every channel, ending all current subscriptions.
+    ///
+    /// Used on shutdown so long-lived streams stop holding their connections
+    /// open.
+    pub fn close(&self) {
+        self.senders().clear();
+    }
+
+    // Every critical section leaves the map consistent, so a poisoned lock is
+    // safe to keep using.
+    fn senders(&self) -> MutexGuard<'_, HashMap<Uuid, broadcast::Sender<JourneyView>>> {
+        self.senders.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::broadcast::error::TryRecvError;
+
+    use super::*;
+
+    fn view(id: Uuid) -> JourneyView {
+        JourneyView {
+            id,
+            ..JourneyView::default()
+        }
+    }
+
+    #[test]
+    fn subscriber_receives_published_view() {
+        let updates = JourneyUpdates::default();
+        let id = Uuid::new_v4();
+        let mut rx = updates.subscribe(id);
+
+        updates.publish(id, view(id));
+
+        assert_eq!(rx.try_recv().unwrap().id, id);
+    }
+
+    #[test]
+    fn subscriber_does_not_receive_other_journeys() {
+        let updates = JourneyUpdates::default();
+        let id = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let mut rx = updates.subscribe(id);
+        let _other_rx = updates.subscribe(other);
+
+        updates.publish(other, view(other));
+
+        assert_eq!(rx.try_recv().unwrap_err(), TryRecvError::Empty);
+    }
+
+    #[test]
+    fn publish_without_subscribers_is_a_noop() {
+        let updates = JourneyUpdates::default();
+        let id = Uuid::new_v4();
+
+        updates.publish(id, view(id));
+
+        assert!(!updates.has_subscribers(&id));
+        assert!(updates.senders().is_empty());
+    }
+
+    #[test]
+    fn channel_is_dropped_after_last_subscriber_disconnects() {
+        let updates = JourneyUpdates::default();
+        let id = Uuid::new_v4();
+        let rx = updates.subscribe(id);
+        assert!(updates.has_subscribers(&id));
+
+        drop(rx);
+        updates.publish(id, view(id));
+
+        assert!(!updates.has_subscribers(&id));
+        assert!(updates.senders().is_empty());
+    }
+
+    #[test]
+    fn close_ends_every_subscription() {
+        let updates = JourneyUpdates::default();
+        let mut rx = updates.subscribe(Uuid::new_v4());
+        let mut other_rx = updates.subscribe(Uuid::new_v4());
+
+        updates.close();
+
+        assert_eq!(rx.try_recv().unwrap_err(), TryRecvError::Closed);
+        assert_eq!(other_rx.try_recv().unwrap_err(), TryRecvError::Closed);
+        assert!(updates.senders().is_empty());
+    }
+
+    #[test]
+    fn slow_subscriber_skips_views_beyond_capacity() {
+        let updates = JourneyUpdates::new(2);
+        let id = Uuid::new_v4();
+        let mut rx = updates.subscribe(id);
+
+        for _ in 0..5 {
+            updates.publish(id, view(id));
+        }
+
+        assert_eq!(rx.try_recv().unwrap_err(), TryRecvError::Lagged(3));
+        assert!(rx.try_recv().is_ok());
+        assert!(rx.try_recv().is_ok());
+        assert_eq!(rx.try_recv().unwrap_err(), TryRecvError::Empty);
+    }
+}
@@ -1,10 +1,24 @@
+pub mod batching_query;
 pub mod command_extractor;
+pub mod command_log;
+pub mod command_rejections;
 pub mod config;
 pub mod domain;
+pub mod event_feed;
+pub mod idempotency;
+pub mod journey_socket;
+pub mod journey_updates;
+pub mod memory_view_repository;
+pub mod pii_cipher;
 pub mod pii_codec;
 pub mod queries;
+pub mod rebuild;
+pub mod reevaluate;
 pub mod route_handler;
 pub mod services;
+pub mod shutdown;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_view_repository;
 pub mod state;
 pub mod subject_lookup_hook;
 pub mod view_repository;
@@ -1,31 +1,84 @@
-use std::{net::SocketAddr, sync::Arc};
+use std::sync::Arc;
 
 use axum::{
     Router,
     routing::{delete, get, post},
 };
 use journey_dynamics::{
-    route_handler::{command_handler, query_handler, shred_subject, shred_subjects_by_email},
+    config::Config,
+    journey_socket::journey_socket_handler,
+    route_handler::{
+        command_handler, event_feed_handler, event_history_handler, explain_handler,
+        export_handler, health_handler, journey_stream_handler, list_journeys_handler,
+        next_actions_handler, preview_handler, query_handler, ready_handler,
+        reevaluate_all_handler, reevaluate_handler, resume_handler, shred_subject,
+        shred_subjects_by_email, start_from_handler, workflow_steps_handler,
+    },
+    shutdown::{serve_until, shutdown_signal},
     state::new_application_state,
 };
 
 #[tokio::main]
 async fn main() {
     dotenv::dotenv().ok();
-    let state = Arc::new(new_application_state().await);
+    let config = Config::from_env().unwrap_or_else(|err| {
+        eprintln!("Error: {err}");
+        std::process::exit(1)
+    });
+    let state = Arc::new(new_application_state(&config).await);
     let router = Router::new()
-        .route("/journeys", post(command_handler))
+        .route("/health", get(health_handler))
+        .route("/ready", get(ready_handler))
+        .route(
+            "/journeys",
+            get(list_journeys_handler).post(command_handler),
+        )
         .route(
             "/journeys/{journey_id}",
             get(query_handler).post(command_handler),
         )
+        .route("/journeys/{journey_id}/events", get(event_history_handler))
+        .route("/events", get(event_feed_handler))
+        .route("/journeys/{journey_id}/export", get(export_handler))
+        .route(
+            "/journeys/{journey_id}/next-actions",
+            get(next_actions_handler),
+        )
+        .route("/journeys/{journey_id}/resume", get(resume_handler))
+        .route("/journeys/{journey_id}/stream", get(journey_stream_handler))
+        .route("/journeys/{journey_id}/ws", get(journey_socket_handler))
+        .route("/journeys/from/{source_id}", post(start_from_handler))
+        .route("/journeys/reevaluate", post(reevaluate_all_handler))
+        .route(
+            "/journeys/{journey_id}/reevaluate",
+            post(reevaluate_handler),
+        )
+        .route("/journeys/{journey_id}/preview", post(preview_handler))
+        .route("/journeys/{journey_id}/explain", get(explain_handler))
+        .route("/workflow/steps", get(workflow_steps_handler))
         .route("/subjects/by-email", delete(shred_subjects_by_email))
         .route("/subjects/{subject_id}", delete(shred_subject))
-        .with_state(state);
+        .with_state(Arc::clone(&state));
 
-    let addr = SocketAddr::from(([0, 0, 0, 0], 3030));
-
-    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+    let listener = tokio::net::TcpListener::bind(config.bind_addr)
+        .await
+        .unwrap();
     println!("Listening on {listener:?}");
-    axum::serve(listener, router).await.unwrap();
+    serve_until(
+        listener,
+        router,
+        state.journey_updates.clone(),
+        shutdown_signal(),
+    )
+    .await
+    .unwrap();
+
+    // Every command has finished writing; apply any batched view writes, and
+    // only then is the pool safe to close.
+    if let Some(view_batcher) = &state.view_batcher {
+        view_batcher.flush().await;
+        println!("Shutting down: batched view writes flushed");
+    }
+    state.pool.close().await;
+    println!("Shutting down: database pool closed");
 }
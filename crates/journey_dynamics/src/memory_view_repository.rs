@@ -0,0 +1,760 @@
+//! In-memory [`JourneyViewStore`], for demos and tests without a database.
+//!
+//! Views live in a map keyed by journey ID, persons included, and are lost
+//! when the process exits. Events are applied in Rust: the view itself
+//! through [`View::update`], the person slots by `apply_to_persons`, which
+//! mirrors the `journey_person` statements in
+//! [`StructuredJourneyViewRepository`](crate::view_repository::StructuredJourneyViewRepository).
+//! The same projection backs the `SQLite` store.
+//!
+//! The server does not fall back to this store when `DATABASE_URL` is
+//! unset: its event store, key store and command logs need Postgres too.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use chrono::Utc;
+use cqrs_es::{EventEnvelope, Query, View};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        assign_all,
+        events::{EventTime, JourneyEvent},
+        journey::Journey,
+    },
+    queries::{JourneyView, PersonView},
+    view_repository::{JourneyViewStore, ViewRepositoryError},
+};
+
+/// A journey view store held in process memory.
+///
+/// Clones share the same views.
+#[derive(Clone, Default)]
+pub struct InMemoryJourneyViewRepository {
+    views: Arc<RwLock<HashMap<Uuid, JourneyView>>>,
+}
+
+impl InMemoryJourneyViewRepository {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Views matching `predicate`, ordered by journey ID.
+    fn filter(&self, predicate: impl Fn(&JourneyView) -> bool) -> Vec<JourneyView> {
+        let mut views: Vec<JourneyView> = self
+            .views()
+            .values()
+            .filter(|view| predicate(view))
+            .cloned()
+            .collect();
+        views.sort_by_key(|view| view.id);
+        views
+    }
+
+    // Every write replaces whole views, so a poisoned lock is safe to keep
+    // using.
+    fn views(&self) -> RwLockReadGuard<'_, HashMap<Uuid, JourneyView>> {
+        self.views.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn views_mut(&self) -> RwLockWriteGuard<'_, HashMap<Uuid, JourneyView>> {
+        self.views.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[async_trait::async_trait]
+impl JourneyViewStore for InMemoryJourneyViewRepository {
+    async fn load(&self, journey_id: &Uuid) -> Result<Option<JourneyView>, ViewRepositoryError> {
+        Ok(self.views().get(journey_id).cloned())
+    }
+
+    async fn load_all(&self) -> Result<Vec<JourneyView>, ViewRepositoryError> {
+        let mut views: Vec<JourneyView> = self.views().values().cloned().collect();
+        views.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(a.id.cmp(&b.id)));
+        Ok(views)
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Vec<JourneyView>, ViewRepositoryError> {
+        let email = email.to_lowercase();
+        Ok(self.filter(|view| {
+            view.persons.iter().any(|person| {
+                !person.forgotten
+                    && person
+                        .email
+                        .as_deref()
+                        .is_some_and(|e| e.to_lowercase() == email)
+            })
+        }))
+    }
+
+    async fn find_where_data_contains(
+        &self,
+        fragment: &Value,
+    ) -> Result<Vec<JourneyView>, ViewRepositoryError> {
+        Ok(self.filter(|view| json_contains(&view.shared_data, fragment)))
+    }
+
+    async fn apply_events(
+        &self,
+        journey_id: Uuid,
+        events: &[EventEnvelope<Journey>],
+    ) -> Result<(), ViewRepositoryError> {
+        let mut views = self.views_mut();
+
+        // Work on a copy so a failing batch leaves the stored view untouched.
+        let mut view = views.get(&journey_id).cloned();
+        apply_events_to(&mut view, journey_id, events)?;
+
+        if let Some(view) = view {
+            views.insert(journey_id, view);
+        }
+        drop(views);
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Query<Journey> for InMemoryJourneyViewRepository {
+    async fn dispatch(&self, view_id: &str, events: &[EventEnvelope<Journey>]) {
+        if events.is_empty() {
+            return;
+        }
+
+        let journey_id = match Uuid::parse_str(view_id) {
+            Ok(id) => id,
+            Err(e) => {
+                eprintln!("Invalid journey ID '{view_id}': {e:?}");
+                return;
+            }
+        };
+
+        if let Err(e) = self.apply_events(journey_id, events).await {
+            eprintln!("Error updating journey view '{view_id}': {e:?}");
+        }
+    }
+}
+
+/// Apply `events` to `view`, the stored view of `journey_id` if there is one.
+///
+/// Events at or below the view's version have already been projected, or
+/// arrived after a later one, and are skipped, as the Postgres repository
+/// skips them, so a replayed, duplicated or out-of-order dispatch cannot
+/// regress the view.
+#[allow(clippy::cast_possible_wrap)]
+pub(crate) fn apply_events_to(
+    view: &mut Option<JourneyView>,
+    journey_id: Uuid,
+    events: &[EventEnvelope<Journey>],
+) -> Result<(), ViewRepositoryError> {
+    for event in events {
+        match (&mut *view, &event.payload) {
+            (Some(view), _) if event.sequence as i64 <= view.version => {
+                eprintln!(
+                    "Skipping event {} for journey view '{journey_id}': already at version {}",
+                    event.sequence, view.version
+                );
+            }
+            // Matches the Postgres INSERT … ON CONFLICT DO NOTHING.
+            (Some(_), JourneyEvent::Started { .. }) => {}
+            (None, JourneyEvent::Started { .. }) => {
+                let mut started = JourneyView::default();
+                apply(&mut started, event);
+                *view = Some(started);
+            }
+            (Some(view), _) => apply(view, event),
+            (None, _) => return Err(ViewRepositoryError::NotFound(journey_id)),
+        }
+    }
+    Ok(())
+}
+
+/// Apply one event to a stored view.
+fn apply(view: &mut JourneyView, event: &EventEnvelope<Journey>) {
+    view.update(event);
+
+    // The Postgres repository stamps events without a time with the clock.
+    if event.occurred_at().is_none() {
+        let now = Some(Utc::now());
+        if matches!(event.payload, JourneyEvent::Started { .. }) {
+            view.created_at = now;
+        }
+        view.updated_at = now;
+    }
+
+    // Unlike the in-memory view, the Postgres projection also merges secret
+    // attributes into shared_data.
+    if let JourneyEvent::AttributesSet {
+        secret_partitions, ..
+    } = &event.payload
+    {
+        for partition in secret_partitions {
+            assign_all(&mut view.shared_data, &partition.changes).unwrap();
+        }
+    }
+
+    apply_to_persons(view.id, &mut view.persons, &event.payload);
+}
+
+/// Project person events onto the view's person slots, kept ordered by
+/// `person_ref`.
+#[allow(deprecated)]
+fn apply_to_persons(journey_id: Uuid, persons: &mut Vec<PersonView>, event: &JourneyEvent) {
+    match event {
+        JourneyEvent::PersonCaptured {
+            person_ref,
+            subject_id,
+            name,
+            email,
+            phone,
+        } => {
+            // Identity fields are overwritten; details and forgotten are kept.
+            if let Some(person) = persons.iter_mut().find(|p| &p.person_ref == person_ref) {
+                person.subject_id = *subject_id;
+                person.name = Some(name.clone());
+                person.email = Some(email.clone());
+                person.phone.clone_from(phone);
+            } else {
+                persons.push(PersonView {
+                    journey_id,
+                    person_ref: person_ref.clone(),
+                    subject_id: *subject_id,
+                    name: Some(name.clone()),
+                    email: Some(email.clone()),
+                    phone: phone.clone(),
+                    details: Value::Object(serde_json::Map::new()),
+                    forgotten: false,
+                });
+                persons.sort_by(|a, b| a.person_ref.cmp(&b.person_ref));
+            }
+        }
+
+        JourneyEvent::PersonDetailsUpdated {
+            person_ref, data, ..
+        } => {
+            if let Some(person) = persons.iter_mut().find(|p| &p.person_ref == person_ref) {
+                shallow_merge(&mut person.details, data);
+            }
+        }
+
+        JourneyEvent::SubjectForgotten { subject_id } => {
+            for person in persons.iter_mut().filter(|p| &p.subject_id == subject_id) {
+                person.name = None;
+                person.email = None;
+                person.phone = None;
+                person.details = Value::Object(serde_json::Map::new());
+                person.forgotten = true;
+            }
+        }
+
+        JourneyEvent::AttributesSet {
+            secret_partitions, ..
+        } => {
+            // Mirror secret changes into details using the path below
+            // /persons/<ref>.
+            for partition in secret_partitions {
+                let Some(person) = persons
+                    .iter_mut()
+                    .find(|p| p.person_ref == partition.person_ref)
+                else {
+                    continue;
+                };
+                let prefix =
+                    jsonptr::PointerBuf::parse(format!("/persons/{}", partition.person_ref))
+                        .unwrap();
+                let mut update = Value::Object(serde_json::Map::new());
+                let suffixed = partition
+                    .changes
+                    .iter()
+                    .filter_map(|(path, value)| path.strip_prefix(&prefix).map(|p| (p, value)));
+                assign_all(&mut update, suffixed).unwrap();
+                shallow_merge(&mut person.details, &update);
+            }
+        }
+
+        _ => {}
+    }
+}
+
+/// Postgres `jsonb || jsonb` for objects: top-level keys of `patch` replace
+/// those of `target`.
+fn shallow_merge(target: &mut Value, patch: &Value) {
+    match (target, patch) {
+        (Value::Object(target), Value::Object(patch)) => {
+            target.extend(patch.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+        (target, patch) => *target = patch.clone(),
+    }
+}
+
+/// Postgres `jsonb @> jsonb`: whether `fragment` is structurally contained
+/// in `value`.
+///
+/// Objects contain every member of the fragment object, arrays contain a
+/// match for every element of the fragment array in any order, and scalars
+/// must be equal.
+#[must_use]
+pub fn json_contains(value: &Value, fragment: &Value) -> bool {
+    match (value, fragment) {
+        (Value::Object(value), Value::Object(fragment)) => fragment
+            .iter()
+            .all(|(key, f)| value.get(key).is_some_and(|v| json_contains(v, f))),
+        (Value::Array(value), Value::Array(fragment)) => fragment
+            .iter()
+            .all(|f| value.iter().any(|v| json_contains(v, f))),
+        (value, fragment) => value == fragment,
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+#[allow(deprecated)]
+mod tests {
+    use chrono::DateTime;
+    use serde_json::json;
+
+    use super::*;
+    use crate::queries::JourneyState;
+
+    fn envelope(
+        journey_id: Uuid,
+        sequence: usize,
+        payload: JourneyEvent,
+    ) -> EventEnvelope<Journey> {
+        EventEnvelope {
+            aggregate_id: journey_id.to_string(),
+            sequence,
+            payload,
+            metadata: HashMap::default(),
+        }
+    }
+
+    fn started(journey_id: Uuid) -> EventEnvelope<Journey> {
+        envelope(
+            journey_id,
+            1,
+            JourneyEvent::Started {
+                id: journey_id,
+                journey_type: None,
+            },
+        )
+    }
+
+    fn capture(
+        journey_id: Uuid,
+        sequence: usize,
+        person_ref: &str,
+        subject_id: Uuid,
+        email: &str,
+    ) -> EventEnvelope<Journey> {
+        envelope(
+            journey_id,
+            sequence,
+            JourneyEvent::PersonCaptured {
+                person_ref: person_ref.to_string(),
+                subject_id,
+                name: "Alice Smith".to_string(),
+                email: email.to_string(),
+                phone: None,
+            },
+        )
+    }
+
+    // ── Journey lifecycle ────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn journey_started_event() {
+        let repo = InMemoryJourneyViewRepository::new();
+        let journey_id = Uuid::new_v4();
+
+        repo.dispatch(&journey_id.to_string(), &[started(journey_id)])
+            .await;
+
+        let view = repo.load(&journey_id).await.unwrap().unwrap();
+        assert_eq!(view.id, journey_id);
+        assert_eq!(view.state, JourneyState::InProgress);
+        assert_eq!(view.shared_data, json!({}));
+        assert!(view.current_step.is_none());
+        assert!(view.created_at.is_some());
+        assert!(repo.load(&Uuid::new_v4()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn replayed_and_out_of_order_events_are_skipped() {
+        let repo = InMemoryJourneyViewRepository::new();
+        let journey_id = Uuid::new_v4();
+        let id = journey_id.to_string();
+        let capture = |sequence, origin| {
+            envelope(
+                journey_id,
+                sequence,
+                JourneyEvent::Modified {
+                    step: "search".to_string(),
+                    data: json!({ "origin": origin }),
+                },
+            )
+        };
+
+        repo.dispatch(
+            &id,
+            &[started(journey_id), capture(2, "LHR"), capture(3, "LGW")],
+        )
+        .await;
+        // At-least-once delivery: the same batch again.
+        repo.dispatch(
+            &id,
+            &[started(journey_id), capture(2, "LHR"), capture(3, "LGW")],
+        )
+        .await;
+        // A stale event, arriving after the one that superseded it.
+        repo.dispatch(&id, &[capture(2, "STN")]).await;
+
+        let view = repo.load(&journey_id).await.unwrap().unwrap();
+        assert_eq!(view.shared_data, json!({ "origin": "LGW" }));
+        assert_eq!(view.version, 3);
+
+        // Later events still apply, including in a batch that repeats earlier ones.
+        repo.dispatch(&id, &[capture(3, "LGW"), capture(4, "LTN")])
+            .await;
+
+        let view = repo.load(&journey_id).await.unwrap().unwrap();
+        assert_eq!(view.shared_data, json!({ "origin": "LTN" }));
+        assert_eq!(view.version, 4);
+    }
+
+    #[tokio::test]
+    async fn journey_full_lifecycle() {
+        let repo = InMemoryJourneyViewRepository::new();
+        let journey_id = Uuid::new_v4();
+
+        repo.dispatch(
+            &journey_id.to_string(),
+            &[
+                started(journey_id),
+                envelope(
+                    journey_id,
+                    2,
+                    JourneyEvent::Modified {
+                        step: "search".to_string(),
+                        data: json!({"origin": "LHR", "destination": "JFK"}),
+                    },
+                ),
+                envelope(
+                    journey_id,
+                    3,
+                    JourneyEvent::WorkflowEvaluated {
+                        suggested_actions: vec!["passenger_details".to_string()],
+                        phase: None,
+                        ranked_actions: vec![],
+                        completion_blocked_by: vec![],
+                    },
+                ),
+                envelope(
+                    journey_id,
+                    4,
+                    JourneyEvent::StepProgressed {
+                        from_step: None,
+                        to_step: "passenger_details".to_string(),
+                    },
+                ),
+                envelope(journey_id, 5, JourneyEvent::Completed),
+            ],
+        )
+        .await;
+
+        let view = repo.load(&journey_id).await.unwrap().unwrap();
+        assert_eq!(view.state, JourneyState::Complete);
+        assert_eq!(view.shared_data["origin"], json!("LHR"));
+        assert_eq!(view.shared_data["destination"], json!("JFK"));
+        assert_eq!(view.current_step, Some("passenger_details".to_string()));
+        assert!(view.latest_workflow_decision.is_some());
+    }
+
+    #[tokio::test]
+    async fn pause_and_resume_persist_state_and_timestamps() {
+        let repo = InMemoryJourneyViewRepository::new();
+        let journey_id = Uuid::new_v4();
+        let paused_at: DateTime<Utc> = "2025-08-15T10:00:00Z".parse().unwrap();
+        let resumed_at: DateTime<Utc> = "2025-08-15T10:01:30Z".parse().unwrap();
+
+        repo.dispatch(
+            &journey_id.to_string(),
+            &[
+                started(journey_id),
+                envelope(journey_id, 2, JourneyEvent::Paused { at: paused_at }),
+            ],
+        )
+        .await;
+
+        let view = repo.load(&journey_id).await.unwrap().unwrap();
+        assert_eq!(view.state, JourneyState::Paused);
+        assert_eq!(view.paused_at, Some(paused_at));
+        assert_eq!(view.resumed_at, None);
+
+        repo.dispatch(
+            &journey_id.to_string(),
+            &[envelope(
+                journey_id,
+                3,
+                JourneyEvent::Resumed { at: resumed_at },
+            )],
+        )
+        .await;
+
+        let view = repo.load(&journey_id).await.unwrap().unwrap();
+        assert_eq!(view.state, JourneyState::InProgress);
+        assert_eq!(view.paused_at, None);
+        assert_eq!(view.resumed_at, Some(resumed_at));
+        assert_eq!(view.paused_duration_ms, 90_000);
+    }
+
+    #[tokio::test]
+    async fn dispatch_rolls_back_whole_batch_on_failure() {
+        let repo = InMemoryJourneyViewRepository::new();
+        let journey_id = Uuid::new_v4();
+
+        let err = repo
+            .apply_events(
+                journey_id,
+                &[envelope(journey_id, 2, JourneyEvent::Completed)],
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ViewRepositoryError::NotFound(id) if id == journey_id));
+        assert!(repo.load(&journey_id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn load_all_returns_most_recently_created_first() {
+        let repo = InMemoryJourneyViewRepository::new();
+        let older = Uuid::new_v4();
+        let newer = Uuid::new_v4();
+        for (journey_id, time) in [
+            (older, "2025-08-15T10:00:00Z"),
+            (newer, "2025-08-16T10:00:00Z"),
+        ] {
+            let mut event = started(journey_id);
+            event.metadata.insert("time".to_string(), time.to_string());
+            repo.dispatch(&journey_id.to_string(), &[event]).await;
+        }
+
+        let views = repo.load_all().await.unwrap();
+        assert_eq!(
+            views.iter().map(|v| v.id).collect::<Vec<_>>(),
+            vec![newer, older]
+        );
+    }
+
+    // ── Persons ──────────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn person_captured_updates_identity_fields() {
+        let repo = InMemoryJourneyViewRepository::new();
+        let journey_id = Uuid::new_v4();
+        let subject_id = Uuid::new_v4();
+
+        repo.dispatch(
+            &journey_id.to_string(),
+            &[
+                started(journey_id),
+                capture(
+                    journey_id,
+                    2,
+                    "lead_booker",
+                    subject_id,
+                    "alice@example.com",
+                ),
+                envelope(
+                    journey_id,
+                    3,
+                    JourneyEvent::PersonDetailsUpdated {
+                        person_ref: "lead_booker".to_string(),
+                        subject_id,
+                        data: json!({"passportNumber": "GB123"}),
+                    },
+                ),
+                capture(
+                    journey_id,
+                    4,
+                    "lead_booker",
+                    subject_id,
+                    "alice@new.example.com",
+                ),
+            ],
+        )
+        .await;
+
+        let view = repo.load(&journey_id).await.unwrap().unwrap();
+        assert_eq!(view.persons.len(), 1);
+        let person = &view.persons[0];
+        assert_eq!(person.journey_id, journey_id);
+        assert_eq!(person.email.as_deref(), Some("alice@new.example.com"));
+        assert_eq!(person.details, json!({"passportNumber": "GB123"}));
+    }
+
+    #[tokio::test]
+    async fn subject_forgotten_only_affects_target_person() {
+        let repo = InMemoryJourneyViewRepository::new();
+        let journey_id = Uuid::new_v4();
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+
+        repo.dispatch(
+            &journey_id.to_string(),
+            &[
+                started(journey_id),
+                capture(journey_id, 2, "lead_booker", alice, "alice@example.com"),
+                capture(journey_id, 3, "passenger_1", bob, "bob@example.com"),
+                envelope(
+                    journey_id,
+                    4,
+                    JourneyEvent::SubjectForgotten { subject_id: alice },
+                ),
+            ],
+        )
+        .await;
+
+        let view = repo.load(&journey_id).await.unwrap().unwrap();
+        let lead = &view.persons[0];
+        assert_eq!(lead.person_ref, "lead_booker");
+        assert!(lead.forgotten);
+        assert_eq!(lead.email, None);
+        let passenger = &view.persons[1];
+        assert!(!passenger.forgotten);
+        assert_eq!(passenger.email.as_deref(), Some("bob@example.com"));
+    }
+
+    #[tokio::test]
+    async fn find_by_email_ignores_case_and_forgotten_persons() {
+        let repo = InMemoryJourneyViewRepository::new();
+        let kept = Uuid::new_v4();
+        let forgotten = Uuid::new_v4();
+        let forgotten_subject = Uuid::new_v4();
+
+        repo.dispatch(
+            &kept.to_string(),
+            &[
+                started(kept),
+                capture(kept, 2, "lead_booker", Uuid::new_v4(), "Alice@Example.com"),
+            ],
+        )
+        .await;
+        repo.dispatch(
+            &forgotten.to_string(),
+            &[
+                started(forgotten),
+                capture(
+                    forgotten,
+                    2,
+                    "lead_booker",
+                    forgotten_subject,
+                    "alice@example.com",
+                ),
+                envelope(
+                    forgotten,
+                    3,
+                    JourneyEvent::SubjectForgotten {
+                        subject_id: forgotten_subject,
+                    },
+                ),
+            ],
+        )
+        .await;
+
+        let views = repo.find_by_email("ALICE@example.COM").await.unwrap();
+        assert_eq!(views.iter().map(|v| v.id).collect::<Vec<_>>(), vec![kept]);
+    }
+
+    #[tokio::test]
+    async fn journey_forgotten_clears_persons_but_view_still_loads() {
+        let repo = InMemoryJourneyViewRepository::new();
+        let journey_id = Uuid::new_v4();
+
+        repo.dispatch(
+            &journey_id.to_string(),
+            &[
+                started(journey_id),
+                capture(
+                    journey_id,
+                    2,
+                    "lead_booker",
+                    Uuid::new_v4(),
+                    "alice@example.com",
+                ),
+                envelope(journey_id, 3, JourneyEvent::Forgotten),
+            ],
+        )
+        .await;
+
+        let view = repo.load(&journey_id).await.unwrap().unwrap();
+        assert!(view.forgotten);
+        assert_eq!(
+            view.persons.len(),
+            1,
+            "person slot is kept for audit counts"
+        );
+        assert!(view.persons[0].forgotten);
+        assert!(view.persons[0].email.is_none());
+        assert!(
+            repo.find_by_email("alice@example.com")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    // ── Data queries ─────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn find_where_data_contains_matches_nested_fragment() {
+        let repo = InMemoryJourneyViewRepository::new();
+        let jfk = Uuid::new_v4();
+        let cdg = Uuid::new_v4();
+        for (journey_id, destination) in [(jfk, "JFK"), (cdg, "CDG")] {
+            repo.dispatch(
+                &journey_id.to_string(),
+                &[
+                    started(journey_id),
+                    envelope(
+                        journey_id,
+                        2,
+                        JourneyEvent::Modified {
+                            step: "search".to_string(),
+                            data: json!({ "search": { "origin": "LHR", "destination": destination } }),
+                        },
+                    ),
+                ],
+            )
+            .await;
+        }
+
+        let views = repo
+            .find_where_data_contains(&json!({ "search": { "destination": "JFK" } }))
+            .await
+            .unwrap();
+
+        assert_eq!(views.iter().map(|v| v.id).collect::<Vec<_>>(), vec![jfk]);
+    }
+
+    #[test]
+    fn json_contains_follows_jsonb_containment() {
+        let value = json!({ "a": { "b": 1, "c": [1, 2, { "d": true }] }, "e": "x" });
+
+        assert!(json_contains(&value, &json!({})));
+        assert!(json_contains(&value, &json!({ "a": { "b": 1 } })));
+        assert!(json_contains(&value, &json!({ "a": { "c": [2, 1] } })));
+        assert!(json_contains(
+            &value,
+            &json!({ "a": { "c": [{ "d": true }] } })
+        ));
+        assert!(!json_contains(&value, &json!({ "a": { "b": 2 } })));
+        assert!(!json_contains(&value, &json!({ "a": { "c": [3] } })));
+        assert!(!json_contains(&value, &json!({ "f": null })));
+    }
+}
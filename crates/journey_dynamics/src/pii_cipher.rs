@@ -0,0 +1,265 @@
+//! Field-level encryption of person identities in the view store.
+//!
+//! With a [`PiiCipher`] configured,
+//! [`StructuredJourneyViewRepository`](crate::view_repository::StructuredJourneyViewRepository)
+//! writes the `name`, `email` and `phone` columns of `journey_person`
+//! encrypted and decrypts them on read. Encrypted email addresses cannot be
+//! compared in SQL, so each row also stores `email_index`: a keyed,
+//! deterministic hash of the lower-cased address that `find_by_email` looks
+//! up instead.
+//!
+//! Stored ciphertext carries [`CIPHERTEXT_PREFIX`]. Values without it were
+//! written before a cipher was configured and are read back unchanged, so
+//! encryption can be switched on for an existing view store; rebuild the
+//! views to encrypt the older rows.
+//!
+//! The AES-256-GCM implementation, [`AesGcmPiiCipher`], needs the
+//! `encryption` feature.
+
+use thiserror::Error;
+
+/// Marks a stored value as ciphertext produced by a [`PiiCipher`].
+pub const CIPHERTEXT_PREFIX: &str = "enc:v1:";
+
+/// Errors returned by [`PiiCipher`] operations.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PiiCipherError {
+    #[error("Invalid view encryption key: expected 32 bytes, got {0}")]
+    InvalidKeyLength(usize),
+    #[error("Encrypted value is malformed")]
+    Malformed,
+    #[error("Decryption failed: authentication tag mismatch or corrupt data")]
+    DecryptionFailed,
+}
+
+/// Encrypts person identity fields for storage in the view.
+pub trait PiiCipher: Send + Sync {
+    /// Encrypt `plaintext` into storable text. Encrypting the same value twice
+    /// gives different results.
+    fn encrypt(&self, plaintext: &str) -> String;
+
+    /// Decrypt text produced by [`PiiCipher::encrypt`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PiiCipherError`] if `ciphertext` is malformed or was not
+    /// encrypted under this cipher's key.
+    fn decrypt(&self, ciphertext: &str) -> Result<String, PiiCipherError>;
+
+    /// A keyed hash of `value`, equal for equal values, for lookups that
+    /// cannot compare ciphertext.
+    fn blind_index(&self, value: &str) -> Vec<u8>;
+}
+
+/// `value` encrypted by `cipher` and marked with [`CIPHERTEXT_PREFIX`].
+pub(crate) fn seal(cipher: &dyn PiiCipher, value: &str) -> String {
+    format!("{CIPHERTEXT_PREFIX}{}", cipher.encrypt(value))
+}
+
+/// The plaintext of a stored `value`: decrypted if it was sealed, unchanged if
+/// it was written before encryption was enabled.
+pub(crate) fn open(cipher: &dyn PiiCipher, value: String) -> Result<String, PiiCipherError> {
+    if let Some(ciphertext) = value.strip_prefix(CIPHERTEXT_PREFIX) {
+        return cipher.decrypt(ciphertext);
+    }
+    Ok(value)
+}
+
+/// The blind index `find_by_email` matches: email addresses compare
+/// case-insensitively.
+pub(crate) fn email_index(cipher: &dyn PiiCipher, email: &str) -> Vec<u8> {
+    cipher.blind_index(&email.to_lowercase())
+}
+
+#[cfg(feature = "encryption")]
+pub use aes::AesGcmPiiCipher;
+
+#[cfg(feature = "encryption")]
+mod aes {
+    use aes_gcm::{
+        Aes256Gcm, KeyInit, Nonce,
+        aead::{Aead, AeadCore, OsRng},
+    };
+    use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+    use hkdf::Hkdf;
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+    use zeroize::Zeroizing;
+
+    use super::{PiiCipher, PiiCipherError};
+
+    const NONCE_LEN: usize = 12;
+
+    /// [`PiiCipher`] using AES-256-GCM, with an HMAC-SHA256 blind index.
+    ///
+    /// The encryption and index keys are both derived from one 256-bit key
+    /// with HKDF-SHA256, so an index value reveals nothing about the
+    /// encryption key.
+    pub struct AesGcmPiiCipher {
+        cipher: Aes256Gcm,
+        index_key: Zeroizing<[u8; 32]>,
+    }
+
+    impl AesGcmPiiCipher {
+        /// Create a cipher from a 256-bit `key`.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`PiiCipherError::InvalidKeyLength`] unless `key` is
+        /// exactly 32 bytes.
+        pub fn new(key: &[u8]) -> Result<Self, PiiCipherError> {
+            if key.len() != 32 {
+                return Err(PiiCipherError::InvalidKeyLength(key.len()));
+            }
+            let hkdf = Hkdf::<Sha256>::new(None, key);
+            let encryption_key = derive(&hkdf, b"journey-view-pii/encryption")?;
+            let index_key = derive(&hkdf, b"journey-view-pii/blind-index")?;
+            Ok(Self {
+                cipher: Aes256Gcm::new(encryption_key.as_ref().into()),
+                index_key,
+            })
+        }
+    }
+
+    fn derive(hkdf: &Hkdf<Sha256>, info: &[u8]) -> Result<Zeroizing<[u8; 32]>, PiiCipherError> {
+        let mut key = Zeroizing::new([0u8; 32]);
+        hkdf.expand(info, key.as_mut())
+            .map_err(|_| PiiCipherError::InvalidKeyLength(key.len()))?;
+        Ok(key)
+    }
+
+    impl PiiCipher for AesGcmPiiCipher {
+        fn encrypt(&self, plaintext: &str) -> String {
+            let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+            let ciphertext = self
+                .cipher
+                .encrypt(&nonce, plaintext.as_bytes())
+                .expect("AES-GCM encryption of an in-memory buffer cannot fail");
+            let mut sealed = nonce.to_vec();
+            sealed.extend(ciphertext);
+            BASE64.encode(sealed)
+        }
+
+        fn decrypt(&self, ciphertext: &str) -> Result<String, PiiCipherError> {
+            let sealed = BASE64
+                .decode(ciphertext)
+                .map_err(|_| PiiCipherError::Malformed)?;
+            if sealed.len() < NONCE_LEN {
+                return Err(PiiCipherError::Malformed);
+            }
+            let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+            let plaintext = self
+                .cipher
+                .decrypt(Nonce::from_slice(nonce), ciphertext)
+                .map_err(|_| PiiCipherError::DecryptionFailed)?;
+            String::from_utf8(plaintext).map_err(|_| PiiCipherError::DecryptionFailed)
+        }
+
+        fn blind_index(&self, value: &str) -> Vec<u8> {
+            let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(self.index_key.as_ref())
+                .expect("HMAC accepts keys of any length");
+            mac.update(value.as_bytes());
+            mac.finalize().into_bytes().to_vec()
+        }
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reverses text; enough to tell sealed values from plaintext.
+    struct ReversingCipher;
+
+    impl PiiCipher for ReversingCipher {
+        fn encrypt(&self, plaintext: &str) -> String {
+            plaintext.chars().rev().collect()
+        }
+
+        fn decrypt(&self, ciphertext: &str) -> Result<String, PiiCipherError> {
+            Ok(ciphertext.chars().rev().collect())
+        }
+
+        fn blind_index(&self, value: &str) -> Vec<u8> {
+            value.as_bytes().to_vec()
+        }
+    }
+
+    #[test]
+    fn sealed_values_open_to_their_plaintext() {
+        let sealed = seal(&ReversingCipher, "Alice");
+
+        assert_eq!(sealed, "enc:v1:ecilA");
+        assert_eq!(open(&ReversingCipher, sealed).unwrap(), "Alice");
+    }
+
+    #[test]
+    fn values_written_before_encryption_open_unchanged() {
+        assert_eq!(
+            open(&ReversingCipher, "Alice".to_string()).unwrap(),
+            "Alice"
+        );
+    }
+
+    #[test]
+    fn email_index_ignores_case() {
+        assert_eq!(
+            email_index(&ReversingCipher, "Alice@Example.com"),
+            email_index(&ReversingCipher, "alice@example.com")
+        );
+    }
+
+    #[cfg(feature = "encryption")]
+    mod aes_gcm {
+        use super::super::*;
+
+        fn cipher(byte: u8) -> AesGcmPiiCipher {
+            AesGcmPiiCipher::new(&[byte; 32]).unwrap()
+        }
+
+        #[test]
+        fn round_trips_and_uses_fresh_nonces() {
+            let cipher = cipher(0x42);
+
+            let first = cipher.encrypt("alice@example.com");
+            let second = cipher.encrypt("alice@example.com");
+
+            assert_ne!(first, second);
+            assert_eq!(cipher.decrypt(&first).unwrap(), "alice@example.com");
+            assert_eq!(cipher.decrypt(&second).unwrap(), "alice@example.com");
+        }
+
+        #[test]
+        fn rejects_another_keys_ciphertext() {
+            let ciphertext = cipher(0x42).encrypt("Alice");
+
+            assert_eq!(
+                cipher(0x43).decrypt(&ciphertext),
+                Err(PiiCipherError::DecryptionFailed)
+            );
+            assert_eq!(
+                cipher(0x42).decrypt("not base64!"),
+                Err(PiiCipherError::Malformed)
+            );
+        }
+
+        #[test]
+        fn blind_index_is_deterministic_and_keyed() {
+            let index = cipher(0x42).blind_index("alice@example.com");
+
+            assert_eq!(index, cipher(0x42).blind_index("alice@example.com"));
+            assert_ne!(index, cipher(0x42).blind_index("bob@example.com"));
+            assert_ne!(index, cipher(0x43).blind_index("alice@example.com"));
+        }
+
+        #[test]
+        fn rejects_short_keys() {
+            assert_eq!(
+                AesGcmPiiCipher::new(&[0; 16]).err(),
+                Some(PiiCipherError::InvalidKeyLength(16))
+            );
+        }
+    }
+}
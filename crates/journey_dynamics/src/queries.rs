@@ -1,11 +1,24 @@
 #![allow(deprecated)]
-use cqrs_es::{EventEnvelope, View, persist::GenericQuery};
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Utc};
+use cqrs_es::{DomainEvent, EventEnvelope, View, persist::GenericQuery};
 use postgres_es::PostgresViewRepository;
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 use uuid::Uuid;
 
-use crate::domain::{assign_all, events::JourneyEvent, journey::Journey};
+use crate::{
+    domain::{
+        assign_all,
+        commands::{JourneySeed, SeedPerson},
+        events::{EventTime, JourneyEvent},
+        get_dotted,
+        journey::Journey,
+        step_patch,
+    },
+    services::decision_engine::RankedAction,
+};
 
 /// Person data for a single slot within a journey.
 /// One row per `(journey_id, person_ref)` in the `journey_person` table.
@@ -32,6 +45,17 @@ pub struct PersonView {
     pub forgotten: bool,
 }
 
+/// A person together with the status of their journey, from
+/// [`StructuredJourneyViewRepository::persons_with_status`](crate::view_repository::StructuredJourneyViewRepository::persons_with_status).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonWithStatus {
+    pub person: PersonView,
+    pub state: JourneyState,
+    pub current_step: Option<String>,
+    /// When the journey was completed; `None` unless it is complete.
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
 // Our Journey query using PostgresViewRepository which will serialize and persist
 // our view after it is updated. It provides a `load` method to deserialize the view on request.
 pub type JourneyQuery =
@@ -45,13 +69,22 @@ pub struct JourneyView {
     /// Unique identifier for the journey
     pub id: Uuid,
 
-    /// Current state of the journey (`InProgress` or `Complete`)
+    /// The type the journey was started with (e.g. `"flight-booking"`), if any.
+    #[serde(default)]
+    pub journey_type: Option<String>,
+
+    /// Current state of the journey (`InProgress`, `Paused` or `Complete`)
     pub state: JourneyState,
 
     /// Shared, non-PII data accumulated during the journey.
     /// Never encrypted. Fully intact after any shredding operation.
     pub shared_data: Value,
 
+    /// Uncommitted data saved by `SaveDraft`, as an object keyed by step.
+    /// A step's draft is discarded once the step is captured.
+    #[serde(default = "empty_object")]
+    pub draft_data: Value,
+
     /// The current step in the journey workflow.
     ///
     /// Deprecated: read `WorkflowEvaluated.phase` from `shared_data` instead.
@@ -69,17 +102,118 @@ pub struct JourneyView {
     /// Populated by `StructuredJourneyViewRepository::load`; empty in the in-memory view.
     #[serde(default)]
     pub persons: Vec<PersonView>,
+
+    /// When the current pause began; `None` unless `state` is `Paused`.
+    #[serde(default)]
+    pub paused_at: Option<DateTime<Utc>>,
+
+    /// When the journey was last resumed.
+    #[serde(default)]
+    pub resumed_at: Option<DateTime<Utc>>,
+
+    /// Total time spent in completed pauses, in milliseconds.
+    #[serde(default)]
+    pub paused_duration_ms: i64,
+
+    /// `true` once a journey-level `Forgotten` event has redacted every
+    /// person and the `persons` subtree of `shared_data`.
+    #[serde(default)]
+    pub forgotten: bool,
+
+    /// Event time of the journey's `Started` event.
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+
+    /// Event time of the latest event applied to the journey.
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+
+    /// Ops metadata set by `Tag`, by key. Kept apart from `shared_data`.
+    #[serde(default)]
+    pub tags: BTreeMap<String, String>,
+
+    /// Sequence number of the latest event applied to the view; `0` before
+    /// any.
+    #[serde(default)]
+    pub version: i64,
 }
 
 impl Default for JourneyView {
     fn default() -> Self {
         Self {
             id: Uuid::default(),
+            journey_type: None,
             state: JourneyState::default(),
             shared_data: json!({}),
+            draft_data: json!({}),
             current_step: None,
             latest_workflow_decision: None,
             persons: Vec::new(),
+            paused_at: None,
+            resumed_at: None,
+            paused_duration_ms: 0,
+            forgotten: false,
+            created_at: None,
+            updated_at: None,
+            tags: BTreeMap::new(),
+            version: 0,
+        }
+    }
+}
+
+/// `draft_data` of views stored before drafts existed.
+fn empty_object() -> Value {
+    json!({})
+}
+
+impl JourneyView {
+    /// Look up a dot-notation path such as `search.origin` or
+    /// `passengerDetails[0].firstName` in [`Self::shared_data`].
+    ///
+    /// See [`get_dotted`] for the path syntax.
+    #[must_use]
+    pub fn get_field(&self, path: &str) -> Option<&Value> {
+        get_dotted(&self.shared_data, path)
+    }
+
+    /// Whether [`Self::get_field`] finds a value at `path`. A stored `null`
+    /// counts as present.
+    #[must_use]
+    pub fn has_field(&self, path: &str) -> bool {
+        self.get_field(path).is_some()
+    }
+}
+
+/// What a journey started from this one copies: its type, its data and its
+/// people that have not been forgotten.
+///
+/// The `person` subtree that
+/// [`with_embedded_persons`](crate::view_repository::StructuredJourneyViewRepository::with_embedded_persons)
+/// copies identities into is left behind: the seed's data is recorded in a
+/// plaintext `Modified`, which shredding cannot reach.
+impl From<JourneyView> for JourneySeed {
+    fn from(mut view: JourneyView) -> Self {
+        if let Some(shared_data) = view.shared_data.as_object_mut() {
+            shared_data.remove("person");
+        }
+        let persons = view
+            .persons
+            .into_iter()
+            .filter(|person| !person.forgotten)
+            .filter_map(|person| {
+                Some(SeedPerson {
+                    person_ref: person.person_ref,
+                    subject_id: person.subject_id,
+                    name: person.name?,
+                    email: person.email?,
+                    phone: person.phone,
+                })
+            })
+            .collect();
+        Self {
+            journey_type: view.journey_type,
+            shared_data: view.shared_data,
+            persons,
         }
     }
 }
@@ -89,9 +223,45 @@ impl Default for JourneyView {
 pub enum JourneyState {
     #[default]
     InProgress,
+    Paused,
     Complete,
 }
 
+impl std::str::FromStr for JourneyState {
+    type Err = String;
+
+    /// Parse the `snake_case` spelling used in query strings, e.g.
+    /// `in_progress`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "in_progress" => Ok(Self::InProgress),
+            "paused" => Ok(Self::Paused),
+            "complete" => Ok(Self::Complete),
+            other => Err(format!(
+                "unknown journey state '{other}'; expected in_progress, paused or complete"
+            )),
+        }
+    }
+}
+
+/// Narrows [`StructuredJourneyViewRepository::find_page`](crate::view_repository::StructuredJourneyViewRepository::find_page).
+/// A `None` field matches every journey.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JourneyFilter {
+    pub state: Option<JourneyState>,
+    pub current_step: Option<String>,
+}
+
+/// One page of journeys, as returned by `GET /journeys`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JourneyPage {
+    pub items: Vec<JourneyView>,
+    /// Journeys matching the filter across every page.
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
 /// The workflow decision state in the view
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct WorkflowDecisionView {
@@ -100,24 +270,152 @@ pub struct WorkflowDecisionView {
     /// `None` until the `WorkflowEvaluated` event carries `phase` (step B1).
     #[serde(default)]
     pub phase: Option<String>,
+    /// Suggested actions with the model's weights, highest first, so a UI can
+    /// highlight the primary path. Empty when the model does not rank.
+    #[serde(default)]
+    pub ranked_actions: Vec<RankedAction>,
+}
+
+/// Where to pick a journey back up, from
+/// [`StructuredJourneyViewRepository::resume_state`](crate::view_repository::StructuredJourneyViewRepository::resume_state).
+///
+/// Much cheaper to load than the full [`JourneyView`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResumeState {
+    pub current_step: Option<String>,
+    /// The action to offer first: the highest-ranked action, or the first
+    /// suggested one when the model does not rank. `None` until evaluated.
+    pub primary_next_step: Option<String>,
+    /// Empty until the journey has been evaluated.
+    pub suggested_actions: Vec<String>,
+    pub is_complete: bool,
+}
+
+/// One `StepProgressed` transition recorded in `journey_step_history`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct StepTransition {
+    /// The step left; `None` for the first step entered.
+    pub from_step: Option<String>,
+    pub to_step: String,
+    /// Sequence number of the `StepProgressed` event.
+    pub sequence: i64,
+    pub entered_at: DateTime<Utc>,
+}
+
+/// One visit to a step, from
+/// [`StructuredJourneyViewRepository::dwell_times`](crate::view_repository::StructuredJourneyViewRepository::dwell_times).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct StepDwell {
+    pub step: String,
+    pub entered_at: DateTime<Utc>,
+    /// Milliseconds until the next step or completion; `None` while the
+    /// journey is still on this step.
+    pub duration_ms: Option<i64>,
+}
+
+/// One step of a completion funnel, from
+/// [`StructuredJourneyViewRepository::funnel`](crate::view_repository::StructuredJourneyViewRepository::funnel).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct FunnelStep {
+    pub step: String,
+    /// Journeys that entered this step at least once.
+    pub reached: i64,
+    /// Of those, the journeys that are now complete.
+    pub completed: i64,
+}
+
+/// Version of the [`JourneyExport`] document format. Bump it whenever the
+/// shape of the document changes, so older exports can be migrated.
+pub const JOURNEY_EXPORT_FORMAT_VERSION: u32 = 1;
+
+/// A whole journey as one portable document, as returned by
+/// `GET /journeys/{id}/export`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JourneyExport {
+    /// [`JOURNEY_EXPORT_FORMAT_VERSION`] at the time of the export.
+    pub format_version: u32,
+    /// State, shared data, persons and latest decision.
+    pub journey: JourneyView,
+    pub step_history: Vec<StepTransition>,
+    /// Every workflow decision made for the journey, oldest first.
+    pub decisions: Vec<DecisionRecord>,
+}
+
+/// One workflow decision in a [`JourneyExport`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionRecord {
+    pub suggested_actions: Vec<String>,
+    pub phase: Option<String>,
+    pub ranked_actions: Vec<RankedAction>,
+    pub decided_at: DateTime<Utc>,
+}
+
+/// A single committed event as returned by `GET /journeys/{id}/events`.
+///
+/// Decouples the HTTP representation from `cqrs_es::EventEnvelope`, whose
+/// shape is owned by the framework. PII fields in `payload` are decrypted on
+/// load; fields belonging to a forgotten subject come back redacted.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JourneyEventView {
+    pub sequence: usize,
+    pub event_type: String,
+    pub event_version: String,
+    pub payload: JourneyEvent,
+    pub metadata: HashMap<String, String>,
+}
+
+impl From<EventEnvelope<Journey>> for JourneyEventView {
+    fn from(envelope: EventEnvelope<Journey>) -> Self {
+        Self {
+            sequence: envelope.sequence,
+            event_type: envelope.payload.event_type(),
+            event_version: envelope.payload.event_version(),
+            payload: envelope.payload,
+            metadata: envelope.metadata,
+        }
+    }
 }
 
 // This updates the view with events as they are committed.
 // The logic should be minimal here - the events should carry all necessary information.
 impl View<Journey> for JourneyView {
+    #[allow(clippy::cast_possible_wrap)]
     fn update(&mut self, event: &EventEnvelope<Journey>) {
+        self.version = event.sequence as i64;
+
+        // Take times from the event, not the clock, so a replay reproduces them.
+        let occurred_at = event.occurred_at();
+        if occurred_at.is_some() {
+            self.updated_at = occurred_at;
+        }
+
         match &event.payload {
-            JourneyEvent::Started { id } => {
+            JourneyEvent::Started { id, journey_type } => {
+                self.created_at = occurred_at;
                 self.id = *id;
+                self.journey_type.clone_from(journey_type);
                 self.state = JourneyState::InProgress;
                 self.shared_data = json!({});
+                self.draft_data = json!({});
                 self.current_step = None;
                 self.latest_workflow_decision = None;
             }
 
-            JourneyEvent::Modified { step: _, data } => {
-                // Merge new data into shared data
-                json_patch::merge(&mut self.shared_data, data);
+            JourneyEvent::Modified { step, data } => {
+                // Merge new data into shared data; the step's draft is done with.
+                json_patch::merge(&mut self.shared_data, &step_patch(step, data));
+                if let Some(drafts) = self.draft_data.as_object_mut() {
+                    drafts.remove(step);
+                }
+            }
+
+            JourneyEvent::DraftSaved { step, data } => {
+                if let Some(drafts) = self.draft_data.as_object_mut() {
+                    json_patch::merge(
+                        drafts.entry(step.clone()).or_insert_with(|| json!({})),
+                        data,
+                    );
+                }
             }
 
             // Person events are projected to structured database tables by
@@ -126,15 +424,23 @@ impl View<Journey> for JourneyView {
             // The persons field on JourneyView is populated by load(), not from events.
             JourneyEvent::PersonCaptured { .. }
             | JourneyEvent::PersonDetailsUpdated { .. }
-            | JourneyEvent::SubjectForgotten { .. } => {}
+            | JourneyEvent::SubjectForgotten { .. }
+            // Skips are read from the event log; the view shows where the
+            // journey is, not how it got there.
+            | JourneyEvent::StepSkipped { .. }
+            // The latest decision already shows there is nothing left to do.
+            | JourneyEvent::WorkflowSatisfied => {}
 
             JourneyEvent::WorkflowEvaluated {
                 suggested_actions,
                 phase,
+                ranked_actions,
+                ..
             } => {
                 self.latest_workflow_decision = Some(WorkflowDecisionView {
                     suggested_actions: suggested_actions.clone(),
                     phase: phase.clone(),
+                    ranked_actions: ranked_actions.clone(),
                 });
             }
 
@@ -149,6 +455,46 @@ impl View<Journey> for JourneyView {
                 self.state = JourneyState::Complete;
             }
 
+            JourneyEvent::Reopened => {
+                self.state = JourneyState::InProgress;
+            }
+
+            JourneyEvent::Paused { at } => {
+                self.state = JourneyState::Paused;
+                self.paused_at = Some(*at);
+            }
+
+            JourneyEvent::Resumed { at } => {
+                self.state = JourneyState::InProgress;
+                if let Some(paused_at) = self.paused_at.take() {
+                    self.paused_duration_ms += (*at - paused_at).num_milliseconds().max(0);
+                }
+                self.resumed_at = Some(*at);
+            }
+
+            JourneyEvent::Forgotten => {
+                self.forgotten = true;
+                self.draft_data = json!({});
+                if let Some(shared_data) = self.shared_data.as_object_mut() {
+                    shared_data.remove("persons");
+                }
+                for person in &mut self.persons {
+                    person.name = None;
+                    person.email = None;
+                    person.phone = None;
+                    person.details = json!({});
+                    person.forgotten = true;
+                }
+            }
+
+            JourneyEvent::Corrected { path, value } => {
+                path.assign(&mut self.shared_data, value.clone()).unwrap();
+            }
+
+            JourneyEvent::Tagged { key, value } => {
+                self.tags.insert(key.clone(), value.clone());
+            }
+
             JourneyEvent::AttributesSet { plaintext, .. } => {
                 // Merge plaintext changes into shared_data.
                 // Secret partitions are projected to journey_person by
@@ -162,8 +508,6 @@ impl View<Journey> for JourneyView {
 #[cfg(test)]
 mod tests {
     #![allow(deprecated)]
-    use std::collections::HashMap;
-
     use super::*;
     use serde_json::json;
 
@@ -175,7 +519,10 @@ mod tests {
         let envelope = EventEnvelope {
             aggregate_id: id.to_string(),
             sequence: 1,
-            payload: JourneyEvent::Started { id },
+            payload: JourneyEvent::Started {
+                id,
+                journey_type: None,
+            },
             metadata: HashMap::default(),
         };
 
@@ -199,6 +546,7 @@ mod tests {
             current_step: None,
             latest_workflow_decision: None,
             persons: vec![],
+            ..JourneyView::default()
         };
 
         let envelope = EventEnvelope {
@@ -216,6 +564,104 @@ mod tests {
         assert_eq!(view.shared_data.get("user_name"), Some(&json!("John Doe")));
     }
 
+    #[test]
+    fn test_journey_view_keeps_drafts_apart_until_captured() {
+        let id = Uuid::new_v4();
+        let mut view = JourneyView {
+            id,
+            ..JourneyView::default()
+        };
+        let envelope = |sequence, payload| EventEnvelope {
+            aggregate_id: id.to_string(),
+            sequence,
+            payload,
+            metadata: HashMap::default(),
+        };
+
+        view.update(&envelope(
+            2,
+            JourneyEvent::DraftSaved {
+                step: "search".to_string(),
+                data: json!({ "search": { "origin": "LHR" } }),
+            },
+        ));
+
+        assert_eq!(view.shared_data, json!({}));
+        assert_eq!(
+            view.draft_data,
+            json!({ "search": { "search": { "origin": "LHR" } } })
+        );
+
+        view.update(&envelope(
+            3,
+            JourneyEvent::Modified {
+                step: "search".to_string(),
+                data: json!({ "search": { "origin": "LHR", "destination": "JFK" } }),
+            },
+        ));
+
+        assert_eq!(view.draft_data, json!({}));
+        assert_eq!(
+            view.shared_data,
+            json!({ "search": { "origin": "LHR", "destination": "JFK" } })
+        );
+    }
+
+    #[test]
+    fn test_journey_view_tagged_event() {
+        let id = Uuid::new_v4();
+        let mut view = JourneyView {
+            id,
+            ..JourneyView::default()
+        };
+        let tagged = |sequence, key: &str, value: &str| EventEnvelope {
+            aggregate_id: id.to_string(),
+            sequence,
+            payload: JourneyEvent::Tagged {
+                key: key.to_string(),
+                value: value.to_string(),
+            },
+            metadata: HashMap::default(),
+        };
+
+        view.update(&tagged(2, "channel", "web"));
+        view.update(&tagged(3, "channel", "mobile"));
+
+        assert_eq!(
+            view.tags,
+            BTreeMap::from([("channel".to_string(), "mobile".to_string())])
+        );
+        assert_eq!(view.shared_data, json!({}));
+    }
+
+    #[test]
+    fn test_journey_view_corrected_event() {
+        let id = Uuid::new_v4();
+        let mut view = JourneyView {
+            id,
+            state: JourneyState::InProgress,
+            shared_data: json!({"search": {"origin": "LHX", "destination": "JFK"}}),
+            ..JourneyView::default()
+        };
+
+        let envelope = EventEnvelope {
+            aggregate_id: id.to_string(),
+            sequence: 3,
+            payload: JourneyEvent::Corrected {
+                path: "/search/origin".parse().unwrap(),
+                value: json!("LHR"),
+            },
+            metadata: HashMap::default(),
+        };
+
+        view.update(&envelope);
+
+        assert_eq!(
+            view.shared_data,
+            json!({"search": {"origin": "LHR", "destination": "JFK"}})
+        );
+    }
+
     #[test]
     fn test_journey_view_person_captured_is_noop() {
         let id = Uuid::new_v4();
@@ -226,6 +672,7 @@ mod tests {
             current_step: None,
             latest_workflow_decision: None,
             persons: vec![],
+            ..JourneyView::default()
         };
         let before = view.shared_data.clone();
 
@@ -259,6 +706,7 @@ mod tests {
             current_step: None,
             latest_workflow_decision: None,
             persons: vec![],
+            ..JourneyView::default()
         };
         let before = view.shared_data.clone();
 
@@ -288,6 +736,7 @@ mod tests {
             current_step: None,
             latest_workflow_decision: None,
             persons: vec![],
+            ..JourneyView::default()
         };
 
         let envelope = EventEnvelope {
@@ -300,6 +749,8 @@ mod tests {
                     "back".to_string(),
                 ],
                 phase: None,
+                ranked_actions: vec![],
+                completion_blocked_by: vec![],
             },
             metadata: HashMap::default(),
         };
@@ -324,6 +775,7 @@ mod tests {
             current_step: Some("step1".to_string()),
             latest_workflow_decision: None,
             persons: vec![],
+            ..JourneyView::default()
         };
 
         let envelope = EventEnvelope {
@@ -351,6 +803,7 @@ mod tests {
             current_step: Some("final_step".to_string()),
             latest_workflow_decision: None,
             persons: vec![],
+            ..JourneyView::default()
         };
 
         let envelope = EventEnvelope {
@@ -365,6 +818,103 @@ mod tests {
         assert_eq!(view.state, JourneyState::Complete);
     }
 
+    #[test]
+    fn test_journey_view_reopened_event() {
+        let id = Uuid::new_v4();
+        let mut view = JourneyView {
+            id,
+            state: JourneyState::Complete,
+            shared_data: json!({ "name": "Joe" }),
+            current_step: Some("final_step".to_string()),
+            ..JourneyView::default()
+        };
+
+        view.update(&EventEnvelope {
+            aggregate_id: id.to_string(),
+            sequence: 6,
+            payload: JourneyEvent::Reopened,
+            metadata: HashMap::default(),
+        });
+
+        assert_eq!(view.state, JourneyState::InProgress);
+        assert_eq!(view.current_step.as_deref(), Some("final_step"));
+        assert_eq!(view.shared_data, json!({ "name": "Joe" }));
+    }
+
+    #[test]
+    fn test_journey_view_pause_and_resume_events() {
+        let id = Uuid::new_v4();
+        let mut view = JourneyView {
+            id,
+            ..JourneyView::default()
+        };
+        let paused_at: DateTime<Utc> = "2025-08-15T10:00:00Z".parse().unwrap();
+        let resumed_at: DateTime<Utc> = "2025-08-15T10:02:30Z".parse().unwrap();
+
+        view.update(&EventEnvelope {
+            aggregate_id: id.to_string(),
+            sequence: 2,
+            payload: JourneyEvent::Paused { at: paused_at },
+            metadata: HashMap::default(),
+        });
+        assert_eq!(view.state, JourneyState::Paused);
+        assert_eq!(view.paused_at, Some(paused_at));
+
+        view.update(&EventEnvelope {
+            aggregate_id: id.to_string(),
+            sequence: 3,
+            payload: JourneyEvent::Resumed { at: resumed_at },
+            metadata: HashMap::default(),
+        });
+        assert_eq!(view.state, JourneyState::InProgress);
+        assert_eq!(view.paused_at, None);
+        assert_eq!(view.resumed_at, Some(resumed_at));
+        assert_eq!(view.paused_duration_ms, 150_000);
+    }
+
+    #[test]
+    fn test_journey_view_tracks_event_times() {
+        let id = Uuid::new_v4();
+        let started = EventEnvelope {
+            aggregate_id: id.to_string(),
+            sequence: 1,
+            payload: JourneyEvent::Started {
+                id,
+                journey_type: None,
+            },
+            metadata: HashMap::from([("time".to_string(), "2025-08-15T10:00:00Z".to_string())]),
+        };
+        let completed = EventEnvelope {
+            aggregate_id: id.to_string(),
+            sequence: 2,
+            payload: JourneyEvent::Completed,
+            metadata: HashMap::from([("time".to_string(), "2025-08-15T10:30:00Z".to_string())]),
+        };
+        let untimed = EventEnvelope {
+            aggregate_id: id.to_string(),
+            sequence: 3,
+            payload: JourneyEvent::SubjectForgotten {
+                subject_id: Uuid::new_v4(),
+            },
+            metadata: HashMap::default(),
+        };
+
+        let mut view = JourneyView::default();
+        view.update(&started);
+        view.update(&completed);
+        view.update(&untimed);
+
+        assert_eq!(
+            view.created_at,
+            Some("2025-08-15T10:00:00Z".parse().unwrap())
+        );
+        // An event without a time leaves the last known time in place.
+        assert_eq!(
+            view.updated_at,
+            Some("2025-08-15T10:30:00Z".parse().unwrap())
+        );
+    }
+
     #[test]
     fn test_journey_view_subject_forgotten_is_noop() {
         let id = Uuid::new_v4();
@@ -375,6 +925,7 @@ mod tests {
             current_step: Some("confirmation".to_string()),
             latest_workflow_decision: None,
             persons: vec![],
+            ..JourneyView::default()
         };
         let before_data = view.shared_data.clone();
         let before_step = view.current_step.clone();
@@ -404,7 +955,10 @@ mod tests {
         view.update(&EventEnvelope {
             aggregate_id: id.to_string(),
             sequence: 1,
-            payload: JourneyEvent::Started { id },
+            payload: JourneyEvent::Started {
+                id,
+                journey_type: None,
+            },
             metadata: HashMap::default(),
         });
 
@@ -440,6 +994,8 @@ mod tests {
             payload: JourneyEvent::WorkflowEvaluated {
                 suggested_actions: vec!["confirmation".to_string(), "continue".to_string()],
                 phase: None,
+                ranked_actions: vec![],
+                completion_blocked_by: vec![],
             },
             metadata: HashMap::default(),
         });
@@ -470,4 +1026,54 @@ mod tests {
         assert_eq!(view.current_step, Some("confirmation".to_string()));
         assert!(view.latest_workflow_decision.is_some());
     }
+
+    #[test]
+    fn test_journey_event_view_from_envelope() {
+        let id = Uuid::new_v4();
+        let envelope = EventEnvelope::<Journey> {
+            aggregate_id: id.to_string(),
+            sequence: 4,
+            payload: JourneyEvent::WorkflowEvaluated {
+                suggested_actions: vec!["search".to_string()],
+                phase: Some("search".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec![],
+            },
+            metadata: HashMap::from([("time".to_string(), "2026-01-01".to_string())]),
+        };
+
+        let view = JourneyEventView::from(envelope);
+
+        assert_eq!(view.sequence, 4);
+        assert_eq!(view.event_type, "WorkflowEvaluated");
+        assert_eq!(view.event_version, "1.3");
+        assert_eq!(view.metadata.get("time"), Some(&"2026-01-01".to_string()));
+
+        let json = serde_json::to_value(&view).unwrap();
+        assert_eq!(
+            json["payload"]["WorkflowEvaluated"]["phase"],
+            json!("search")
+        );
+    }
+
+    #[test]
+    fn test_get_field_reads_nested_objects_and_array_indices() {
+        let view = JourneyView {
+            shared_data: json!({
+                "search": {"origin": "LHR"},
+                "passengerDetails": [{"firstName": "Alice"}, {"firstName": "Bob", "middleName": null}]
+            }),
+            ..JourneyView::default()
+        };
+
+        assert_eq!(view.get_field("search.origin"), Some(&json!("LHR")));
+        assert_eq!(
+            view.get_field("passengerDetails[1].firstName"),
+            Some(&json!("Bob"))
+        );
+        assert_eq!(view.get_field("passengerDetails[2].firstName"), None);
+        assert!(view.has_field("passengerDetails[0].firstName"));
+        assert!(view.has_field("passengerDetails[1].middleName"));
+        assert!(!view.has_field("search.destination"));
+    }
 }
@@ -0,0 +1,131 @@
+//! Rebuild the journey projection from the event store.
+//!
+//! The view tables are derived state. When they drift — after a projection bug
+//! fix, a schema change, or a manual edit — [`rebuild_views`] throws away each
+//! journey's rows and replays its full history through
+//! [`StructuredJourneyViewRepository`], exactly as live dispatch would have.
+//!
+//! Journeys are rebuilt one at a time, each in its own transaction, so a
+//! rebuild never holds more than one journey's events in memory and readers
+//! never see a half-built view. Running it again produces the same rows.
+
+use cqrs_es::{AggregateError, EventStore};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    domain::journey::{Journey, JourneyError},
+    view_repository::{StructuredJourneyViewRepository, ViewRepositoryError},
+};
+
+/// Number of journey IDs fetched per page when rebuilding every journey.
+pub const REBUILD_PAGE_SIZE: i64 = 100;
+
+/// What a rebuild touched.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RebuildStats {
+    /// Journeys whose views were rebuilt.
+    pub journeys: usize,
+    /// Events replayed across all of them.
+    pub events: usize,
+}
+
+#[derive(Debug, Error)]
+pub enum RebuildError {
+    #[error("failed to load events for journey {journey_id}: {source}")]
+    EventStore {
+        journey_id: String,
+        source: AggregateError<JourneyError>,
+    },
+    #[error("failed to rebuild the view for journey {journey_id}: {source}")]
+    Projection {
+        journey_id: String,
+        source: ViewRepositoryError,
+    },
+    #[error("failed to list journeys in the event store: {0}")]
+    ListJourneys(ViewRepositoryError),
+}
+
+/// Rebuild the views of `journey_ids`, or of every journey in the event store
+/// when `None`.
+///
+/// Journeys with no events are skipped rather than deleted. Aggregate IDs that
+/// are not UUIDs are logged and skipped.
+///
+/// # Errors
+///
+/// Stops at the first journey whose events cannot be loaded or whose view
+/// cannot be written. Journeys already rebuilt stay rebuilt, and the failing
+/// journey keeps its previous view.
+pub async fn rebuild_views<ES>(
+    event_store: &ES,
+    view_repo: &StructuredJourneyViewRepository,
+    journey_ids: Option<Vec<Uuid>>,
+) -> Result<RebuildStats, RebuildError>
+where
+    ES: EventStore<Journey>,
+{
+    let mut stats = RebuildStats::default();
+
+    if let Some(journey_ids) = journey_ids {
+        for journey_id in journey_ids {
+            rebuild_one(event_store, view_repo, &journey_id.to_string(), &mut stats).await?;
+        }
+        return Ok(stats);
+    }
+
+    let mut after: Option<String> = None;
+    loop {
+        let page = view_repo
+            .journey_aggregate_ids(after.as_deref(), REBUILD_PAGE_SIZE)
+            .await
+            .map_err(RebuildError::ListJourneys)?;
+
+        for aggregate_id in &page {
+            rebuild_one(event_store, view_repo, aggregate_id, &mut stats).await?;
+        }
+
+        match page.into_iter().last() {
+            Some(last) => after = Some(last),
+            None => return Ok(stats),
+        }
+    }
+}
+
+async fn rebuild_one<ES>(
+    event_store: &ES,
+    view_repo: &StructuredJourneyViewRepository,
+    aggregate_id: &str,
+    stats: &mut RebuildStats,
+) -> Result<(), RebuildError>
+where
+    ES: EventStore<Journey>,
+{
+    let Ok(journey_id) = Uuid::parse_str(aggregate_id) else {
+        eprintln!("Skipping journey with non-UUID aggregate id '{aggregate_id}'");
+        return Ok(());
+    };
+
+    let events = event_store
+        .load_events(aggregate_id)
+        .await
+        .map_err(|source| RebuildError::EventStore {
+            journey_id: aggregate_id.to_string(),
+            source,
+        })?;
+    if events.is_empty() {
+        return Ok(());
+    }
+
+    view_repo
+        .replace_journey(journey_id, &events)
+        .await
+        .map_err(|source| RebuildError::Projection {
+            journey_id: aggregate_id.to_string(),
+            source,
+        })?;
+
+    stats.journeys += 1;
+    stats.events += events.len();
+    Ok(())
+}
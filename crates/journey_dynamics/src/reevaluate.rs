@@ -0,0 +1,132 @@
+//! Refresh stored workflow decisions after the decision model changes.
+//!
+//! A journey's `latest_workflow_decision` is only recomputed when data is
+//! captured, so after a new model is loaded in-progress journeys keep
+//! suggesting what the old one did. [`reevaluate_all`] sends each of them a
+//! `Reevaluate` command, which runs the current model over the data already
+//! captured and records the fresh decision as a `WorkflowEvaluated` event.
+//!
+//! Unlike a preview, the decision is persisted. Journeys are re-evaluated one
+//! at a time, each in its own command, so a journey that fails does not stop
+//! the others and running it again only records another decision.
+
+use std::collections::HashMap;
+
+use cqrs_es::{AggregateError, CqrsFramework, EventStore};
+use serde::Serialize;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        commands::JourneyCommand,
+        events::{COMMAND_METADATA_KEY, EVENT_TIME_METADATA_KEY},
+        journey::{Journey, JourneyError},
+    },
+    route_handler::execute_with_retry,
+    view_repository::{StructuredJourneyViewRepository, ViewRepositoryError},
+};
+
+/// Number of journey IDs fetched per page when re-evaluating.
+pub const REEVALUATE_PAGE_SIZE: i64 = 100;
+
+/// Attempts made at a journey's `Reevaluate` command that keeps losing an
+/// optimistic-lock race to the journey's own traffic.
+const REEVALUATE_MAX_ATTEMPTS: usize = 3;
+
+/// What a re-evaluation touched.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ReevaluateStats {
+    /// Journeys that recorded a fresh decision.
+    pub journeys: usize,
+    /// Journeys that stopped being in progress, or were forgotten, before
+    /// their turn came.
+    pub skipped: usize,
+    /// Journeys whose re-evaluation failed, such as those the new model
+    /// cannot evaluate. They keep their previous decision.
+    pub failed: Vec<Uuid>,
+}
+
+#[derive(Debug, Error)]
+pub enum ReevaluateError {
+    #[error("failed to list journeys to re-evaluate: {0}")]
+    ListJourneys(ViewRepositoryError),
+}
+
+/// Re-evaluate one journey against the current decision model.
+///
+/// # Errors
+///
+/// Returns the error of the `Reevaluate` command, after retrying it when it
+/// loses an optimistic-lock race.
+pub async fn reevaluate<ES>(
+    cqrs: &CqrsFramework<Journey, ES>,
+    journey_id: Uuid,
+) -> Result<(), AggregateError<JourneyError>>
+where
+    ES: EventStore<Journey>,
+{
+    execute_with_retry(
+        cqrs,
+        &journey_id.to_string(),
+        JourneyCommand::Reevaluate,
+        HashMap::from([
+            (
+                EVENT_TIME_METADATA_KEY.to_string(),
+                chrono::Utc::now().to_rfc3339(),
+            ),
+            (
+                COMMAND_METADATA_KEY.to_string(),
+                JourneyCommand::Reevaluate.name().to_string(),
+            ),
+        ]),
+        REEVALUATE_MAX_ATTEMPTS,
+    )
+    .await
+}
+
+/// Re-evaluate every in-progress journey, or only those of `journey_type`
+/// when given.
+///
+/// Failures are logged and reported in [`ReevaluateStats::failed`] rather
+/// than stopping the run.
+///
+/// # Errors
+///
+/// Returns an error only if the journeys cannot be listed. Journeys already
+/// re-evaluated keep their fresh decision.
+pub async fn reevaluate_all<ES>(
+    cqrs: &CqrsFramework<Journey, ES>,
+    view_repo: &StructuredJourneyViewRepository,
+    journey_type: Option<&str>,
+) -> Result<ReevaluateStats, ReevaluateError>
+where
+    ES: EventStore<Journey>,
+{
+    let mut stats = ReevaluateStats::default();
+    let mut after: Option<Uuid> = None;
+    loop {
+        let page = view_repo
+            .in_progress_journey_ids(journey_type, after, REEVALUATE_PAGE_SIZE)
+            .await
+            .map_err(ReevaluateError::ListJourneys)?;
+
+        for &journey_id in &page {
+            match reevaluate(cqrs, journey_id).await {
+                Ok(()) => stats.journeys += 1,
+                Err(AggregateError::UserError(
+                    JourneyError::AlreadyCompleted | JourneyError::Paused | JourneyError::Forgotten,
+                )) => stats.skipped += 1,
+                Err(err) => {
+                    eprintln!("Error re-evaluating journey '{journey_id}': {err:#?}");
+                    stats.failed.push(journey_id);
+                }
+            }
+        }
+
+        match page.last() {
+            Some(&last) => after = Some(last),
+            None => return Ok(stats),
+        }
+    }
+}
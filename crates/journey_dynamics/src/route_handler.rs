@@ -1,24 +1,222 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use axum::{
     Json,
-    extract::{Path, State},
-    http::{HeaderMap, HeaderValue, StatusCode, header},
-    response::{IntoResponse, Response},
+    extract::{Path, Query, State},
+    http::{HeaderMap, HeaderValue, StatusCode, Uri, header},
+    response::{
+        IntoResponse, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
 };
-use serde::Deserialize;
+use chrono::Utc;
+use cqrs_es::{AggregateContext, AggregateError, CqrsFramework, EventEnvelope, EventStore};
+use futures_util::{Stream, StreamExt, stream};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use sqlx::{Pool, Postgres};
+use tokio::sync::broadcast::{self, error::RecvError};
 use uuid::Uuid;
 
 use crate::{
-    command_extractor::CommandExtractor, domain::commands::JourneyCommand, state::ApplicationState,
+    command_extractor::{CommandExtractor, command_metadata},
+    command_rejections::{CommandRejection, RejectionLog},
+    domain::{
+        commands::JourneyCommand,
+        events::{COMMAND_METADATA_KEY, EVENT_TIME_METADATA_KEY},
+        journey::{Journey, JourneyError, JourneyServices},
+    },
+    idempotency::{Claim, IdempotencyStore, RecordedResponse, fingerprint},
+    queries::{JourneyEventView, JourneyFilter, JourneyView},
+    reevaluate::{ReevaluateError, reevaluate, reevaluate_all},
+    services::decision_engine::{DecisionEngine, ExplainError},
+    state::ApplicationState,
+    view_repository::{JourneyViewStore, StructuredJourneyViewRepository, ViewRepositoryError},
 };
 
+/// Attempts `command_handler` makes at a command that keeps losing an
+/// optimistic-lock race before it reports the conflict.
+pub(crate) const COMMAND_MAX_ATTEMPTS: usize = 3;
+
+/// `Retry-After` value, in seconds, sent with a `409` for a lost race.
+const CONFLICT_RETRY_AFTER_SECS: &str = "1";
+
+/// Header a client sets so a retried command is not applied twice.
+const IDEMPOTENCY_KEY_HDR: &str = "Idempotency-Key";
+
+/// Header marking a response replayed for a repeated `Idempotency-Key`.
+const IDEMPOTENT_REPLAYED_HDR: &str = "Idempotent-Replayed";
+
+/// Page size `GET /journeys` and `GET /events` serve when the request names
+/// none.
+const DEFAULT_PAGE_LIMIT: i64 = 50;
+
+/// Largest page `GET /journeys` and `GET /events` serve.
+const MAX_PAGE_LIMIT: i64 = 500;
+
+/// Query string of `GET /journeys`, e.g.
+/// `?state=in_progress&step=payment&limit=50&offset=0`.
+#[derive(Debug, Default, Deserialize)]
+pub struct ListJourneysParams {
+    pub state: Option<String>,
+    pub step: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl ListJourneysParams {
+    /// Validate the parameters into a filter, a limit and an offset.
+    fn parse(self) -> Result<(JourneyFilter, i64, i64), String> {
+        let state = self.state.as_deref().map(str::parse).transpose()?;
+        let limit = self.limit.unwrap_or(DEFAULT_PAGE_LIMIT);
+        if !(1..=MAX_PAGE_LIMIT).contains(&limit) {
+            return Err(format!("limit must be between 1 and {MAX_PAGE_LIMIT}"));
+        }
+        let offset = self.offset.unwrap_or(0);
+        if offset < 0 {
+            return Err("offset must not be negative".to_string());
+        }
+        let filter = JourneyFilter {
+            state,
+            current_step: self.step,
+        };
+        Ok((filter, limit, offset))
+    }
+}
+
+/// Query string of `GET /events`, e.g. `?after=1200&limit=100`.
+#[derive(Debug, Default, Deserialize)]
+pub struct EventFeedParams {
+    pub after: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+impl EventFeedParams {
+    /// Validate the parameters into a cursor and a limit.
+    fn parse(self) -> Result<(i64, i64), String> {
+        let after = self.after.unwrap_or(0);
+        if after < 0 {
+            return Err("after must not be negative".to_string());
+        }
+        let limit = self.limit.unwrap_or(DEFAULT_PAGE_LIMIT);
+        if !(1..=MAX_PAGE_LIMIT).contains(&limit) {
+            return Err(format!("limit must be between 1 and {MAX_PAGE_LIMIT}"));
+        }
+        Ok((after, limit))
+    }
+}
+
+/// Query parameters of the endpoints that serve events and exports, e.g.
+/// `?pretty=true`.
+#[derive(Debug, Default, Deserialize)]
+pub struct FormatParams {
+    /// Indent the JSON for reading, with object keys sorted.
+    #[serde(default)]
+    pub pretty: bool,
+}
+
+/// How a JSON response body is laid out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JsonFormat {
+    /// On one line, for machines.
+    #[default]
+    Compact,
+    /// Indented, with object keys sorted so the same events always render
+    /// the same.
+    Pretty,
+}
+
+impl JsonFormat {
+    /// `Pretty` when asked for by `?pretty=true` or by an
+    /// `Accept: application/json; pretty=true` header, `Compact` otherwise.
+    fn negotiate(params: &FormatParams, headers: &HeaderMap) -> Self {
+        let accepts_pretty = headers
+            .get_all(header::ACCEPT)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .any(|media_type| {
+                let mut parts = media_type.split(';').map(str::trim);
+                parts
+                    .next()
+                    .is_some_and(|essence| essence.eq_ignore_ascii_case("application/json"))
+                    && parts.any(|param| param.eq_ignore_ascii_case("pretty=true"))
+            });
+        if params.pretty || accepts_pretty {
+            Self::Pretty
+        } else {
+            Self::Compact
+        }
+    }
+
+    /// `value` as a `200 OK` JSON response laid out in this format.
+    fn respond<T: Serialize>(self, value: &T) -> Response {
+        let body = match self {
+            Self::Compact => serde_json::to_string(value),
+            // Going through `Value` sorts object keys, including those of
+            // metadata maps, which have no order of their own.
+            Self::Pretty => {
+                serde_json::to_value(value).and_then(|value| serde_json::to_string_pretty(&value))
+            }
+        };
+        match body {
+            Ok(body) => (
+                StatusCode::OK,
+                [(header::CONTENT_TYPE, "application/json")],
+                body,
+            )
+                .into_response(),
+            Err(err) => {
+                eprintln!("Error: {err:#?}");
+                (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response()
+            }
+        }
+    }
+}
+
+/// `events` as a JSON array of [`JourneyEventView`]s laid out in `format`.
+fn render_events(events: Vec<EventEnvelope<Journey>>, format: JsonFormat) -> Response {
+    let events: Vec<JourneyEventView> = events.into_iter().map(JourneyEventView::from).collect();
+    format.respond(&events)
+}
+
 /// Request body for `DELETE /subjects/by-email`.
 #[derive(Debug, Deserialize)]
 pub struct EraseByEmailBody {
     pub email: String,
 }
 
+/// Request body for `POST /journeys/{journey_id}/preview`.
+#[derive(Debug, Deserialize)]
+pub struct PreviewCaptureBody {
+    pub step: String,
+    pub data: Value,
+}
+
+/// Query parameters for `POST /journeys/from/{source_id}`.
+#[derive(Debug, Default, Deserialize)]
+pub struct StartFromParams {
+    /// Copy the source's people and their attributes too.
+    #[serde(default)]
+    pub include_person: bool,
+}
+
+/// Query parameters for `POST /journeys/reevaluate`.
+#[derive(Debug, Deserialize)]
+pub struct ReevaluateParams {
+    pub journey_type: Option<String>,
+}
+
+/// Query parameters for `GET /journeys/{journey_id}/explain`.
+#[derive(Debug, Deserialize)]
+pub struct ExplainParams {
+    pub step: String,
+    /// Include what each node of the decision model did, when the engine
+    /// traces them.
+    #[serde(default)]
+    pub trace: bool,
+}
+
 // Handles GDPR right-to-erasure requests by crypto-shredding the subject's DEK,
 // which permanently renders all encrypted PII irrecoverable, then emits a
 // `SubjectForgotten` audit event on every affected journey.
@@ -55,7 +253,7 @@ pub async fn shred_subjects_by_email(
                 "Error looking up subjects for email {}: {err:#?}",
                 body.email
             );
-            return (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response();
+            return err.into_response();
         }
     };
 
@@ -123,14 +321,16 @@ async fn shred_one_subject(state: &ApplicationState, subject_id: Uuid) -> Result
         .await
         .map_err(|err| {
             eprintln!("Error fetching journeys for subject {subject_id}: {err:#?}");
-            (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response()
+            err.into_response()
         })?;
 
-    // Step 2 — atomically delete the DEK and the email → subject_id lookup entry.
+    // Step 2 — atomically delete the DEK, the email → subject_id lookup entry
+    // and the affected journeys' snapshots.
     //
-    // Both rows are PII: the DEK renders all ciphertext permanently unreadable;
-    // the subject_lookup row holds the plaintext email address. Committing them
-    // together ensures neither survives a mid-shred crash.
+    // All are PII: the DEK renders all ciphertext permanently unreadable; the
+    // subject_lookup row holds the plaintext email address; snapshots hold the
+    // decrypted journey state. Committing them together ensures none survives
+    // a mid-shred crash.
     let mut tx = state.pool.begin().await.map_err(|err| {
         eprintln!("Error starting shred transaction for subject {subject_id}: {err:#?}");
         (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response()
@@ -151,7 +351,16 @@ async fn shred_one_subject(state: &ApplicationState, subject_id: Uuid) -> Result
         .await
         .map_err(|err| {
             eprintln!("Error removing subject_lookup for {subject_id}: {err:#?}");
-            (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response()
+            err.into_response()
+        })?;
+
+    state
+        .journey_query
+        .delete_snapshots_in_tx(&mut tx, &journeys)
+        .await
+        .map_err(|err| {
+            eprintln!("Error removing snapshots for subject {subject_id}: {err:#?}");
+            err.into_response()
         })?;
 
     tx.commit().await.map_err(|err| {
@@ -161,9 +370,15 @@ async fn shred_one_subject(state: &ApplicationState, subject_id: Uuid) -> Result
 
     // Step 3 — emit SubjectForgotten audit events (best-effort).
     for aggregate_id in &journeys {
+        let metadata =
+            HashMap::from([(EVENT_TIME_METADATA_KEY.to_string(), Utc::now().to_rfc3339())]);
         if let Err(err) = state
             .cqrs
-            .execute(aggregate_id, JourneyCommand::ForgetSubject { subject_id })
+            .execute_with_metadata(
+                aggregate_id,
+                JourneyCommand::ForgetSubject { subject_id },
+                metadata,
+            )
             .await
         {
             // PII is already gone; log and continue so we still attempt all journeys.
@@ -174,6 +389,55 @@ async fn shred_one_subject(state: &ApplicationState, subject_id: Uuid) -> Result
     Ok(())
 }
 
+// Starts a new journey seeded with the data of journey `source_id`, e.g. to
+// rebook, and answers `201` with its location. Answers `404` for an unknown
+// source.
+pub async fn start_from_handler(
+    Path(source_id): Path<Uuid>,
+    Query(params): Query<StartFromParams>,
+    State(state): State<Arc<ApplicationState>>,
+    uri: Uri,
+    headers: HeaderMap,
+) -> Response {
+    start_from(
+        state.journey_query.as_ref(),
+        &state.cqrs,
+        source_id,
+        params.include_person,
+        command_metadata(&uri, &headers),
+    )
+    .await
+}
+
+async fn start_from<ES: EventStore<Journey>>(
+    views: &dyn JourneyViewStore,
+    cqrs: &CqrsFramework<Journey, ES>,
+    source_id: Uuid,
+    include_person: bool,
+    mut metadata: HashMap<String, String>,
+) -> Response {
+    let source = match views.load(&source_id).await {
+        Ok(Some(view)) => view,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(err) => return err.into_response(),
+    };
+    let journey_id = Uuid::new_v4();
+    let command = JourneyCommand::StartFrom {
+        id: journey_id,
+        source_id,
+        include_person,
+        source: source.into(),
+    };
+    metadata.insert(COMMAND_METADATA_KEY.to_string(), command.name().to_string());
+    match cqrs
+        .execute_with_metadata(&journey_id.to_string(), command, metadata)
+        .await
+    {
+        Ok(()) => command_success_response(journey_id, true),
+        Err(err) => command_error_response(err),
+    }
+}
+
 // Serves as our query endpoint to respond with the materialized `JourneyView`
 // for the requested journey.
 pub async fn query_handler(
@@ -183,6 +447,251 @@ pub async fn query_handler(
     match state.journey_query.load(&journey_id).await {
         Ok(Some(journey_view)) => (StatusCode::OK, Json(journey_view)).into_response(),
         Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+// Serves only the journey's latest workflow decision, for frontends polling
+// for what can be done next without loading the whole view.
+pub async fn next_actions_handler(
+    Path(journey_id): Path<Uuid>,
+    State(state): State<Arc<ApplicationState>>,
+) -> Response {
+    match state.journey_query.load_latest_decision(&journey_id).await {
+        Ok(Some(decision)) => (StatusCode::OK, Json(decision)).into_response(),
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+// Serves just enough to pick a journey back up: where it is, whether it is
+// complete and what to do next, without loading the whole view.
+pub async fn resume_handler(
+    Path(journey_id): Path<Uuid>,
+    State(state): State<Arc<ApplicationState>>,
+) -> Response {
+    match state.journey_query.resume_state(&journey_id).await {
+        Ok(Some(resume)) => (StatusCode::OK, Json(resume)).into_response(),
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+// Serves a page of journeys, newest first, optionally narrowed to a state
+// and/or current step.
+pub async fn list_journeys_handler(
+    Query(params): Query<ListJourneysParams>,
+    State(state): State<Arc<ApplicationState>>,
+) -> Response {
+    list_journeys(&state.journey_query, params).await
+}
+
+async fn list_journeys(
+    repo: &StructuredJourneyViewRepository,
+    params: ListJourneysParams,
+) -> Response {
+    let (filter, limit, offset) = match params.parse() {
+        Ok(parsed) => parsed,
+        Err(message) => return (StatusCode::BAD_REQUEST, message).into_response(),
+    };
+    match repo.find_page(&filter, limit, offset).await {
+        Ok(page) => (StatusCode::OK, Json(page)).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+// Serves a whole journey — view, persons, step history and decisions — as one
+// downloadable JSON document, compact or pretty.
+pub async fn export_handler(
+    Path(journey_id): Path<Uuid>,
+    Query(params): Query<FormatParams>,
+    headers: HeaderMap,
+    State(state): State<Arc<ApplicationState>>,
+) -> Response {
+    match state.journey_query.export(&journey_id).await {
+        Ok(export) => {
+            let mut response = JsonFormat::negotiate(&params, &headers).respond(&export);
+            if let Ok(disposition) = HeaderValue::from_str(&format!(
+                "attachment; filename=\"journey-{journey_id}.json\""
+            )) {
+                response
+                    .headers_mut()
+                    .insert(header::CONTENT_DISPOSITION, disposition);
+            }
+            response
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// Serves the ordered event history of a journey straight from the event store,
+// with PII decrypted (or redacted, for forgotten subjects) on the way out.
+pub async fn event_history_handler(
+    Path(journey_id): Path<Uuid>,
+    Query(params): Query<FormatParams>,
+    headers: HeaderMap,
+    State(state): State<Arc<ApplicationState>>,
+) -> Response {
+    event_history(
+        state.event_store.as_ref(),
+        journey_id,
+        JsonFormat::negotiate(&params, &headers),
+    )
+    .await
+}
+
+async fn event_history(
+    store: &impl EventStore<Journey>,
+    journey_id: Uuid,
+    format: JsonFormat,
+) -> Response {
+    match store.load_events(&journey_id.to_string()).await {
+        Ok(events) if events.is_empty() => StatusCode::NOT_FOUND.into_response(),
+        Ok(events) => render_events(events, format),
+        Err(err) => {
+            eprintln!("Error: {err:#?}");
+            (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response()
+        }
+    }
+}
+
+// Serves every journey's events after the `after` cursor, oldest first, with
+// the cursor to ask from next. A cursor at or past the tail gets no events and
+// itself back.
+pub async fn event_feed_handler(
+    Query(params): Query<EventFeedParams>,
+    Query(format): Query<FormatParams>,
+    headers: HeaderMap,
+    State(state): State<Arc<ApplicationState>>,
+) -> Response {
+    let (after, limit) = match params.parse() {
+        Ok(parsed) => parsed,
+        Err(message) => return (StatusCode::BAD_REQUEST, message).into_response(),
+    };
+    match state.event_feed.read_after(after, limit).await {
+        Ok(page) => JsonFormat::negotiate(&format, &headers).respond(&page),
+        Err(err) => {
+            eprintln!("Error: {err:#?}");
+            (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response()
+        }
+    }
+}
+
+// Previews what the workflow would suggest if `data` were captured at `step`,
+// without persisting anything: the journey is rehydrated from the event store
+// and evaluated as a throwaway copy.
+pub async fn preview_handler(
+    Path(journey_id): Path<Uuid>,
+    State(state): State<Arc<ApplicationState>>,
+    Json(body): Json<PreviewCaptureBody>,
+) -> Response {
+    preview_capture(
+        state.event_store.as_ref(),
+        &state.journey_services,
+        journey_id,
+        &body,
+    )
+    .await
+}
+
+async fn preview_capture(
+    store: &impl EventStore<Journey>,
+    services: &JourneyServices,
+    journey_id: Uuid,
+    body: &PreviewCaptureBody,
+) -> Response {
+    let mut context = match store.load_aggregate(&journey_id.to_string()).await {
+        Ok(context) => context,
+        Err(err) => {
+            eprintln!("Error: {err:#?}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response();
+        }
+    };
+    match services
+        .preview_capture(context.aggregate(), &body.step, &body.data)
+        .await
+    {
+        Ok(preview) => (StatusCode::OK, Json(preview)).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+// Re-runs the current decision model over the journey's captured data and
+// persists the fresh decision, for refreshing journeys after a model change.
+pub async fn reevaluate_handler(
+    Path(journey_id): Path<Uuid>,
+    State(state): State<Arc<ApplicationState>>,
+) -> Response {
+    match reevaluate(&state.cqrs, journey_id).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(err) => command_error_response(err),
+    }
+}
+
+// Re-evaluates every in-progress journey, optionally only those of one
+// journey type, and reports how many were refreshed, skipped and failed.
+pub async fn reevaluate_all_handler(
+    Query(params): Query<ReevaluateParams>,
+    State(state): State<Arc<ApplicationState>>,
+) -> Response {
+    match reevaluate_all(
+        &state.cqrs,
+        &state.journey_query,
+        params.journey_type.as_deref(),
+    )
+    .await
+    {
+        Ok(reevaluated) => (StatusCode::OK, Json(reevaluated)).into_response(),
+        Err(ReevaluateError::ListJourneys(err)) => err.into_response(),
+    }
+}
+
+// Explains why `step` is or isn't among the journey's suggested actions, for
+// support agents, with the model's node-by-node trace when `trace=true`.
+// Answers `501` when the decision engine cannot explain.
+pub async fn explain_handler(
+    Path(journey_id): Path<Uuid>,
+    Query(params): Query<ExplainParams>,
+    State(state): State<Arc<ApplicationState>>,
+) -> Response {
+    explain_step(
+        state.event_store.as_ref(),
+        state.journey_services.decision_engine().as_ref(),
+        journey_id,
+        &params.step,
+        params.trace,
+    )
+    .await
+}
+
+async fn explain_step(
+    store: &impl EventStore<Journey>,
+    engine: &dyn DecisionEngine,
+    journey_id: Uuid,
+    step: &str,
+    trace: bool,
+) -> Response {
+    let mut context = match store.load_aggregate(&journey_id.to_string()).await {
+        Ok(context) => context,
+        Err(err) => {
+            eprintln!("Error: {err:#?}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response();
+        }
+    };
+    let journey = context.aggregate();
+    if journey.id() == Uuid::default() {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    match engine.explain(journey, step).await {
+        Ok(mut explanation) => {
+            if !trace {
+                explanation.trace.clear();
+            }
+            (StatusCode::OK, Json(explanation)).into_response()
+        }
+        Err(err @ ExplainError::Unsupported) => {
+            (StatusCode::NOT_IMPLEMENTED, err.to_string()).into_response()
+        }
         Err(err) => {
             eprintln!("Error: {err:#?}");
             (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response()
@@ -190,25 +699,125 @@ pub async fn query_handler(
     }
 }
 
+// Liveness: the process is up and serving requests.
+#[allow(clippy::unused_async)] // axum handlers must be async
+pub async fn health_handler() -> StatusCode {
+    StatusCode::OK
+}
+
+// Readiness: Postgres answers a trivial query and the decision model evaluates
+// an empty journey. Responds `503` naming the failed checks otherwise.
+pub async fn ready_handler(State(state): State<Arc<ApplicationState>>) -> Response {
+    readiness(&state.pool, &state.journey_services).await
+}
+
+async fn readiness(pool: &Pool<Postgres>, services: &JourneyServices) -> Response {
+    let (database, decision_engine) = tokio::join!(
+        async {
+            sqlx::query("SELECT 1")
+                .execute(pool)
+                .await
+                .map(drop)
+                .map_err(|e| e.to_string())
+        },
+        async {
+            services
+                .decide(services.decision_engine().evaluate_next_steps(
+                    &Journey::default(),
+                    "",
+                    &json!({}),
+                ))
+                .await
+                .map(drop)
+                .map_err(|e| e.to_string())
+        },
+    );
+
+    let (status, label) = if database.is_ok() && decision_engine.is_ok() {
+        (StatusCode::OK, "ready")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "unavailable")
+    };
+    let outcome = |check: Result<(), String>| check.err().unwrap_or_else(|| "ok".to_string());
+    let body = json!({
+        "status": label,
+        "checks": {
+            "database": outcome(database),
+            "decision_engine": outcome(decision_engine),
+        },
+    });
+    (status, Json(body)).into_response()
+}
+
+// Lists the workflow's steps as declared by the loaded decision model, so a
+// frontend can render the whole journey rather than only the next actions.
+#[allow(clippy::unused_async)] // axum handlers must be async
+pub async fn workflow_steps_handler(State(state): State<Arc<ApplicationState>>) -> Response {
+    (StatusCode::OK, Json(state.workflow_steps.as_ref())).into_response()
+}
+
+// Streams the journey's view over Server-Sent Events: the current view first,
+// then the updated view after every committed change. Each message is a
+// `journey` event whose data is the `JourneyView` as JSON.
+pub async fn journey_stream_handler(
+    Path(journey_id): Path<Uuid>,
+    State(state): State<Arc<ApplicationState>>,
+) -> Response {
+    // Subscribe before loading the snapshot so no update committed in between is lost.
+    let receiver = state.journey_updates.subscribe(journey_id);
+    let snapshot = match state.journey_query.load(&journey_id).await {
+        Ok(Some(journey_view)) => journey_view,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(err) => return err.into_response(),
+    };
+
+    // When the client disconnects axum drops the stream, and with it the
+    // receiver; the channel is released once its last receiver is gone.
+    let events = stream::once(async { snapshot })
+        .chain(view_updates(receiver))
+        .map(|journey_view| Event::default().event("journey").json_data(journey_view));
+
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// Yields each view received on `receiver` until the channel closes.
+///
+/// A receiver that lagged behind skips the views it missed: every view is a
+/// full snapshot, so the next one supersedes them.
+fn view_updates(receiver: broadcast::Receiver<JourneyView>) -> impl Stream<Item = JourneyView> {
+    stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(journey_view) => return Some((journey_view, receiver)),
+                Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    })
+}
+
 // Serves as our command endpoint to make changes in a `Journey` aggregate.
 // Handles both journey creation (no journey_id in path) and modification (with journey_id).
 pub async fn command_handler(
     path: Option<Path<Uuid>>,
     State(state): State<Arc<ApplicationState>>,
+    headers: HeaderMap,
     CommandExtractor(metadata, command): CommandExtractor,
 ) -> Response {
     // Determine the journey_id and creation status based on path and command
     let (journey_id, is_creating) = match path {
         Some(Path(id)) => {
-            // Path parameter provided - check if it's a Start command
-            let is_creating = matches!(command, JourneyCommand::Start { .. });
+            // Path parameter provided - check if it's a start command
+            let is_creating = command.started_id().is_some();
             (id, is_creating)
         }
         None => {
             // No path parameter - this must be journey creation
-            match &command {
-                JourneyCommand::Start { id } => (*id, true),
-                _ => {
+            match command.started_id() {
+                Some(id) => (id, true),
+                None => {
                     // No path parameter and not a Start command - invalid
                     return (
                         StatusCode::BAD_REQUEST,
@@ -220,47 +829,339 @@ pub async fn command_handler(
         }
     };
 
-    match state
-        .cqrs
-        .execute_with_metadata(&journey_id.to_string(), command, metadata)
+    let key = match headers.get(IDEMPOTENCY_KEY_HDR).map(HeaderValue::to_str) {
+        None => None,
+        Some(Ok(key)) => Some(key),
+        Some(Err(_)) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                "Idempotency-Key must be visible ASCII",
+            )
+                .into_response();
+        }
+    };
+
+    let command_type = command.name();
+    let key = key.map(|key| (key, fingerprint(&command)));
+    let key = key.as_ref().map(|(key, print)| (*key, print.as_str()));
+    execute_idempotently(state.idempotency.as_ref(), journey_id, key, || async {
+        match execute_with_retry(
+            &state.cqrs,
+            &journey_id.to_string(),
+            command,
+            metadata,
+            COMMAND_MAX_ATTEMPTS,
+        )
         .await
-    {
-        Ok(()) => {
-            if is_creating {
-                let mut headers = HeaderMap::new();
+        {
+            Ok(()) => command_success_response(journey_id, is_creating),
+            Err(err) => {
+                record_rejection(state.rejections.as_ref(), journey_id, command_type, &err).await;
+                command_error_response(err)
+            }
+        }
+    })
+    .await
+}
 
-                let location = format!("/journeys/{journey_id}");
-                let Ok(header_value) = HeaderValue::from_str(&location) else {
-                    return (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        "Failed to create location header",
-                    )
-                        .into_response();
-                };
-                headers.insert(header::LOCATION, header_value);
+fn command_success_response(journey_id: Uuid, is_creating: bool) -> Response {
+    if !is_creating {
+        return StatusCode::NO_CONTENT.into_response();
+    }
+    let location = format!("/journeys/{journey_id}");
+    let Ok(header_value) = HeaderValue::from_str(&location) else {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to create location header",
+        )
+            .into_response();
+    };
+    let mut headers = HeaderMap::new();
+    headers.insert(header::LOCATION, header_value);
+    (StatusCode::CREATED, headers).into_response()
+}
 
-                (StatusCode::CREATED, headers).into_response()
-            } else {
-                StatusCode::NO_CONTENT.into_response()
-            }
+/// Record `err` in `log` if the aggregate rejected the command named
+/// `command_type`. Conflicts and infrastructure failures are not rejections.
+///
+/// Best-effort: a failure to record is logged, and the command's own
+/// response is still sent.
+pub(crate) async fn record_rejection(
+    log: &dyn RejectionLog,
+    journey_id: Uuid,
+    command_type: &'static str,
+    err: &AggregateError<JourneyError>,
+) {
+    let AggregateError::UserError(err) = err else {
+        return;
+    };
+    if let Err(err) = log
+        .record(&CommandRejection::new(journey_id, command_type, err))
+        .await
+    {
+        eprintln!("Error: {err:#?}");
+    }
+}
+
+/// Runs `execute` at most once per idempotency `key` of `journey_id`, or
+/// always when there is no key. The key comes with the [`fingerprint`] of the
+/// command.
+///
+/// A repeated key replays the recorded response of the command that
+/// succeeded, or gets a `409` while that command is still running. A command
+/// that fails releases the key, so the client can retry it. A key repeated
+/// with another command gets a `422`.
+async fn execute_idempotently<F, Fut>(
+    store: &dyn IdempotencyStore,
+    journey_id: Uuid,
+    key: Option<(&str, &str)>,
+    execute: F,
+) -> Response
+where
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = Response>,
+{
+    let Some((key, fingerprint)) = key else {
+        return execute().await;
+    };
+    match store.claim(journey_id, key, fingerprint).await {
+        Ok(Claim::Claimed) => {}
+        Ok(Claim::Completed(recorded)) => return replay(&recorded),
+        Ok(Claim::InProgress) => {
+            return (
+                StatusCode::CONFLICT,
+                [(header::RETRY_AFTER, CONFLICT_RETRY_AFTER_SECS)],
+                "a command with this Idempotency-Key is still in progress",
+            )
+                .into_response();
+        }
+        Ok(Claim::Mismatch) => {
+            return (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "this Idempotency-Key was already used with a different command",
+            )
+                .into_response();
         }
         Err(err) => {
             eprintln!("Error: {err:#?}");
-            (StatusCode::BAD_REQUEST, err.to_string()).into_response()
+            return (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response();
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::sync::Mutex;
+    let response = execute().await;
+    let recorded = if response.status().is_success() {
+        let location = response
+            .headers()
+            .get(header::LOCATION)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let recorded = RecordedResponse {
+            status: response.status().as_u16(),
+            location,
+        };
+        store.complete(journey_id, key, &recorded).await
+    } else {
+        store.release(journey_id, key).await
+    };
+    if let Err(err) = recorded {
+        // The command itself went through; only a repeat may now re-run it.
+        eprintln!("Error: {err:#?}");
+    }
+    response
+}
 
-    use uuid::Uuid;
+/// The response recorded for an idempotency key, marked as a replay.
+fn replay(recorded: &RecordedResponse) -> Response {
+    let status = StatusCode::from_u16(recorded.status).unwrap_or(StatusCode::OK);
+    let mut headers = HeaderMap::new();
+    headers.insert(IDEMPOTENT_REPLAYED_HDR, HeaderValue::from_static("true"));
+    if let Some(location) = recorded
+        .location
+        .as_deref()
+        .and_then(|location| HeaderValue::from_str(location).ok())
+    {
+        headers.insert(header::LOCATION, location);
+    }
+    (status, headers).into_response()
+}
 
-    use super::shred_each;
+/// Execute `command` against journey `id`, retrying when it loses an
+/// optimistic-lock race to a concurrent command on the same journey.
+///
+/// The framework reloads the aggregate on every attempt, so a retry
+/// re-validates the command against whatever the winning command committed.
+/// At most `max_attempts` attempts are made (at least one); if the last one
+/// still conflicts, `AggregateConflict` is returned. Any other error is
+/// returned immediately.
+///
+/// # Errors
+///
+/// Returns the error from the final attempt.
+// `CqrsFramework::execute_with_metadata` takes the default-hasher `HashMap`.
+#[allow(clippy::implicit_hasher)]
+pub async fn execute_with_retry<ES: EventStore<Journey>>(
+    cqrs: &CqrsFramework<Journey, ES>,
+    id: &str,
+    command: JourneyCommand,
+    metadata: HashMap<String, String>,
+    max_attempts: usize,
+) -> Result<(), AggregateError<JourneyError>> {
+    retry_on_conflict(max_attempts, || {
+        cqrs.execute_with_metadata(id, command.clone(), metadata.clone())
+    })
+    .await
+}
 
-    /// Best-effort: a failure on one subject must not stop the others, and the
-    /// failing subject must be reported so a retry can re-run only what's left.
+/// Runs `attempt` until it does anything other than conflict, or until
+/// `max_attempts` attempts have been made.
+async fn retry_on_conflict<F, Fut>(
+    max_attempts: usize,
+    mut attempt: F,
+) -> Result<(), AggregateError<JourneyError>>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<(), AggregateError<JourneyError>>>,
+{
+    let mut attempts = 1;
+    loop {
+        match attempt().await {
+            Err(AggregateError::AggregateConflict) if attempts < max_attempts => attempts += 1,
+            result => return result,
+        }
+    }
+}
+
+impl IntoResponse for JourneyError {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            Self::NotFound => StatusCode::NOT_FOUND,
+            Self::AlreadyStarted
+            | Self::AlreadyCompleted
+            | Self::NotCompleted
+            | Self::Paused
+            | Self::Forgotten
+            | Self::StepNotAllowed { .. }
+            | Self::CannotComplete { .. }
+            | Self::PersonRefConflict(_) => StatusCode::CONFLICT,
+            Self::InvalidData(_)
+            | Self::PersonNotFound(_)
+            | Self::InvalidPerson(_)
+            | Self::InvalidTag(_)
+            | Self::UnknownAttributePath(_)
+            | Self::NothingToCorrect(_)
+            | Self::SecretCorrection(_)
+            | Self::InvalidJsonPointer(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::DataTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::DecisionEngineError(_) => StatusCode::BAD_GATEWAY,
+        };
+        if let Self::InvalidData(issues) = self {
+            // One entry per field, so a frontend can show each next to its input.
+            return (status, Json(json!({ "errors": issues }))).into_response();
+        }
+        (status, self.to_string()).into_response()
+    }
+}
+
+/// A view store failure as a response: `404` for an unknown journey, `400`
+/// for a request the store cannot answer, and `500` otherwise, logged here
+/// and answered without the underlying database or decoding error.
+impl IntoResponse for ViewRepositoryError {
+    fn into_response(self) -> Response {
+        match self {
+            Self::NotFound(_) => StatusCode::NOT_FOUND.into_response(),
+            Self::InvalidJourneyId { .. } | Self::Unsupported(_) => {
+                (StatusCode::BAD_REQUEST, self.to_string()).into_response()
+            }
+            Self::InconsistentRow(_) | Self::Database(_) => {
+                eprintln!("Error: {self:#?}");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "failed to read the journey view",
+                )
+                    .into_response()
+            }
+        }
+    }
+}
+
+/// Map a failed command to a response: business-rule violations via
+/// [`JourneyError`]'s `IntoResponse`, an optimistic-lock conflict to `409`,
+/// and infrastructure failures to `500`.
+///
+/// A conflict reaching here has already exhausted [`execute_with_retry`], so
+/// it carries a `Retry-After` hint for the client to back off and resubmit.
+pub(crate) fn command_error_response(err: AggregateError<JourneyError>) -> Response {
+    match err {
+        AggregateError::UserError(err) => err.into_response(),
+        AggregateError::AggregateConflict => (
+            StatusCode::CONFLICT,
+            [(header::RETRY_AFTER, CONFLICT_RETRY_AFTER_SECS)],
+            err.to_string(),
+        )
+            .into_response(),
+        AggregateError::DatabaseConnectionError(_)
+        | AggregateError::DeserializationError(_)
+        | AggregateError::UnexpectedError(_) => {
+            eprintln!("Error: {err:#?}");
+            (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{HashMap, HashSet},
+        sync::{Arc, Mutex},
+    };
+
+    use uuid::Uuid;
+
+    use futures_util::StreamExt;
+    use tokio::sync::broadcast;
+
+    use axum::{
+        http::{HeaderMap, HeaderValue, StatusCode, header},
+        response::{IntoResponse, Response},
+    };
+    use cqrs_es::{
+        Aggregate, AggregateContext, AggregateError, CqrsFramework, EventEnvelope, EventStore,
+        event_sink::EventSink,
+        mem_store::{MemStore, MemStoreAggregateContext},
+    };
+    use serde_json::{Value, json};
+    use std::assert_matches;
+
+    use super::{
+        EventFeedParams, FormatParams, JsonFormat, ListJourneysParams, PreviewCaptureBody,
+        command_error_response, command_success_response, event_history, execute_idempotently,
+        explain_step, list_journeys, preview_capture, readiness, record_rejection, replay,
+        retry_on_conflict, shred_each, start_from, view_updates,
+    };
+    use crate::{
+        command_rejections::{CommandRejection, InMemoryRejectionLog},
+        domain::{
+            AttributeSchema,
+            commands::{JOURNEY_KEY_NAMESPACE, JourneyCommand},
+            events::JourneyEvent,
+            flatten,
+            journey::{Journey, JourneyError, JourneyServices},
+        },
+        idempotency::{IdempotencyStore, InMemoryIdempotencyStore, RecordedResponse, fingerprint},
+        memory_view_repository::InMemoryJourneyViewRepository,
+        queries::{JourneyEventView, JourneyFilter, JourneyState, JourneyView},
+        services::{
+            decision_engine::{
+                DecisionEngine, DecisionEngineError, GoRulesDecisionEngine, MockDecisionEngine,
+                SimpleDecisionEngine, WorkflowDecision,
+            },
+            schema_validator::{JsonSchemaValidator, ValidationIssue},
+        },
+        view_repository::{JourneyViewStore, StructuredJourneyViewRepository, ViewRepositoryError},
+    };
+
+    /// Best-effort: a failure on one subject must not stop the others, and the
+    /// failing subject must be reported so a retry can re-run only what's left.
     #[tokio::test]
     async fn shred_each_attempts_every_subject_and_reports_failures() {
         let s1 = Uuid::new_v4();
@@ -300,4 +1201,1054 @@ mod tests {
             shred_each(Vec::new(), |_subject_id| async { Ok::<(), &str>(()) }).await;
         assert_eq!(result, Ok(()));
     }
+
+    #[tokio::test]
+    async fn view_updates_skips_lagged_views_and_ends_on_close() {
+        let (sender, receiver) = broadcast::channel(2);
+        let ids: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        for id in &ids {
+            sender
+                .send(JourneyView {
+                    id: *id,
+                    ..JourneyView::default()
+                })
+                .unwrap();
+        }
+        drop(sender);
+
+        let yielded: Vec<Uuid> = view_updates(receiver).map(|view| view.id).collect().await;
+
+        // Capacity 2: the two oldest views were overwritten before being read.
+        assert_eq!(yielded, ids[2..]);
+    }
+
+    #[test]
+    fn journey_error_status_codes() {
+        let cases = [
+            (JourneyError::NotFound, StatusCode::NOT_FOUND),
+            (JourneyError::AlreadyStarted, StatusCode::CONFLICT),
+            (JourneyError::AlreadyCompleted, StatusCode::CONFLICT),
+            (JourneyError::NotCompleted, StatusCode::CONFLICT),
+            (JourneyError::Paused, StatusCode::CONFLICT),
+            (JourneyError::Forgotten, StatusCode::CONFLICT),
+            (
+                JourneyError::StepNotAllowed {
+                    step: "payment".to_string(),
+                    allowed: vec!["passengers".to_string()],
+                },
+                StatusCode::CONFLICT,
+            ),
+            (
+                JourneyError::CannotComplete {
+                    missing: vec!["payment".to_string()],
+                },
+                StatusCode::CONFLICT,
+            ),
+            (
+                JourneyError::PersonRefConflict("lead_booker".to_string()),
+                StatusCode::CONFLICT,
+            ),
+            (
+                JourneyError::InvalidData(vec![ValidationIssue::new("", "bad")]),
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                JourneyError::PersonNotFound("lead_booker".to_string()),
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                JourneyError::InvalidPerson("name must not be empty".to_string()),
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                JourneyError::InvalidTag("key must not be empty".to_string()),
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                JourneyError::UnknownAttributePath(vec!["/nope".parse().unwrap()]),
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                JourneyError::NothingToCorrect("search.origin".to_string()),
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                JourneyError::SecretCorrection("/persons/lead_booker/passport".parse().unwrap()),
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                JourneyError::DataTooLarge {
+                    size: 2048,
+                    limit: 1024,
+                },
+                StatusCode::PAYLOAD_TOO_LARGE,
+            ),
+            (
+                JourneyError::DecisionEngineError(DecisionEngineError::new("timeout")),
+                StatusCode::BAD_GATEWAY,
+            ),
+        ];
+        let mut codes = HashSet::new();
+        for (err, expected) in cases {
+            let description = format!("{err:?}");
+            // Rejections are counted by code, so no two kinds may share one.
+            assert!(codes.insert(err.code()), "{description}: code reused");
+            assert_eq!(err.into_response().status(), expected, "{description}");
+        }
+    }
+
+    /// The flight example's passenger group, as `schemars` emits it for an
+    /// optional `Vec<PassengerDetail>`.
+    fn passenger_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "passengerDetails": {
+                    "anyOf": [
+                        { "type": "array", "items": { "$ref": "#/$defs/PassengerDetail" } },
+                        { "type": "null" }
+                    ]
+                }
+            },
+            "$defs": {
+                "PassengerDetail": {
+                    "type": "object",
+                    "properties": {
+                        "firstName": { "type": "string", "minLength": 1 },
+                        "lastName": { "type": "string", "minLength": 1 },
+                        "dateOfBirth": { "type": "string" },
+                        "passengerType": { "type": "string", "enum": ["adult", "child", "infant"] }
+                    },
+                    "required": ["firstName", "lastName", "dateOfBirth", "passengerType"]
+                }
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn invalid_data_response_lists_issues_by_pointer() {
+        let services = JourneyServices::new(
+            Arc::new(SimpleDecisionEngine),
+            Arc::new(JsonSchemaValidator::new(&passenger_schema()).unwrap()),
+            Arc::new(AttributeSchema::permissive()),
+        );
+        let cqrs = CqrsFramework::new(MemStore::<Journey>::default(), vec![], services);
+        let journey_id = Uuid::new_v4();
+        cqrs.execute(
+            &journey_id.to_string(),
+            JourneyCommand::Start {
+                id: journey_id,
+                journey_type: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        // The second passenger has no date of birth.
+        let err = cqrs
+            .execute(
+                &journey_id.to_string(),
+                JourneyCommand::SetAttributes {
+                    changes: flatten(&json!({
+                        "passengerDetails": [
+                            { "firstName": "Alice", "lastName": "Smith",
+                              "dateOfBirth": "1990-05-15", "passengerType": "adult" },
+                            { "firstName": "Bob", "lastName": "Smith", "passengerType": "child" }
+                        ]
+                    })),
+                },
+            )
+            .await
+            .unwrap_err();
+        let response = command_error_response(err);
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "errors": [{
+                    "path": "/passengerDetails/1/dateOfBirth",
+                    "code": "required",
+                    "message": "required"
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn aggregate_error_status_codes() {
+        let user_error = command_error_response(AggregateError::UserError(JourneyError::NotFound));
+        assert_eq!(user_error.status(), StatusCode::NOT_FOUND);
+
+        let conflict = command_error_response(AggregateError::AggregateConflict);
+        assert_eq!(conflict.status(), StatusCode::CONFLICT);
+        assert_eq!(conflict.headers()[header::RETRY_AFTER], "1");
+
+        let database = command_error_response(AggregateError::DatabaseConnectionError(
+            "connection refused".into(),
+        ));
+        assert_eq!(database.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let unexpected = command_error_response(AggregateError::UnexpectedError("boom".into()));
+        assert_eq!(unexpected.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn view_repository_error_status_codes() {
+        let journey_id = Uuid::new_v4();
+        let not_found = ViewRepositoryError::NotFound(journey_id).into_response();
+        assert_eq!(not_found.status(), StatusCode::NOT_FOUND);
+
+        let unsupported = ViewRepositoryError::Unsupported("names are encrypted").into_response();
+        assert_eq!(unsupported.status(), StatusCode::BAD_REQUEST);
+
+        let database =
+            ViewRepositoryError::Database(sqlx::Error::Protocol("relation \"secret\"".into()))
+                .into_response();
+        assert_eq!(database.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let body = axum::body::to_bytes(database.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, "failed to read the journey view");
+    }
+
+    #[tokio::test]
+    async fn only_aggregate_rejections_are_recorded() {
+        let log = InMemoryRejectionLog::new();
+        let journey_id = Uuid::new_v4();
+
+        for err in [
+            AggregateError::UserError(JourneyError::Paused),
+            AggregateError::AggregateConflict,
+            AggregateError::UnexpectedError("boom".into()),
+        ] {
+            record_rejection(&log, journey_id, "Capture", &err).await;
+        }
+
+        assert_eq!(
+            log.rejections(),
+            vec![CommandRejection {
+                journey_id,
+                command_type: "Capture",
+                error_code: "paused",
+                detail: JourneyError::Paused.to_string(),
+            }]
+        );
+    }
+
+    // ── retry_on_conflict ───────────────────────────────────────────────────
+
+    /// Simulates a command racing a concurrent writer: it loses `conflicts`
+    /// times before its write lands.
+    fn racing_command(
+        conflicts: usize,
+        calls: &Mutex<usize>,
+    ) -> impl FnMut() -> std::future::Ready<Result<(), AggregateError<JourneyError>>> + '_ {
+        move || {
+            let mut calls = calls.lock().unwrap();
+            *calls += 1;
+            std::future::ready(if *calls <= conflicts {
+                Err(AggregateError::AggregateConflict)
+            } else {
+                Ok(())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn retry_on_conflict_succeeds_once_the_race_is_won() {
+        let calls = Mutex::new(0);
+        let result = retry_on_conflict(3, racing_command(2, &calls)).await;
+
+        assert!(result.is_ok());
+        assert_eq!(*calls.lock().unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn retry_on_conflict_gives_up_after_max_attempts() {
+        let calls = Mutex::new(0);
+        let result = retry_on_conflict(3, racing_command(usize::MAX, &calls)).await;
+
+        assert!(matches!(result, Err(AggregateError::AggregateConflict)));
+        assert_eq!(*calls.lock().unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn retry_on_conflict_always_makes_one_attempt() {
+        let calls = Mutex::new(0);
+        let result = retry_on_conflict(0, racing_command(0, &calls)).await;
+
+        assert!(result.is_ok());
+        assert_eq!(*calls.lock().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn retry_on_conflict_does_not_retry_other_errors() {
+        let mut calls = 0;
+        let result = retry_on_conflict(3, || {
+            calls += 1;
+            std::future::ready(Err(AggregateError::UserError(
+                JourneyError::AlreadyCompleted,
+            )))
+        })
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(AggregateError::UserError(JourneyError::AlreadyCompleted))
+        ));
+        assert_eq!(calls, 1);
+    }
+
+    /// A `MemStore` that, like the Postgres event store, refuses to commit
+    /// against an aggregate another writer has moved on since it was loaded.
+    #[derive(Clone, Default)]
+    struct OptimisticStore(MemStore<Journey>);
+
+    impl EventStore<Journey> for OptimisticStore {
+        type AC = MemStoreAggregateContext<Journey>;
+
+        async fn load_events(
+            &self,
+            aggregate_id: &str,
+        ) -> Result<Vec<EventEnvelope<Journey>>, AggregateError<JourneyError>> {
+            self.0.load_events(aggregate_id).await
+        }
+
+        async fn load_aggregate(
+            &self,
+            aggregate_id: &str,
+        ) -> Result<Self::AC, AggregateError<JourneyError>> {
+            self.0.load_aggregate(aggregate_id).await
+        }
+
+        async fn commit(
+            &self,
+            events: Vec<JourneyEvent>,
+            context: Self::AC,
+            metadata: HashMap<String, String>,
+        ) -> Result<Vec<EventEnvelope<Journey>>, AggregateError<JourneyError>> {
+            let committed = self.0.load_events(&context.aggregate_id).await?;
+            if committed.len() != context.current_sequence {
+                return Err(AggregateError::AggregateConflict);
+            }
+            self.0.commit(events, context, metadata).await
+        }
+    }
+
+    /// Suggests every top-level key of the data merged so far, so a decision
+    /// shows which captures it saw.
+    fn captured_keys_engine() -> MockDecisionEngine {
+        MockDecisionEngine::with_fn(|journey, _, data| {
+            let mut merged = journey.shared_data().clone();
+            json_patch::merge(&mut merged, data);
+            Ok(WorkflowDecision {
+                suggested_actions: merged.as_object().unwrap().keys().cloned().collect(),
+                phase: None,
+                ranked_actions: vec![],
+                can_complete: false,
+                completion_blocked_by: vec![],
+            })
+        })
+    }
+
+    #[allow(deprecated)]
+    fn capture(step: &str, data: Value) -> JourneyCommand {
+        JourneyCommand::Capture {
+            step: step.to_string(),
+            data,
+        }
+    }
+
+    /// Two clients capture different steps at once. The one that loaded the
+    /// journey first loses the commit race, is retried against the merged
+    /// journey, and its decision accounts for both captures.
+    #[tokio::test]
+    async fn interleaved_captures_evaluate_against_both() {
+        let services = services_with(Arc::new(captured_keys_engine()));
+        let store = OptimisticStore::default();
+        let cqrs = CqrsFramework::new(store.clone(), vec![], services.clone());
+        let journey_id = Uuid::new_v4();
+        let id = journey_id.to_string();
+        cqrs.execute(
+            &id,
+            JourneyCommand::Start {
+                id: journey_id,
+                journey_type: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        // The second client loads the journey before the first one's capture lands.
+        let mut stale = Some(store.load_aggregate(&id).await.unwrap());
+        cqrs.execute(
+            &id,
+            capture("search", json!({ "search": { "origin": "LHR" } })),
+        )
+        .await
+        .unwrap();
+
+        let passengers = capture("passengers", json!({ "passengers": { "adults": 1 } }));
+        let mut attempts = 0;
+        retry_on_conflict(3, || {
+            attempts += 1;
+            let stale = stale.take();
+            let (store, cqrs, services, id) = (&store, &cqrs, &services, &id);
+            let passengers = passengers.clone();
+            async move {
+                let Some(mut context) = stale else {
+                    return cqrs.execute(id, passengers).await;
+                };
+                let sink = EventSink::default();
+                context
+                    .aggregate()
+                    .handle(passengers, services, &sink)
+                    .await
+                    .map_err(AggregateError::UserError)?;
+                store
+                    .commit(sink.collect().await, context, HashMap::new())
+                    .await
+                    .map(drop)
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(attempts, 2);
+        let events = store.load_events(&id).await.unwrap();
+        let last_decision = events
+            .iter()
+            .rev()
+            .find_map(|envelope| match &envelope.payload {
+                JourneyEvent::WorkflowEvaluated {
+                    suggested_actions, ..
+                } => Some(suggested_actions.clone()),
+                _ => None,
+            });
+        assert_eq!(
+            last_decision,
+            Some(vec!["passengers".to_string(), "search".to_string()])
+        );
+    }
+
+    // ── Idempotency keys ─────────────────────────────────────────────────────
+
+    /// Runs `command` through `execute_idempotently` as `command_handler` does.
+    async fn submit(
+        cqrs: &CqrsFramework<Journey, MemStore<Journey>>,
+        store: &InMemoryIdempotencyStore,
+        journey_id: Uuid,
+        key: Option<&str>,
+        command: JourneyCommand,
+    ) -> Response {
+        let print = fingerprint(&command);
+        let key = key.map(|key| (key, print.as_str()));
+        execute_idempotently(store, journey_id, key, || async {
+            match cqrs.execute(&journey_id.to_string(), command).await {
+                Ok(()) => command_success_response(journey_id, false),
+                Err(err) => command_error_response(err),
+            }
+        })
+        .await
+    }
+
+    #[allow(deprecated)]
+    async fn captures_recorded(events: &MemStore<Journey>, journey_id: Uuid) -> usize {
+        events
+            .load_events(&journey_id.to_string())
+            .await
+            .unwrap()
+            .iter()
+            .filter(|envelope| matches!(envelope.payload, JourneyEvent::Modified { .. }))
+            .count()
+    }
+
+    #[tokio::test]
+    async fn a_repeated_idempotency_key_applies_the_capture_once() {
+        let events = MemStore::<Journey>::default();
+        let cqrs = CqrsFramework::new(
+            events.clone(),
+            vec![],
+            services_with(Arc::new(SimpleDecisionEngine)),
+        );
+        let keys = InMemoryIdempotencyStore::new();
+        let journey_id = Uuid::new_v4();
+        let start = JourneyCommand::Start {
+            id: journey_id,
+            journey_type: None,
+        };
+        cqrs.execute(&journey_id.to_string(), start).await.unwrap();
+        let search = || capture("search", json!({ "search": { "origin": "LHR" } }));
+
+        let first = submit(&cqrs, &keys, journey_id, Some("k1"), search()).await;
+        let retried = submit(&cqrs, &keys, journey_id, Some("k1"), search()).await;
+
+        assert_eq!(first.status(), StatusCode::NO_CONTENT);
+        assert_eq!(retried.status(), StatusCode::NO_CONTENT);
+        assert_eq!(retried.headers()["Idempotent-Replayed"], "true");
+        assert_eq!(captures_recorded(&events, journey_id).await, 1);
+
+        // Without a key, or with another one, the command runs again.
+        submit(&cqrs, &keys, journey_id, None, search()).await;
+        submit(&cqrs, &keys, journey_id, Some("k2"), search()).await;
+        assert_eq!(captures_recorded(&events, journey_id).await, 3);
+    }
+
+    #[tokio::test]
+    async fn a_failed_command_releases_its_idempotency_key() {
+        let cqrs = CqrsFramework::new(
+            MemStore::<Journey>::default(),
+            vec![],
+            services_with(Arc::new(SimpleDecisionEngine)),
+        );
+        let keys = InMemoryIdempotencyStore::new();
+        let journey_id = Uuid::new_v4();
+        let search = || capture("search", json!({ "search": { "origin": "LHR" } }));
+
+        // The journey has not started yet.
+        let failed = submit(&cqrs, &keys, journey_id, Some("k1"), search()).await;
+        assert_eq!(failed.status(), StatusCode::NOT_FOUND);
+
+        let start = JourneyCommand::Start {
+            id: journey_id,
+            journey_type: None,
+        };
+        cqrs.execute(&journey_id.to_string(), start).await.unwrap();
+        let retried = submit(&cqrs, &keys, journey_id, Some("k1"), search()).await;
+        assert_eq!(retried.status(), StatusCode::NO_CONTENT);
+        assert!(!retried.headers().contains_key("Idempotent-Replayed"));
+    }
+
+    #[tokio::test]
+    async fn a_key_still_in_progress_is_a_conflict() {
+        let keys = InMemoryIdempotencyStore::new();
+        let journey_id = Uuid::new_v4();
+        keys.claim(journey_id, "k1", "f1").await.unwrap();
+
+        let response = execute_idempotently(&keys, journey_id, Some(("k1", "f1")), || async {
+            unreachable!("the command must not run twice")
+        })
+        .await;
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn a_key_repeated_with_another_command_is_unprocessable() {
+        let events = MemStore::<Journey>::default();
+        let cqrs = CqrsFramework::new(
+            events.clone(),
+            vec![],
+            services_with(Arc::new(SimpleDecisionEngine)),
+        );
+        let keys = InMemoryIdempotencyStore::new();
+        let journey_id = Uuid::new_v4();
+        let start = JourneyCommand::Start {
+            id: journey_id,
+            journey_type: None,
+        };
+        cqrs.execute(&journey_id.to_string(), start).await.unwrap();
+
+        let search = capture("search", json!({ "search": { "origin": "LHR" } }));
+        let other = capture("search", json!({ "search": { "origin": "LGW" } }));
+        submit(&cqrs, &keys, journey_id, Some("k1"), search).await;
+        let reused = submit(&cqrs, &keys, journey_id, Some("k1"), other).await;
+
+        assert_eq!(reused.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(captures_recorded(&events, journey_id).await, 1);
+    }
+
+    #[test]
+    fn replays_keep_the_location_of_a_created_journey() {
+        let response = replay(&RecordedResponse {
+            status: 201,
+            location: Some("/journeys/abc".to_string()),
+        });
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(response.headers()[header::LOCATION], "/journeys/abc");
+    }
+
+    // ── Deterministic starts ─────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn a_retried_deterministic_start_creates_one_journey() {
+        let events = MemStore::<Journey>::default();
+        let cqrs = CqrsFramework::new(
+            events.clone(),
+            vec![],
+            services_with(Arc::new(SimpleDecisionEngine)),
+        );
+        let start = || JourneyCommand::StartDeterministic {
+            namespace: JOURNEY_KEY_NAMESPACE,
+            name: "order-1234".to_string(),
+            journey_type: None,
+        };
+        let journey_id = start().started_id().unwrap();
+        assert_eq!(start().started_id(), Some(journey_id));
+
+        cqrs.execute(&journey_id.to_string(), start())
+            .await
+            .unwrap();
+        let retried = cqrs.execute(&journey_id.to_string(), start()).await;
+
+        assert_matches!(
+            retried,
+            Err(AggregateError::UserError(JourneyError::AlreadyStarted))
+        );
+        assert_eq!(
+            command_error_response(retried.unwrap_err()).status(),
+            StatusCode::CONFLICT
+        );
+        let recorded = events.load_events(&journey_id.to_string()).await.unwrap();
+        assert_eq!(recorded.len(), 1);
+    }
+
+    /// A journey with a capture whose metadata has several keys, so map
+    /// ordering shows in the output.
+    async fn journey_with_events() -> (MemStore<Journey>, Uuid) {
+        let services = JourneyServices::without_validation(Arc::new(SimpleDecisionEngine));
+        let store = MemStore::<Journey>::default();
+        let cqrs = CqrsFramework::new(store.clone(), vec![], services);
+        let journey_id = Uuid::new_v4();
+        cqrs.execute(
+            &journey_id.to_string(),
+            JourneyCommand::Start {
+                id: journey_id,
+                journey_type: None,
+            },
+        )
+        .await
+        .unwrap();
+        let metadata = ["user_agent", "request_id", "channel", "actor"]
+            .into_iter()
+            .map(|key| (key.to_string(), format!("{key}-value")))
+            .collect();
+        cqrs.execute_with_metadata(
+            &journey_id.to_string(),
+            JourneyCommand::Tag {
+                key: "channel".to_string(),
+                value: "mobile".to_string(),
+            },
+            metadata,
+        )
+        .await
+        .unwrap();
+        (store, journey_id)
+    }
+
+    async fn body_text(response: Response) -> String {
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn event_history_compact_and_pretty_parse_back_to_the_same_events() {
+        let (store, journey_id) = journey_with_events().await;
+
+        let compact = body_text(event_history(&store, journey_id, JsonFormat::Compact).await).await;
+        let pretty = body_text(event_history(&store, journey_id, JsonFormat::Pretty).await).await;
+
+        assert!(!compact.contains('\n'));
+        assert!(pretty.contains("\n  "));
+        let compact: Vec<JourneyEventView> = serde_json::from_str(&compact).unwrap();
+        let pretty: Vec<JourneyEventView> = serde_json::from_str(&pretty).unwrap();
+        let stored = store.load_events(&journey_id.to_string()).await.unwrap();
+        for events in [&compact, &pretty] {
+            assert_eq!(events.len(), stored.len());
+            for (event, envelope) in events.iter().zip(&stored) {
+                assert_eq!(event.sequence, envelope.sequence);
+                assert_eq!(event.payload, envelope.payload);
+                assert_eq!(event.metadata, envelope.metadata);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn event_history_pretty_sorts_keys_and_renders_stably() {
+        let (store, journey_id) = journey_with_events().await;
+
+        let first = body_text(event_history(&store, journey_id, JsonFormat::Pretty).await).await;
+        let second = body_text(event_history(&store, journey_id, JsonFormat::Pretty).await).await;
+
+        assert_eq!(first, second);
+        let position = |key: &str| first.find(&format!("\"{key}\"")).unwrap();
+        assert!(position("actor") < position("channel"));
+        assert!(position("channel") < position("request_id"));
+        assert!(position("request_id") < position("user_agent"));
+        assert!(position("event_type") < position("event_version"));
+        assert!(position("metadata") < position("payload"));
+    }
+
+    #[tokio::test]
+    async fn event_history_of_unknown_journey_is_not_found() {
+        let store = MemStore::<Journey>::default();
+        let response = event_history(&store, Uuid::new_v4(), JsonFormat::Pretty).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn json_format_is_pretty_by_query_or_accept_header() {
+        let accept = |value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::ACCEPT, HeaderValue::from_static(value));
+            headers
+        };
+        let pretty = FormatParams { pretty: true };
+        let plain = FormatParams::default();
+
+        assert_eq!(
+            JsonFormat::negotiate(&plain, &HeaderMap::new()),
+            JsonFormat::Compact
+        );
+        assert_eq!(
+            JsonFormat::negotiate(&pretty, &HeaderMap::new()),
+            JsonFormat::Pretty
+        );
+        assert_eq!(
+            JsonFormat::negotiate(&plain, &accept("application/json; pretty=true")),
+            JsonFormat::Pretty
+        );
+        assert_eq!(
+            JsonFormat::negotiate(&plain, &accept("text/html, Application/JSON;Pretty=True")),
+            JsonFormat::Pretty
+        );
+        assert_eq!(
+            JsonFormat::negotiate(&plain, &accept("application/json")),
+            JsonFormat::Compact
+        );
+        assert_eq!(
+            JsonFormat::negotiate(&plain, &accept("text/plain; pretty=true")),
+            JsonFormat::Compact
+        );
+    }
+
+    #[tokio::test]
+    async fn preview_evaluates_without_persisting() {
+        let services = JourneyServices::without_validation(Arc::new(SimpleDecisionEngine));
+        let store = MemStore::<Journey>::default();
+        let cqrs = CqrsFramework::new(store.clone(), vec![], services.clone());
+        let journey_id = Uuid::new_v4();
+        cqrs.execute(
+            &journey_id.to_string(),
+            JourneyCommand::Start {
+                id: journey_id,
+                journey_type: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let body = PreviewCaptureBody {
+            step: "personal_info".to_string(),
+            data: json!({ "email": "alice@example.com", "first_name": "Alice" }),
+        };
+        let response = preview_capture(&store, &services, journey_id, &body).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let preview: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(preview["decision"]["suggested_actions"], json!(["form_3"]));
+        assert_eq!(preview["validation_errors"], json!([]));
+
+        // Nothing was committed and the journey holds no data.
+        let events = store.load_events(&journey_id.to_string()).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_matches!(events[0].payload, JourneyEvent::Started { .. });
+        let mut context = store.load_aggregate(&journey_id.to_string()).await.unwrap();
+        assert_eq!(context.aggregate().shared_data(), &json!({}));
+    }
+
+    #[tokio::test]
+    async fn preview_of_unknown_journey_is_not_found() {
+        let services = JourneyServices::without_validation(Arc::new(SimpleDecisionEngine));
+        let body = PreviewCaptureBody {
+            step: "personal_info".to_string(),
+            data: json!({}),
+        };
+        let response = preview_capture(
+            &MemStore::<Journey>::default(),
+            &services,
+            Uuid::new_v4(),
+            &body,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn explain_of_unknown_journey_is_not_found() {
+        let response = explain_step(
+            &MemStore::<Journey>::default(),
+            &SimpleDecisionEngine,
+            Uuid::new_v4(),
+            "search",
+            false,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn explain_is_not_implemented_by_engines_without_explanations() {
+        let store = MemStore::<Journey>::default();
+        let journey_id = Uuid::new_v4();
+        let cqrs = CqrsFramework::new(
+            store.clone(),
+            vec![],
+            services_with(Arc::new(SimpleDecisionEngine)),
+        );
+        cqrs.execute(
+            &journey_id.to_string(),
+            JourneyCommand::Start {
+                id: journey_id,
+                journey_type: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let response =
+            explain_step(&store, &SimpleDecisionEngine, journey_id, "search", false).await;
+        assert_eq!(response.status(), StatusCode::NOT_IMPLEMENTED);
+    }
+
+    #[tokio::test]
+    async fn explain_includes_the_node_trace_only_when_asked() {
+        let engine = Arc::new(GoRulesDecisionEngine::new(include_str!(
+            "../../../examples/flight-booking/jdm-models/flight-booking-orchestrator.jdm.json"
+        )));
+        let store = MemStore::<Journey>::default();
+        let journey_id = Uuid::new_v4();
+        let cqrs = CqrsFramework::new(store.clone(), vec![], services_with(engine.clone()));
+        cqrs.execute(
+            &journey_id.to_string(),
+            JourneyCommand::Start {
+                id: journey_id,
+                journey_type: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let mut bodies = Vec::new();
+        for trace in [false, true] {
+            let response = explain_step(&store, engine.as_ref(), journey_id, "search", trace).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            bodies.push(serde_json::from_slice::<Value>(&body).unwrap());
+        }
+
+        assert!(bodies[0].get("trace").is_none());
+        let node_ids: Vec<&str> = bodies[1]["trace"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|node| node["node_id"].as_str().unwrap())
+            .collect();
+        assert_eq!(node_ids.first(), Some(&"input"));
+        assert_eq!(node_ids.last(), Some(&"output"));
+    }
+
+    // ── Start from ────────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn start_from_seeds_a_fresh_journey_with_the_source_data() {
+        let views = InMemoryJourneyViewRepository::default();
+        let cqrs = CqrsFramework::new(
+            MemStore::<Journey>::default(),
+            vec![Box::new(views.clone())],
+            services_with(Arc::new(SimpleDecisionEngine)),
+        );
+        let source_id = Uuid::new_v4();
+        for command in [
+            JourneyCommand::Start {
+                id: source_id,
+                journey_type: Some("flight-booking".to_string()),
+            },
+            JourneyCommand::SetAttributes {
+                changes: flatten(&json!({ "search": { "origin": "LHR" } })),
+            },
+            JourneyCommand::Complete,
+        ] {
+            cqrs.execute(&source_id.to_string(), command).await.unwrap();
+        }
+
+        let response = start_from(&views, &cqrs, source_id, false, HashMap::new()).await;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let location = response.headers()[header::LOCATION].to_str().unwrap();
+        let journey_id: Uuid = location
+            .strip_prefix("/journeys/")
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_ne!(journey_id, source_id);
+        let view = views.load(&journey_id).await.unwrap().unwrap();
+        assert_eq!(view.id, journey_id);
+        assert_eq!(view.journey_type.as_deref(), Some("flight-booking"));
+        assert_eq!(view.state, JourneyState::InProgress);
+        assert_eq!(view.shared_data, json!({ "search": { "origin": "LHR" } }));
+        assert!(view.latest_workflow_decision.is_none());
+    }
+
+    #[tokio::test]
+    async fn start_from_an_unknown_source_is_not_found() {
+        let views = InMemoryJourneyViewRepository::default();
+        let cqrs = CqrsFramework::new(
+            MemStore::<Journey>::default(),
+            vec![Box::new(views.clone())],
+            services_with(Arc::new(SimpleDecisionEngine)),
+        );
+
+        let response = start_from(&views, &cqrs, Uuid::new_v4(), true, HashMap::new()).await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    // ── Readiness ─────────────────────────────────────────────────────────────
+
+    /// A pool whose every query fails: nothing listens on port 1.
+    fn unreachable_pool() -> sqlx::Pool<sqlx::Postgres> {
+        sqlx::postgres::PgPoolOptions::new()
+            .acquire_timeout(std::time::Duration::from_millis(500))
+            .connect_lazy("postgres://postgres@127.0.0.1:1/journey_dynamics")
+            .unwrap()
+    }
+
+    fn services_with(engine: Arc<dyn DecisionEngine>) -> JourneyServices {
+        JourneyServices::without_validation(engine)
+    }
+
+    async fn readiness_of(services: &JourneyServices) -> (StatusCode, Value) {
+        let response = readiness(&unreachable_pool(), services).await;
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn readiness_reports_an_unreachable_database() {
+        let (status, body) = readiness_of(&services_with(Arc::new(SimpleDecisionEngine))).await;
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["status"], "unavailable");
+        assert_ne!(body["checks"]["database"], "ok");
+        assert_eq!(body["checks"]["decision_engine"], "ok");
+    }
+
+    #[tokio::test]
+    async fn readiness_reports_a_failing_decision_engine() {
+        let (status, body) = readiness_of(&services_with(Arc::new(MockDecisionEngine::with_fn(
+            |_, _, _| Err(DecisionEngineError::new("model not loaded")),
+        ))))
+        .await;
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            body["checks"]["decision_engine"],
+            "Decision engine error: model not loaded"
+        );
+    }
+
+    #[tokio::test]
+    async fn health_is_always_ok() {
+        assert_eq!(super::health_handler().await, StatusCode::OK);
+    }
+
+    // ── List journeys ─────────────────────────────────────────────────────────
+
+    fn params(query: &str) -> ListJourneysParams {
+        axum::extract::Query::try_from_uri(&format!("/journeys?{query}").parse().unwrap())
+            .unwrap()
+            .0
+    }
+
+    #[test]
+    fn list_params_default_to_the_first_page_of_everything() {
+        let (filter, limit, offset) = params("").parse().unwrap();
+
+        assert_eq!(filter, JourneyFilter::default());
+        assert_eq!((limit, offset), (50, 0));
+    }
+
+    #[test]
+    fn list_params_parse_state_and_step() {
+        let (filter, limit, offset) = params("state=in_progress&step=payment&limit=10&offset=20")
+            .parse()
+            .unwrap();
+
+        assert_eq!(filter.state, Some(JourneyState::InProgress));
+        assert_eq!(filter.current_step.as_deref(), Some("payment"));
+        assert_eq!((limit, offset), (10, 20));
+        assert_eq!(
+            params("state=complete").parse().unwrap().0.state,
+            Some(JourneyState::Complete)
+        );
+    }
+
+    #[test]
+    fn list_params_reject_bad_values() {
+        assert_matches!(params("state=InProgress").parse(), Err(message) if message.contains("unknown journey state 'InProgress'"));
+        assert_matches!(params("limit=0").parse(), Err(_));
+        assert_matches!(params("limit=501").parse(), Err(_));
+        assert_matches!(params("offset=-1").parse(), Err(_));
+    }
+
+    #[tokio::test]
+    async fn list_journeys_rejects_bad_params_before_querying() {
+        let repo = StructuredJourneyViewRepository::new(unreachable_pool());
+
+        let response = list_journeys(&repo, params("state=archived")).await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn list_journeys_reports_a_failing_query() {
+        let repo = StructuredJourneyViewRepository::new(unreachable_pool());
+
+        let response = list_journeys(&repo, params("state=paused")).await;
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    // ── Event feed ────────────────────────────────────────────────────────────
+
+    fn feed_params(query: &str) -> EventFeedParams {
+        axum::extract::Query::try_from_uri(&format!("/events?{query}").parse().unwrap())
+            .unwrap()
+            .0
+    }
+
+    #[test]
+    fn feed_params_default_to_the_start_of_the_log() {
+        assert_eq!(feed_params("").parse().unwrap(), (0, 50));
+        assert_eq!(
+            feed_params("after=1200&limit=100").parse().unwrap(),
+            (1200, 100)
+        );
+    }
+
+    #[test]
+    fn feed_params_reject_a_negative_cursor_or_bad_limit() {
+        assert_matches!(feed_params("after=-1").parse(), Err(_));
+        assert_matches!(feed_params("limit=0").parse(), Err(_));
+        assert_matches!(feed_params("limit=501").parse(), Err(_));
+    }
 }
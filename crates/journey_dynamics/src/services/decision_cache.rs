@@ -0,0 +1,250 @@
+//! Bounded least-recently-used memo of [`WorkflowDecision`]s.
+//!
+//! Used by [`GoRulesDecisionEngine`](super::decision_engine::GoRulesDecisionEngine)
+//! to skip re-evaluating a JDM against a context it has already seen, and by
+//! [`CachingDecisionEngine`](super::decision_engine::CachingDecisionEngine),
+//! whose entries also expire after a TTL. Both key entries by a SHA-256
+//! digest of the serialised evaluation context, so distinct contexts never
+//! share a decision in practice.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use super::decision_engine::WorkflowDecision;
+
+/// Hit/miss counters for a decision cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DecisionCacheStats {
+    /// Evaluations answered from the cache.
+    pub hits: u64,
+    /// Evaluations that had to run the engine.
+    pub misses: u64,
+    /// Decisions currently held.
+    pub len: usize,
+}
+
+pub struct DecisionCache<K = [u8; 32]> {
+    capacity: usize,
+    /// How long an entry is served after it was stored; `None` keeps entries
+    /// until they are evicted.
+    ttl: Option<Duration>,
+    entries: HashMap<K, (Instant, WorkflowDecision)>,
+    /// Keys from least to most recently used.
+    recency: VecDeque<K>,
+    hits: u64,
+    misses: u64,
+}
+
+impl DecisionCache {
+    /// Digest an evaluation context into a cache key.
+    pub fn key(context: &Value) -> [u8; 32] {
+        Sha256::digest(context.to_string().as_bytes()).into()
+    }
+}
+
+impl<K: Copy + Eq + Hash> DecisionCache<K> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ttl: None,
+            entries: HashMap::with_capacity(capacity),
+            recency: VecDeque::with_capacity(capacity),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Serve each entry for at most `ttl` after it was stored.
+    pub const fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Look up `key`, marking it most recently used on a hit. An expired
+    /// entry is dropped and counts as a miss.
+    pub fn get(&mut self, key: K) -> Option<WorkflowDecision> {
+        let expired = |stored_at: &Instant| self.ttl.is_some_and(|ttl| stored_at.elapsed() >= ttl);
+        let decision = match self.entries.get(&key) {
+            Some((stored_at, _)) if expired(stored_at) => {
+                self.remove(key);
+                None
+            }
+            Some((_, decision)) => Some(decision.clone()),
+            None => None,
+        };
+        let Some(decision) = decision else {
+            self.misses += 1;
+            return None;
+        };
+        self.hits += 1;
+        self.touch(key);
+        Some(decision)
+    }
+
+    /// Store `decision` under `key`, evicting the least recently used entry
+    /// when full. A zero-capacity cache stores nothing.
+    pub fn insert(&mut self, key: K, decision: WorkflowDecision) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self
+            .entries
+            .insert(key, (Instant::now(), decision))
+            .is_some()
+        {
+            self.touch(key);
+            return;
+        }
+        self.recency.push_back(key);
+        if self.entries.len() > self.capacity
+            && let Some(oldest) = self.recency.pop_front()
+        {
+            self.entries.remove(&oldest);
+        }
+    }
+
+    /// Drop every entry, keeping the hit/miss counters.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    pub fn stats(&self) -> DecisionCacheStats {
+        DecisionCacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            len: self.entries.len(),
+        }
+    }
+
+    fn touch(&mut self, key: K) {
+        if let Some(position) = self.recency.iter().position(|k| *k == key) {
+            self.recency.remove(position);
+        }
+        self.recency.push_back(key);
+    }
+
+    fn remove(&mut self, key: K) {
+        self.entries.remove(&key);
+        if let Some(position) = self.recency.iter().position(|k| *k == key) {
+            self.recency.remove(position);
+        }
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn decision(action: &str) -> WorkflowDecision {
+        WorkflowDecision {
+            suggested_actions: vec![action.to_string()],
+            phase: None,
+            ranked_actions: Vec::new(),
+            can_complete: false,
+            completion_blocked_by: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn hit_returns_stored_decision() {
+        let mut cache = DecisionCache::new(2);
+        let key = DecisionCache::key(&json!({ "currentStep": "search" }));
+
+        assert!(cache.get(key).is_none());
+        cache.insert(key, decision("search"));
+
+        assert_eq!(cache.get(key).unwrap().suggested_actions, vec!["search"]);
+        assert_eq!(
+            cache.stats(),
+            DecisionCacheStats {
+                hits: 1,
+                misses: 1,
+                len: 1
+            }
+        );
+    }
+
+    #[test]
+    fn equal_contexts_share_a_key() {
+        let a = json!({ "currentStep": "search", "capturedData": { "origin": "LHR" } });
+        let b = json!({ "currentStep": "search", "capturedData": { "origin": "LHR" } });
+        let c = json!({ "currentStep": "search", "capturedData": { "origin": "CDG" } });
+
+        assert_eq!(DecisionCache::key(&a), DecisionCache::key(&b));
+        assert_ne!(DecisionCache::key(&a), DecisionCache::key(&c));
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = DecisionCache::new(2);
+        cache.insert(1, decision("one"));
+        cache.insert(2, decision("two"));
+
+        // Touch 1 so that 2 becomes the least recently used.
+        assert!(cache.get(1).is_some());
+        cache.insert(3, decision("three"));
+
+        assert!(cache.get(2).is_none());
+        assert!(cache.get(1).is_some());
+        assert!(cache.get(3).is_some());
+        assert_eq!(cache.stats().len, 2);
+    }
+
+    #[test]
+    fn reinserting_a_key_does_not_grow_the_cache() {
+        let mut cache = DecisionCache::new(2);
+        cache.insert(1, decision("one"));
+        cache.insert(1, decision("uno"));
+
+        assert_eq!(cache.stats().len, 1);
+        assert_eq!(cache.get(1).unwrap().suggested_actions, vec!["uno"]);
+    }
+
+    #[test]
+    fn zero_capacity_stores_nothing() {
+        let mut cache = DecisionCache::new(0);
+        cache.insert(1, decision("one"));
+
+        assert!(cache.get(1).is_none());
+        assert_eq!(cache.stats().len, 0);
+    }
+
+    #[test]
+    fn expired_entries_are_misses() {
+        let mut cache = DecisionCache::new(2).with_ttl(Duration::ZERO);
+        cache.insert(1, decision("one"));
+
+        assert!(cache.get(1).is_none());
+        assert_eq!(
+            cache.stats(),
+            DecisionCacheStats {
+                hits: 0,
+                misses: 1,
+                len: 0
+            }
+        );
+    }
+
+    #[test]
+    fn clear_drops_every_entry() {
+        let mut cache = DecisionCache::new(2);
+        cache.insert(1, decision("one"));
+        cache.insert(2, decision("two"));
+
+        cache.clear();
+
+        assert!(cache.get(1).is_none());
+        assert_eq!(cache.stats().len, 0);
+    }
+}
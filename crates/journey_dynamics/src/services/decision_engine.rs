@@ -1,20 +1,28 @@
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, HashSet, VecDeque},
+    fmt, fs,
     future::Future,
-    sync::{Arc, OnceLock},
+    io,
+    path::Path,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError},
     thread::available_parallelism,
+    time::Duration,
 };
 
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 use tokio::task::JoinHandle;
 use tokio_util::task::LocalPoolHandle;
 use zen_engine::{
-    DecisionEngine as ZenEngine, DecisionGraphResponse, EvaluationOptions, model::DecisionContent,
+    DecisionEngine as ZenEngine, EvaluationError, EvaluationOptions, Variable,
+    model::{DecisionContent, DecisionNode, DecisionNodeKind, DecisionTableInputField},
 };
 
+use super::decision_cache::{DecisionCache, DecisionCacheStats};
 use crate::domain::{
-    assign_all,
+    assign_all, get_dotted,
     journey::{Journey, JourneyState},
 };
 use jsonptr::PointerBuf;
@@ -27,6 +35,13 @@ use jsonptr::PointerBuf;
 // The GoRules docs recommend using LocalPoolHandle::spawn_pinned() from
 // tokio-util for multi-threaded workloads.  This replaces the previous
 // spawn_blocking + Handle::block_on antipattern.
+//
+// Each worker thread owns a current-thread runtime of its own, created with
+// the pool on first use, and tasks are handed to it through that runtime's
+// handle. Nothing is ever blocked on, and the caller's runtime is only used to
+// await the returned JoinHandle — so evaluation works the same from a
+// multi-threaded or a current-thread runtime, including from inside
+// `#[tokio::main(flavor = "current_thread")]`.
 // ---------------------------------------------------------------------------
 
 fn worker_pool() -> LocalPoolHandle {
@@ -53,12 +68,210 @@ where
 // Public types
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone)]
+/// A step a workflow can be in, as declared by its decision model.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StepDescriptor {
+    /// The phase label the model emits for this step.
+    pub id: String,
+    /// Actions the model may suggest while in this step — the ways out of it.
+    pub suggested_actions: Vec<String>,
+}
+
+/// A suggested action with the weight the decision model gave it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RankedAction {
+    pub action: String,
+    pub weight: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkflowDecision {
     pub suggested_actions: Vec<String>,
     /// Optional phase label returned by the decision engine.
     /// `None` until the JDM model emits a `phase` output key.
+    #[serde(default)]
     pub phase: Option<String>,
+    /// Suggested actions with weights, highest first. Read from the JDM
+    /// `rankedActions` output key — a list of `{ "action", "weight" }`
+    /// objects; empty when the model does not emit it.
+    #[serde(default)]
+    pub ranked_actions: Vec<RankedAction>,
+    /// Whether the journey may be completed as it stands. Read from the JDM
+    /// `canComplete` output key; `false` when the model does not emit it.
+    /// Only enforced when `JourneyServices::with_can_complete_check` is on.
+    #[serde(default)]
+    pub can_complete: bool,
+    /// Steps that must still be done before the journey may complete. Read
+    /// from the JDM `completionBlockedBy` output key — a list of step names;
+    /// empty when the model does not emit it. Unlike `can_complete`, always
+    /// enforced: `Complete` is rejected while the latest decision lists any.
+    #[serde(default)]
+    pub completion_blocked_by: Vec<String>,
+}
+
+/// Why a step is or isn't among the actions suggested for a journey.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StepExplanation {
+    pub step: String,
+    /// Whether the step is currently suggested.
+    pub available: bool,
+    /// A one-line summary, such as
+    /// `return_flight_selection unavailable: computed.isRoundTrip is false, needs true`.
+    pub reason: String,
+    /// Every rule that would suggest the step, with how its conditions fare.
+    pub rules: Vec<RuleExplanation>,
+    /// The nodes the evaluation ran, in order, when the engine traces them.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub trace: Vec<NodeEvaluation>,
+}
+
+/// What one node of a decision model did in an evaluation, read from the
+/// engine's trace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NodeEvaluation {
+    pub node_id: String,
+    /// The node's name in the model, such as `Phase and Actions`.
+    pub name: String,
+    /// What the node received; `null` for the input node.
+    pub input: Value,
+    /// What the node produced; `null` for the output node.
+    pub output: Value,
+    /// How long the node took, when the engine reported it.
+    #[serde(rename = "duration_us", serialize_with = "serialize_micros")]
+    pub duration: Option<Duration>,
+}
+
+#[allow(clippy::ref_option)] // serde passes the field by reference
+fn serialize_micros<S: serde::Serializer>(
+    duration: &Option<Duration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    duration
+        .map(|duration| u64::try_from(duration.as_micros()).unwrap_or(u64::MAX))
+        .serialize(serializer)
+}
+
+/// Read a node's duration from zen's trace, which formats it as `{:.1?}`,
+/// e.g. `12.3µs` or `1.5ms`.
+fn parse_duration(performance: &str) -> Option<Duration> {
+    const UNITS: [(&str, f64); 4] = [("ns", 1e-9), ("µs", 1e-6), ("ms", 1e-3), ("s", 1.0)];
+    UNITS.iter().find_map(|(suffix, seconds)| {
+        let amount: f64 = performance.strip_suffix(suffix)?.parse().ok()?;
+        Duration::try_from_secs_f64(amount * seconds).ok()
+    })
+}
+
+/// How a rule that suggests the explained step fares against the journey.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RuleExplanation {
+    /// The rule's `_id` in the decision table, when it has one.
+    pub rule_id: Option<String>,
+    pub satisfied: Vec<ConditionCheck>,
+    pub unsatisfied: Vec<ConditionCheck>,
+}
+
+/// One condition of a rule, and the value it was tested against.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConditionCheck {
+    /// The table input tested, such as `computed.isRoundTrip`.
+    pub field: String,
+    /// The rule's condition on it, such as `true`.
+    pub condition: String,
+    /// The value the input had.
+    pub actual: Value,
+}
+
+/// Why a [`DecisionEngine`] could not explain a step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExplainError {
+    /// The engine cannot explain its decisions.
+    Unsupported,
+    /// Evaluating the model failed.
+    Engine(DecisionEngineError),
+}
+
+impl fmt::Display for ExplainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsupported => f.write_str("This decision engine cannot explain its decisions"),
+            Self::Engine(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ExplainError {}
+
+impl From<DecisionEngineError> for ExplainError {
+    fn from(err: DecisionEngineError) -> Self {
+        Self::Engine(err)
+    }
+}
+
+/// Why a [`DecisionEngine`] could not produce a decision.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecisionEngineError {
+    pub message: String,
+    /// The context the engine was evaluated against. Only captured when the
+    /// engine opts in (see [`GoRulesDecisionEngine::with_error_context`]),
+    /// since it may hold PII.
+    pub context: Option<Value>,
+    /// The JDM node that failed, when the engine can tell.
+    pub node_id: Option<String>,
+    /// Whether the failure was in the machinery around the model rather than
+    /// the model itself, so the same evaluation may succeed if retried.
+    pub transient: bool,
+}
+
+impl DecisionEngineError {
+    #[must_use]
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            context: None,
+            node_id: None,
+            transient: false,
+        }
+    }
+
+    #[must_use]
+    pub fn with_context(mut self, context: Value) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    #[must_use]
+    pub fn with_node_id(mut self, node_id: impl Into<String>) -> Self {
+        self.node_id = Some(node_id.into());
+        self
+    }
+
+    /// Mark the error as worth retrying; see [`RetryingDecisionEngine`].
+    #[must_use]
+    pub const fn with_transient(mut self, transient: bool) -> Self {
+        self.transient = transient;
+        self
+    }
+}
+
+impl fmt::Display for DecisionEngineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)?;
+        if let Some(node_id) = &self.node_id {
+            write!(f, " (node '{node_id}')")?;
+        }
+        if let Some(context) = &self.context {
+            write!(f, "; context: {context}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for DecisionEngineError {}
+
+impl From<jsonptr::assign::Error> for DecisionEngineError {
+    fn from(err: jsonptr::assign::Error) -> Self {
+        Self::new(err.to_string())
+    }
 }
 
 #[async_trait]
@@ -68,7 +281,7 @@ pub trait DecisionEngine: Send + Sync {
         journey: &Journey,
         current_step: &str,
         new_data: &Value,
-    ) -> Result<WorkflowDecision, Box<dyn std::error::Error + Send + Sync>>;
+    ) -> Result<WorkflowDecision, DecisionEngineError>;
 
     /// Evaluate the workflow after a `SetAttributes` command.
     ///
@@ -81,11 +294,23 @@ pub trait DecisionEngine: Send + Sync {
         &self,
         journey: &Journey,
         pending_changes: &BTreeMap<PointerBuf, Value>,
-    ) -> Result<WorkflowDecision, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
         let mut merged = journey.shared_data().clone();
         assign_all(&mut merged, pending_changes)?;
         self.evaluate_next_steps(journey, "", &merged).await
     }
+
+    /// Explain why `step` is or isn't among the actions suggested for
+    /// `journey` as it stands.
+    ///
+    /// The default implementation returns [`ExplainError::Unsupported`].
+    async fn explain(
+        &self,
+        _journey: &Journey,
+        _step: &str,
+    ) -> Result<StepExplanation, ExplainError> {
+        Err(ExplainError::Unsupported)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -101,14 +326,14 @@ impl DecisionEngine for SimpleDecisionEngine {
         journey: &Journey,
         current_step: &str,
         new_data: &Value,
-    ) -> Result<WorkflowDecision, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
         let mut accumulated_data = journey.shared_data().clone();
         let keyed_data = serde_json::json!({ current_step: new_data });
         json_patch::merge(&mut accumulated_data, &keyed_data);
         let state = journey.state();
 
         let suggested_actions = match state {
-            JourneyState::InProgress => {
+            JourneyState::InProgress | JourneyState::Paused => {
                 // Check if any step has "first_name" key
                 let has_first_name = accumulated_data.as_object().is_some_and(|obj| {
                     obj.values().any(|value| {
@@ -130,13 +355,225 @@ impl DecisionEngine for SimpleDecisionEngine {
             JourneyState::Complete => vec![],
         };
 
+        // Nothing left to suggest means nothing left to do.
+        let can_complete = suggested_actions.is_empty();
+
         Ok(WorkflowDecision {
             suggested_actions,
             phase: None,
+            ranked_actions: Vec::new(),
+            can_complete,
+            completion_blocked_by: vec![],
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// TableDecisionEngine — declarative transition table
+// ---------------------------------------------------------------------------
+
+/// A step in a [`TableDecisionEngine`]'s transition table.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableStep {
+    /// Dot-notation paths, as read by [`get_dotted`], that must hold a
+    /// non-null value before the journey may leave this step.
+    #[serde(default)]
+    pub requires: Vec<String>,
+    /// Steps offered once every required field is present. A step with none
+    /// is a final step.
+    #[serde(default)]
+    pub next: Vec<String>,
+}
+
+/// Decides from a transition table mapping each step to the fields it
+/// requires and the steps that follow it, without a JDM model.
+///
+/// While any field the current step requires is missing from the journey's
+/// data nothing is suggested; once all are present its `next` steps are.
+/// The phase is the current step: the one being captured, or else the phase
+/// of the journey's latest decision. A journey with neither is offered the
+/// table's entry steps — those no other step leads to. The journey may
+/// complete once a final step's fields are all present.
+pub struct TableDecisionEngine {
+    steps: BTreeMap<String, TableStep>,
+}
+
+impl TableDecisionEngine {
+    #[must_use]
+    pub const fn new(steps: BTreeMap<String, TableStep>) -> Self {
+        Self { steps }
+    }
+
+    /// Read a table from JSON such as
+    /// `{ "search": { "requires": ["search.origin"], "next": ["results"] } }`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `json` is not a valid table.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json).map(Self::new)
+    }
+
+    /// The steps no other step leads to, in name order.
+    fn entry_steps(&self) -> Vec<String> {
+        let targets: HashSet<&str> = self
+            .steps
+            .values()
+            .flat_map(|step| step.next.iter().map(String::as_str))
+            .collect();
+        self.steps
+            .keys()
+            .filter(|name| !targets.contains(name.as_str()))
+            .cloned()
+            .collect()
+    }
+}
+
+#[async_trait]
+impl DecisionEngine for TableDecisionEngine {
+    /// An empty `current_step` falls back to the journey's own step, then to
+    /// the phase of its latest decision: `Capture` records no phase.
+    #[allow(deprecated)]
+    async fn evaluate_next_steps(
+        &self,
+        journey: &Journey,
+        current_step: &str,
+        new_data: &Value,
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
+        let mut captured_data = journey.shared_data().clone();
+        json_patch::merge(&mut captured_data, new_data);
+
+        let current_step = Some(current_step)
+            .filter(|step| !step.is_empty())
+            .or_else(|| journey.current_step().map(String::as_str))
+            .or_else(|| {
+                journey
+                    .latest_workflow_decision()
+                    .and_then(|decision| decision.phase.as_deref())
+            });
+        let Some(current_step) = current_step else {
+            return Ok(WorkflowDecision {
+                suggested_actions: self.entry_steps(),
+                phase: None,
+                ranked_actions: Vec::new(),
+                can_complete: false,
+                completion_blocked_by: Vec::new(),
+            });
+        };
+        let step = self.steps.get(current_step).ok_or_else(|| {
+            DecisionEngineError::new(format!(
+                "step '{current_step}' is not in the transition table"
+            ))
+        })?;
+
+        let satisfied = step
+            .requires
+            .iter()
+            .all(|field| get_dotted(&captured_data, field).is_some_and(|value| !value.is_null()));
+        Ok(WorkflowDecision {
+            suggested_actions: if satisfied {
+                step.next.clone()
+            } else {
+                Vec::new()
+            },
+            phase: Some(current_step.to_string()),
+            ranked_actions: Vec::new(),
+            can_complete: satisfied && step.next.is_empty(),
+            completion_blocked_by: Vec::new(),
         })
     }
 }
 
+// ---------------------------------------------------------------------------
+// MockDecisionEngine — scripted engine for tests
+// ---------------------------------------------------------------------------
+
+/// A closure computing a decision from an evaluation's arguments.
+#[cfg(test)]
+pub type DecisionFn =
+    dyn Fn(&Journey, &str, &Value) -> Result<WorkflowDecision, DecisionEngineError> + Send + Sync;
+
+#[cfg(test)]
+enum Script {
+    Actions(Vec<Vec<String>>),
+    Fn(Box<DecisionFn>),
+}
+
+/// A decision engine whose answers a test controls, without loading a JDM.
+///
+/// It counts its evaluations, so a test can also assert how often the
+/// aggregate consulted it.
+#[cfg(test)]
+pub struct MockDecisionEngine {
+    script: Script,
+    calls: std::sync::atomic::AtomicUsize,
+}
+
+#[cfg(test)]
+impl MockDecisionEngine {
+    /// Suggest `actions[n]` on the `n`th evaluation, repeating the last list
+    /// once the script runs out. An empty list of actions means the journey
+    /// can complete.
+    #[must_use]
+    pub fn with_actions(actions: Vec<Vec<String>>) -> Self {
+        Self::scripted(Script::Actions(actions))
+    }
+
+    /// Compute every decision with `decide`.
+    #[must_use]
+    pub fn with_fn(
+        decide: impl Fn(&Journey, &str, &Value) -> Result<WorkflowDecision, DecisionEngineError>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        Self::scripted(Script::Fn(Box::new(decide)))
+    }
+
+    const fn scripted(script: Script) -> Self {
+        Self {
+            script,
+            calls: std::sync::atomic::AtomicUsize::new(0),
+        }
+    }
+
+    /// How many evaluations the engine has answered.
+    #[must_use]
+    pub fn calls(&self) -> usize {
+        self.calls.load(std::sync::atomic::Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+#[async_trait]
+impl DecisionEngine for MockDecisionEngine {
+    async fn evaluate_next_steps(
+        &self,
+        journey: &Journey,
+        current_step: &str,
+        new_data: &Value,
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
+        let call = self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        match &self.script {
+            Script::Actions(script) => {
+                let suggested_actions = script
+                    .get(call)
+                    .or_else(|| script.last())
+                    .cloned()
+                    .unwrap_or_default();
+                Ok(WorkflowDecision {
+                    can_complete: suggested_actions.is_empty(),
+                    suggested_actions,
+                    phase: None,
+                    ranked_actions: Vec::new(),
+                    completion_blocked_by: Vec::new(),
+                })
+            }
+            Script::Fn(decide) => decide(journey, current_step, new_data),
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // GoRulesDecisionEngine — production JDM engine
 //
@@ -147,27 +584,71 @@ impl DecisionEngine for SimpleDecisionEngine {
 //     engine itself is `Send + Sync` and can be cheaply cloned into each task.
 //
 //  2. `DecisionContent` is wrapped in `Arc` so the deserialized decision graph
-//     is never copied — only a pointer is cloned per evaluation.
+//     is never copied — only a pointer is cloned per evaluation. (zen already
+//     keeps nodes and edges behind `Arc`s, so even a plain clone of the
+//     flight-booking model is only 2 allocations, 88 bytes; the `Arc` clone
+//     allocates nothing.)
 //
 //  3. `spawn_pinned` (via `LocalPoolHandle`) is used instead of the previous
 //     `spawn_blocking` + `Handle::block_on` antipattern.
 //
 //  4. `decision.compile()` is called before each evaluation so that the graph
 //     is parsed and optimised ahead of time, reducing per-evaluation overhead.
+//
+//  5. Decisions are memoised in a bounded LRU keyed by a hash of the
+//     evaluation context. The JDM is a pure function of that context, so an
+//     identical context (e.g. a resubmitted form) reuses the prior decision.
 // ---------------------------------------------------------------------------
 
+/// Default number of decisions memoised by [`GoRulesDecisionEngine`].
+pub const DEFAULT_DECISION_CACHE_CAPACITY: usize = 1024;
+
+/// Evaluates a `GoRules` JDM decision graph.
+///
+/// Construction needs no runtime. Evaluation must be awaited on a Tokio
+/// runtime of either flavour; the graph itself runs on a shared pool of
+/// thread-pinned workers, since zen's evaluation future is `!Send`.
 pub struct GoRulesDecisionEngine {
     engine: Arc<ZenEngine>,
     decision_content: Arc<DecisionContent>,
+    cache: Mutex<DecisionCache>,
+    error_context: bool,
+    sort_actions: bool,
+    strict_output: bool,
 }
 
 impl GoRulesDecisionEngine {
     /// # Panics
     ///
-    /// Panics if `json` cannot be deserialized as a [`DecisionContent`].
+    /// Panics if `json` cannot be deserialized as a [`DecisionContent`]; use
+    /// [`Self::from_slice`] to handle that instead.
     #[must_use]
     pub fn new(json: &str) -> Self {
-        let mut decision_content: DecisionContent = serde_json::from_str(json).unwrap();
+        Self::from_slice(json.as_bytes()).unwrap()
+    }
+
+    /// Load a model from JSON bytes, such as an `include_bytes!` of a
+    /// `.jdm.json` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` are not a valid [`DecisionContent`].
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, serde_json::Error> {
+        serde_json::from_slice(bytes).map(Self::from_content)
+    }
+
+    /// Load a model by reading JSON from `reader` to its end. Wrap unbuffered
+    /// readers, such as a [`fs::File`], in an [`io::BufReader`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading fails or the JSON read is not a valid
+    /// [`DecisionContent`].
+    pub fn from_reader<R: io::Read>(reader: R) -> Result<Self, serde_json::Error> {
+        serde_json::from_reader(reader).map(Self::from_content)
+    }
+
+    fn from_content(mut decision_content: DecisionContent) -> Self {
         // Compile once at startup: pre-computes all expression bytecodes into
         // an OpcodeCache stored inside DecisionContent.  Every Decision created
         // from this Arc will carry the compiled cache, so no per-request
@@ -176,23 +657,370 @@ impl GoRulesDecisionEngine {
         Self {
             engine: Arc::new(ZenEngine::default()),
             decision_content: Arc::new(decision_content),
+            cache: Mutex::new(DecisionCache::new(DEFAULT_DECISION_CACHE_CAPACITY)),
+            error_context: false,
+            sort_actions: false,
+            strict_output: false,
+        }
+    }
+
+    /// Attach the evaluation context to every [`DecisionEngineError`], to
+    /// debug misrouting. Off by default: the context is the journey's data
+    /// and may contain PII, so leave this off in production.
+    #[must_use]
+    pub const fn with_error_context(mut self, enabled: bool) -> Self {
+        self.error_context = enabled;
+        self
+    }
+
+    /// List suggested actions in name order rather than the model's, so
+    /// models that build the list in varying order yield a stable one. The
+    /// highest-ranked action, if the model ranks, stays first. Off by default.
+    #[must_use]
+    pub const fn with_sorted_actions(mut self, enabled: bool) -> Self {
+        self.sort_actions = enabled;
+        self
+    }
+
+    /// Fail an evaluation whose output suggests no actions with "No available
+    /// actions", instead of returning an empty decision.
+    ///
+    /// Off by default: a model that suggests nothing has reached a terminal
+    /// state, which is not an error. Either way, a model that fails to
+    /// evaluate is reported as an error.
+    #[must_use]
+    pub const fn with_strict_output(mut self, enabled: bool) -> Self {
+        self.strict_output = enabled;
+        self
+    }
+
+    /// Memoise at most `capacity` decisions; `0` disables the cache.
+    #[must_use]
+    pub fn with_cache_capacity(mut self, capacity: usize) -> Self {
+        self.cache = Mutex::new(DecisionCache::new(capacity));
+        self
+    }
+
+    /// Hit/miss counters for the decision cache.
+    #[must_use]
+    pub fn cache_stats(&self) -> DecisionCacheStats {
+        self.cache().stats()
+    }
+
+    /// Every phase the loaded JDM can emit, with the actions it suggests there.
+    ///
+    /// Read from the decision tables' `phase` and `suggestedActions` outputs
+    /// without evaluating the graph, so only literal rule outputs are listed.
+    /// Steps are ordered by a walk of the graph from its input node, then by
+    /// rule order; a phase emitted by several rules is listed once, with the
+    /// union of their actions.
+    #[must_use]
+    pub fn list_steps(&self) -> Vec<StepDescriptor> {
+        let mut steps: Vec<StepDescriptor> = Vec::new();
+        for node in graph_order(&self.decision_content) {
+            let DecisionNodeKind::DecisionTableNode { content } = &node.kind else {
+                continue;
+            };
+            let output_id = |field: &str| {
+                content
+                    .outputs
+                    .iter()
+                    .find(|output| &*output.field == field)
+                    .map(|output| output.id.clone())
+            };
+            let Some(phase_id) = output_id("phase") else {
+                continue;
+            };
+            let actions_id = output_id("suggestedActions");
+
+            for rule in content.rules.iter() {
+                let Some(Value::String(id)) = rule.get(&phase_id).and_then(|e| literal(e)) else {
+                    continue;
+                };
+                let actions = actions_id
+                    .as_ref()
+                    .and_then(|actions_id| rule.get(actions_id))
+                    .and_then(|e| literal(e))
+                    .and_then(|value| match value {
+                        Value::Array(items) => Some(items),
+                        _ => None,
+                    })
+                    .unwrap_or_default();
+
+                let position = steps
+                    .iter()
+                    .position(|step| step.id == id)
+                    .unwrap_or_else(|| {
+                        steps.push(StepDescriptor {
+                            id,
+                            suggested_actions: Vec::new(),
+                        });
+                        steps.len() - 1
+                    });
+                let step = &mut steps[position];
+                for action in actions {
+                    if let Value::String(action) = action
+                        && !step.suggested_actions.contains(&action)
+                    {
+                        step.suggested_actions.push(action);
+                    }
+                }
+            }
+        }
+        steps
+    }
+
+    // The cache is only ever mutated in single, complete steps, so a poisoned
+    // lock still guards a consistent value.
+    fn cache(&self) -> MutexGuard<'_, DecisionCache> {
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl GoRulesDecisionEngine {
+    fn explain_step(
+        &self,
+        step: &str,
+        decision: &WorkflowDecision,
+        trace: Vec<NodeEvaluation>,
+    ) -> StepExplanation {
+        let mut rules = Vec::new();
+        for node in graph_order(&self.decision_content) {
+            let DecisionNodeKind::DecisionTableNode { content } = &node.kind else {
+                continue;
+            };
+            let Some(actions_id) = content
+                .outputs
+                .iter()
+                .find(|output| &*output.field == "suggestedActions")
+                .map(|output| output.id.clone())
+            else {
+                continue;
+            };
+            // A table the evaluation never reached has nothing to explain.
+            let Some(input) = trace
+                .iter()
+                .find(|evaluation| *evaluation.node_id == *node.id)
+                .map(|evaluation| &evaluation.input)
+            else {
+                continue;
+            };
+
+            for rule in content.rules.iter() {
+                let suggests_step =
+                    rule.get(&actions_id)
+                        .and_then(|e| literal(e))
+                        .is_some_and(|actions| match actions {
+                            Value::Array(actions) => actions.iter().any(|action| action == step),
+                            _ => false,
+                        });
+                if !suggests_step {
+                    continue;
+                }
+                let mut explanation = RuleExplanation {
+                    rule_id: rule.get("_id").map(ToString::to_string),
+                    satisfied: Vec::new(),
+                    unsatisfied: Vec::new(),
+                };
+                for column in content.inputs.iter() {
+                    let Some(condition) = rule.get(&column.id).filter(|c| !c.trim().is_empty())
+                    else {
+                        continue;
+                    };
+                    let (check, holds) = check_condition(column, condition, input);
+                    if holds {
+                        explanation.satisfied.push(check);
+                    } else {
+                        explanation.unsatisfied.push(check);
+                    }
+                }
+                rules.push(explanation);
+            }
+        }
+
+        let available = decision
+            .suggested_actions
+            .iter()
+            .any(|action| action == step);
+        let reason = if available {
+            format!("{step} available")
+        } else if rules.is_empty() {
+            format!("{step} unavailable: no rule suggests it")
+        } else if rules.iter().any(|rule| rule.unsatisfied.is_empty()) {
+            // Under a `first` hit policy an earlier rule can win.
+            format!("{step} unavailable: an earlier rule matched first")
+        } else {
+            let closest = rules
+                .iter()
+                .min_by_key(|rule| rule.unsatisfied.len())
+                .map(|rule| &rule.unsatisfied)
+                .into_iter()
+                .flatten()
+                .map(|check| {
+                    format!(
+                        "{} is {}, needs {}",
+                        check.field, check.actual, check.condition
+                    )
+                })
+                .collect::<Vec<_>>();
+            format!("{step} unavailable: {}", closest.join("; "))
+        };
+
+        StepExplanation {
+            step: step.to_string(),
+            available,
+            reason,
+            rules,
+            trace,
+        }
+    }
+}
+
+/// Test one cell of a decision-table rule against the table's `input`, the
+/// way zen does: a column with a field binds its value to `$` and the cell is
+/// a unary test; a column without one evaluates the cell as a whole.
+fn check_condition(
+    column: &DecisionTableInputField,
+    condition: &str,
+    input: &Value,
+) -> (ConditionCheck, bool) {
+    let evaluate = |expression: &str, scope: Value| {
+        zen_expression::evaluate_expression(expression, scope.into())
+            .map_or(Value::Null, |value| value.to_value())
+    };
+    let Some(field) = &column.field else {
+        let actual = evaluate(condition, input.clone());
+        let holds = actual == Value::Bool(true);
+        let check = ConditionCheck {
+            field: column.name.to_string(),
+            condition: condition.to_string(),
+            actual,
+        };
+        return (check, holds);
+    };
+    let actual = evaluate(field, input.clone());
+    let mut scope = input.clone();
+    if let Value::Object(scope) = &mut scope {
+        scope.insert("$".to_string(), actual.clone());
+    }
+    let holds = zen_expression::evaluate_unary_expression(condition, scope.into()).unwrap_or(false);
+    let check = ConditionCheck {
+        field: field.to_string(),
+        condition: condition.to_string(),
+        actual,
+    };
+    (check, holds)
+}
+
+/// The graph's nodes breadth-first from its input nodes, following edges in
+/// declaration order, then any unreachable nodes in declaration order.
+fn graph_order(content: &DecisionContent) -> Vec<&DecisionNode> {
+    let mut queue: VecDeque<&DecisionNode> = content
+        .nodes
+        .iter()
+        .filter(|node| matches!(node.kind, DecisionNodeKind::InputNode { .. }))
+        .map(AsRef::as_ref)
+        .collect();
+    let mut seen: HashSet<&str> = queue.iter().map(|node| &*node.id).collect();
+    let mut ordered = Vec::with_capacity(content.nodes.len());
+
+    while let Some(node) = queue.pop_front() {
+        ordered.push(node);
+        for edge in content
+            .edges
+            .iter()
+            .filter(|edge| edge.source_id == node.id)
+        {
+            if let Some(target) = content.nodes.iter().find(|n| n.id == edge.target_id)
+                && seen.insert(&target.id)
+            {
+                queue.push_back(target);
+            }
+        }
+    }
+    for node in &content.nodes {
+        if seen.insert(&node.id) {
+            ordered.push(node);
         }
     }
+    ordered
+}
+
+/// Drop repeated entries from `items`, keeping each one where it first
+/// appears.
+fn dedup_first_seen<T>(items: &mut Vec<T>, key: impl Fn(&T) -> &str) {
+    let mut seen = HashSet::new();
+    items.retain(|item| seen.insert(key(item).to_string()));
+}
+
+/// `new_data` merged into the journey's accumulated data, wrapped in the
+/// legacy `{ currentStep, capturedData }` envelope expected by JDMs that route
+/// on `currentStep`. Nested groups such as `search` merge key by key and keep
+/// their nesting, so rules read `capturedData.search.origin`.
+fn step_context(journey: &Journey, current_step: &str, new_data: &Value) -> Value {
+    let mut captured_data = journey.shared_data().clone();
+    json_patch::merge(&mut captured_data, new_data);
+
+    let mut context = Map::new();
+    context.insert(
+        "currentStep".to_string(),
+        Value::String(current_step.to_string()),
+    );
+    context.insert("capturedData".to_string(), captured_data);
+    Value::Object(context)
+}
+
+/// The value of a rule output expression that does not depend on its input,
+/// such as `'search'` or `['a', 'b']`.
+fn literal(expression: &str) -> Option<Value> {
+    let value = zen_expression::evaluate_expression(expression, Variable::empty_object()).ok()?;
+    serde_json::to_value(value).ok()
 }
 
 impl GoRulesDecisionEngine {
+    /// Evaluate `context`, answering from the decision cache when possible.
+    async fn run(&self, context: Value) -> Result<WorkflowDecision, DecisionEngineError> {
+        let key = DecisionCache::key(&context);
+        let cached = self.cache().get(key);
+        if let Some(decision) = cached {
+            return Ok(decision);
+        }
+        let decision = self.evaluate(context).await?;
+        self.cache().insert(key, decision.clone());
+        Ok(decision)
+    }
+
     /// Evaluate the loaded JDM with `context` and extract a [`WorkflowDecision`].
-    async fn run(
+    async fn evaluate(&self, context: Value) -> Result<WorkflowDecision, DecisionEngineError> {
+        let error_context = self.error_context.then(|| context.clone());
+        let fail = |err: DecisionEngineError| match &error_context {
+            Some(context) => err.with_context(context.clone()),
+            None => err,
+        };
+
+        let (result, _) = self.run_graph(context, false).await.map_err(fail)?;
+        let decision = self.decision_from(&result).map_err(fail)?;
+        if self.strict_output && decision.suggested_actions.is_empty() {
+            return Err(fail(DecisionEngineError::new("No available actions")));
+        }
+        Ok(decision)
+    }
+
+    /// Run the loaded JDM against `context`, returning its output and, when
+    /// `with_trace` is set, what each node did, in the order the nodes ran.
+    async fn run_graph(
         &self,
         context: Value,
-    ) -> Result<WorkflowDecision, Box<dyn std::error::Error + Send + Sync>> {
+        with_trace: bool,
+    ) -> Result<(Value, Vec<NodeEvaluation>), DecisionEngineError> {
         let engine = Arc::clone(&self.engine);
         let jdm_content = Arc::clone(&self.decision_content);
 
-        // Serialise the response inside the closure so the JoinHandle output
-        // type is Send (DecisionGraphResponse holds zen_engine::Variable which
-        // is !Send).
-        let result: Value = spawn_pinned(move || async move {
+        // Convert the result inside the closure so the JoinHandle output type
+        // is Send (DecisionGraphResponse holds zen_engine::Variable which is
+        // !Send, and so does EvaluationError). `Variable::to_value` rather than
+        // serde: zen's `Serialize` impl truncates fractional numbers.
+        spawn_pinned(move || async move {
             let decision = engine.create_decision(jdm_content);
             let response = decision
                 .evaluate_with_opts(
@@ -203,82 +1031,3148 @@ impl GoRulesDecisionEngine {
                     },
                 )
                 .await
-                .map_err(|e| e.to_string())?;
-            serde_json::to_value(response).map_err(|e| e.to_string())
+                .map_err(|err| {
+                    let error = DecisionEngineError::new(err.to_string());
+                    match *err {
+                        EvaluationError::NodeError { node_id, .. } => error.with_node_id(&*node_id),
+                        _ => error,
+                    }
+                })?;
+            let mut traces: Vec<_> = response
+                .trace
+                .filter(|_| with_trace)
+                .unwrap_or_default()
+                .into_values()
+                .collect();
+            traces.sort_by_key(|trace| trace.order);
+            let trace = traces
+                .into_iter()
+                .map(|trace| NodeEvaluation {
+                    node_id: trace.id.to_string(),
+                    name: trace.name.to_string(),
+                    input: trace.input.to_value(),
+                    output: trace.output.to_value(),
+                    duration: trace.performance.as_deref().and_then(parse_duration),
+                })
+                .collect();
+            Ok((response.result.to_value(), trace))
         })
         .await
-        .map_err(|e| {
-            Box::new(std::io::Error::other(e.to_string()))
-                as Box<dyn std::error::Error + Send + Sync>
-        })?
-        .map_err(|e| {
-            Box::new(std::io::Error::other(e)) as Box<dyn std::error::Error + Send + Sync>
-        })?;
-
-        let DecisionGraphResponse { result, .. } = serde_json::from_value(result)?;
-        let unwrapped_map = result.as_object().unwrap();
-        let take = unwrapped_map.take();
-
-        let suggested_actions: Vec<String> = take
-            .get("suggestedActions")
-            .and_then(zen_engine::Variable::as_array)
-            .map(|arr| {
-                arr.take()
-                    .into_iter()
-                    .filter_map(|item| item.as_str().map(str::to_string))
-                    .collect()
-            })
-            .unwrap_or_default();
-
-        let phase: Option<String> = take
-            .get("phase")
-            .and_then(zen_engine::Variable::as_str)
-            .map(str::to_string);
+        // The worker died or was cancelled; the model never ran.
+        .map_err(|e| DecisionEngineError::new(e.to_string()).with_transient(true))?
+    }
 
-        Ok(WorkflowDecision {
-            suggested_actions,
-            phase,
-        })
+    /// Read a [`WorkflowDecision`] from the model's output.
+    fn decision_from(&self, result: &Value) -> Result<WorkflowDecision, DecisionEngineError> {
+        let mut decision = parse_output(result)?;
+        if self.sort_actions {
+            let suggested_actions = &mut decision.suggested_actions;
+            suggested_actions.sort();
+            let primary = decision.ranked_actions.first().and_then(|top| {
+                suggested_actions
+                    .iter()
+                    .position(|action| *action == top.action)
+            });
+            if let Some(primary) = primary {
+                suggested_actions[..=primary].rotate_right(1);
+            }
+        }
+        Ok(decision)
     }
-}
 
-#[async_trait]
-impl DecisionEngine for GoRulesDecisionEngine {
-    async fn evaluate_next_steps(
+    /// Evaluate a `Capture` as [`DecisionEngine::evaluate_next_steps`] does,
+    /// also returning what each node of the model did, in the order the
+    /// nodes ran.
+    ///
+    /// For debugging: the decision cache is bypassed, so every call runs the
+    /// model.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DecisionEngineError`] if the model fails to evaluate or
+    /// its output is malformed.
+    pub async fn evaluate_with_trace(
         &self,
         journey: &Journey,
         current_step: &str,
         new_data: &Value,
-    ) -> Result<WorkflowDecision, Box<dyn std::error::Error + Send + Sync>> {
-        let mut captured_data = journey.shared_data().clone();
-        json_patch::merge(&mut captured_data, new_data);
-
-        // Wrap in the legacy { currentStep, capturedData } envelope expected by
-        // JDMs that route on currentStep.
-        let mut context = Map::new();
-        context.insert(
-            "currentStep".to_string(),
-            Value::String(current_step.to_string()),
-        );
-        context.insert("capturedData".to_string(), captured_data);
-        let context = serde_json::to_value(&context).unwrap();
+    ) -> Result<(WorkflowDecision, Vec<NodeEvaluation>), DecisionEngineError> {
+        let (result, trace) = self
+            .run_graph(step_context(journey, current_step, new_data), true)
+            .await?;
+        Ok((self.decision_from(&result)?, trace))
+    }
 
-        self.run(context).await
+    /// Check that every literal value the model's decision tables and
+    /// expressions give the output fields read by the engine has the right
+    /// shape, and that the model declares `suggestedActions` or
+    /// `rankedActions` at all.
+    ///
+    /// Meant to run once, after loading, so a model that would fail every
+    /// evaluation is rejected at startup. Outputs computed from the input, or
+    /// by function nodes, can only be checked when evaluated.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DecisionEngineError`] naming the node of the first
+    /// incompatible output.
+    pub fn check_outputs(&self) -> Result<(), DecisionEngineError> {
+        let mut declares_actions = false;
+        for node in graph_order(&self.decision_content) {
+            let outputs: Vec<(&str, Vec<&str>)> = match &node.kind {
+                DecisionNodeKind::DecisionTableNode { content } => content
+                    .outputs
+                    .iter()
+                    .map(|output| {
+                        let values = content
+                            .rules
+                            .iter()
+                            .filter_map(|rule| rule.get(&output.id))
+                            .map(AsRef::as_ref)
+                            .collect();
+                        (&*output.field, values)
+                    })
+                    .collect(),
+                DecisionNodeKind::ExpressionNode { content } => content
+                    .expressions
+                    .iter()
+                    .map(|expression| (&*expression.key, vec![&*expression.value]))
+                    .collect(),
+                _ => continue,
+            };
+            for (field, expressions) in outputs {
+                if !OUTPUT_FIELDS.contains(&field) {
+                    continue;
+                }
+                declares_actions |= field == "suggestedActions" || field == "rankedActions";
+                // Expressions reading the input are evaluated against an
+                // empty one, so a value holding `null` may depend on it.
+                let literals = expressions
+                    .into_iter()
+                    .filter_map(literal)
+                    .filter(|value| !contains_null(value));
+                for value in literals {
+                    let mut output = Map::new();
+                    output.insert(field.to_string(), value);
+                    parse_output(&Value::Object(output))
+                        .map_err(|err| err.with_node_id(&*node.id))?;
+                }
+            }
+        }
+        if declares_actions {
+            Ok(())
+        } else {
+            Err(DecisionEngineError::new(
+                "Decision model declares neither a suggestedActions nor a rankedActions output",
+            ))
+        }
     }
 
-    /// Evaluate the workflow after a `SetAttributes` command.
+    /// Evaluate the model against `sample_context` and check that its output
+    /// reads as a [`WorkflowDecision`] with `suggestedActions` or
+    /// `rankedActions`.
     ///
-    /// Overrides the default implementation to pass the merged attribute bag
-    /// **at the top level** — no `currentStep` or `capturedData` wrapper.
-    /// The JDM receives `{ search: {...}, booking: {...}, persons: {...} }`
-    /// and derives the current phase purely from the data.
-    async fn evaluate_attributes(
-        &self,
-        journey: &Journey,
-        pending_changes: &BTreeMap<PointerBuf, Value>,
-    ) -> Result<WorkflowDecision, Box<dyn std::error::Error + Send + Sync>> {
-        let mut data = journey.shared_data().clone();
-        assign_all(&mut data, pending_changes)?;
-        self.run(data).await
+    /// Meant to run once at startup, with a context like those the journeys
+    /// will send, so a model that fails to evaluate or routes nothing to its
+    /// output is rejected before it serves traffic. Bypasses the decision
+    /// cache and ignores [`Self::with_strict_output`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DecisionEngineError`] if the model fails to evaluate, its
+    /// output is malformed or it gives neither output field.
+    pub async fn self_check(&self, sample_context: &Value) -> Result<(), DecisionEngineError> {
+        let (result, _) = self.run_graph(sample_context.clone(), false).await?;
+        parse_output(&result)?;
+        let declares = |field| result.get(field).is_some_and(|value| !value.is_null());
+        if declares("suggestedActions") || declares("rankedActions") {
+            Ok(())
+        } else {
+            Err(DecisionEngineError::new(format!(
+                "Decision model output has neither suggestedActions nor rankedActions: {result}"
+            )))
+        }
+    }
+}
+
+/// The output fields a [`WorkflowDecision`] is read from.
+const OUTPUT_FIELDS: [&str; 5] = [
+    "suggestedActions",
+    "rankedActions",
+    "phase",
+    "canComplete",
+    "completionBlockedBy",
+];
+
+/// The [`WorkflowDecision`] a model's `output` describes.
+///
+/// Missing and `null` fields take their defaults. A field holding anything
+/// else of the wrong type is an error naming it, so a broken model is
+/// reported rather than read as suggesting less than it meant to.
+fn parse_output(output: &Value) -> Result<WorkflowDecision, DecisionEngineError> {
+    // An output node with nothing routed to it yields no object at all.
+    let empty = Map::new();
+    let output = match output {
+        Value::Object(output) => output,
+        Value::Null => &empty,
+        other => return Err(malformed_output("the output", other, "an object")),
+    };
+    let field = |name: &str| output.get(name).filter(|value| !value.is_null());
+
+    let mut ranked_actions = match field("rankedActions") {
+        None => Vec::new(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let action = item.get("action").and_then(Value::as_str);
+                let weight = item.get("weight").and_then(Value::as_f64);
+                match (action, weight) {
+                    (Some(action), Some(weight)) => Ok(RankedAction {
+                        action: action.to_string(),
+                        weight,
+                    }),
+                    _ => Err(malformed_output(
+                        &format!("rankedActions[{index}]"),
+                        item,
+                        "an object with a string action and a numeric weight",
+                    )),
+                }
+            })
+            .collect::<Result<_, _>>()?,
+        Some(other) => return Err(malformed_output("rankedActions", other, "an array")),
+    };
+    // Stable, so equal weights keep the model's order.
+    ranked_actions.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+    dedup_first_seen(&mut ranked_actions, |r| &r.action);
+
+    // Models that only rank their suggestions still get a flat list.
+    let mut suggested_actions = match field("suggestedActions") {
+        None => ranked_actions.iter().map(|r| r.action.clone()).collect(),
+        Some(value) => string_list("suggestedActions", value)?,
+    };
+    dedup_first_seen(&mut suggested_actions, String::as_str);
+
+    let phase = match field("phase") {
+        None => None,
+        Some(Value::String(phase)) => Some(phase.clone()),
+        Some(other) => return Err(malformed_output("phase", other, "a string")),
+    };
+
+    let can_complete = match field("canComplete") {
+        None => false,
+        Some(Value::Bool(can_complete)) => *can_complete,
+        Some(other) => return Err(malformed_output("canComplete", other, "a boolean")),
+    };
+
+    let mut completion_blocked_by = match field("completionBlockedBy") {
+        None => Vec::new(),
+        Some(value) => string_list("completionBlockedBy", value)?,
+    };
+    dedup_first_seen(&mut completion_blocked_by, String::as_str);
+
+    Ok(WorkflowDecision {
+        suggested_actions,
+        phase,
+        ranked_actions,
+        can_complete,
+        completion_blocked_by,
+    })
+}
+
+fn contains_null(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Array(items) => items.iter().any(contains_null),
+        Value::Object(map) => map.values().any(contains_null),
+        _ => false,
+    }
+}
+
+/// The strings in `value`, the output field `name`.
+fn string_list(name: &str, value: &Value) -> Result<Vec<String>, DecisionEngineError> {
+    let Value::Array(items) = value else {
+        return Err(malformed_output(name, value, "an array of strings"));
+    };
+    items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            item.as_str()
+                .map(str::to_string)
+                .ok_or_else(|| malformed_output(&format!("{name}[{index}]"), item, "a string"))
+        })
+        .collect()
+}
+
+/// Read an [`OverlayDecision`] from an overlay model's output.
+fn parse_overlay(output: &Value) -> Result<OverlayDecision, DecisionEngineError> {
+    let empty = Map::new();
+    let output = match output {
+        Value::Object(output) => output,
+        Value::Null => &empty,
+        other => return Err(malformed_output("the output", other, "an object")),
+    };
+    let list = |name: &str| {
+        output
+            .get(name)
+            .filter(|value| !value.is_null())
+            .map_or_else(|| Ok(Vec::new()), |value| string_list(name, value))
+    };
+    Ok(OverlayDecision {
+        deny: list("deny")?,
+        force: list("force")?,
+    })
+}
+
+fn malformed_output(path: &str, value: &Value, expected: &str) -> DecisionEngineError {
+    DecisionEngineError::new(format!(
+        "Malformed decision output: {path} is {value}, expected {expected}"
+    ))
+}
+
+#[async_trait]
+impl DecisionEngine for GoRulesDecisionEngine {
+    async fn evaluate_next_steps(
+        &self,
+        journey: &Journey,
+        current_step: &str,
+        new_data: &Value,
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
+        self.run(step_context(journey, current_step, new_data))
+            .await
+    }
+
+    /// Evaluate the workflow after a `SetAttributes` command.
+    ///
+    /// Overrides the default implementation to pass the merged attribute bag
+    /// **at the top level** — no `currentStep` or `capturedData` wrapper.
+    /// The JDM receives `{ search: {...}, booking: {...}, persons: {...} }`
+    /// and derives the current phase purely from the data.
+    async fn evaluate_attributes(
+        &self,
+        journey: &Journey,
+        pending_changes: &BTreeMap<PointerBuf, Value>,
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
+        let mut data = journey.shared_data().clone();
+        assign_all(&mut data, pending_changes)?;
+        self.run(data).await
+    }
+
+    /// Evaluate the journey's attributes as [`Self::evaluate_attributes`]
+    /// does, then test the conditions of every decision-table rule whose
+    /// literal `suggestedActions` include `step`. Bypasses the decision cache,
+    /// which keeps no trace.
+    async fn explain(
+        &self,
+        journey: &Journey,
+        step: &str,
+    ) -> Result<StepExplanation, ExplainError> {
+        let (result, trace) = self.run_graph(journey.shared_data().clone(), true).await?;
+        let decision = self.decision_from(&result)?;
+        Ok(self.explain_step(step, &decision, trace))
+    }
+}
+
+/// A `GoRules` model used as an overlay reads the same input as one used as
+/// a [`DecisionEngine`] and emits `deny` and `force`. Overlays bypass the
+/// decision cache, which holds decisions.
+#[async_trait]
+impl OverlayEngine for GoRulesDecisionEngine {
+    async fn overlay_next_steps(
+        &self,
+        journey: &Journey,
+        current_step: &str,
+        new_data: &Value,
+    ) -> Result<OverlayDecision, DecisionEngineError> {
+        let (result, _) = self
+            .run_graph(step_context(journey, current_step, new_data), false)
+            .await?;
+        parse_overlay(&result)
+    }
+
+    async fn overlay_attributes(
+        &self,
+        journey: &Journey,
+        pending_changes: &BTreeMap<PointerBuf, Value>,
+    ) -> Result<OverlayDecision, DecisionEngineError> {
+        let mut data = journey.shared_data().clone();
+        assign_all(&mut data, pending_changes)?;
+        let (result, _) = self.run_graph(data, false).await?;
+        parse_overlay(&result)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// CompositeDecisionEngine — ordered fallback across several engines
+//
+// Lets a deployment put a primary engine (typically GoRules) in front of one
+// or more fallbacks. Engines are tried in order and the first acceptable
+// decision wins; `FallbackPolicy` decides what "acceptable" means.
+// ---------------------------------------------------------------------------
+
+/// When a [`CompositeDecisionEngine`] stops trying further engines.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FallbackPolicy {
+    /// Accept the first decision that evaluates without error, even if it
+    /// suggests no actions.
+    #[default]
+    FirstSuccess,
+    /// Accept the first decision that suggests at least one action. If every
+    /// engine comes back empty, the first empty decision is returned.
+    FirstNonEmpty,
+}
+
+type Evaluation<'a> =
+    Pin<Box<dyn Future<Output = Result<WorkflowDecision, DecisionEngineError>> + Send + 'a>>;
+
+pub struct CompositeDecisionEngine {
+    engines: Vec<Arc<dyn DecisionEngine>>,
+    policy: FallbackPolicy,
+}
+
+impl CompositeDecisionEngine {
+    /// Try `engines` in order under [`FallbackPolicy::FirstSuccess`].
+    #[must_use]
+    pub fn new(engines: Vec<Arc<dyn DecisionEngine>>) -> Self {
+        Self {
+            engines,
+            policy: FallbackPolicy::default(),
+        }
+    }
+
+    #[must_use]
+    pub const fn with_policy(mut self, policy: FallbackPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Run `evaluate` against each engine in turn until one is accepted.
+    ///
+    /// Errors from engines that fall through are logged; if no engine
+    /// produced a decision the last error is returned.
+    async fn evaluate_in_order<'a>(
+        &'a self,
+        evaluate: impl Fn(&'a dyn DecisionEngine) -> Evaluation<'a> + Send + Sync,
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
+        let mut empty_decision = None;
+        let mut last_error = None;
+
+        for (index, engine) in self.engines.iter().enumerate() {
+            match evaluate(engine.as_ref()).await {
+                Ok(decision)
+                    if self.policy == FallbackPolicy::FirstSuccess
+                        || !decision.suggested_actions.is_empty() =>
+                {
+                    return Ok(decision);
+                }
+                Ok(decision) => {
+                    empty_decision.get_or_insert(decision);
+                }
+                Err(err) => {
+                    eprintln!("Decision engine #{index} failed, falling back: {err}");
+                    last_error = Some(err);
+                }
+            }
+        }
+
+        empty_decision.map_or_else(
+            || {
+                Err(last_error
+                    .unwrap_or_else(|| DecisionEngineError::new("no decision engines configured")))
+            },
+            Ok,
+        )
+    }
+}
+
+#[async_trait]
+impl DecisionEngine for CompositeDecisionEngine {
+    async fn evaluate_next_steps(
+        &self,
+        journey: &Journey,
+        current_step: &str,
+        new_data: &Value,
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
+        self.evaluate_in_order(|engine| engine.evaluate_next_steps(journey, current_step, new_data))
+            .await
+    }
+
+    /// Delegates to each engine's own `evaluate_attributes`, so engines that
+    /// override it (such as [`GoRulesDecisionEngine`]) keep their behaviour.
+    async fn evaluate_attributes(
+        &self,
+        journey: &Journey,
+        pending_changes: &BTreeMap<PointerBuf, Value>,
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
+        self.evaluate_in_order(|engine| engine.evaluate_attributes(journey, pending_changes))
+            .await
+    }
+
+    /// Asks each engine in turn, answering with the first that can explain.
+    async fn explain(
+        &self,
+        journey: &Journey,
+        step: &str,
+    ) -> Result<StepExplanation, ExplainError> {
+        for engine in &self.engines {
+            match engine.explain(journey, step).await {
+                Err(ExplainError::Unsupported) => {}
+                explained => return explained,
+            }
+        }
+        Err(ExplainError::Unsupported)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// OverlayDecisionEngine — deployment rules layered over a base model
+//
+// Unlike `CompositeDecisionEngine`, which picks one engine's decision, an
+// overlay combines them: the base engine decides as usual, then each overlay
+// may deny some of its actions or force others in. A market-specific overlay
+// can, for instance, forbid a step the core routing model would suggest,
+// without forking that model.
+// ---------------------------------------------------------------------------
+
+/// What an overlay says about the base engine's actions.
+///
+/// A `GoRules` overlay model emits it as `{ "deny": [...], "force": [...] }`;
+/// either list may be left out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OverlayDecision {
+    /// Actions to remove from the decision, whoever suggested them.
+    pub deny: Vec<String>,
+    /// Actions to suggest even if the base engine did not.
+    pub force: Vec<String>,
+}
+
+/// An engine whose output adjusts another's decision; see
+/// [`OverlayDecisionEngine`].
+#[async_trait]
+pub trait OverlayEngine: Send + Sync {
+    /// The overlay for a `Capture` of `new_data` at `current_step`, given
+    /// the same input as [`DecisionEngine::evaluate_next_steps`].
+    async fn overlay_next_steps(
+        &self,
+        journey: &Journey,
+        current_step: &str,
+        new_data: &Value,
+    ) -> Result<OverlayDecision, DecisionEngineError>;
+
+    /// The overlay after a `SetAttributes` command, given the same input as
+    /// [`DecisionEngine::evaluate_attributes`].
+    ///
+    /// The default implementation merges `pending_changes` into the journey's
+    /// data and delegates to [`Self::overlay_next_steps`] with an empty step.
+    async fn overlay_attributes(
+        &self,
+        journey: &Journey,
+        pending_changes: &BTreeMap<PointerBuf, Value>,
+    ) -> Result<OverlayDecision, DecisionEngineError> {
+        let mut merged = journey.shared_data().clone();
+        assign_all(&mut merged, pending_changes)?;
+        self.overlay_next_steps(journey, "", &merged).await
+    }
+}
+
+pub struct OverlayDecisionEngine {
+    base: Arc<dyn DecisionEngine>,
+    overlays: Vec<Arc<dyn OverlayEngine>>,
+}
+
+impl OverlayDecisionEngine {
+    /// Decide with `base`, then apply every one of `overlays`.
+    ///
+    /// Every overlay runs on every evaluation. Their forced actions are
+    /// appended after the base engine's, in overlay order, and then every
+    /// denied action is removed, so a deny beats a force. Denied actions also
+    /// leave the ranked actions; forced ones are not ranked. An overlay that
+    /// fails fails the evaluation, since skipping it could suggest a step it
+    /// exists to forbid.
+    #[must_use]
+    pub fn new(base: Arc<dyn DecisionEngine>, overlays: Vec<Arc<dyn OverlayEngine>>) -> Self {
+        Self { base, overlays }
+    }
+
+    /// The overlays' denies and forces combined, in overlay order.
+    async fn combined<'a>(
+        &'a self,
+        overlay: impl Fn(&'a dyn OverlayEngine) -> OverlayEvaluation<'a> + Send + Sync,
+    ) -> Result<OverlayDecision, DecisionEngineError> {
+        let mut combined = OverlayDecision::default();
+        for engine in &self.overlays {
+            let OverlayDecision { deny, force } = overlay(engine.as_ref()).await?;
+            combined.deny.extend(deny);
+            combined.force.extend(force);
+        }
+        Ok(combined)
+    }
+}
+
+type OverlayEvaluation<'a> =
+    Pin<Box<dyn Future<Output = Result<OverlayDecision, DecisionEngineError>> + Send + 'a>>;
+
+/// `decision` with `overlay`'s forced actions added and its denied ones
+/// removed.
+fn apply_overlay(mut decision: WorkflowDecision, overlay: &OverlayDecision) -> WorkflowDecision {
+    decision
+        .suggested_actions
+        .extend(overlay.force.iter().cloned());
+    dedup_first_seen(&mut decision.suggested_actions, |action| action);
+    decision
+        .suggested_actions
+        .retain(|action| !overlay.deny.contains(action));
+    decision
+        .ranked_actions
+        .retain(|ranked| !overlay.deny.contains(&ranked.action));
+    decision
+}
+
+#[async_trait]
+impl DecisionEngine for OverlayDecisionEngine {
+    async fn evaluate_next_steps(
+        &self,
+        journey: &Journey,
+        current_step: &str,
+        new_data: &Value,
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
+        let decision = self
+            .base
+            .evaluate_next_steps(journey, current_step, new_data)
+            .await?;
+        let overlay = self
+            .combined(|engine| engine.overlay_next_steps(journey, current_step, new_data))
+            .await?;
+        Ok(apply_overlay(decision, &overlay))
+    }
+
+    async fn evaluate_attributes(
+        &self,
+        journey: &Journey,
+        pending_changes: &BTreeMap<PointerBuf, Value>,
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
+        let decision = self
+            .base
+            .evaluate_attributes(journey, pending_changes)
+            .await?;
+        let overlay = self
+            .combined(|engine| engine.overlay_attributes(journey, pending_changes))
+            .await?;
+        Ok(apply_overlay(decision, &overlay))
+    }
+
+    /// The base engine's explanation, corrected for a step the overlays deny
+    /// or force as the journey stands.
+    async fn explain(
+        &self,
+        journey: &Journey,
+        step: &str,
+    ) -> Result<StepExplanation, ExplainError> {
+        let mut explanation = self.base.explain(journey, step).await?;
+        let no_changes = BTreeMap::new();
+        let overlay = self
+            .combined(|engine| engine.overlay_attributes(journey, &no_changes))
+            .await?;
+        if overlay.deny.iter().any(|denied| denied == step) {
+            explanation.available = false;
+            explanation.reason = format!("{step} unavailable: denied by an overlay");
+        } else if !explanation.available && overlay.force.iter().any(|forced| forced == step) {
+            explanation.available = true;
+            explanation.reason = format!("{step} available: forced by an overlay");
+        }
+        Ok(explanation)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// RetryingDecisionEngine — exponential backoff around a flaky engine
+//
+// Evaluation runs on a pinned worker pool; a worker that dies under load fails
+// the command even though the model is fine. Only errors the inner engine
+// marks `transient` are retried — a broken model fails the same way every
+// time. Wrap each engine of a `CompositeDecisionEngine` to retry it before
+// falling back, or the composite itself to retry the whole chain.
+// ---------------------------------------------------------------------------
+
+/// Attempts a [`RetryingDecisionEngine`] makes by default, the first included.
+pub const DEFAULT_RETRY_ATTEMPTS: usize = 3;
+
+/// Delay before a [`RetryingDecisionEngine`]'s first retry by default.
+pub const DEFAULT_RETRY_BASE_DELAY: Duration = Duration::from_millis(50);
+
+pub struct RetryingDecisionEngine {
+    inner: Arc<dyn DecisionEngine>,
+    max_attempts: usize,
+    base_delay: Duration,
+}
+
+impl RetryingDecisionEngine {
+    /// Retry `inner` up to [`DEFAULT_RETRY_ATTEMPTS`] times, starting from
+    /// [`DEFAULT_RETRY_BASE_DELAY`].
+    #[must_use]
+    pub fn new(inner: Arc<dyn DecisionEngine>) -> Self {
+        Self {
+            inner,
+            max_attempts: DEFAULT_RETRY_ATTEMPTS,
+            base_delay: DEFAULT_RETRY_BASE_DELAY,
+        }
+    }
+
+    /// Evaluate at most `max_attempts` times; `0` is treated as `1`.
+    #[must_use]
+    pub const fn with_max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Wait `base_delay` before the first retry, doubling before each one after.
+    #[must_use]
+    pub const fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    async fn evaluate_with_retry<'a>(
+        &'a self,
+        evaluate: impl Fn(&'a dyn DecisionEngine) -> Evaluation<'a> + Send + Sync,
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
+        let mut delay = self.base_delay;
+        let mut attempt = 1;
+        loop {
+            match evaluate(self.inner.as_ref()).await {
+                Err(err) if err.transient && attempt < self.max_attempts => {
+                    eprintln!(
+                        "Decision engine attempt {attempt}/{} failed, retrying in {delay:?}: {err}",
+                        self.max_attempts
+                    );
+                    tokio::time::sleep(delay).await;
+                    delay = delay.saturating_mul(2);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl DecisionEngine for RetryingDecisionEngine {
+    async fn evaluate_next_steps(
+        &self,
+        journey: &Journey,
+        current_step: &str,
+        new_data: &Value,
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
+        self.evaluate_with_retry(|engine| {
+            engine.evaluate_next_steps(journey, current_step, new_data)
+        })
+        .await
+    }
+
+    /// Delegates to the inner engine's own `evaluate_attributes`.
+    async fn evaluate_attributes(
+        &self,
+        journey: &Journey,
+        pending_changes: &BTreeMap<PointerBuf, Value>,
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
+        self.evaluate_with_retry(|engine| engine.evaluate_attributes(journey, pending_changes))
+            .await
+    }
+
+    /// Delegates to the inner engine, without retrying.
+    async fn explain(
+        &self,
+        journey: &Journey,
+        step: &str,
+    ) -> Result<StepExplanation, ExplainError> {
+        self.inner.explain(journey, step).await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// InstrumentedDecisionEngine — evaluation latency metrics
+//
+// Times each evaluation of the inner engine and records it, labelled by step,
+// in a histogram of the `metrics` facade, for whichever recorder the process
+// installs. Without the `metrics` feature it only delegates. Wrap a
+// `RetryingDecisionEngine` to time evaluations with their retries, or the
+// engines inside one to time each attempt.
+// ---------------------------------------------------------------------------
+
+/// Histogram [`InstrumentedDecisionEngine`] records evaluation times in, in
+/// seconds, labelled `step`.
+pub const DECISION_EVALUATION_SECONDS: &str = "journey_decision_evaluation_seconds";
+
+pub struct InstrumentedDecisionEngine {
+    inner: Arc<dyn DecisionEngine>,
+}
+
+impl InstrumentedDecisionEngine {
+    #[must_use]
+    pub fn new(inner: Arc<dyn DecisionEngine>) -> Self {
+        Self { inner }
+    }
+
+    #[cfg(feature = "metrics")]
+    async fn timed(
+        step: &str,
+        evaluation: Evaluation<'_>,
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
+        let started = std::time::Instant::now();
+        let result = evaluation.await;
+        metrics::histogram!(DECISION_EVALUATION_SECONDS, "step" => step.to_string())
+            .record(started.elapsed());
+        result
+    }
+
+    #[cfg(not(feature = "metrics"))]
+    async fn timed(
+        _step: &str,
+        evaluation: Evaluation<'_>,
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
+        evaluation.await
+    }
+}
+
+#[async_trait]
+impl DecisionEngine for InstrumentedDecisionEngine {
+    async fn evaluate_next_steps(
+        &self,
+        journey: &Journey,
+        current_step: &str,
+        new_data: &Value,
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
+        Self::timed(
+            current_step,
+            self.inner
+                .evaluate_next_steps(journey, current_step, new_data),
+        )
+        .await
+    }
+
+    /// Delegates to the inner engine's own `evaluate_attributes`, timed
+    /// under an empty step as attribute evaluations have none.
+    async fn evaluate_attributes(
+        &self,
+        journey: &Journey,
+        pending_changes: &BTreeMap<PointerBuf, Value>,
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
+        Self::timed("", self.inner.evaluate_attributes(journey, pending_changes)).await
+    }
+
+    /// Delegates to the inner engine, untimed.
+    async fn explain(
+        &self,
+        journey: &Journey,
+        step: &str,
+    ) -> Result<StepExplanation, ExplainError> {
+        self.inner.explain(journey, step).await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// CachingDecisionEngine — decisions shared across journeys
+//
+// Journeys of a templated flow often reach identical states. This wrapper
+// keys decisions by a SHA-256 digest of everything an evaluation reads — the
+// journey's type, state, data, step and latest decision, the step evaluated and
+// the new data — so those journeys share one evaluation whatever engine runs
+// it. Unlike the memo
+// inside `GoRulesDecisionEngine`, entries expire after a TTL, and the cache
+// can be cleared when the model behind it changes. Only successful decisions
+// are cached. With the `metrics` feature, hits and misses are also counted
+// through the `metrics` facade.
+// ---------------------------------------------------------------------------
+
+/// How long [`CachingDecisionEngine`] serves a decision by default.
+pub const DEFAULT_SHARED_DECISION_CACHE_TTL: Duration = Duration::from_mins(5);
+
+/// Counter of evaluations [`CachingDecisionEngine`] answered from its cache.
+pub const DECISION_CACHE_HITS: &str = "journey_decision_cache_hits_total";
+
+/// Counter of evaluations [`CachingDecisionEngine`] passed to its inner engine.
+pub const DECISION_CACHE_MISSES: &str = "journey_decision_cache_misses_total";
+
+pub struct CachingDecisionEngine {
+    inner: Arc<dyn DecisionEngine>,
+    capacity: usize,
+    ttl: Duration,
+    cache: Mutex<DecisionCache>,
+}
+
+impl CachingDecisionEngine {
+    /// Cache up to [`DEFAULT_DECISION_CACHE_CAPACITY`] decisions of `inner`
+    /// for [`DEFAULT_SHARED_DECISION_CACHE_TTL`] each.
+    #[must_use]
+    pub fn new(inner: Arc<dyn DecisionEngine>) -> Self {
+        Self {
+            inner,
+            capacity: DEFAULT_DECISION_CACHE_CAPACITY,
+            ttl: DEFAULT_SHARED_DECISION_CACHE_TTL,
+            cache: Mutex::new(
+                DecisionCache::new(DEFAULT_DECISION_CACHE_CAPACITY)
+                    .with_ttl(DEFAULT_SHARED_DECISION_CACHE_TTL),
+            ),
+        }
+    }
+
+    /// Cache at most `capacity` decisions; `0` disables the cache.
+    #[must_use]
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self.cache = Mutex::new(DecisionCache::new(capacity).with_ttl(self.ttl));
+        self
+    }
+
+    /// Serve each decision for at most `ttl` after it was computed.
+    #[must_use]
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self.cache = Mutex::new(DecisionCache::new(self.capacity).with_ttl(ttl));
+        self
+    }
+
+    /// Hit/miss counters for the cache.
+    #[must_use]
+    pub fn cache_stats(&self) -> DecisionCacheStats {
+        self.cache().stats()
+    }
+
+    /// Forget every cached decision, for instance after the inner engine's
+    /// model has been replaced.
+    pub fn invalidate(&self) {
+        self.cache().clear();
+    }
+
+    /// The cache key of an evaluation of `journey` at `step` with `input`.
+    ///
+    /// Engines fall back to the journey's own step and the phase of its
+    /// latest decision when `step` is empty, so both are part of the key.
+    #[allow(deprecated)]
+    fn key(journey: &Journey, step: Option<&str>, input: &Value) -> [u8; 32] {
+        let context = serde_json::json!({
+            "journeyType": journey.journey_type(),
+            "state": journey.state(),
+            "journeyStep": journey.current_step(),
+            "latestDecision": journey.latest_workflow_decision(),
+            "currentStep": step,
+            "sharedData": journey.shared_data(),
+            "input": input,
+        });
+        DecisionCache::key(&context)
+    }
+
+    /// The cached decision for `key`, or the decision `evaluation` makes,
+    /// cached.
+    async fn cached(
+        &self,
+        key: [u8; 32],
+        evaluation: Evaluation<'_>,
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
+        let cached = self.cache().get(key);
+        record_cache_lookup(cached.is_some());
+        if let Some(decision) = cached {
+            return Ok(decision);
+        }
+        let decision = evaluation.await?;
+        self.cache().insert(key, decision.clone());
+        Ok(decision)
+    }
+
+    // The cache is only ever mutated in single, complete steps, so a poisoned
+    // lock still guards a consistent value.
+    fn cache(&self) -> MutexGuard<'_, DecisionCache> {
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(feature = "metrics")]
+fn record_cache_lookup(hit: bool) {
+    metrics::counter!(if hit {
+        DECISION_CACHE_HITS
+    } else {
+        DECISION_CACHE_MISSES
+    })
+    .increment(1);
+}
+
+#[cfg(not(feature = "metrics"))]
+const fn record_cache_lookup(_hit: bool) {}
+
+#[async_trait]
+impl DecisionEngine for CachingDecisionEngine {
+    async fn evaluate_next_steps(
+        &self,
+        journey: &Journey,
+        current_step: &str,
+        new_data: &Value,
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
+        self.cached(
+            Self::key(journey, Some(current_step), new_data),
+            self.inner
+                .evaluate_next_steps(journey, current_step, new_data),
+        )
+        .await
+    }
+
+    /// Delegates to the inner engine's own `evaluate_attributes`, keyed by the
+    /// pending changes.
+    async fn evaluate_attributes(
+        &self,
+        journey: &Journey,
+        pending_changes: &BTreeMap<PointerBuf, Value>,
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
+        let changes: Map<String, Value> = pending_changes
+            .iter()
+            .map(|(pointer, value)| (pointer.to_string(), value.clone()))
+            .collect();
+        self.cached(
+            Self::key(journey, None, &Value::Object(changes)),
+            self.inner.evaluate_attributes(journey, pending_changes),
+        )
+        .await
+    }
+
+    /// Delegates to the inner engine, uncached.
+    async fn explain(
+        &self,
+        journey: &Journey,
+        step: &str,
+    ) -> Result<StepExplanation, ExplainError> {
+        self.inner.explain(journey, step).await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// DirectoryDecisionEngine — one JDM model per journey type
+//
+// Operators keep a model per kind of journey in a single directory. Each
+// `<journey_type>.jdm.json` file becomes a `GoRulesDecisionEngine`, and a
+// journey is evaluated against the model named by the type it was started
+// with.
+// ---------------------------------------------------------------------------
+
+/// File suffix [`DirectoryDecisionEngine`] loads models from.
+pub const JDM_FILE_SUFFIX: &str = ".jdm.json";
+
+pub struct DirectoryDecisionEngine {
+    engines: BTreeMap<String, GoRulesDecisionEngine>,
+}
+
+impl DirectoryDecisionEngine {
+    /// Load every `*.jdm.json` file in `path`, keyed by its file name without
+    /// the suffix (`flight-booking.jdm.json` serves `"flight-booking"`).
+    /// Subdirectories and other files are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or one of its models cannot be read,
+    /// or with [`io::ErrorKind::InvalidData`] if a model is not a valid
+    /// [`DecisionContent`].
+    pub fn new(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut engines = BTreeMap::new();
+        for entry in fs::read_dir(path)? {
+            let path = entry?.path();
+            let Some(journey_type) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(JDM_FILE_SUFFIX))
+            else {
+                continue;
+            };
+            if !path.is_file() {
+                continue;
+            }
+            let engine = GoRulesDecisionEngine::from_slice(&fs::read(&path)?).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid decision model {}: {err}", path.display()),
+                )
+            })?;
+            engines.insert(journey_type.to_string(), engine);
+        }
+        Ok(Self { engines })
+    }
+
+    /// The journey types a model was loaded for, in name order.
+    pub fn journey_types(&self) -> impl Iterator<Item = &str> {
+        self.engines.keys().map(String::as_str)
+    }
+
+    fn engine_for(&self, journey: &Journey) -> Result<&GoRulesDecisionEngine, DecisionEngineError> {
+        let journey_type = journey
+            .journey_type()
+            .ok_or_else(|| DecisionEngineError::new("journey has no type to select a model by"))?;
+        self.engines.get(journey_type).ok_or_else(|| {
+            DecisionEngineError::new(format!(
+                "no decision model registered for journey type '{journey_type}'"
+            ))
+        })
+    }
+}
+
+#[async_trait]
+impl DecisionEngine for DirectoryDecisionEngine {
+    async fn evaluate_next_steps(
+        &self,
+        journey: &Journey,
+        current_step: &str,
+        new_data: &Value,
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
+        self.engine_for(journey)?
+            .evaluate_next_steps(journey, current_step, new_data)
+            .await
+    }
+
+    /// Delegates to the selected model's own `evaluate_attributes`.
+    async fn evaluate_attributes(
+        &self,
+        journey: &Journey,
+        pending_changes: &BTreeMap<PointerBuf, Value>,
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
+        self.engine_for(journey)?
+            .evaluate_attributes(journey, pending_changes)
+            .await
+    }
+
+    /// Delegates to the selected model.
+    async fn explain(
+        &self,
+        journey: &Journey,
+        step: &str,
+    ) -> Result<StepExplanation, ExplainError> {
+        self.engine_for(journey)?.explain(journey, step).await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HttpDecisionEngine — rules evaluated by an external service
+//
+// Some teams run their rules engine as a separate service. This engine POSTs
+// the `{ currentStep, capturedData }` context a JDM would receive and reads
+// the response as a JDM's output, `suggestedActions`, `rankedActions` and the
+// rest, checked and ordered as `parse_output` does for a local model.
+// Timeouts, connection failures, 429s and 5xxs are marked transient, so
+// wrapping the engine in a `RetryingDecisionEngine` retries them; a 4xx or an
+// unreadable decision is returned as is.
+// ---------------------------------------------------------------------------
+
+/// Path, relative to its base URL, an [`HttpDecisionEngine`] posts to by
+/// default.
+#[cfg(feature = "http-engine")]
+pub const DEFAULT_HTTP_ENGINE_PATH: &str = "evaluate";
+
+/// How long an [`HttpDecisionEngine`] waits for a decision by default.
+#[cfg(feature = "http-engine")]
+pub const DEFAULT_HTTP_ENGINE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[cfg(feature = "http-engine")]
+pub struct HttpDecisionEngine {
+    base_url: String,
+    path: String,
+    client: reqwest::Client,
+    timeout: Duration,
+}
+
+#[cfg(feature = "http-engine")]
+impl HttpDecisionEngine {
+    /// Post evaluations to [`DEFAULT_HTTP_ENGINE_PATH`] under `base_url`
+    /// with `client`, waiting at most [`DEFAULT_HTTP_ENGINE_TIMEOUT`] for
+    /// each.
+    #[must_use]
+    pub fn new(base_url: impl Into<String>, client: reqwest::Client) -> Self {
+        Self {
+            base_url: base_url.into(),
+            path: DEFAULT_HTTP_ENGINE_PATH.to_string(),
+            client,
+            timeout: DEFAULT_HTTP_ENGINE_TIMEOUT,
+        }
+    }
+
+    /// Post to `path` under the base URL instead.
+    #[must_use]
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Give up on an evaluation after `timeout`, failing it as transient.
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn endpoint(&self) -> String {
+        format!(
+            "{}/{}",
+            self.base_url.trim_end_matches('/'),
+            self.path.trim_start_matches('/')
+        )
+    }
+
+    async fn post(&self, context: &Value) -> Result<WorkflowDecision, DecisionEngineError> {
+        let endpoint = self.endpoint();
+        let unreachable = |err: reqwest::Error| {
+            DecisionEngineError::new(format!("decision service at {endpoint} failed: {err}"))
+                // Only a request that could not be built would fail again.
+                .with_transient(!err.is_builder())
+        };
+
+        let response = self
+            .client
+            .post(&endpoint)
+            .timeout(self.timeout)
+            .json(context)
+            .send()
+            .await
+            .map_err(unreachable)?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(DecisionEngineError::new(format!(
+                "decision service at {endpoint} returned {status}: {body}"
+            ))
+            .with_transient(
+                status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS,
+            ));
+        }
+        let output: Value = response.json().await.map_err(|err| {
+            if err.is_decode() {
+                DecisionEngineError::new(format!(
+                    "decision service at {endpoint} returned an unreadable decision: {err}"
+                ))
+            } else {
+                unreachable(err)
+            }
+        })?;
+        parse_output(&output).map_err(|err| {
+            DecisionEngineError::new(format!(
+                "decision service at {endpoint} returned an unreadable decision: {}",
+                err.message
+            ))
+        })
+    }
+}
+
+#[cfg(feature = "http-engine")]
+#[async_trait]
+impl DecisionEngine for HttpDecisionEngine {
+    async fn evaluate_next_steps(
+        &self,
+        journey: &Journey,
+        current_step: &str,
+        new_data: &Value,
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
+        self.post(&step_context(journey, current_step, new_data))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use cqrs_es::Aggregate;
+    use serde_json::json;
+
+    use super::*;
+    use crate::domain::events::JourneyEvent;
+
+    /// Suggests `actions` on every evaluation.
+    fn fixed(actions: &[&str]) -> Arc<MockDecisionEngine> {
+        Arc::new(MockDecisionEngine::with_actions(vec![
+            actions.iter().map(ToString::to_string).collect(),
+        ]))
+    }
+
+    fn failing() -> Arc<MockDecisionEngine> {
+        Arc::new(MockDecisionEngine::with_fn(|_, _, _| {
+            Err(DecisionEngineError::new("primary unavailable"))
+        }))
+    }
+
+    async fn evaluate(
+        engine: &impl DecisionEngine,
+    ) -> Result<WorkflowDecision, DecisionEngineError> {
+        engine
+            .evaluate_next_steps(&Journey::default(), "search", &json!({}))
+            .await
+    }
+
+    #[tokio::test]
+    async fn mock_engine_follows_its_script_then_repeats_the_last_step() {
+        let engine = MockDecisionEngine::with_actions(vec![vec!["search".to_string()], vec![]]);
+
+        let first = evaluate(&engine).await.unwrap();
+        let second = evaluate(&engine).await.unwrap();
+        let third = evaluate(&engine).await.unwrap();
+
+        assert_eq!(first.suggested_actions, vec!["search"]);
+        assert!(!first.can_complete);
+        assert!(second.suggested_actions.is_empty() && second.can_complete);
+        assert!(third.can_complete);
+        assert_eq!(engine.calls(), 3);
+    }
+
+    #[tokio::test]
+    async fn failing_primary_falls_back_to_next_engine() {
+        let fallback = fixed(&["search"]);
+        let composite = CompositeDecisionEngine::new(vec![
+            failing(),
+            Arc::clone(&fallback) as Arc<dyn DecisionEngine>,
+        ]);
+
+        let decision = evaluate(&composite).await.unwrap();
+
+        assert_eq!(decision.suggested_actions, vec!["search"]);
+        assert_eq!(fallback.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn successful_primary_short_circuits() {
+        let primary = fixed(&["flights"]);
+        let fallback = fixed(&["search"]);
+        let composite = CompositeDecisionEngine::new(vec![
+            Arc::clone(&primary) as Arc<dyn DecisionEngine>,
+            Arc::clone(&fallback) as Arc<dyn DecisionEngine>,
+        ]);
+
+        let decision = evaluate(&composite).await.unwrap();
+
+        assert_eq!(decision.suggested_actions, vec!["flights"]);
+        assert_eq!(fallback.calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn first_success_accepts_an_empty_decision() {
+        let composite = CompositeDecisionEngine::new(vec![
+            fixed(&[]) as Arc<dyn DecisionEngine>,
+            fixed(&["search"]),
+        ]);
+
+        let decision = evaluate(&composite).await.unwrap();
+
+        assert!(decision.suggested_actions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn first_non_empty_skips_empty_decisions() {
+        let composite = CompositeDecisionEngine::new(vec![
+            fixed(&[]) as Arc<dyn DecisionEngine>,
+            failing(),
+            fixed(&["search"]),
+        ])
+        .with_policy(FallbackPolicy::FirstNonEmpty);
+
+        let decision = evaluate(&composite).await.unwrap();
+
+        assert_eq!(decision.suggested_actions, vec!["search"]);
+    }
+
+    #[tokio::test]
+    async fn first_non_empty_returns_empty_decision_when_nothing_better() {
+        let composite =
+            CompositeDecisionEngine::new(vec![failing() as Arc<dyn DecisionEngine>, fixed(&[])])
+                .with_policy(FallbackPolicy::FirstNonEmpty);
+
+        let decision = evaluate(&composite).await.unwrap();
+
+        assert!(decision.suggested_actions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn all_engines_failing_returns_last_error() {
+        let composite =
+            CompositeDecisionEngine::new(vec![failing() as Arc<dyn DecisionEngine>, failing()]);
+
+        let err = evaluate(&composite).await.unwrap_err();
+
+        assert_eq!(err.to_string(), "primary unavailable");
+    }
+
+    #[tokio::test]
+    async fn no_engines_is_an_error() {
+        let composite = CompositeDecisionEngine::new(vec![]);
+
+        assert!(evaluate(&composite).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn evaluate_attributes_falls_back_too() {
+        let composite = CompositeDecisionEngine::new(vec![
+            failing() as Arc<dyn DecisionEngine>,
+            fixed(&["passengers"]),
+        ]);
+
+        let decision = composite
+            .evaluate_attributes(&Journey::default(), &BTreeMap::new())
+            .await
+            .unwrap();
+
+        assert_eq!(decision.suggested_actions, vec!["passengers"]);
+    }
+
+    // ── OverlayDecisionEngine ────────────────────────────────────────────────
+
+    /// The base flight-booking decision after an outbound flight is chosen.
+    fn flight_actions() -> Arc<MockDecisionEngine> {
+        fixed(&["return_flight_selection", "passenger_details"])
+    }
+
+    /// A `GoRules` overlay whose output is `key` set to `value`.
+    fn overlay(key: &str, value: &str) -> Arc<dyn OverlayEngine> {
+        Arc::new(GoRulesDecisionEngine::new(&output_jdm(key, value)))
+    }
+
+    #[tokio::test]
+    async fn overlay_removes_a_denied_action() {
+        let engine = OverlayDecisionEngine::new(
+            flight_actions(),
+            vec![overlay("deny", "['return_flight_selection']")],
+        );
+
+        let decision = evaluate(&engine).await.unwrap();
+
+        assert_eq!(decision.suggested_actions, vec!["passenger_details"]);
+    }
+
+    #[tokio::test]
+    async fn overlay_denies_according_to_the_journey() {
+        // A market where return flights are sold separately.
+        let engine = OverlayDecisionEngine::new(
+            flight_actions(),
+            vec![overlay(
+                "deny",
+                "capturedData.market == 'XX' ? ['return_flight_selection'] : []",
+            )],
+        );
+
+        let elsewhere = engine
+            .evaluate_next_steps(&Journey::default(), "search", &json!({ "market": "GB" }))
+            .await
+            .unwrap();
+        let in_market = engine
+            .evaluate_next_steps(&Journey::default(), "search", &json!({ "market": "XX" }))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            elsewhere.suggested_actions,
+            vec!["return_flight_selection", "passenger_details"]
+        );
+        assert_eq!(in_market.suggested_actions, vec!["passenger_details"]);
+    }
+
+    #[tokio::test]
+    async fn overlay_denies_ranked_actions_too() {
+        let base = MockDecisionEngine::with_fn(|_, _, _| {
+            Ok(WorkflowDecision {
+                suggested_actions: vec![
+                    "return_flight_selection".to_string(),
+                    "passenger_details".to_string(),
+                ],
+                phase: None,
+                ranked_actions: vec![
+                    RankedAction {
+                        action: "return_flight_selection".to_string(),
+                        weight: 0.9,
+                    },
+                    RankedAction {
+                        action: "passenger_details".to_string(),
+                        weight: 0.4,
+                    },
+                ],
+                can_complete: false,
+                completion_blocked_by: vec![],
+            })
+        });
+        let engine = OverlayDecisionEngine::new(
+            Arc::new(base),
+            vec![overlay("deny", "['return_flight_selection']")],
+        );
+
+        let decision = evaluate(&engine).await.unwrap();
+
+        assert_eq!(
+            decision.ranked_actions,
+            vec![RankedAction {
+                action: "passenger_details".to_string(),
+                weight: 0.4,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn overlay_forces_actions_in_and_deny_beats_force() {
+        let engine = OverlayDecisionEngine::new(
+            flight_actions(),
+            vec![
+                overlay("force", "['travel_insurance', 'passenger_details']"),
+                overlay("force", "['return_flight_selection', 'seat_selection']"),
+                overlay("deny", "['return_flight_selection', 'seat_selection']"),
+            ],
+        );
+
+        let decision = evaluate(&engine).await.unwrap();
+
+        assert_eq!(
+            decision.suggested_actions,
+            vec!["passenger_details", "travel_insurance"]
+        );
+    }
+
+    #[tokio::test]
+    async fn a_failing_overlay_fails_the_evaluation() {
+        let engine = OverlayDecisionEngine::new(
+            flight_actions(),
+            vec![overlay("deny", "'return_flight_selection'")],
+        );
+
+        let err = evaluate(&engine).await.unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Malformed decision output: deny is \"return_flight_selection\", expected an array of strings"
+        );
+    }
+
+    #[tokio::test]
+    async fn overlay_corrects_the_explanation_of_a_denied_step() {
+        let engine = OverlayDecisionEngine::new(
+            Arc::new(GoRulesDecisionEngine::new(&actions_jdm(
+                "['return_flight_selection']",
+            ))),
+            vec![overlay("deny", "['return_flight_selection']")],
+        );
+
+        let explanation = engine
+            .explain(&Journey::default(), "return_flight_selection")
+            .await
+            .unwrap();
+
+        assert!(!explanation.available);
+        assert_eq!(
+            explanation.reason,
+            "return_flight_selection unavailable: denied by an overlay"
+        );
+    }
+
+    // ── GoRulesDecisionEngine decision cache ─────────────────────────────────
+
+    /// input → expression (fixed actions) → output
+    const FIXED_JDM: &str = r#"{
+        "contentType": "application/vnd.gorules.decision",
+        "nodes": [
+            { "id": "input", "type": "inputNode", "name": "Input",
+              "position": { "x": 0, "y": 0 } },
+            { "id": "actions", "type": "expressionNode", "name": "Actions",
+              "position": { "x": 100, "y": 0 },
+              "content": { "expressions": [
+                  { "id": "e1", "key": "suggestedActions", "value": "['search']" },
+                  { "id": "e2", "key": "phase", "value": "'search'" }
+              ] } },
+            { "id": "output", "type": "outputNode", "name": "Output",
+              "position": { "x": 200, "y": 0 } }
+        ],
+        "edges": [
+            { "id": "e-input", "type": "edge", "sourceId": "input", "targetId": "actions" },
+            { "id": "e-output", "type": "edge", "sourceId": "actions", "targetId": "output" }
+        ]
+    }"#;
+
+    #[tokio::test]
+    async fn identical_evaluation_is_served_from_cache() {
+        let engine = GoRulesDecisionEngine::new(FIXED_JDM);
+        let journey = Journey::default();
+        let data = json!({ "search": { "origin": "LHR" } });
+
+        let first = engine
+            .evaluate_next_steps(&journey, "search", &data)
+            .await
+            .unwrap();
+        let second = engine
+            .evaluate_next_steps(&journey, "search", &data)
+            .await
+            .unwrap();
+
+        assert_eq!(first.suggested_actions, vec!["search"]);
+        assert_eq!(second.suggested_actions, first.suggested_actions);
+        assert_eq!(second.phase.as_deref(), Some("search"));
+        // Only the first call ran the JDM.
+        assert_eq!(
+            engine.cache_stats(),
+            DecisionCacheStats {
+                hits: 1,
+                misses: 1,
+                len: 1
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn repeated_evaluations_of_the_shared_model_agree() {
+        // No cache, so every call evaluates the same shared decision content.
+        let engine = GoRulesDecisionEngine::new(FLIGHT_BOOKING_JDM).with_cache_capacity(0);
+        let data: BTreeMap<PointerBuf, Value> = BTreeMap::from([
+            ("/search/origin".parse().unwrap(), json!("LHR")),
+            ("/search/destination".parse().unwrap(), json!("JFK")),
+        ]);
+
+        let mut decisions = Vec::new();
+        for _ in 0..3 {
+            let decision = engine
+                .evaluate_attributes(&Journey::default(), &data)
+                .await
+                .unwrap();
+            decisions.push((decision.phase, decision.suggested_actions));
+        }
+
+        assert_eq!(
+            decisions[0],
+            (
+                Some("selecting_outbound".to_string()),
+                vec!["flight_search_results".to_string()]
+            )
+        );
+        assert!(decisions.iter().all(|decision| *decision == decisions[0]));
+        assert_eq!(engine.cache_stats().len, 0);
+    }
+
+    #[tokio::test]
+    async fn different_context_runs_the_engine_again() {
+        let engine = GoRulesDecisionEngine::new(FIXED_JDM);
+        let journey = Journey::default();
+
+        engine
+            .evaluate_next_steps(&journey, "search", &json!({ "origin": "LHR" }))
+            .await
+            .unwrap();
+        engine
+            .evaluate_next_steps(&journey, "search", &json!({ "origin": "CDG" }))
+            .await
+            .unwrap();
+
+        let stats = engine.cache_stats();
+        assert_eq!((stats.hits, stats.misses, stats.len), (0, 2, 2));
+    }
+
+    #[tokio::test]
+    async fn zero_capacity_disables_the_cache() {
+        let engine = GoRulesDecisionEngine::new(FIXED_JDM).with_cache_capacity(0);
+        let journey = Journey::default();
+        let data = json!({ "origin": "LHR" });
+
+        for _ in 0..2 {
+            engine
+                .evaluate_next_steps(&journey, "search", &data)
+                .await
+                .unwrap();
+        }
+
+        let stats = engine.cache_stats();
+        assert_eq!((stats.hits, stats.misses, stats.len), (0, 2, 0));
+    }
+
+    // ── GoRulesDecisionEngine runtimes ───────────────────────────────────────
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn evaluates_concurrently_on_a_multi_threaded_runtime() {
+        let engine = Arc::new(GoRulesDecisionEngine::new(FIXED_JDM).with_cache_capacity(0));
+
+        let evaluations: Vec<_> = (0..16)
+            .map(|_| {
+                let engine = Arc::clone(&engine);
+                tokio::spawn(async move { evaluate(engine.as_ref()).await })
+            })
+            .collect();
+
+        for evaluation in evaluations {
+            let decision = evaluation.await.unwrap().unwrap();
+            assert_eq!(decision.suggested_actions, vec!["search"]);
+        }
+    }
+
+    #[test]
+    fn evaluates_on_a_current_thread_runtime() {
+        // Built outside any runtime, as service start-up code may do.
+        let engine = GoRulesDecisionEngine::new(FIXED_JDM);
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        let decision = runtime.block_on(evaluate(&engine)).unwrap();
+
+        assert_eq!(decision.suggested_actions, vec!["search"]);
+    }
+
+    // ── GoRulesDecisionEngine ranked actions ─────────────────────────────────
+
+    /// input → expression emitting only weighted actions → output
+    const RANKED_JDM: &str = r#"{
+        "contentType": "application/vnd.gorules.decision",
+        "nodes": [
+            { "id": "input", "type": "inputNode", "name": "Input",
+              "position": { "x": 0, "y": 0 } },
+            { "id": "actions", "type": "expressionNode", "name": "Actions",
+              "position": { "x": 100, "y": 0 },
+              "content": { "expressions": [
+                  { "id": "e1", "key": "rankedActions",
+                    "value": "[{ action: 'flights', weight: 0.2 }, { action: 'search', weight: 0.7 }, { action: 'help', weight: 0.2 }]" }
+              ] } },
+            { "id": "output", "type": "outputNode", "name": "Output",
+              "position": { "x": 200, "y": 0 } }
+        ],
+        "edges": [
+            { "id": "e-input", "type": "edge", "sourceId": "input", "targetId": "actions" },
+            { "id": "e-output", "type": "edge", "sourceId": "actions", "targetId": "output" }
+        ]
+    }"#;
+
+    #[tokio::test]
+    async fn ranked_actions_are_sorted_and_derive_suggested_actions() {
+        let engine = GoRulesDecisionEngine::new(RANKED_JDM);
+
+        let decision = engine
+            .evaluate_next_steps(&Journey::default(), "search", &json!({}))
+            .await
+            .unwrap();
+
+        let ranked: Vec<(&str, f64)> = decision
+            .ranked_actions
+            .iter()
+            .map(|r| (r.action.as_str(), r.weight))
+            .collect();
+        // Highest first; the two 0.2 weights keep the model's order.
+        assert_eq!(
+            ranked,
+            vec![("search", 0.7), ("flights", 0.2), ("help", 0.2)]
+        );
+        assert_eq!(
+            decision.suggested_actions,
+            vec!["search", "flights", "help"]
+        );
+    }
+
+    #[tokio::test]
+    async fn models_without_ranking_have_no_ranked_actions() {
+        let engine = GoRulesDecisionEngine::new(FIXED_JDM);
+
+        let decision = engine
+            .evaluate_next_steps(&Journey::default(), "search", &json!({}))
+            .await
+            .unwrap();
+
+        assert!(decision.ranked_actions.is_empty());
+        assert_eq!(decision.suggested_actions, vec!["search"]);
+    }
+
+    // ── GoRulesDecisionEngine completion rules ───────────────────────────────
+
+    /// input → expression naming the steps that block completion → output
+    const BLOCKING_JDM: &str = r#"{
+        "contentType": "application/vnd.gorules.decision",
+        "nodes": [
+            { "id": "input", "type": "inputNode", "name": "Input",
+              "position": { "x": 0, "y": 0 } },
+            { "id": "actions", "type": "expressionNode", "name": "Actions",
+              "position": { "x": 100, "y": 0 },
+              "content": { "expressions": [
+                  { "id": "e1", "key": "suggestedActions", "value": "['payment']" },
+                  { "id": "e2", "key": "completionBlockedBy",
+                    "value": "['payment', 'review', 'payment']" }
+              ] } },
+            { "id": "output", "type": "outputNode", "name": "Output",
+              "position": { "x": 200, "y": 0 } }
+        ],
+        "edges": [
+            { "id": "e-input", "type": "edge", "sourceId": "input", "targetId": "actions" },
+            { "id": "e-output", "type": "edge", "sourceId": "actions", "targetId": "output" }
+        ]
+    }"#;
+
+    #[tokio::test]
+    async fn completion_blocked_by_is_parsed_and_deduplicated() {
+        let engine = GoRulesDecisionEngine::new(BLOCKING_JDM);
+
+        let decision = engine
+            .evaluate_next_steps(&Journey::default(), "search", &json!({}))
+            .await
+            .unwrap();
+
+        assert_eq!(decision.completion_blocked_by, vec!["payment", "review"]);
+    }
+
+    #[tokio::test]
+    async fn models_without_completion_rules_block_nothing() {
+        let engine = GoRulesDecisionEngine::new(FIXED_JDM);
+
+        let decision = engine
+            .evaluate_next_steps(&Journey::default(), "search", &json!({}))
+            .await
+            .unwrap();
+
+        assert!(decision.completion_blocked_by.is_empty());
+    }
+
+    // ── GoRulesDecisionEngine action clean-up ────────────────────────────────
+
+    /// input → expression emitting repeated actions → output
+    const DUPLICATE_ACTIONS_JDM: &str = r#"{
+        "contentType": "application/vnd.gorules.decision",
+        "nodes": [
+            { "id": "input", "type": "inputNode", "name": "Input",
+              "position": { "x": 0, "y": 0 } },
+            { "id": "actions", "type": "expressionNode", "name": "Actions",
+              "position": { "x": 100, "y": 0 },
+              "content": { "expressions": [
+                  { "id": "e1", "key": "suggestedActions",
+                    "value": "['seats', 'extras', 'seats', 'baggage', 'extras']" },
+                  { "id": "e2", "key": "rankedActions",
+                    "value": "[{ action: 'extras', weight: 0.9 }, { action: 'seats', weight: 0.5 }, { action: 'extras', weight: 0.1 }]" }
+              ] } },
+            { "id": "output", "type": "outputNode", "name": "Output",
+              "position": { "x": 200, "y": 0 } }
+        ],
+        "edges": [
+            { "id": "e-input", "type": "edge", "sourceId": "input", "targetId": "actions" },
+            { "id": "e-output", "type": "edge", "sourceId": "actions", "targetId": "output" }
+        ]
+    }"#;
+
+    #[tokio::test]
+    async fn duplicate_actions_are_dropped_keeping_first_seen_order() {
+        let engine = GoRulesDecisionEngine::new(DUPLICATE_ACTIONS_JDM);
+
+        let decision = engine
+            .evaluate_next_steps(&Journey::default(), "search", &json!({}))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            decision.suggested_actions,
+            vec!["seats", "extras", "baggage"]
+        );
+        let ranked: Vec<(&str, f64)> = decision
+            .ranked_actions
+            .iter()
+            .map(|r| (r.action.as_str(), r.weight))
+            .collect();
+        assert_eq!(ranked, vec![("extras", 0.9), ("seats", 0.5)]);
+    }
+
+    #[tokio::test]
+    async fn sorted_actions_keep_the_top_ranked_action_first() {
+        let engine = GoRulesDecisionEngine::new(DUPLICATE_ACTIONS_JDM).with_sorted_actions(true);
+
+        let decision = engine
+            .evaluate_next_steps(&Journey::default(), "search", &json!({}))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            decision.suggested_actions,
+            vec!["extras", "baggage", "seats"]
+        );
+    }
+
+    // ── TableDecisionEngine ──────────────────────────────────────────────────
+
+    /// search → passengers → payment.
+    const BOOKING_TABLE: &str = r#"{
+        "search": {
+            "requires": ["search.origin", "search.destination"],
+            "next": ["passengers"]
+        },
+        "passengers": {
+            "requires": ["passengers[0].name"],
+            "next": ["payment"]
+        },
+        "payment": { "requires": ["payment.cardToken"] }
+    }"#;
+
+    fn booking_table() -> TableDecisionEngine {
+        TableDecisionEngine::from_json(BOOKING_TABLE).unwrap()
+    }
+
+    #[tokio::test]
+    async fn table_holds_the_journey_until_required_fields_are_present() {
+        let journey = journey_with(&json!({ "search": { "origin": "LHR" } }));
+
+        let decision = booking_table()
+            .evaluate_next_steps(&journey, "search", &json!({}))
+            .await
+            .unwrap();
+
+        assert!(decision.suggested_actions.is_empty());
+        assert_eq!(decision.phase.as_deref(), Some("search"));
+        assert!(!decision.can_complete);
+    }
+
+    #[tokio::test]
+    async fn table_offers_the_next_step_once_new_data_fills_the_gap() {
+        let journey = journey_with(&json!({ "search": { "origin": "LHR" } }));
+
+        let decision = booking_table()
+            .evaluate_next_steps(
+                &journey,
+                "search",
+                &json!({ "search": { "destination": "JFK" } }),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(decision.suggested_actions, vec!["passengers"]);
+    }
+
+    #[tokio::test]
+    async fn table_treats_null_fields_as_missing() {
+        let journey = journey_with(&json!({ "passengers": [{ "name": null }] }));
+
+        let decision = booking_table()
+            .evaluate_next_steps(&journey, "passengers", &json!({}))
+            .await
+            .unwrap();
+
+        assert!(decision.suggested_actions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn table_completes_once_the_final_step_is_filled() {
+        let table = booking_table();
+        let journey = journey_with(&json!({ "payment": {} }));
+
+        let pending = table
+            .evaluate_next_steps(&journey, "payment", &json!({}))
+            .await
+            .unwrap();
+        let done = table
+            .evaluate_next_steps(
+                &journey,
+                "payment",
+                &json!({ "payment": { "cardToken": "tok_1" } }),
+            )
+            .await
+            .unwrap();
+
+        assert!(!pending.can_complete);
+        assert!(done.can_complete);
+        assert!(done.suggested_actions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn table_offers_entry_steps_without_a_current_step() {
+        let decision = booking_table()
+            .evaluate_next_steps(&Journey::default(), "", &json!({}))
+            .await
+            .unwrap();
+
+        assert_eq!(decision.suggested_actions, vec!["search"]);
+        assert_eq!(decision.phase, None);
+    }
+
+    #[tokio::test]
+    async fn table_reads_the_latest_phase_after_set_attributes() {
+        let mut journey = journey_with(&json!({ "search": { "origin": "LHR" } }));
+        journey.apply(JourneyEvent::WorkflowEvaluated {
+            suggested_actions: vec![],
+            phase: Some("search".to_string()),
+            ranked_actions: vec![],
+            completion_blocked_by: vec![],
+        });
+        let changes = BTreeMap::from([(
+            "/search/destination".parse::<PointerBuf>().unwrap(),
+            json!("JFK"),
+        )]);
+
+        let decision = booking_table()
+            .evaluate_attributes(&journey, &changes)
+            .await
+            .unwrap();
+
+        assert_eq!(decision.suggested_actions, vec!["passengers"]);
+    }
+
+    #[tokio::test]
+    #[allow(deprecated)]
+    async fn table_reads_the_journeys_step_before_the_latest_phase() {
+        let mut journey = journey_with(&json!({
+            "search": { "origin": "LHR", "destination": "JFK" },
+            "passengers": [{ "name": "Ada" }]
+        }));
+        journey.apply(JourneyEvent::WorkflowEvaluated {
+            suggested_actions: vec![],
+            phase: None,
+            ranked_actions: vec![],
+            completion_blocked_by: vec![],
+        });
+        journey.apply(JourneyEvent::StepProgressed {
+            from_step: None,
+            to_step: "passengers".to_string(),
+        });
+
+        let decision = booking_table()
+            .evaluate_next_steps(&journey, "", &json!({}))
+            .await
+            .unwrap();
+
+        assert_eq!(decision.phase.as_deref(), Some("passengers"));
+        assert_eq!(decision.suggested_actions, vec!["payment"]);
+    }
+
+    #[tokio::test]
+    async fn table_rejects_a_step_it_does_not_know() {
+        let err = booking_table()
+            .evaluate_next_steps(&Journey::default(), "checkout", &json!({}))
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            err.message,
+            "step 'checkout' is not in the transition table"
+        );
+    }
+
+    // ── DirectoryDecisionEngine ──────────────────────────────────────────────
+
+    fn typed_journey(journey_type: Option<&str>) -> Journey {
+        let mut journey = Journey::default();
+        journey.apply(JourneyEvent::Started {
+            id: uuid::Uuid::new_v4(),
+            journey_type: journey_type.map(str::to_string),
+        });
+        journey
+    }
+
+    /// A directory holding `flights.jdm.json` (suggests `search`),
+    /// `insurance.jdm.json` (suggests `quote`) and an unrelated file.
+    fn model_directory() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("jdm-models-{}", uuid::Uuid::new_v4()));
+        fs::create_dir(&dir).unwrap();
+        fs::write(dir.join("flights.jdm.json"), FIXED_JDM).unwrap();
+        fs::write(
+            dir.join("insurance.jdm.json"),
+            FIXED_JDM.replace("['search']", "['quote']"),
+        )
+        .unwrap();
+        fs::write(dir.join("README.md"), "not a model").unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn directory_engine_routes_by_journey_type() {
+        let dir = model_directory();
+        let engine = DirectoryDecisionEngine::new(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            engine.journey_types().collect::<Vec<_>>(),
+            vec!["flights", "insurance"]
+        );
+        for (journey_type, expected) in [("flights", "search"), ("insurance", "quote")] {
+            let decision = engine
+                .evaluate_next_steps(&typed_journey(Some(journey_type)), "", &json!({}))
+                .await
+                .unwrap();
+            assert_eq!(decision.suggested_actions, vec![expected], "{journey_type}");
+        }
+    }
+
+    #[tokio::test]
+    async fn directory_engine_rejects_unregistered_and_untyped_journeys() {
+        let dir = model_directory();
+        let engine = DirectoryDecisionEngine::new(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let err = engine
+            .evaluate_next_steps(&typed_journey(Some("onboarding")), "", &json!({}))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.message,
+            "no decision model registered for journey type 'onboarding'"
+        );
+
+        let err = engine
+            .evaluate_attributes(&typed_journey(None), &BTreeMap::new())
+            .await
+            .unwrap_err();
+        assert_eq!(err.message, "journey has no type to select a model by");
+    }
+
+    #[test]
+    fn directory_engine_rejects_a_malformed_model() {
+        let dir = model_directory();
+        fs::write(dir.join("broken.jdm.json"), "{ not json").unwrap();
+        let err = DirectoryDecisionEngine::new(&dir).err().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(
+            err.to_string().contains("broken.jdm.json"),
+            "error should name the file: {err}"
+        );
+    }
+
+    // ── GoRulesDecisionEngine errors ─────────────────────────────────────────
+
+    /// input → expression with an unparseable expression → output
+    const BROKEN_JDM: &str = r#"{
+        "contentType": "application/vnd.gorules.decision",
+        "nodes": [
+            { "id": "input", "type": "inputNode", "name": "Input",
+              "position": { "x": 0, "y": 0 } },
+            { "id": "route", "type": "expressionNode", "name": "Route",
+              "position": { "x": 100, "y": 0 },
+              "content": { "expressions": [
+                  { "id": "e1", "key": "suggestedActions", "value": "capturedData.search.(" }
+              ] } },
+            { "id": "output", "type": "outputNode", "name": "Output",
+              "position": { "x": 200, "y": 0 } }
+        ],
+        "edges": [
+            { "id": "e-input", "type": "edge", "sourceId": "input", "targetId": "route" },
+            { "id": "e-output", "type": "edge", "sourceId": "route", "targetId": "output" }
+        ]
+    }"#;
+
+    #[tokio::test]
+    async fn malformed_model_reports_the_failing_node() {
+        let engine = GoRulesDecisionEngine::new(BROKEN_JDM);
+
+        let err = engine
+            .evaluate_next_steps(&Journey::default(), "search", &json!({ "origin": "LHR" }))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.node_id.as_deref(), Some("route"));
+        assert!(err.to_string().contains("(node 'route')"), "{err}");
+        // Context capture is off by default.
+        assert_eq!(err.context, None);
+    }
+
+    /// input → output, so the model suggests nothing.
+    const EMPTY_OUTPUT_JDM: &str = r#"{
+        "contentType": "application/vnd.gorules.decision",
+        "nodes": [
+            { "id": "input", "type": "inputNode", "name": "Input",
+              "position": { "x": 0, "y": 0 } },
+            { "id": "output", "type": "outputNode", "name": "Output",
+              "position": { "x": 100, "y": 0 } }
+        ],
+        "edges": [
+            { "id": "e-output", "type": "edge", "sourceId": "input", "targetId": "output" }
+        ]
+    }"#;
+
+    #[tokio::test]
+    async fn empty_output_is_an_empty_decision() {
+        let engine = GoRulesDecisionEngine::new(EMPTY_OUTPUT_JDM);
+
+        let decision = engine
+            .evaluate_next_steps(&Journey::default(), "payment", &json!({}))
+            .await
+            .unwrap();
+
+        assert!(decision.suggested_actions.is_empty());
+        assert!(decision.ranked_actions.is_empty());
+        assert_eq!(decision.phase, None);
+    }
+
+    #[tokio::test]
+    async fn strict_output_rejects_an_empty_output() {
+        let engine = GoRulesDecisionEngine::new(EMPTY_OUTPUT_JDM).with_strict_output(true);
+
+        let err = engine
+            .evaluate_next_steps(&Journey::default(), "payment", &json!({}))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.message, "No available actions");
+        assert_eq!(err.node_id, None);
+    }
+
+    #[tokio::test]
+    async fn evaluation_errors_are_reported_in_either_mode() {
+        for strict in [false, true] {
+            let engine = GoRulesDecisionEngine::new(BROKEN_JDM).with_strict_output(strict);
+
+            let err = engine
+                .evaluate_next_steps(&Journey::default(), "search", &json!({}))
+                .await
+                .unwrap_err();
+
+            assert_eq!(err.node_id.as_deref(), Some("route"), "strict: {strict}");
+        }
+    }
+
+    #[tokio::test]
+    async fn error_context_includes_the_evaluated_context() {
+        let engine = GoRulesDecisionEngine::new(BROKEN_JDM).with_error_context(true);
+
+        let err = engine
+            .evaluate_next_steps(&Journey::default(), "search", &json!({ "origin": "LHR" }))
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            err.context,
+            Some(json!({
+                "currentStep": "search",
+                "capturedData": { "origin": "LHR" }
+            }))
+        );
+        assert!(err.to_string().contains(r#""origin":"LHR""#), "{err}");
+    }
+
+    // ── GoRulesDecisionEngine output checks ──────────────────────────────────
+
+    /// A model whose expression node emits `suggestedActions` as `actions`.
+    fn actions_jdm(actions: &str) -> String {
+        output_jdm("suggestedActions", actions)
+    }
+
+    /// A model whose expression node emits one output, `key`, as `value`.
+    fn output_jdm(key: &str, value: &str) -> String {
+        let model = json!({
+            "contentType": "application/vnd.gorules.decision",
+            "nodes": [
+                { "id": "input", "type": "inputNode", "name": "Input",
+                  "position": { "x": 0, "y": 0 } },
+                { "id": "actions", "type": "expressionNode", "name": "Actions",
+                  "position": { "x": 100, "y": 0 },
+                  "content": { "expressions": [
+                      { "id": "e1", "key": key, "value": value }
+                  ] } },
+                { "id": "output", "type": "outputNode", "name": "Output",
+                  "position": { "x": 200, "y": 0 } }
+            ],
+            "edges": [
+                { "id": "e-input", "type": "edge", "sourceId": "input", "targetId": "actions" },
+                { "id": "e-output", "type": "edge", "sourceId": "actions", "targetId": "output" }
+            ]
+        });
+        model.to_string()
+    }
+
+    #[tokio::test]
+    async fn a_numeric_action_is_an_error_not_a_panic() {
+        let engine = GoRulesDecisionEngine::new(&actions_jdm("['search', 3]"));
+
+        let err = engine
+            .evaluate_next_steps(&Journey::default(), "search", &json!({}))
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            err.message,
+            "Malformed decision output: suggestedActions[1] is 3, expected a string"
+        );
+        assert!(!err.transient);
+    }
+
+    #[test]
+    fn malformed_output_fields_are_named() {
+        for (output, message) in [
+            (
+                json!({ "suggestedActions": "search" }),
+                r#"suggestedActions is "search", expected an array of strings"#,
+            ),
+            (
+                json!({ "rankedActions": [{ "action": "search" }] }),
+                r#"rankedActions[0] is {"action":"search"}, expected an object with a string action and a numeric weight"#,
+            ),
+            (json!({ "phase": 1 }), "phase is 1, expected a string"),
+            (
+                json!({ "canComplete": "yes" }),
+                r#"canComplete is "yes", expected a boolean"#,
+            ),
+            (
+                json!(["search"]),
+                r#"the output is ["search"], expected an object"#,
+            ),
+        ] {
+            let err = parse_output(&output).unwrap_err();
+            assert_eq!(err.message, format!("Malformed decision output: {message}"));
+        }
+        // Missing and null fields are defaults, not errors.
+        let decision = parse_output(&json!({ "phase": null, "suggestedActions": null })).unwrap();
+        assert!(decision.suggested_actions.is_empty());
+        assert_eq!(decision.phase, None);
+    }
+
+    #[test]
+    fn check_outputs_accepts_readable_models() {
+        GoRulesDecisionEngine::new(FLIGHT_BOOKING_JDM)
+            .check_outputs()
+            .unwrap();
+        GoRulesDecisionEngine::new(RANKED_JDM)
+            .check_outputs()
+            .unwrap();
+        // Computed outputs can only be checked when evaluated.
+        GoRulesDecisionEngine::new(&actions_jdm("[currentStep]"))
+            .check_outputs()
+            .unwrap();
+    }
+
+    #[test]
+    fn check_outputs_rejects_an_unreadable_literal() {
+        let err = GoRulesDecisionEngine::new(&actions_jdm("['search', 3]"))
+            .check_outputs()
+            .unwrap_err();
+
+        assert_eq!(err.node_id.as_deref(), Some("actions"));
+        assert!(err.message.contains("suggestedActions[1] is 3"), "{err}");
+    }
+
+    #[test]
+    fn check_outputs_rejects_a_model_without_actions() {
+        let err = GoRulesDecisionEngine::new(EMPTY_OUTPUT_JDM)
+            .check_outputs()
+            .unwrap_err();
+
+        assert_eq!(
+            err.message,
+            "Decision model declares neither a suggestedActions nor a rankedActions output"
+        );
+    }
+
+    #[tokio::test]
+    async fn self_check_accepts_a_model_that_suggests_actions() {
+        GoRulesDecisionEngine::new(&actions_jdm("['search']"))
+            .self_check(&json!({}))
+            .await
+            .unwrap();
+        // Suggesting nothing for the sample is still a readable decision.
+        GoRulesDecisionEngine::new(&actions_jdm("[]"))
+            .self_check(&json!({}))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn self_check_accepts_the_flight_booking_model_for_an_empty_journey() {
+        GoRulesDecisionEngine::new(FLIGHT_BOOKING_JDM)
+            .self_check(&json!({}))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn self_check_rejects_a_model_whose_output_lacks_actions() {
+        let err = GoRulesDecisionEngine::new(&output_jdm("phase", "'search'"))
+            .self_check(&json!({}))
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            err.message,
+            r#"Decision model output has neither suggestedActions nor rankedActions: {"phase":"search"}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn self_check_rejects_a_malformed_output() {
+        let err = GoRulesDecisionEngine::new(&actions_jdm("['search', 3]"))
+            .self_check(&json!({}))
+            .await
+            .unwrap_err();
+
+        assert!(err.message.contains("suggestedActions[1] is 3"), "{err}");
+    }
+
+    // ── GoRulesDecisionEngine::evaluate_with_trace ───────────────────────────
+
+    #[tokio::test]
+    async fn evaluate_with_trace_lists_the_flight_booking_nodes_in_order() {
+        let engine = GoRulesDecisionEngine::new(FLIGHT_BOOKING_JDM);
+
+        let (decision, trace) = engine
+            .evaluate_with_trace(
+                &Journey::default(),
+                "search",
+                &json!({ "search": { "tripType": "round-trip" } }),
+            )
+            .await
+            .unwrap();
+
+        let node_ids: Vec<&str> = trace.iter().map(|node| node.node_id.as_str()).collect();
+        assert_eq!(
+            node_ids,
+            vec![
+                "input",
+                "derive-person-list",
+                "derive-passenger-counts",
+                "derive-flags",
+                "phase-table",
+                "output",
+            ]
+        );
+        let phase_table = &trace[4];
+        assert_eq!(phase_table.name, "Phase and Actions");
+        assert_eq!(
+            phase_table.output["suggestedActions"],
+            json!(decision.suggested_actions)
+        );
+        // Each node sees what the one before it produced.
+        assert_eq!(trace[2].input, trace[1].output);
+        assert!(trace.iter().all(|node| node.duration.is_some()));
+    }
+
+    #[tokio::test]
+    async fn evaluate_with_trace_decides_as_evaluate_next_steps_does() {
+        let engine = GoRulesDecisionEngine::new(FLIGHT_BOOKING_JDM);
+        let data = json!({ "search": { "tripType": "one-way" } });
+
+        let (traced, _) = engine
+            .evaluate_with_trace(&Journey::default(), "search", &data)
+            .await
+            .unwrap();
+        let decision = engine
+            .evaluate_next_steps(&Journey::default(), "search", &data)
+            .await
+            .unwrap();
+
+        assert_eq!(traced.suggested_actions, decision.suggested_actions);
+        assert_eq!(traced.phase, decision.phase);
+    }
+
+    #[tokio::test]
+    async fn step_context_merges_a_grouped_payload_into_the_accumulated_data() {
+        let engine = GoRulesDecisionEngine::new(FLIGHT_BOOKING_JDM);
+        let journey = journey_with(&json!({
+            "search": { "tripType": "round-trip", "origin": "LHR" },
+            "booking": { "selectedOutboundFlight": "BA117" },
+        }));
+
+        let (_, trace) = engine
+            .evaluate_with_trace(
+                &journey,
+                "search",
+                &json!({ "search": { "origin": "CDG", "destination": "JFK" } }),
+            )
+            .await
+            .unwrap();
+
+        // Groups are merged key by key, not replaced, and stay nested.
+        assert_eq!(
+            trace[0].output,
+            json!({
+                "currentStep": "search",
+                "capturedData": {
+                    "search": { "tripType": "round-trip", "origin": "CDG", "destination": "JFK" },
+                    "booking": { "selectedOutboundFlight": "BA117" },
+                },
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn step_context_reads_the_accumulated_data_without_new_data() {
+        let engine = GoRulesDecisionEngine::new(FLIGHT_BOOKING_JDM);
+        let attributes = json!({ "search": { "tripType": "one-way" } });
+
+        let (_, trace) = engine
+            .evaluate_with_trace(&journey_with(&attributes), "passengers", &json!({}))
+            .await
+            .unwrap();
+
+        assert_eq!(trace[0].output["currentStep"], "passengers");
+        assert_eq!(trace[0].output["capturedData"], attributes);
+    }
+
+    #[test]
+    fn parse_duration_reads_zen_performance_strings() {
+        assert_eq!(parse_duration("850.0ns"), Some(Duration::from_nanos(850)));
+        assert_eq!(parse_duration("12.5µs"), Some(Duration::from_nanos(12_500)));
+        assert_eq!(parse_duration("1.5ms"), Some(Duration::from_micros(1_500)));
+        assert_eq!(parse_duration("2.0s"), Some(Duration::from_secs(2)));
+        assert_eq!(parse_duration("soon"), None);
+    }
+
+    // ── GoRulesDecisionEngine::list_steps ────────────────────────────────────
+
+    const FLIGHT_BOOKING_JDM: &str = include_str!(
+        "../../../../examples/flight-booking/jdm-models/flight-booking-orchestrator.jdm.json"
+    );
+
+    #[test]
+    fn list_steps_reads_the_flight_booking_phase_table() {
+        let steps = GoRulesDecisionEngine::new(FLIGHT_BOOKING_JDM).list_steps();
+
+        let ids: Vec<&str> = steps.iter().map(|step| step.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "booking_confirmed",
+                "collecting_search",
+                "selecting_outbound",
+                "selecting_return",
+                "collecting_passengers",
+                "collecting_payment",
+            ]
+        );
+
+        let search = &steps[1];
+        assert!(search.suggested_actions.is_empty());
+        let passengers = steps.iter().find(|s| s.id == "collecting_passengers");
+        assert_eq!(
+            passengers.unwrap().suggested_actions,
+            vec!["passenger_details"]
+        );
+    }
+
+    // ── GoRulesDecisionEngine loading ────────────────────────────────────────
+
+    #[test]
+    fn loads_the_flight_booking_model_from_bytes() {
+        let bytes = include_bytes!(
+            "../../../../examples/flight-booking/jdm-models/flight-booking-orchestrator.jdm.json"
+        );
+
+        let engine = GoRulesDecisionEngine::from_slice(bytes).unwrap();
+
+        assert_eq!(
+            engine.list_steps(),
+            GoRulesDecisionEngine::new(FLIGHT_BOOKING_JDM).list_steps()
+        );
+    }
+
+    #[tokio::test]
+    async fn loads_the_flight_booking_model_from_a_reader() {
+        let reader = io::Cursor::new(FLIGHT_BOOKING_JDM);
+
+        let engine = GoRulesDecisionEngine::from_reader(reader).unwrap();
+        let decision = engine
+            .evaluate_attributes(&Journey::default(), &BTreeMap::new())
+            .await
+            .unwrap();
+
+        assert_eq!(decision.phase.as_deref(), Some("collecting_search"));
+    }
+
+    #[test]
+    fn loading_an_invalid_model_fails() {
+        let err = GoRulesDecisionEngine::from_slice(b"{\"nodes\": 42}").err();
+        assert!(err.is_some_and(|err| err.is_data()));
+
+        let err = GoRulesDecisionEngine::from_reader(io::Cursor::new("{")).err();
+        assert!(err.is_some_and(|err| err.is_eof()));
+    }
+
+    // ── explain ──────────────────────────────────────────────────────────────
+
+    fn journey_with(attributes: &Value) -> Journey {
+        let mut journey = typed_journey(None);
+        journey.apply(JourneyEvent::AttributesSet {
+            plaintext: BTreeMap::from([(PointerBuf::root(), attributes.clone())]),
+            secret_partitions: vec![],
+        });
+        journey
+    }
+
+    #[tokio::test]
+    async fn explain_reports_a_blocked_return_flight_on_a_one_way_trip() {
+        let engine = GoRulesDecisionEngine::new(FLIGHT_BOOKING_JDM);
+        let journey = journey_with(&json!({
+            "search": {"tripType": "one-way", "origin": "LHR", "destination": "JFK"},
+            "booking": {"selectedOutboundFlight": "BA117"},
+        }));
+
+        let explanation = engine
+            .explain(&journey, "return_flight_selection")
+            .await
+            .unwrap();
+
+        assert!(!explanation.available);
+        assert_eq!(
+            explanation.reason,
+            "return_flight_selection unavailable: computed.isRoundTrip is false, needs true"
+        );
+        let [rule] = explanation.rules.as_slice() else {
+            panic!("expected one rule, got {:?}", explanation.rules);
+        };
+        assert_eq!(rule.rule_id.as_deref(), Some("r4-no-return"));
+        let unsatisfied: Vec<&str> = rule.unsatisfied.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(unsatisfied, vec!["computed.isRoundTrip"]);
+        let satisfied: Vec<&str> = rule.satisfied.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(
+            satisfied,
+            vec![
+                "computed.hasSearch",
+                "computed.hasOutbound",
+                "computed.hasReturn"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn explain_reports_an_available_step() {
+        let engine = GoRulesDecisionEngine::new(FLIGHT_BOOKING_JDM);
+        let journey = journey_with(&json!({
+            "search": {"tripType": "round-trip", "origin": "LHR", "destination": "JFK"},
+            "booking": {"selectedOutboundFlight": "BA117"},
+        }));
+
+        let explanation = engine
+            .explain(&journey, "return_flight_selection")
+            .await
+            .unwrap();
+
+        assert!(explanation.available);
+        assert!(explanation.rules[0].unsatisfied.is_empty());
+    }
+
+    #[tokio::test]
+    async fn engines_without_explanations_are_unsupported() {
+        let engine = CompositeDecisionEngine::new(vec![fixed(&["search"])]);
+
+        assert_eq!(
+            engine.explain(&typed_journey(None), "search").await,
+            Err(ExplainError::Unsupported)
+        );
+    }
+
+    #[test]
+    fn list_steps_ignores_models_without_a_phase_table() {
+        assert!(
+            GoRulesDecisionEngine::new(FIXED_JDM)
+                .list_steps()
+                .is_empty()
+        );
+    }
+
+    // ── RetryingDecisionEngine ───────────────────────────────────────────────
+
+    /// Fails with `error` for the first `failures` calls, then decides `search`.
+    struct FlakyEngine {
+        failures: usize,
+        error: DecisionEngineError,
+        calls: AtomicUsize,
+    }
+
+    impl FlakyEngine {
+        fn new(failures: usize, error: DecisionEngineError) -> Arc<Self> {
+            Arc::new(Self {
+                failures,
+                error,
+                calls: AtomicUsize::new(0),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl DecisionEngine for FlakyEngine {
+        async fn evaluate_next_steps(
+            &self,
+            _journey: &Journey,
+            _current_step: &str,
+            _new_data: &Value,
+        ) -> Result<WorkflowDecision, DecisionEngineError> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                return Err(self.error.clone());
+            }
+            Ok(WorkflowDecision {
+                suggested_actions: vec!["search".to_string()],
+                phase: None,
+                ranked_actions: Vec::new(),
+                can_complete: false,
+                completion_blocked_by: Vec::new(),
+            })
+        }
+    }
+
+    fn retrying(inner: &Arc<FlakyEngine>) -> RetryingDecisionEngine {
+        RetryingDecisionEngine::new(Arc::clone(inner) as Arc<dyn DecisionEngine>)
+            .with_base_delay(Duration::from_millis(1))
+    }
+
+    fn worker_died() -> DecisionEngineError {
+        DecisionEngineError::new("worker died").with_transient(true)
+    }
+
+    #[tokio::test]
+    async fn transient_failures_are_retried_until_success() {
+        let flaky = FlakyEngine::new(2, worker_died());
+
+        let decision = retrying(&flaky)
+            .evaluate_next_steps(&Journey::default(), "search", &json!({}))
+            .await
+            .unwrap();
+
+        assert_eq!(decision.suggested_actions, vec!["search"]);
+        assert_eq!(flaky.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn retries_give_up_after_max_attempts() {
+        let flaky = FlakyEngine::new(5, worker_died());
+
+        let err = retrying(&flaky)
+            .with_max_attempts(2)
+            .evaluate_next_steps(&Journey::default(), "search", &json!({}))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err, worker_died());
+        assert_eq!(flaky.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn model_errors_are_not_retried() {
+        let flaky = FlakyEngine::new(1, DecisionEngineError::new("bad expression"));
+
+        let err = retrying(&flaky)
+            .evaluate_next_steps(&Journey::default(), "search", &json!({}))
+            .await
+            .unwrap_err();
+
+        assert!(!err.transient);
+        assert_eq!(flaky.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn retrying_engine_composes_with_fallback() {
+        let flaky = FlakyEngine::new(1, worker_died());
+        let fallback = fixed(&["fallback"]);
+        let composite = CompositeDecisionEngine::new(vec![
+            Arc::new(retrying(&flaky)),
+            Arc::clone(&fallback) as Arc<dyn DecisionEngine>,
+        ]);
+
+        let decision = evaluate(&composite).await.unwrap();
+
+        assert_eq!(decision.suggested_actions, vec!["search"]);
+        assert_eq!(fallback.calls(), 0);
+    }
+
+    // ── InstrumentedDecisionEngine ───────────────────────────────────────────
+
+    #[tokio::test]
+    async fn instrumented_engine_returns_the_inner_decision() {
+        let inner = fixed(&["search", "passengers"]);
+        let instrumented =
+            InstrumentedDecisionEngine::new(Arc::clone(&inner) as Arc<dyn DecisionEngine>);
+
+        let decision = instrumented
+            .evaluate_next_steps(&Journey::default(), "search", &json!({}))
+            .await
+            .unwrap();
+
+        assert_eq!(decision.suggested_actions, vec!["search", "passengers"]);
+        assert!(!decision.can_complete);
+        assert_eq!(inner.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn instrumented_engine_returns_the_inner_error() {
+        let flaky = FlakyEngine::new(1, worker_died());
+        let instrumented = InstrumentedDecisionEngine::new(flaky);
+
+        let err = evaluate(&instrumented).await.unwrap_err();
+
+        assert_eq!(err, worker_died());
+    }
+
+    #[tokio::test]
+    async fn instrumented_engine_composes_with_retry() {
+        let flaky = FlakyEngine::new(1, worker_died());
+        let instrumented = InstrumentedDecisionEngine::new(Arc::new(retrying(&flaky)));
+
+        let decision = evaluate(&instrumented).await.unwrap();
+
+        assert_eq!(decision.suggested_actions, vec!["search"]);
+        assert_eq!(flaky.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[cfg(feature = "metrics")]
+    #[tokio::test]
+    async fn instrumented_engine_records_a_timing_per_step() {
+        use metrics_util::{
+            CompositeKey, MetricKind,
+            debugging::{DebugValue, DebuggingRecorder},
+        };
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        // The test runtime is single-threaded, so the evaluation below
+        // records on this thread.
+        let _guard = metrics::set_default_local_recorder(&recorder);
+        let instrumented = InstrumentedDecisionEngine::new(fixed(&["passengers"]));
+
+        instrumented
+            .evaluate_next_steps(&Journey::default(), "search", &json!({}))
+            .await
+            .unwrap();
+
+        let key = CompositeKey::new(
+            MetricKind::Histogram,
+            metrics::Key::from_parts(
+                DECISION_EVALUATION_SECONDS,
+                vec![metrics::Label::new("step", "search")],
+            ),
+        );
+        let timings = snapshotter.snapshot().into_hashmap();
+        let Some((_, _, DebugValue::Histogram(values))) = timings.get(&key) else {
+            panic!("no {DECISION_EVALUATION_SECONDS} histogram for step search: {timings:?}");
+        };
+        assert_eq!(values.len(), 1);
+        assert!(values[0].into_inner() >= 0.0);
+    }
+
+    #[tokio::test]
+    async fn go_rules_model_errors_are_not_transient() {
+        let err = GoRulesDecisionEngine::new(BROKEN_JDM)
+            .evaluate_next_steps(&Journey::default(), "search", &json!({}))
+            .await
+            .unwrap_err();
+
+        assert!(!err.transient);
+    }
+
+    // ── CachingDecisionEngine ────────────────────────────────────────────────
+
+    fn caching(inner: &Arc<MockDecisionEngine>) -> CachingDecisionEngine {
+        CachingDecisionEngine::new(Arc::clone(inner) as Arc<dyn DecisionEngine>)
+    }
+
+    fn captured(journey_type: &str, attributes: &Value) -> Journey {
+        let mut journey = typed_journey(Some(journey_type));
+        journey.apply(JourneyEvent::AttributesSet {
+            plaintext: BTreeMap::from([(PointerBuf::root(), attributes.clone())]),
+            secret_partitions: vec![],
+        });
+        journey
+    }
+
+    #[tokio::test]
+    async fn journeys_with_identical_captures_share_a_cached_decision() {
+        let inner = fixed(&["passengers"]);
+        let engine = caching(&inner);
+        let data = json!({ "search": { "origin": "LHR", "destination": "JFK" } });
+        let first = captured("flights", &data);
+        let second = captured("flights", &data);
+
+        let decided = engine
+            .evaluate_next_steps(&first, "search", &json!({}))
+            .await
+            .unwrap();
+        let shared = engine
+            .evaluate_next_steps(&second, "search", &json!({}))
+            .await
+            .unwrap();
+
+        assert_eq!(shared.suggested_actions, decided.suggested_actions);
+        assert_eq!(inner.calls(), 1);
+        assert_eq!(
+            engine.cache_stats(),
+            DecisionCacheStats {
+                hits: 1,
+                misses: 1,
+                len: 1
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn step_and_journey_type_are_part_of_the_cache_key() {
+        let inner = fixed(&["passengers"]);
+        let engine = caching(&inner);
+        let data = json!({ "search": { "origin": "LHR" } });
+
+        for (journey_type, step) in [("flights", "search"), ("hotels", "search"), ("flights", "")] {
+            engine
+                .evaluate_next_steps(&captured(journey_type, &data), step, &json!({}))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(inner.calls(), 3);
+        assert_eq!(engine.cache_stats().hits, 0);
+    }
+
+    #[tokio::test]
+    async fn journeys_in_different_phases_do_not_share_a_decision() {
+        let engine = CachingDecisionEngine::new(Arc::new(booking_table()));
+        let data = json!({ "search": { "origin": "LHR", "destination": "JFK" } });
+        let mut decisions = Vec::new();
+        for phase in ["search", "passengers"] {
+            let mut journey = captured("flights", &data);
+            journey.apply(JourneyEvent::WorkflowEvaluated {
+                suggested_actions: vec![],
+                phase: Some(phase.to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec![],
+            });
+            decisions.push(
+                engine
+                    .evaluate_next_steps(&journey, "", &json!({}))
+                    .await
+                    .unwrap(),
+            );
+        }
+
+        assert_eq!(decisions[0].phase.as_deref(), Some("search"));
+        assert_eq!(decisions[1].phase.as_deref(), Some("passengers"));
+        assert_eq!(engine.cache_stats().hits, 0);
+    }
+
+    #[tokio::test]
+    async fn invalidated_and_failed_decisions_are_evaluated_again() {
+        let inner = fixed(&["passengers"]);
+        let engine = caching(&inner);
+        evaluate(&engine).await.unwrap();
+
+        engine.invalidate();
+        evaluate(&engine).await.unwrap();
+
+        assert_eq!(inner.calls(), 2);
+
+        let failing = failing();
+        let engine = caching(&failing);
+        assert!(evaluate(&engine).await.is_err());
+        assert!(evaluate(&engine).await.is_err());
+        assert_eq!(failing.calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn expired_decisions_are_evaluated_again() {
+        let inner = fixed(&["passengers"]);
+        let engine = caching(&inner).with_ttl(Duration::ZERO);
+
+        evaluate(&engine).await.unwrap();
+        evaluate(&engine).await.unwrap();
+
+        assert_eq!(inner.calls(), 2);
+    }
+
+    #[cfg(feature = "metrics")]
+    #[tokio::test]
+    async fn caching_engine_counts_hits_and_misses() {
+        use metrics_util::{
+            CompositeKey, MetricKind,
+            debugging::{DebugValue, DebuggingRecorder},
+        };
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+        let engine = caching(&fixed(&["passengers"]));
+
+        for _ in 0..3 {
+            evaluate(&engine).await.unwrap();
+        }
+
+        let counters = snapshotter.snapshot().into_hashmap();
+        let count = |name: &'static str| {
+            let key = CompositeKey::new(MetricKind::Counter, metrics::Key::from_name(name));
+            match counters.get(&key) {
+                Some((_, _, DebugValue::Counter(count))) => *count,
+                other => panic!("no {name} counter: {other:?}"),
+            }
+        };
+        assert_eq!(count(DECISION_CACHE_HITS), 2);
+        assert_eq!(count(DECISION_CACHE_MISSES), 1);
+    }
+
+    // ── HttpDecisionEngine ───────────────────────────────────────────────────
+
+    #[cfg(feature = "http-engine")]
+    mod http_engine {
+        use axum::{Json, Router, http::StatusCode, response::IntoResponse, routing::post};
+        use tokio::net::TcpListener;
+
+        use super::*;
+
+        type Reply = Box<dyn Fn(usize, Value) -> axum::response::Response + Send + Sync>;
+
+        /// Serve `reply` at `/evaluate` on a local port, returning the base
+        /// URL and a count of the requests received.
+        async fn decision_service(reply: Reply) -> (String, Arc<AtomicUsize>) {
+            let calls = Arc::new(AtomicUsize::new(0));
+            let reply = Arc::new(reply);
+            let router = {
+                let calls = Arc::clone(&calls);
+                Router::new().route(
+                    "/evaluate",
+                    post(move |Json(context): Json<Value>| async move {
+                        reply(calls.fetch_add(1, Ordering::SeqCst), context)
+                    }),
+                )
+            };
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let base_url = format!("http://{}", listener.local_addr().unwrap());
+            tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+            (base_url, calls)
+        }
+
+        fn engine(base_url: &str) -> HttpDecisionEngine {
+            HttpDecisionEngine::new(base_url, reqwest::Client::new())
+        }
+
+        fn unavailable_once() -> Reply {
+            Box::new(|call, _| {
+                if call == 0 {
+                    StatusCode::SERVICE_UNAVAILABLE.into_response()
+                } else {
+                    Json(json!({ "suggestedActions": ["search"] })).into_response()
+                }
+            })
+        }
+
+        #[tokio::test]
+        async fn posts_the_step_context_and_reads_the_decision() {
+            let (base_url, _) = decision_service(Box::new(|_, context| {
+                assert_eq!(context["currentStep"], "search");
+                assert_eq!(context["capturedData"]["origin"], "LHR");
+                Json(json!({
+                    "suggestedActions": ["passengers"],
+                    "phase": "search",
+                    "rankedActions": [{ "action": "passengers", "weight": 1.0 }],
+                    "canComplete": false,
+                    "completionBlockedBy": ["payment"]
+                }))
+                .into_response()
+            }))
+            .await;
+
+            let decision = engine(&base_url)
+                .evaluate_next_steps(&Journey::default(), "search", &json!({ "origin": "LHR" }))
+                .await
+                .unwrap();
+
+            assert_eq!(decision.suggested_actions, vec!["passengers"]);
+            assert_eq!(decision.phase.as_deref(), Some("search"));
+            assert_eq!(decision.ranked_actions[0].action, "passengers");
+            assert_eq!(decision.completion_blocked_by, vec!["payment"]);
+        }
+
+        #[tokio::test]
+        async fn decisions_are_ordered_and_deduplicated_like_a_local_model() {
+            let (base_url, _) = decision_service(Box::new(|_, _| {
+                Json(json!({
+                    "rankedActions": [
+                        { "action": "seats", "weight": 0.2 },
+                        { "action": "passengers", "weight": 0.9 },
+                        { "action": "seats", "weight": 0.1 }
+                    ],
+                    "completionBlockedBy": ["payment", "payment"]
+                }))
+                .into_response()
+            }))
+            .await;
+
+            let decision = evaluate(&engine(&base_url)).await.unwrap();
+
+            assert_eq!(decision.suggested_actions, vec!["passengers", "seats"]);
+            assert_eq!(decision.completion_blocked_by, vec!["payment"]);
+        }
+
+        #[tokio::test]
+        async fn omitted_decision_fields_default() {
+            let (base_url, _) = decision_service(Box::new(|_, _| {
+                Json(json!({ "suggestedActions": [] })).into_response()
+            }))
+            .await;
+
+            let decision = evaluate(&engine(&base_url)).await.unwrap();
+
+            assert!(decision.suggested_actions.is_empty());
+            assert_eq!(decision.phase, None);
+            assert!(!decision.can_complete);
+        }
+
+        #[tokio::test]
+        async fn server_errors_are_transient_and_retried() {
+            let (base_url, calls) = decision_service(unavailable_once()).await;
+
+            let err = evaluate(&engine(&base_url)).await.unwrap_err();
+            assert!(err.transient);
+            assert!(err.message.contains("503"), "{err}");
+
+            let (base_url, calls_with_retry) = decision_service(unavailable_once()).await;
+            let retrying = RetryingDecisionEngine::new(Arc::new(engine(&base_url)))
+                .with_base_delay(Duration::from_millis(1));
+            let decision = evaluate(&retrying).await.unwrap();
+
+            assert_eq!(decision.suggested_actions, vec!["search"]);
+            assert_eq!(calls.load(Ordering::SeqCst), 1);
+            assert_eq!(calls_with_retry.load(Ordering::SeqCst), 2);
+        }
+
+        #[tokio::test]
+        async fn client_errors_are_not_transient() {
+            let (base_url, _) = decision_service(Box::new(|_, _| {
+                (StatusCode::BAD_REQUEST, "unknown step").into_response()
+            }))
+            .await;
+
+            let err = evaluate(&engine(&base_url)).await.unwrap_err();
+
+            assert!(!err.transient);
+            assert!(err.message.contains("400"), "{err}");
+            assert!(err.message.contains("unknown step"), "{err}");
+        }
+
+        #[tokio::test]
+        async fn unreadable_decisions_are_not_transient() {
+            let (base_url, _) = decision_service(Box::new(|_, _| {
+                (StatusCode::OK, "not json").into_response()
+            }))
+            .await;
+
+            let err = evaluate(&engine(&base_url)).await.unwrap_err();
+
+            assert!(!err.transient);
+            assert!(err.message.contains("unreadable decision"), "{err}");
+        }
+
+        #[tokio::test]
+        async fn malformed_decisions_name_the_field() {
+            let (base_url, _) = decision_service(Box::new(|_, _| {
+                Json(json!({ "suggestedActions": "search" })).into_response()
+            }))
+            .await;
+
+            let err = evaluate(&engine(&base_url)).await.unwrap_err();
+
+            assert!(!err.transient);
+            assert!(err.message.contains("unreadable decision"), "{err}");
+            assert!(err.message.contains("suggestedActions"), "{err}");
+        }
+
+        #[tokio::test]
+        async fn timeouts_are_transient() {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let base_url = format!("http://{}", listener.local_addr().unwrap());
+            let router = Router::new().route(
+                "/evaluate",
+                post(|| async {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    StatusCode::OK
+                }),
+            );
+            tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+            let err = evaluate(&engine(&base_url).with_timeout(Duration::from_millis(50)))
+                .await
+                .unwrap_err();
+
+            assert!(err.transient, "{err}");
+        }
+
+        #[tokio::test]
+        async fn unreachable_services_are_transient() {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let base_url = format!("http://{}", listener.local_addr().unwrap());
+            drop(listener);
+
+            let err = evaluate(&engine(&base_url)).await.unwrap_err();
+
+            assert!(err.transient, "{err}");
+        }
     }
 }
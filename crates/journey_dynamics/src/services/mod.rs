@@ -1,2 +1,5 @@
+mod decision_cache;
 pub mod decision_engine;
 pub mod schema_validator;
+
+pub use decision_cache::DecisionCacheStats;
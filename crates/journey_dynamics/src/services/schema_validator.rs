@@ -1,5 +1,13 @@
-use serde_json::Value;
+use std::{fmt, fs, path::Path};
+
+use jsonschema::{
+    HttpOptions, HttpRetriever, JsonType, Retrieve, Uri, ValidationError,
+    error::{TypeKind, ValidationErrorKind},
+};
+use serde::Serialize;
+use serde_json::{Map, Value};
 use thiserror::Error;
+use url::Url;
 
 /// Trait for validating data against schemas
 pub trait SchemaValidator: Send + Sync {
@@ -8,13 +16,108 @@ pub trait SchemaValidator: Send + Sync {
     /// # Errors
     /// Returns an error if the data fails schema validation
     fn validate(&self, data: &Value) -> Result<(), SchemaValidationError>;
+
+    /// Validate an in-progress draft of `step`: the fields present must be
+    /// valid, but required fields may still be missing.
+    ///
+    /// The default implementation accepts everything; a draft is validated
+    /// in full once it is captured.
+    ///
+    /// # Errors
+    /// Returns an error if a field present in `data` fails schema validation
+    fn validate_partial(&self, _step: &str, _data: &Value) -> Result<(), SchemaValidationError> {
+        Ok(())
+    }
+
+    /// Which of the fields `step` requires are filled in `data`.
+    ///
+    /// Returns `None` when the validator knows nothing about `step`; the
+    /// default implementation always does.
+    fn completeness(&self, _step: &str, _data: &Value) -> Option<CompletenessReport> {
+        None
+    }
+}
+
+/// The required fields of a step, split by whether the data fills them — for
+/// progress indicators such as "3 of 5 required fields filled".
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CompletenessReport {
+    /// Every required field, in the order the schema lists them.
+    pub required: Vec<String>,
+    pub present: Vec<String>,
+    pub missing: Vec<String>,
+}
+
+/// One reason data failed validation, located by JSON pointer so a client
+/// can attach it to the offending form field.
+///
+/// `message` is English prose for logs. Clients showing the issue to users
+/// should localise it from `code` and `params` instead.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValidationIssue {
+    /// JSON pointer to the invalid value; `""` for the document as a whole.
+    /// A missing property is reported at the pointer it should have had.
+    pub path: String,
+    /// Stable, machine-readable reason, such as `required`, `type_mismatch`
+    /// or `min_length`; `invalid` when there is no more specific one.
+    pub code: &'static str,
+    /// The values a localised message needs, depending on `code`: the
+    /// `expected` type, value or pattern, the `actual` type, value, length
+    /// or count, and the schema's `limit`.
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub params: Map<String, Value>,
+    pub message: String,
+}
+
+impl ValidationIssue {
+    #[must_use]
+    pub fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            code: "invalid",
+            params: Map::new(),
+            message: message.into(),
+        }
+    }
+
+    #[must_use]
+    pub const fn with_code(mut self, code: &'static str) -> Self {
+        self.code = code;
+        self
+    }
+
+    #[must_use]
+    pub fn with_param(mut self, name: &str, value: impl Into<Value>) -> Self {
+        self.params.insert(name.to_string(), value.into());
+        self
+    }
+
+    /// Join `issues` into one line, for logs and plain-text responses.
+    #[must_use]
+    pub fn join(issues: &[Self]) -> String {
+        issues
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            f.write_str(&self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
 }
 
 /// Error types for schema validation
 #[derive(Debug, Error)]
 pub enum SchemaValidationError {
-    #[error("Schema validation failed: {0}")]
-    ValidationFailed(String),
+    #[error("Schema validation failed: {}", ValidationIssue::join(.0))]
+    ValidationFailed(Vec<ValidationIssue>),
 
     #[error("Schema not found: {0}")]
     SchemaNotFound(String),
@@ -26,6 +129,18 @@ pub enum SchemaValidationError {
     JsonError(String),
 }
 
+impl SchemaValidationError {
+    /// The error as validation issues: the per-field issues of a failed
+    /// validation, or the whole error at the document root otherwise.
+    #[must_use]
+    pub fn into_issues(self) -> Vec<ValidationIssue> {
+        match self {
+            Self::ValidationFailed(issues) => issues,
+            other => vec![ValidationIssue::new("", other.to_string())],
+        }
+    }
+}
+
 /// No-op validator that accepts all data
 pub struct NoOpValidator;
 
@@ -35,22 +150,110 @@ impl SchemaValidator for NoOpValidator {
     }
 }
 
+/// How a [`JsonSchemaValidator`] resolves `$ref`s to other documents.
+///
+/// Relative refs resolve against the base URI, and `file:` documents are
+/// read from disk. Refs to `http:`/`https:` URLs fail unless remote refs are
+/// allowed, so compiling a schema never reaches the network by accident.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaRefOptions {
+    base_uri: Option<String>,
+    allow_remote_refs: bool,
+}
+
+impl SchemaRefOptions {
+    /// Resolve relative `$ref`s against `base_uri`, e.g.
+    /// `file:///etc/journeys/schemas/`.
+    #[must_use]
+    pub fn with_base_uri(mut self, base_uri: impl Into<String>) -> Self {
+        self.base_uri = Some(base_uri.into());
+        self
+    }
+
+    /// Fetch `$ref`s to `http:` and `https:` URLs. Off by default.
+    #[must_use]
+    pub const fn with_remote_refs(mut self, allow: bool) -> Self {
+        self.allow_remote_refs = allow;
+        self
+    }
+}
+
+/// Fetches the documents `$ref`s point to, as configured by
+/// [`SchemaRefOptions`].
+struct RefRetriever {
+    allow_remote_refs: bool,
+}
+
+impl Retrieve for RefRetriever {
+    fn retrieve(
+        &self,
+        uri: &Uri<String>,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        match uri.scheme().as_str() {
+            "file" => {
+                let path = Url::parse(uri.as_str())?
+                    .to_file_path()
+                    .map_err(|()| format!("'{uri}' is not a local file path"))?;
+                Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+            }
+            "http" | "https" if self.allow_remote_refs => {
+                HttpRetriever::new(&HttpOptions::new())?.retrieve(uri)
+            }
+            "http" | "https" => Err(format!("remote $ref to '{uri}' is not allowed").into()),
+            scheme => Err(format!("unsupported $ref scheme '{scheme}' in '{uri}'").into()),
+        }
+    }
+}
+
 /// JSON Schema validator using the jsonschema crate
 #[derive(Debug)]
 pub struct JsonSchemaValidator {
     validator: jsonschema::Validator,
+    /// The same schema with every `required` keyword removed, for drafts.
+    partial_validator: jsonschema::Validator,
+    schema: Value,
 }
 
 impl JsonSchemaValidator {
     /// Create a new validator from a JSON schema
     ///
+    /// `$ref`s to other documents are resolved with the default
+    /// [`SchemaRefOptions`]: absolute `file:` URIs only.
+    ///
     /// # Errors
     /// Returns an error if the schema cannot be compiled
     pub fn new(schema: &Value) -> Result<Self, SchemaValidationError> {
-        let validator = jsonschema::validator_for(schema)
+        Self::with_ref_options(schema, &SchemaRefOptions::default())
+    }
+
+    /// Create a new validator from a JSON schema, resolving `$ref`s to other
+    /// documents as `options` allow.
+    ///
+    /// # Errors
+    /// Returns an error if the schema, or a document it refers to, cannot be
+    /// retrieved or compiled
+    pub fn with_ref_options(
+        schema: &Value,
+        options: &SchemaRefOptions,
+    ) -> Result<Self, SchemaValidationError> {
+        let mut builder = jsonschema::options().with_retriever(RefRetriever {
+            allow_remote_refs: options.allow_remote_refs,
+        });
+        if let Some(base_uri) = &options.base_uri {
+            builder = builder.with_base_uri(base_uri.clone());
+        }
+        let validator = builder
+            .build(schema)
+            .map_err(|e| SchemaValidationError::InvalidSchema(e.to_string()))?;
+        let partial_validator = builder
+            .build(&without_required(schema))
             .map_err(|e| SchemaValidationError::InvalidSchema(e.to_string()))?;
 
-        Ok(Self { validator })
+        Ok(Self {
+            validator,
+            partial_validator,
+            schema: schema.clone(),
+        })
     }
 
     /// Create a new validator from a JSON schema string
@@ -62,23 +265,336 @@ impl JsonSchemaValidator {
             .map_err(|e| SchemaValidationError::JsonError(e.to_string()))?;
         Self::new(&schema)
     }
+
+    /// Create a new validator from a schema file, resolving relative `$ref`s
+    /// against the file's directory so a schema can be split across sibling
+    /// files.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or parsed, or the schema
+    /// cannot be compiled
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, SchemaValidationError> {
+        let path = path.as_ref();
+        let not_found = |e: std::io::Error| {
+            SchemaValidationError::SchemaNotFound(format!("{}: {e}", path.display()))
+        };
+        let content = fs::read_to_string(path).map_err(not_found)?;
+        let schema: Value = serde_json::from_str(&content)
+            .map_err(|e| SchemaValidationError::JsonError(e.to_string()))?;
+
+        let directory = fs::canonicalize(path)
+            .map_err(not_found)?
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let base_uri = Url::from_directory_path(&directory).map_err(|()| {
+            SchemaValidationError::SchemaNotFound(format!(
+                "{}: not an absolute path",
+                directory.display()
+            ))
+        })?;
+
+        Self::with_ref_options(
+            &schema,
+            &SchemaRefOptions::default().with_base_uri(base_uri),
+        )
+    }
 }
 
 impl SchemaValidator for JsonSchemaValidator {
     fn validate(&self, data: &Value) -> Result<(), SchemaValidationError> {
-        // Use iter_errors to get an iterator over validation errors
-        let errors: Vec<String> = self
-            .validator
-            .iter_errors(data)
-            .map(|error| error.to_string())
-            .collect();
-
-        if errors.is_empty() {
-            Ok(())
-        } else {
-            Err(SchemaValidationError::ValidationFailed(errors.join(", ")))
+        check(&self.validator, data)
+    }
+
+    /// Validate `data` against the schema with every `required` keyword
+    /// removed. Like [`validate`](Self::validate), `data` is the whole
+    /// document, so `step` is not consulted. Documents reached through
+    /// `$ref`s to other files keep their `required` fields.
+    fn validate_partial(&self, _step: &str, data: &Value) -> Result<(), SchemaValidationError> {
+        check(&self.partial_validator, data)
+    }
+
+    fn completeness(&self, step: &str, data: &Value) -> Option<CompletenessReport> {
+        Self::completeness(self, step, data)
+    }
+}
+
+impl JsonSchemaValidator {
+    /// Report which of the fields required at `step` are filled in `data`.
+    ///
+    /// `step` is a `/`-separated path of properties from the document root,
+    /// such as `search` or `search/passengers`. The schema is followed through
+    /// `properties`, `anyOf`/`oneOf`/`allOf` branches and `$ref`s within the
+    /// schema document, and the `required` arrays of every object schema found
+    /// at `step` are reported. A field holding `null` counts as missing.
+    /// Returns `None` when the schema declares no object at `step`.
+    #[must_use]
+    pub fn completeness(&self, step: &str, data: &Value) -> Option<CompletenessReport> {
+        let mut schemas = object_schemas(&self.schema, &self.schema);
+        for property in step.split('/').filter(|p| !p.is_empty()) {
+            schemas = schemas
+                .iter()
+                .filter_map(|schema| schema.get("properties")?.get(property))
+                .flat_map(|schema| object_schemas(&self.schema, schema))
+                .collect();
+        }
+        if schemas.is_empty() {
+            return None;
+        }
+
+        let mut required: Vec<String> = Vec::new();
+        for field in schemas
+            .iter()
+            .filter_map(|schema| schema.get("required")?.as_array())
+            .flatten()
+            .filter_map(Value::as_str)
+        {
+            if !required.iter().any(|r| r == field) {
+                required.push(field.to_string());
+            }
+        }
+
+        let at_step = step
+            .split('/')
+            .filter(|p| !p.is_empty())
+            .try_fold(data, |value, property| value.get(property));
+        let (present, missing) = required.iter().cloned().partition(|field| {
+            at_step
+                .and_then(|value| value.get(field))
+                .is_some_and(|value| !value.is_null())
+        });
+        Some(CompletenessReport {
+            required,
+            present,
+            missing,
+        })
+    }
+}
+
+fn check(validator: &jsonschema::Validator, data: &Value) -> Result<(), SchemaValidationError> {
+    let issues: Vec<ValidationIssue> = validator
+        .iter_errors(data)
+        .flat_map(|error| issues_for(&error))
+        .collect();
+
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(SchemaValidationError::ValidationFailed(issues))
+    }
+}
+
+/// `schema` with the `required` keyword removed from every subschema.
+///
+/// Only keyword positions are rewritten: a property that happens to be named
+/// `required` is kept, and so are `const`/`enum`/`default`/`examples` values.
+fn without_required(schema: &Value) -> Value {
+    match schema {
+        Value::Object(map) => map
+            .iter()
+            .filter(|(keyword, _)| keyword.as_str() != "required")
+            .map(|(keyword, value)| {
+                let value = match keyword.as_str() {
+                    "const" | "enum" | "default" | "examples" => value.clone(),
+                    // Maps of name to subschema.
+                    "properties" | "patternProperties" | "$defs" | "definitions"
+                    | "dependentSchemas" => value.as_object().map_or_else(
+                        || value.clone(),
+                        |subschemas| {
+                            Value::Object(
+                                subschemas
+                                    .iter()
+                                    .map(|(name, subschema)| {
+                                        (name.clone(), without_required(subschema))
+                                    })
+                                    .collect(),
+                            )
+                        },
+                    ),
+                    _ => without_required(value),
+                };
+                (keyword.clone(), value)
+            })
+            .collect(),
+        Value::Array(items) => Value::Array(items.iter().map(without_required).collect()),
+        other => other.clone(),
+    }
+}
+
+/// The object schemas `schema` stands for: itself, the targets of its local
+/// `$ref`s and the branches of its `anyOf`/`oneOf`/`allOf`, recursively.
+/// Refs to other documents are not followed.
+fn object_schemas<'a>(root: &'a Value, schema: &'a Value) -> Vec<&'a Value> {
+    let mut found = Vec::new();
+    let mut pending = vec![schema];
+    // Bounded, so a self-referential schema cannot loop forever.
+    let mut budget = 256;
+    while let Some(schema) = pending.pop() {
+        budget -= 1;
+        if budget == 0 {
+            break;
+        }
+        if let Some(target) = schema
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|reference| reference.strip_prefix('#'))
+            .and_then(|pointer| root.pointer(pointer))
+        {
+            pending.push(target);
+        }
+        for keyword in ["anyOf", "oneOf", "allOf"] {
+            if let Some(branches) = schema.get(keyword).and_then(Value::as_array) {
+                pending.extend(branches.iter().rev());
+            }
+        }
+        if schema.get("properties").is_some() || schema.get("required").is_some() {
+            found.push(schema);
         }
     }
+    found
+}
+
+/// Locate `error` as precisely as the schema allows.
+///
+/// `anyOf`/`oneOf` failures are reported where they happen inside the one
+/// branch that accepts the value's type — for an optional group declared as
+/// `anyOf: [{ "$ref": ... }, { "type": "null" }]` that is the group's own
+/// schema. When no single branch fits, the composite error is kept as is.
+fn issues_for(error: &ValidationError<'_>) -> Vec<ValidationIssue> {
+    let path = error.instance_path().as_str();
+    match error.kind() {
+        ValidationErrorKind::AnyOf { context } | ValidationErrorKind::OneOfNotValid { context } => {
+            let mut candidates = context.iter().filter(|branch| {
+                !branch.iter().all(|e| {
+                    matches!(e.kind(), ValidationErrorKind::Type { .. })
+                        && e.instance_path() == error.instance_path()
+                })
+            });
+            if let (Some(branch), None) = (candidates.next(), candidates.next()) {
+                return branch.iter().flat_map(|e| issues_for(e)).collect();
+            }
+            vec![issue(path, error)]
+        }
+        ValidationErrorKind::Required {
+            property: Value::String(property),
+        } => {
+            let token = property.replace('~', "~0").replace('/', "~1");
+            vec![ValidationIssue::new(format!("{path}/{token}"), "required").with_code("required")]
+        }
+        _ => vec![issue(path, error)],
+    }
+}
+
+/// `error` as an issue at `path`, with the code and parameters a client needs
+/// to localise it.
+fn issue(path: &str, error: &ValidationError<'_>) -> ValidationIssue {
+    let issue = ValidationIssue::new(path, error.to_string());
+    let instance = error.instance();
+    let count = || match instance.as_ref() {
+        Value::String(text) => Some(text.chars().count()),
+        Value::Array(items) => Some(items.len()),
+        Value::Object(members) => Some(members.len()),
+        _ => None,
+    };
+    let bounded = |issue: ValidationIssue, code, limit: Value| {
+        let issue = issue.with_code(code).with_param("limit", limit);
+        match count() {
+            Some(count) => issue.with_param("actual", count),
+            None => issue,
+        }
+    };
+    match error.kind() {
+        ValidationErrorKind::Type { kind } => {
+            let expected = match kind {
+                TypeKind::Single(expected) => Value::from(expected.as_str()),
+                TypeKind::Multiple(expected) => expected
+                    .iter()
+                    .map(JsonType::as_str)
+                    .collect::<Vec<_>>()
+                    .into(),
+            };
+            issue
+                .with_code("type_mismatch")
+                .with_param("expected", expected)
+                .with_param("actual", json_type(instance))
+        }
+        ValidationErrorKind::MinLength { limit } => bounded(issue, "min_length", (*limit).into()),
+        ValidationErrorKind::MaxLength { limit } => bounded(issue, "max_length", (*limit).into()),
+        ValidationErrorKind::MinItems { limit } => bounded(issue, "min_items", (*limit).into()),
+        ValidationErrorKind::MaxItems { limit } => bounded(issue, "max_items", (*limit).into()),
+        ValidationErrorKind::MinProperties { limit } => {
+            bounded(issue, "min_properties", (*limit).into())
+        }
+        ValidationErrorKind::MaxProperties { limit } => {
+            bounded(issue, "max_properties", (*limit).into())
+        }
+        ValidationErrorKind::Minimum { limit } => numeric(issue, "minimum", limit, instance),
+        ValidationErrorKind::Maximum { limit } => numeric(issue, "maximum", limit, instance),
+        ValidationErrorKind::ExclusiveMinimum { limit } => {
+            numeric(issue, "exclusive_minimum", limit, instance)
+        }
+        ValidationErrorKind::ExclusiveMaximum { limit } => {
+            numeric(issue, "exclusive_maximum", limit, instance)
+        }
+        ValidationErrorKind::MultipleOf { multiple_of } => numeric(
+            issue,
+            "multiple_of",
+            &serde_json::json!(multiple_of),
+            instance,
+        ),
+        ValidationErrorKind::Enum { options } => issue
+            .with_code("enum")
+            .with_param("expected", options.clone()),
+        ValidationErrorKind::Constant { expected_value } => issue
+            .with_code("const")
+            .with_param("expected", expected_value.clone()),
+        ValidationErrorKind::Pattern { pattern } => issue
+            .with_code("pattern")
+            .with_param("expected", pattern.as_str()),
+        ValidationErrorKind::Format { format } => issue
+            .with_code("format")
+            .with_param("expected", format.as_str()),
+        ValidationErrorKind::AdditionalProperties { unexpected } => issue
+            .with_code("additional_properties")
+            .with_param("actual", unexpected.clone()),
+        ValidationErrorKind::UnevaluatedProperties { unexpected } => issue
+            .with_code("unevaluated_properties")
+            .with_param("actual", unexpected.clone()),
+        ValidationErrorKind::UniqueItems => issue.with_code("unique_items"),
+        ValidationErrorKind::Contains => issue.with_code("contains"),
+        ValidationErrorKind::Not { .. } => issue.with_code("not"),
+        ValidationErrorKind::AnyOf { .. } => issue.with_code("any_of"),
+        ValidationErrorKind::OneOfNotValid { .. }
+        | ValidationErrorKind::OneOfMultipleValid { .. } => issue.with_code("one_of"),
+        _ => issue,
+    }
+}
+
+/// `issue` as a failed numeric bound `limit` on `instance`.
+fn numeric(
+    issue: ValidationIssue,
+    code: &'static str,
+    limit: &Value,
+    instance: &Value,
+) -> ValidationIssue {
+    issue
+        .with_code(code)
+        .with_param("limit", limit.clone())
+        .with_param("actual", instance.clone())
+}
+
+/// The JSON Schema type name of `value`, counting whole numbers as
+/// `integer`.
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(number) if number.is_i64() || number.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
 }
 
 #[cfg(test)]
@@ -137,6 +653,137 @@ mod tests {
         assert!(validator.validate(&invalid_data_constraint).is_err());
     }
 
+    #[test]
+    fn test_json_schema_validator_reports_issues_by_pointer() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "search": {
+                    "anyOf": [
+                        {
+                            "type": "object",
+                            "properties": { "adults": { "type": "integer" } },
+                            "required": ["a/b", "adults"]
+                        },
+                        { "type": "null" }
+                    ]
+                }
+            }
+        });
+        let validator = JsonSchemaValidator::new(&schema).unwrap();
+
+        let Err(SchemaValidationError::ValidationFailed(issues)) =
+            validator.validate(&json!({ "search": { "a/b": 1 } }))
+        else {
+            panic!("expected a validation failure");
+        };
+        // Reported inside the object branch, with the missing key escaped.
+        assert_eq!(
+            issues,
+            vec![ValidationIssue::new("/search/adults", "required").with_code("required")]
+        );
+
+        let Err(SchemaValidationError::ValidationFailed(issues)) =
+            validator.validate(&json!({ "search": { "adults": 1 } }))
+        else {
+            panic!("expected a validation failure");
+        };
+        assert_eq!(
+            issues,
+            vec![ValidationIssue::new("/search/a~1b", "required").with_code("required")]
+        );
+    }
+
+    // ── Localisation ─────────────────────────────────────────────────────────
+
+    fn issues(schema: &Value, data: &Value) -> Vec<ValidationIssue> {
+        JsonSchemaValidator::new(schema)
+            .unwrap()
+            .validate(data)
+            .unwrap_err()
+            .into_issues()
+    }
+
+    #[test]
+    fn min_length_failure_carries_its_code_limit_and_path() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "passengers": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": { "lastName": { "type": "string", "minLength": 2 } }
+                    }
+                }
+            }
+        });
+
+        let issues = issues(&schema, &json!({ "passengers": [{ "lastName": "O" }] }));
+
+        assert_eq!(issues.len(), 1);
+        let issue = &issues[0];
+        assert_eq!(issue.path, "/passengers/0/lastName");
+        assert_eq!(issue.code, "min_length");
+        assert_eq!(issue.params["limit"], json!(2));
+        assert_eq!(issue.params["actual"], json!(1));
+    }
+
+    #[test]
+    fn type_mismatch_names_the_expected_and_actual_types() {
+        let schema = json!({
+            "type": "object",
+            "properties": { "adults": { "type": "integer" } }
+        });
+
+        let issues = issues(&schema, &json!({ "adults": "two" }));
+
+        assert_eq!(
+            issues,
+            vec![
+                ValidationIssue::new("/adults", "\"two\" is not of type \"integer\"")
+                    .with_code("type_mismatch")
+                    .with_param("expected", "integer")
+                    .with_param("actual", "string")
+            ]
+        );
+    }
+
+    #[test]
+    fn enum_and_bound_failures_are_parameterised() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "cabin": { "enum": ["economy", "business"] },
+                "adults": { "type": "integer", "maximum": 9 }
+            }
+        });
+
+        let issues = issues(&schema, &json!({ "cabin": "first", "adults": 12 }));
+
+        let by_code = |code: &str| issues.iter().find(|issue| issue.code == code).unwrap();
+        assert_eq!(
+            by_code("enum").params["expected"],
+            json!(["economy", "business"])
+        );
+        assert_eq!(by_code("maximum").params["limit"], json!(9));
+        assert_eq!(by_code("maximum").params["actual"], json!(12));
+    }
+
+    #[test]
+    fn issues_serialise_their_code_and_params() {
+        let issue = ValidationIssue::new("/name", "required").with_code("required");
+
+        assert_eq!(
+            serde_json::to_value(&issue).unwrap(),
+            json!({ "path": "/name", "code": "required", "message": "required" })
+        );
+        assert_eq!(
+            serde_json::to_value(issue.with_param("limit", 2)).unwrap()["params"],
+            json!({ "limit": 2 })
+        );
+    }
+
     #[test]
     fn test_json_schema_validator_with_refs() {
         let schema = json!({
@@ -178,4 +825,185 @@ mod tests {
         assert!(validator.validate(&valid_data).is_ok());
         assert!(validator.validate(&invalid_enum_data).is_err());
     }
+
+    /// A booking schema whose passenger lives in a sibling file, which in
+    /// turn refers back to the booking schema for a shared definition.
+    fn split_schema_directory() -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("split-schema-{}", uuid::Uuid::new_v4()));
+        fs::create_dir(&dir).unwrap();
+        fs::write(
+            dir.join("booking.json"),
+            json!({
+                "type": "object",
+                "properties": { "passenger": { "$ref": "passenger.json" } },
+                "$defs": { "Email": { "type": "string", "pattern": "^[^@]+@[^@]+$" } }
+            })
+            .to_string(),
+        )
+        .unwrap();
+        fs::write(
+            dir.join("passenger.json"),
+            json!({
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string" },
+                    "email": { "$ref": "booking.json#/$defs/Email" }
+                },
+                "required": ["name"]
+            })
+            .to_string(),
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn from_path_resolves_refs_between_sibling_files() {
+        let dir = split_schema_directory();
+        let validator = JsonSchemaValidator::from_path(dir.join("booking.json")).unwrap();
+
+        assert!(
+            validator
+                .validate(
+                    &json!({ "passenger": { "name": "Alice", "email": "alice@example.com" } })
+                )
+                .is_ok()
+        );
+        let issues = validator
+            .validate(&json!({ "passenger": { "email": "not-an-email" } }))
+            .unwrap_err()
+            .into_issues();
+        let paths: Vec<&str> = issues.iter().map(|i| i.path.as_str()).collect();
+        assert_eq!(paths, ["/passenger/name", "/passenger/email"]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn from_path_reports_a_missing_file() {
+        let path = std::env::temp_dir().join(format!("missing-{}.json", uuid::Uuid::new_v4()));
+
+        assert!(matches!(
+            JsonSchemaValidator::from_path(&path),
+            Err(SchemaValidationError::SchemaNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn remote_refs_are_refused_unless_allowed() {
+        let schema = json!({ "$ref": "https://schemas.example.com/booking.json" });
+
+        let err = JsonSchemaValidator::new(&schema).unwrap_err();
+
+        assert!(
+            err.to_string().contains("not allowed"),
+            "unexpected error: {err}"
+        );
+    }
+
+    // ── Completeness ─────────────────────────────────────────────────────────
+
+    const FLIGHT_BOOKING_SCHEMA: &str =
+        include_str!("../../../../examples/flight-booking/schemas/flight-booking-schema.json");
+
+    #[test]
+    fn completeness_splits_required_fields_by_presence() {
+        let validator = JsonSchemaValidator::from_json_str(FLIGHT_BOOKING_SCHEMA).unwrap();
+        let data = json!({
+            "search": {
+                "tripType": "one-way",
+                "origin": "LHR",
+                "destination": null,
+                "passengers": { "adults": 1 }
+            }
+        });
+
+        let search = validator.completeness("search", &data).unwrap();
+        assert_eq!(
+            search,
+            CompletenessReport {
+                required: vec![
+                    "tripType".into(),
+                    "origin".into(),
+                    "destination".into(),
+                    "departureDate".into(),
+                    "passengers".into(),
+                ],
+                present: vec!["tripType".into(), "origin".into(), "passengers".into()],
+                missing: vec!["destination".into(), "departureDate".into()],
+            }
+        );
+
+        let passengers = validator.completeness("search/passengers", &data).unwrap();
+        assert_eq!(passengers.present, vec!["adults"]);
+        assert_eq!(passengers.missing, vec!["children", "infants"]);
+    }
+
+    #[test]
+    fn completeness_of_an_unknown_step_is_none() {
+        let validator = JsonSchemaValidator::from_json_str(FLIGHT_BOOKING_SCHEMA).unwrap();
+
+        assert_eq!(
+            validator.completeness("passenger_details", &json!({})),
+            None
+        );
+        assert_eq!(NoOpValidator.completeness("search", &json!({})), None);
+    }
+
+    #[test]
+    fn partial_validation_ignores_missing_required_fields() {
+        let validator = JsonSchemaValidator::from_json_str(FLIGHT_BOOKING_SCHEMA).unwrap();
+        let draft = json!({ "search": { "origin": "LHR" } });
+
+        assert!(validator.validate(&draft).is_err());
+        assert!(validator.validate_partial("search", &draft).is_ok());
+    }
+
+    #[test]
+    fn partial_validation_still_checks_present_fields() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "search": {
+                    "$ref": "#/$defs/search"
+                }
+            },
+            "required": ["search"],
+            "$defs": {
+                "search": {
+                    "type": "object",
+                    "properties": {
+                        "adults": { "type": "integer", "minimum": 1 },
+                        "required": { "type": "boolean" }
+                    },
+                    "required": ["adults"]
+                }
+            }
+        });
+        let validator = JsonSchemaValidator::new(&schema).unwrap();
+
+        assert!(validator.validate_partial("search", &json!({})).is_ok());
+        let Err(SchemaValidationError::ValidationFailed(issues)) =
+            validator.validate_partial("search", &json!({ "search": { "adults": "two" } }))
+        else {
+            panic!("expected a validation failure");
+        };
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, "/search/adults");
+        // A property named `required` is a property, not the keyword.
+        assert!(
+            validator
+                .validate_partial("search", &json!({ "search": { "required": "yes" } }))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn no_op_validator_accepts_any_draft() {
+        assert!(
+            NoOpValidator
+                .validate_partial("search", &json!({ "anything": "goes" }))
+                .is_ok()
+        );
+    }
 }
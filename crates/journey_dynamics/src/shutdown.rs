@@ -0,0 +1,157 @@
+//! Graceful shutdown for the HTTP server.
+//!
+//! Event writes are not atomic with view updates, so a command cut off
+//! mid-dispatch can leave the views behind the event store. On SIGINT or
+//! SIGTERM the server therefore stops accepting connections, lets every
+//! in-flight request run to completion, and only then returns so the caller
+//! can close the database pool.
+
+use std::{future::Future, io};
+
+use axum::Router;
+use tokio::net::TcpListener;
+
+use crate::journey_updates::JourneyUpdates;
+
+/// Resolves on the first SIGINT (Ctrl-C) or, on Unix, SIGTERM.
+///
+/// # Panics
+///
+/// Panics if the signal handlers cannot be installed.
+pub async fn shutdown_signal() {
+    let interrupt = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install the SIGINT handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install the SIGTERM handler")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = interrupt => println!("Received SIGINT"),
+        () = terminate => println!("Received SIGTERM"),
+    }
+}
+
+/// Serve `router` on `listener` until `signal` resolves, then drain.
+///
+/// Once `signal` resolves the listener is closed and every journey update
+/// channel in `updates` is closed too, ending the SSE and WebSocket
+/// subscriptions that would otherwise hold their connections open forever.
+/// Returns after the last in-flight request has completed.
+///
+/// # Errors
+///
+/// Returns an [`io::Error`] if the server fails while accepting connections.
+pub async fn serve_until(
+    listener: TcpListener,
+    router: Router,
+    updates: JourneyUpdates,
+    signal: impl Future<Output = ()> + Send + 'static,
+) -> io::Result<()> {
+    axum::serve(listener, router)
+        .with_graceful_shutdown(async move {
+            signal.await;
+            println!("Shutting down: no longer accepting connections");
+            updates.close();
+        })
+        .await?;
+    println!("Shutting down: in-flight requests drained");
+    Ok(())
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+    use axum::routing::get;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+        sync::{Notify, oneshot},
+    };
+    use uuid::Uuid;
+
+    use super::*;
+
+    /// Send a bare HTTP/1.1 `GET` and return the full response text.
+    async fn get_text(address: SocketAddr, path: &str) -> io::Result<String> {
+        let mut stream = TcpStream::connect(address).await?;
+        let request = format!("GET {path} HTTP/1.1\r\nHost: test\r\nConnection: close\r\n\r\n");
+        stream.write_all(request.as_bytes()).await?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await?;
+        Ok(response)
+    }
+
+    #[tokio::test]
+    async fn shutdown_drains_in_flight_requests_and_stops_accepting() {
+        let started = Arc::new(Notify::new());
+        let release = Arc::new(Notify::new());
+        let router = {
+            let (started, release) = (Arc::clone(&started), Arc::clone(&release));
+            Router::new().route(
+                "/slow",
+                get(move || async move {
+                    started.notify_one();
+                    release.notified().await;
+                    "done"
+                }),
+            )
+        };
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let (trigger, signal) = oneshot::channel::<()>();
+        let server = tokio::spawn(serve_until(
+            listener,
+            router,
+            JourneyUpdates::default(),
+            async move {
+                signal.await.ok();
+            },
+        ));
+
+        let in_flight = tokio::spawn(get_text(address, "/slow"));
+        started.notified().await;
+        trigger.send(()).unwrap();
+
+        // The server keeps running while the request is still in flight.
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!server.is_finished());
+        assert!(TcpStream::connect(address).await.is_err());
+
+        release.notify_one();
+        let response = in_flight.await.unwrap().unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        assert!(response.ends_with("done"), "{response}");
+
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .expect("server did not stop after draining")
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn shutdown_ends_update_subscriptions() {
+        let updates = JourneyUpdates::default();
+        let mut receiver = updates.subscribe(Uuid::new_v4());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+
+        serve_until(listener, Router::new(), updates, async {})
+            .await
+            .unwrap();
+
+        assert!(receiver.recv().await.is_err());
+    }
+}
@@ -0,0 +1,430 @@
+//! SQLite-backed [`JourneyViewStore`], for local development and tests.
+//!
+//! Each journey is stored as one JSON document — the serialised
+//! [`JourneyView`], persons included — rather than across the three tables the
+//! Postgres repository uses. Events are applied in Rust by the projection
+//! shared with
+//! [`InMemoryJourneyViewRepository`](crate::memory_view_repository::InMemoryJourneyViewRepository),
+//! which mirrors the
+//! `journey_person` statements in
+//! [`StructuredJourneyViewRepository`](crate::view_repository::StructuredJourneyViewRepository).
+//!
+//! `SQLite` has no JSONB operators, so their uses are replaced:
+//!
+//! | Postgres                      | `SQLite`                                 |
+//! |-------------------------------|------------------------------------------|
+//! | `details \|\| $patch`         | top-level key merge                      |
+//! | `shared_data @> $fragment`    | [`json_contains`] over every row         |
+//! | `journey_person.email` lookup | `json_each` over `$.persons`             |
+//!
+//! Enabled by the `sqlite` feature.
+
+use cqrs_es::{EventEnvelope, Query};
+use serde_json::Value;
+use sqlx::{
+    Pool, Sqlite,
+    sqlite::{SqliteConnectOptions, SqlitePoolOptions},
+};
+use uuid::Uuid;
+
+pub use crate::memory_view_repository::json_contains;
+use crate::{
+    domain::journey::Journey,
+    memory_view_repository::apply_events_to,
+    queries::JourneyView,
+    view_repository::{JourneyViewStore, ViewRepositoryError},
+};
+
+const SCHEMA: &str = r"
+CREATE TABLE IF NOT EXISTS journey_view (
+    id   TEXT PRIMARY KEY NOT NULL,
+    view TEXT NOT NULL
+)
+";
+
+/// A journey view store backed by `SQLite`.
+#[derive(Clone)]
+pub struct SqliteJourneyViewRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteJourneyViewRepository {
+    #[must_use]
+    pub const fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    /// A store over a fresh in-memory database, with its schema created.
+    ///
+    /// The pool holds a single connection that is never recycled, because
+    /// every connection to `sqlite::memory:` opens a separate database.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be opened or migrated.
+    pub async fn in_memory() -> Result<Self, sqlx::Error> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(SqliteConnectOptions::new().in_memory(true))
+            .await?;
+        let repo = Self::new(pool);
+        repo.migrate().await?;
+        Ok(repo)
+    }
+
+    /// Create the schema if it does not exist yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the statement fails.
+    pub async fn migrate(&self) -> Result<(), sqlx::Error> {
+        sqlx::query(SCHEMA).execute(&self.pool).await?;
+        Ok(())
+    }
+
+    async fn load_where(
+        &self,
+        sql: &str,
+        bind: Option<&str>,
+    ) -> Result<Vec<JourneyView>, ViewRepositoryError> {
+        let mut query = sqlx::query_scalar::<_, String>(sql);
+        if let Some(bind) = bind {
+            query = query.bind(bind);
+        }
+        query
+            .fetch_all(&self.pool)
+            .await?
+            .iter()
+            .map(|json| decode(json))
+            .collect()
+    }
+}
+
+#[async_trait::async_trait]
+impl JourneyViewStore for SqliteJourneyViewRepository {
+    async fn load(&self, journey_id: &Uuid) -> Result<Option<JourneyView>, ViewRepositoryError> {
+        let json: Option<String> = sqlx::query_scalar("SELECT view FROM journey_view WHERE id = ?")
+            .bind(journey_id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+        json.as_deref().map(decode).transpose()
+    }
+
+    async fn load_all(&self) -> Result<Vec<JourneyView>, ViewRepositoryError> {
+        self.load_where(
+            "SELECT view FROM journey_view \
+             ORDER BY json_extract(view, '$.created_at') DESC, id",
+            None,
+        )
+        .await
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Vec<JourneyView>, ViewRepositoryError> {
+        self.load_where(
+            "SELECT view FROM journey_view AS j \
+             WHERE EXISTS ( \
+                 SELECT 1 FROM json_each(j.view, '$.persons') AS p \
+                 WHERE lower(json_extract(p.value, '$.email')) = lower(?) \
+                   AND json_extract(p.value, '$.forgotten') = 0 \
+             ) \
+             ORDER BY id",
+            Some(email),
+        )
+        .await
+    }
+
+    async fn find_where_data_contains(
+        &self,
+        fragment: &Value,
+    ) -> Result<Vec<JourneyView>, ViewRepositoryError> {
+        let views = self
+            .load_where("SELECT view FROM journey_view ORDER BY id", None)
+            .await?;
+        Ok(views
+            .into_iter()
+            .filter(|view| json_contains(&view.shared_data, fragment))
+            .collect())
+    }
+
+    async fn apply_events(
+        &self,
+        journey_id: Uuid,
+        events: &[EventEnvelope<Journey>],
+    ) -> Result<(), ViewRepositoryError> {
+        let mut tx = self.pool.begin().await?;
+
+        let json: Option<String> = sqlx::query_scalar("SELECT view FROM journey_view WHERE id = ?")
+            .bind(journey_id.to_string())
+            .fetch_optional(&mut *tx)
+            .await?;
+        let mut view = json.as_deref().map(decode).transpose()?;
+        apply_events_to(&mut view, journey_id, events)?;
+
+        if let Some(view) = view {
+            let json = serde_json::to_string(&view)
+                .map_err(|e| ViewRepositoryError::InconsistentRow(e.to_string()))?;
+            sqlx::query("INSERT OR REPLACE INTO journey_view (id, view) VALUES (?, ?)")
+                .bind(journey_id.to_string())
+                .bind(json)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Query<Journey> for SqliteJourneyViewRepository {
+    async fn dispatch(&self, view_id: &str, events: &[EventEnvelope<Journey>]) {
+        if events.is_empty() {
+            return;
+        }
+
+        let journey_id = match Uuid::parse_str(view_id) {
+            Ok(id) => id,
+            Err(e) => {
+                eprintln!("Invalid journey ID '{view_id}': {e:?}");
+                return;
+            }
+        };
+
+        if let Err(e) = self.apply_events(journey_id, events).await {
+            eprintln!("Error updating journey view '{view_id}': {e:?}");
+        }
+    }
+}
+
+fn decode(json: &str) -> Result<JourneyView, ViewRepositoryError> {
+    serde_json::from_str(json).map_err(|e| ViewRepositoryError::InconsistentRow(e.to_string()))
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+#[allow(deprecated)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use jsonptr::PointerBuf;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        domain::events::{JourneyEvent, SecretPartitionData},
+        queries::JourneyState,
+    };
+
+    fn envelope(
+        journey_id: Uuid,
+        sequence: usize,
+        payload: JourneyEvent,
+    ) -> EventEnvelope<Journey> {
+        EventEnvelope {
+            aggregate_id: journey_id.to_string(),
+            sequence,
+            payload,
+            metadata: HashMap::from([("time".to_string(), "2025-08-15T10:00:00Z".to_string())]),
+        }
+    }
+
+    fn changes(pairs: &[(&str, Value)]) -> BTreeMap<PointerBuf, Value> {
+        pairs
+            .iter()
+            .map(|(path, value)| (path.parse().unwrap(), value.clone()))
+            .collect()
+    }
+
+    async fn start(repo: &SqliteJourneyViewRepository) -> Uuid {
+        let journey_id = Uuid::new_v4();
+        repo.apply_events(
+            journey_id,
+            &[envelope(
+                journey_id,
+                1,
+                JourneyEvent::Started {
+                    id: journey_id,
+                    journey_type: None,
+                },
+            )],
+        )
+        .await
+        .unwrap();
+        journey_id
+    }
+
+    fn capture(
+        journey_id: Uuid,
+        sequence: usize,
+        subject_id: Uuid,
+        email: &str,
+    ) -> EventEnvelope<Journey> {
+        envelope(
+            journey_id,
+            sequence,
+            JourneyEvent::PersonCaptured {
+                person_ref: "lead_booker".to_string(),
+                subject_id,
+                name: "Alice Smith".to_string(),
+                email: email.to_string(),
+                phone: None,
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn started_journey_round_trips() {
+        let repo = SqliteJourneyViewRepository::in_memory().await.unwrap();
+        let journey_id = start(&repo).await;
+
+        let view = repo.load(&journey_id).await.unwrap().unwrap();
+        assert_eq!(view.id, journey_id);
+        assert_eq!(view.state, JourneyState::InProgress);
+        assert_eq!(view.shared_data, json!({}));
+        assert_eq!(
+            view.created_at,
+            Some("2025-08-15T10:00:00Z".parse().unwrap())
+        );
+        assert!(repo.load(&Uuid::new_v4()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn attributes_deep_merge_and_mirror_secrets_into_details() {
+        let repo = SqliteJourneyViewRepository::in_memory().await.unwrap();
+        let journey_id = start(&repo).await;
+        let subject_id = Uuid::new_v4();
+
+        repo.apply_events(
+            journey_id,
+            &[
+                capture(journey_id, 2, subject_id, "alice@example.com"),
+                envelope(
+                    journey_id,
+                    3,
+                    JourneyEvent::AttributesSet {
+                        plaintext: changes(&[("/search/origin", json!("LHR"))]),
+                        secret_partitions: vec![],
+                    },
+                ),
+                envelope(
+                    journey_id,
+                    4,
+                    JourneyEvent::AttributesSet {
+                        plaintext: changes(&[("/search/destination", json!("JFK"))]),
+                        secret_partitions: vec![SecretPartitionData {
+                            subject_id,
+                            person_ref: "lead_booker".to_string(),
+                            changes: changes(&[(
+                                "/persons/lead_booker/passportNumber",
+                                json!("GB123"),
+                            )]),
+                        }],
+                    },
+                ),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let view = repo.load(&journey_id).await.unwrap().unwrap();
+        assert_eq!(
+            view.shared_data,
+            json!({
+                "search": { "origin": "LHR", "destination": "JFK" },
+                "persons": { "lead_booker": { "passportNumber": "GB123" } }
+            })
+        );
+        assert_eq!(view.persons.len(), 1);
+        assert_eq!(
+            view.persons[0].details,
+            json!({ "passportNumber": "GB123" })
+        );
+    }
+
+    #[tokio::test]
+    async fn find_by_email_ignores_case_and_forgotten_subjects() {
+        let repo = SqliteJourneyViewRepository::in_memory().await.unwrap();
+        let kept = start(&repo).await;
+        let forgotten = start(&repo).await;
+        let kept_subject = Uuid::new_v4();
+        let forgotten_subject = Uuid::new_v4();
+
+        repo.apply_events(kept, &[capture(kept, 2, kept_subject, "Alice@Example.com")])
+            .await
+            .unwrap();
+        repo.apply_events(
+            forgotten,
+            &[
+                capture(forgotten, 2, forgotten_subject, "alice@example.com"),
+                envelope(
+                    forgotten,
+                    3,
+                    JourneyEvent::SubjectForgotten {
+                        subject_id: forgotten_subject,
+                    },
+                ),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let views = repo.find_by_email("ALICE@example.COM").await.unwrap();
+        assert_eq!(views.iter().map(|v| v.id).collect::<Vec<_>>(), vec![kept]);
+
+        let shredded = repo.load(&forgotten).await.unwrap().unwrap();
+        assert!(shredded.persons[0].forgotten);
+        assert_eq!(shredded.persons[0].email, None);
+    }
+
+    #[tokio::test]
+    async fn find_where_data_contains_matches_nested_fragment() {
+        let repo = SqliteJourneyViewRepository::in_memory().await.unwrap();
+        let jfk = start(&repo).await;
+        let cdg = start(&repo).await;
+        for (journey_id, destination) in [(jfk, "JFK"), (cdg, "CDG")] {
+            repo.apply_events(
+                journey_id,
+                &[envelope(
+                    journey_id,
+                    2,
+                    JourneyEvent::AttributesSet {
+                        plaintext: changes(&[
+                            ("/search/origin", json!("LHR")),
+                            ("/search/destination", json!(destination)),
+                        ]),
+                        secret_partitions: vec![],
+                    },
+                )],
+            )
+            .await
+            .unwrap();
+        }
+
+        let views = repo
+            .find_where_data_contains(&json!({ "search": { "destination": "JFK" } }))
+            .await
+            .unwrap();
+
+        assert_eq!(views.iter().map(|v| v.id).collect::<Vec<_>>(), vec![jfk]);
+        assert_eq!(repo.load_all().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn events_for_an_unknown_journey_are_rejected() {
+        let repo = SqliteJourneyViewRepository::in_memory().await.unwrap();
+        let journey_id = Uuid::new_v4();
+
+        let err = repo
+            .apply_events(
+                journey_id,
+                &[envelope(journey_id, 2, JourneyEvent::Completed)],
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ViewRepositoryError::NotFound(id) if id == journey_id));
+    }
+}
@@ -1,62 +1,146 @@
-use std::sync::Arc;
+use std::{path::Path, sync::Arc, time::Duration};
 
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
 use postgres_es::default_postgress_pool;
 use sqlx::{Pool, Postgres};
 
 use cqrs_es_crypto::{
-    FieldCipher, KeyStore, PostgresKeyStore, RewrapWorker, RewrapWorkerOptions, StaticKekProvider,
+    FieldCipher, KekProvider, KeyStore, PostgresKeyStore, RewrapWorker, RewrapWorkerOptions,
+    StaticKekProvider,
 };
 
 use crate::{
-    config::{CryptoCqrs, cqrs_framework},
-    domain::{AttributeSchema, AttributeSchemaConfig},
-    services::{decision_engine::GoRulesDecisionEngine, schema_validator::JsonSchemaValidator},
+    batching_query::BatchingQuery,
+    command_log::CommandLog,
+    command_rejections::{PostgresRejectionLog, RejectionLog},
+    config::{
+        Config, CryptoCqrs, CryptoEventStore, RetentionSweep, cqrs_framework, event_history_store,
+        journey_services,
+    },
+    domain::{AttributeSchema, AttributeSchemaConfig, journey::JourneyServices},
+    event_feed::EventFeed,
+    idempotency::{
+        DEFAULT_IDEMPOTENCY_LEASE, DEFAULT_IDEMPOTENCY_TTL, IdempotencyStore,
+        PostgresIdempotencyStore,
+    },
+    journey_updates::JourneyUpdates,
+    services::{
+        decision_engine::{GoRulesDecisionEngine, StepDescriptor},
+        schema_validator::JsonSchemaValidator,
+    },
     view_repository::StructuredJourneyViewRepository,
 };
+#[cfg(feature = "encryption")]
+use crate::{config::ViewEncryptionKey, pii_cipher::PiiCipher};
 
 #[derive(Clone)]
 pub struct ApplicationState {
     pub pool: Pool<Postgres>,
     pub cqrs: Arc<CryptoCqrs>,
     pub journey_query: Arc<StructuredJourneyViewRepository>,
+    /// The queue view writes go through when they are batched; see
+    /// [`Config::view_batching`]. Flush it before closing the pool.
+    pub view_batcher: Option<BatchingQuery>,
+    pub event_store: Arc<CryptoEventStore>,
+    /// Every journey's events in one feed, for `GET /events`.
+    pub event_feed: Arc<EventFeed>,
+    pub journey_updates: JourneyUpdates,
     pub key_store: Arc<dyn KeyStore>,
+    /// The services commands run with, for evaluating previews outside the
+    /// framework.
+    pub journey_services: Arc<JourneyServices>,
+    /// Steps declared by the decision model, served by `GET /workflow/steps`.
+    pub workflow_steps: Arc<Vec<StepDescriptor>>,
+    /// Keys of commands already submitted, for `Idempotency-Key`.
+    pub idempotency: Arc<dyn IdempotencyStore>,
+    /// Where commands the aggregate rejected are recorded.
+    pub rejections: Arc<dyn RejectionLog>,
+    /// The commands the aggregate accepted, as recorded by the framework.
+    pub command_log: CommandLog,
 }
 
-/// Load a [`GoRulesDecisionEngine`] from the path named by
-/// `JOURNEY_DECISION_ENGINE_PATH`.
+/// Load a [`GoRulesDecisionEngine`] from the JDM file at `path`.
+///
+/// `error_context` attaches the evaluation context to decision engine errors;
+/// see [`Config::decision_tracing`]. Setting `JOURNEY_DECISION_ENGINE_STRICT`
+/// to `1` or `true` makes an evaluation that suggests no actions an error.
+/// Setting `JOURNEY_DECISION_ENGINE_CHECK_OUTPUTS` likewise rejects a model
+/// whose declared outputs the engine cannot read; see
+/// [`GoRulesDecisionEngine::check_outputs`].
 ///
 /// # Panics
 ///
-/// Panics if `JOURNEY_DECISION_ENGINE_PATH` is not set or the file cannot be
-/// read or parsed.
+/// Panics if the file cannot be read or parsed, or fails the output check.
 #[must_use]
-pub fn load_decision_engine() -> std::sync::Arc<GoRulesDecisionEngine> {
-    let path = std::env::var("JOURNEY_DECISION_ENGINE_PATH")
-        .expect("JOURNEY_DECISION_ENGINE_PATH environment variable must be set");
-    let content = std::fs::read_to_string(&path)
-        .unwrap_or_else(|e| panic!("JOURNEY_DECISION_ENGINE_PATH={path:?}: cannot read file: {e}"));
-    std::sync::Arc::new(GoRulesDecisionEngine::new(&content))
+pub fn load_decision_engine(path: &Path, error_context: bool) -> Arc<GoRulesDecisionEngine> {
+    let content = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        panic!(
+            "JOURNEY_DECISION_ENGINE_PATH={}: cannot read file: {e}",
+            path.display()
+        )
+    });
+    let engine = GoRulesDecisionEngine::new(&content)
+        .with_error_context(error_context)
+        .with_strict_output(env_flag("JOURNEY_DECISION_ENGINE_STRICT"));
+    if env_flag("JOURNEY_DECISION_ENGINE_CHECK_OUTPUTS")
+        && let Err(e) = engine.check_outputs()
+    {
+        panic!(
+            "JOURNEY_DECISION_ENGINE_PATH={}: incompatible decision model: {e}",
+            path.display()
+        );
+    }
+    Arc::new(engine)
 }
 
-/// Load a [`JsonSchemaValidator`] from the path named by
-/// `JOURNEY_DATA_SCHEMA_PATH`.
+/// Check that `engine`, loaded from `path`, keeps the output contract.
+///
+/// The model is evaluated against the context in the file named by
+/// `JOURNEY_DECISION_ENGINE_SAMPLE_CONTEXT_PATH`, or an empty journey's `{}`
+/// when unset; see [`GoRulesDecisionEngine::self_check`].
 ///
 /// # Panics
 ///
-/// Panics if `JOURNEY_DATA_SCHEMA_PATH` is not set or the file cannot be read
-/// or parsed.
+/// Panics if the sample cannot be read or the model fails the check.
+pub async fn self_check_decision_engine(engine: &GoRulesDecisionEngine, path: &Path) {
+    let sample = std::env::var("JOURNEY_DECISION_ENGINE_SAMPLE_CONTEXT_PATH").map_or_else(
+        |_| serde_json::json!({}),
+        |sample_path| {
+            let content = std::fs::read_to_string(&sample_path).unwrap_or_else(|e| {
+                panic!("JOURNEY_DECISION_ENGINE_SAMPLE_CONTEXT_PATH={sample_path:?}: cannot read file: {e}")
+            });
+            serde_json::from_str(&content).unwrap_or_else(|e| {
+                panic!("JOURNEY_DECISION_ENGINE_SAMPLE_CONTEXT_PATH={sample_path:?}: invalid JSON: {e}")
+            })
+        },
+    );
+    if let Err(e) = engine.self_check(&sample).await {
+        panic!(
+            "JOURNEY_DECISION_ENGINE_PATH={}: decision model failed its self-check: {e}",
+            path.display()
+        );
+    }
+}
+
+/// Whether the environment variable `name` is set to `1` or `true`.
+fn env_flag(name: &str) -> bool {
+    std::env::var(name).is_ok_and(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+}
+
+/// Load a [`JsonSchemaValidator`] from the file at `path`. Relative `$ref`s
+/// resolve against the file's directory.
+///
+/// # Panics
+///
+/// Panics if the file cannot be read or parsed.
 #[must_use]
-pub fn load_schema_validator() -> std::sync::Arc<JsonSchemaValidator> {
-    let path = std::env::var("JOURNEY_DATA_SCHEMA_PATH")
-        .expect("JOURNEY_DATA_SCHEMA_PATH environment variable must be set");
-    let content = std::fs::read_to_string(&path)
-        .unwrap_or_else(|e| panic!("JOURNEY_DATA_SCHEMA_PATH={path:?}: cannot read file: {e}"));
-    std::sync::Arc::new(
-        JsonSchemaValidator::from_json_str(&content).unwrap_or_else(|e| {
-            panic!("JOURNEY_DATA_SCHEMA_PATH={path:?}: invalid JSON schema: {e}")
-        }),
-    )
+pub fn load_schema_validator(path: &Path) -> Arc<JsonSchemaValidator> {
+    Arc::new(JsonSchemaValidator::from_path(path).unwrap_or_else(|e| {
+        panic!(
+            "JOURNEY_DATA_SCHEMA_PATH={}: invalid JSON schema: {e}",
+            path.display()
+        )
+    }))
 }
 
 /// Load an [`AttributeSchema`] from the path named by `JOURNEY_ATTRIBUTE_SCHEMA_PATH`.
@@ -83,21 +167,120 @@ pub fn load_attribute_schema() -> std::sync::Arc<AttributeSchema> {
     )
 }
 
+/// How long idempotency keys are remembered: `JOURNEY_IDEMPOTENCY_TTL_SECS`
+/// seconds, or [`DEFAULT_IDEMPOTENCY_TTL`].
+///
+/// # Panics
+///
+/// Panics if `JOURNEY_IDEMPOTENCY_TTL_SECS` is set but is not a whole number.
+fn idempotency_ttl() -> Duration {
+    std::env::var("JOURNEY_IDEMPOTENCY_TTL_SECS").map_or(DEFAULT_IDEMPOTENCY_TTL, |secs| {
+        Duration::from_secs(secs.parse().unwrap_or_else(|e| {
+            panic!("JOURNEY_IDEMPOTENCY_TTL_SECS={secs:?}: not a number of seconds: {e}")
+        }))
+    })
+}
+
+/// How long an unfinished idempotency claim holds its key:
+/// `JOURNEY_IDEMPOTENCY_LEASE_SECS` seconds, or [`DEFAULT_IDEMPOTENCY_LEASE`].
+///
+/// # Panics
+///
+/// Panics if `JOURNEY_IDEMPOTENCY_LEASE_SECS` is set but is not a whole number.
+fn idempotency_lease() -> Duration {
+    std::env::var("JOURNEY_IDEMPOTENCY_LEASE_SECS").map_or(DEFAULT_IDEMPOTENCY_LEASE, |secs| {
+        Duration::from_secs(secs.parse().unwrap_or_else(|e| {
+            panic!("JOURNEY_IDEMPOTENCY_LEASE_SECS={secs:?}: not a number of seconds: {e}")
+        }))
+    })
+}
+
+/// Spawn the background re-wrap sweeper.  It polls every 5 minutes and re-wraps
+/// any DEKs still encrypted under a retired KEK version.  Safe to run alongside
+/// live traffic — the CAS UPDATE in `rewrap_key` makes concurrent re-wraps idempotent.
+fn spawn_rewrap_worker(key_store: Arc<dyn KeyStore>, provider: Arc<dyn KekProvider>) {
+    let worker = RewrapWorker::new(key_store, provider, RewrapWorkerOptions::default());
+    tokio::spawn(async move {
+        worker
+            .run_forever(
+                #[allow(clippy::duration_suboptimal_units)]
+                std::time::Duration::from_secs(5 * 60),
+            )
+            .await;
+    });
+}
+
+/// Run `sweep` every `sweep.interval` for as long as the process lives. It
+/// only redacts views; the events of swept journeys stay in the event store.
+fn spawn_retention_sweep(
+    journey_query: Arc<StructuredJourneyViewRepository>,
+    sweep: RetentionSweep,
+) {
+    tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(sweep.interval);
+        loop {
+            ticks.tick().await;
+            match journey_query
+                .sweep_stale(sweep.older_than, &sweep.states)
+                .await
+            {
+                Ok(0) => {}
+                Ok(swept) => println!("Retention sweep forgot {swept} stale journey views"),
+                Err(err) => eprintln!("Error: {err:#?}"),
+            }
+        }
+    });
+}
+
+/// The cipher keyed by `JOURNEY_VIEW_ENCRYPTION_KEY`, or `None` when it is
+/// not set.
+///
+/// The key is read as [`Config::view_encryption_key`] reads it, for tools,
+/// such as `rebuild_views`, that do not read a whole [`Config`].
+///
+/// # Panics
+///
+/// Panics if `JOURNEY_VIEW_ENCRYPTION_KEY` is not valid base64 or does not
+/// decode to exactly 32 bytes.
+#[cfg(feature = "encryption")]
+#[must_use]
+pub fn view_pii_cipher() -> Option<Arc<dyn PiiCipher>> {
+    let key = std::env::var("JOURNEY_VIEW_ENCRYPTION_KEY")
+        .ok()
+        .filter(|key| !key.is_empty())?;
+    let key = ViewEncryptionKey::from_base64(&key)
+        .unwrap_or_else(|reason| panic!("JOURNEY_VIEW_ENCRYPTION_KEY: {reason}"));
+    Some(key.cipher())
+}
+
+/// The structured view repository, configured by `config`.
+fn journey_view_repository(
+    pool: Pool<Postgres>,
+    updates: JourneyUpdates,
+    config: &Config,
+) -> StructuredJourneyViewRepository {
+    let repo = StructuredJourneyViewRepository::new(pool)
+        .with_updates(updates)
+        .with_embedded_persons(config.embed_persons_in_view);
+    #[cfg(feature = "encryption")]
+    let repo = match &config.view_encryption_key {
+        Some(key) => repo.with_pii_cipher(key.cipher()),
+        None => repo,
+    };
+    repo
+}
+
 /// # Panics
 ///
 /// Panics if:
-/// - `DATABASE_URL` environment variable is not set
+/// - The database named by [`Config::database_url`] cannot be reached
 /// - `JOURNEY_KEK_PRIMARY` or the corresponding key variable are not set, are
 ///   not valid base64, or do not decode to exactly 32 bytes each
 /// - Database migrations fail
+/// - `JOURNEY_IDEMPOTENCY_TTL_SECS` is set but is not a whole number
 #[allow(clippy::missing_panics_doc)]
-pub async fn new_application_state() -> ApplicationState {
-    let pool = default_postgress_pool(
-        std::env::var("DATABASE_URL")
-            .expect("DATABASE_URL environment variable must be set")
-            .as_str(),
-    )
-    .await;
+pub async fn new_application_state(config: &Config) -> ApplicationState {
+    let pool = default_postgress_pool(&config.database_url).await;
 
     sqlx::migrate!("../../migrations")
         .run(&pool)
@@ -115,24 +298,23 @@ pub async fn new_application_state() -> ApplicationState {
     //   JOURNEY_KEK=<base64>
     //
     // We prefer the multi-version schema; fall back to the single-version variable.
-    let provider: Arc<dyn cqrs_es_crypto::KekProvider> =
-        if std::env::var("JOURNEY_KEK_PRIMARY").is_ok() {
-            Arc::new(
-                StaticKekProvider::from_env("JOURNEY_KEK")
-                    .expect("JOURNEY_KEK_PRIMARY / JOURNEY_KEK_<id> env config is invalid"),
-            )
-        } else {
-            // Legacy single-variable path: JOURNEY_KEK=<base64-encoded 32-byte key>
-            let kek_b64 = std::env::var("JOURNEY_KEK")
-                .expect("Either JOURNEY_KEK_PRIMARY or JOURNEY_KEK must be set");
-            let kek = BASE64
-                .decode(kek_b64.trim())
-                .expect("JOURNEY_KEK must be valid base64");
-            Arc::new(
-                StaticKekProvider::single("legacy:v1", kek)
-                    .expect("JOURNEY_KEK must decode to exactly 32 bytes"),
-            )
-        };
+    let provider: Arc<dyn KekProvider> = if std::env::var("JOURNEY_KEK_PRIMARY").is_ok() {
+        Arc::new(
+            StaticKekProvider::from_env("JOURNEY_KEK")
+                .expect("JOURNEY_KEK_PRIMARY / JOURNEY_KEK_<id> env config is invalid"),
+        )
+    } else {
+        // Legacy single-variable path: JOURNEY_KEK=<base64-encoded 32-byte key>
+        let kek_b64 = std::env::var("JOURNEY_KEK")
+            .expect("Either JOURNEY_KEK_PRIMARY or JOURNEY_KEK must be set");
+        let kek = BASE64
+            .decode(kek_b64.trim())
+            .expect("JOURNEY_KEK must be valid base64");
+        Arc::new(
+            StaticKekProvider::single("legacy:v1", kek)
+                .expect("JOURNEY_KEK must decode to exactly 32 bytes"),
+        )
+    };
 
     // Boot-time fail-fast: verify every kek_id in the database is resolvable
     // by the current provider.  If not, a KEK version was retired before all DEKs
@@ -167,34 +349,57 @@ pub async fn new_application_state() -> ApplicationState {
     // AES-256-GCM field encryption — it does not need the KEK at all.
     let cipher = FieldCipher::new();
 
-    let (cqrs, journey_query) = cqrs_framework(
+    let journey_updates = JourneyUpdates::default();
+
+    // The model is static for the life of the process, so load it once,
+    // check it and list its steps, and serve commands with that instance.
+    let decision_engine = load_decision_engine(&config.jdm_model_path, config.decision_tracing);
+    self_check_decision_engine(&decision_engine, &config.jdm_model_path).await;
+    let workflow_steps = Arc::new(decision_engine.list_steps());
+    let journey_services = journey_services(config, decision_engine);
+
+    let journey_view_repo = journey_view_repository(pool.clone(), journey_updates.clone(), config);
+
+    let (cqrs, journey_query, view_batcher) = cqrs_framework(
         pool.clone(),
         Arc::clone(&key_store),
         cipher,
         Arc::clone(&provider),
+        journey_view_repo,
+        journey_services.clone(),
+        config.view_batching,
     );
 
-    // Spawn the background re-wrap sweeper.  It polls every 5 minutes and re-wraps
-    // any DEKs still encrypted under a retired KEK version.  Safe to run alongside
-    // live traffic — the CAS UPDATE in rewrap_key makes concurrent re-wraps idempotent.
-    let worker = RewrapWorker::new(
-        Arc::clone(&key_store),
-        Arc::clone(&provider),
-        RewrapWorkerOptions::default(),
+    let event_store = event_history_store(pool.clone(), Arc::clone(&key_store));
+    let event_feed = Arc::new(EventFeed::new(pool.clone(), Arc::clone(&key_store)));
+
+    let idempotency = Arc::new(
+        PostgresIdempotencyStore::new(pool.clone())
+            .with_ttl(idempotency_ttl())
+            .with_lease(idempotency_lease()),
     );
-    tokio::spawn(async move {
-        worker
-            .run_forever(
-                #[allow(clippy::duration_suboptimal_units)]
-                std::time::Duration::from_secs(5 * 60),
-            )
-            .await;
-    });
+    let rejections = Arc::new(PostgresRejectionLog::new(pool.clone()));
+    let command_log = CommandLog::new(pool.clone());
+
+    spawn_rewrap_worker(Arc::clone(&key_store), provider);
+
+    if let Some(sweep) = config.retention.clone() {
+        spawn_retention_sweep(Arc::clone(&journey_query), sweep);
+    }
 
     ApplicationState {
         pool,
         cqrs,
         journey_query,
+        view_batcher,
+        event_store,
+        event_feed,
+        journey_updates,
         key_store,
+        journey_services: Arc::new(journey_services),
+        workflow_steps,
+        idempotency,
+        rejections,
+        command_log,
     }
 }
@@ -1,17 +1,59 @@
-use cqrs_es::{EventEnvelope, Query};
+use chrono::{DateTime, Utc};
+use cqrs_es::{Aggregate, EventEnvelope, Query};
 use futures_util::{Stream, TryStreamExt, stream};
-use sqlx::{Pool, Postgres, Row};
-use std::collections::{HashMap, VecDeque};
+use sqlx::{FromRow, Pool, Postgres, Row, types::Json};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
 use uuid::Uuid;
 
 use serde_json::{Value, json};
 
 use crate::{
-    domain::{assign_all, events::JourneyEvent, journey::Journey},
-    queries::{JourneyState, JourneyView, PersonView, WorkflowDecisionView},
+    domain::{
+        assign_all,
+        events::{EventTime, JourneyEvent},
+        journey::Journey,
+        merge_diff, step_patch,
+    },
+    journey_updates::JourneyUpdates,
+    pii_cipher::{self, PiiCipher},
+    queries::{
+        DecisionRecord, FunnelStep, JOURNEY_EXPORT_FORMAT_VERSION, JourneyExport, JourneyFilter,
+        JourneyPage, JourneyState, JourneyView, PersonView, PersonWithStatus, ResumeState,
+        StepDwell, StepTransition, WorkflowDecisionView,
+    },
+    services::decision_engine::RankedAction,
 };
 use jsonptr::PointerBuf;
 
+/// Why a journey view could not be read or written.
+#[derive(Debug, Error)]
+pub enum ViewRepositoryError {
+    /// No view exists for the journey.
+    #[error("no view for journey {0}")]
+    NotFound(Uuid),
+    /// A view id that is not a UUID, as dispatched by the event store.
+    #[error("invalid journey id '{id}': {source}")]
+    InvalidJourneyId {
+        id: String,
+        #[source]
+        source: uuid::Error,
+    },
+    /// Stored data that cannot be read back, such as an event payload that
+    /// no longer deserializes or a person field that fails to decrypt.
+    #[error("inconsistent journey view data: {0}")]
+    InconsistentRow(String),
+    /// A query the repository cannot answer as configured, such as a name
+    /// search while names are encrypted.
+    #[error("unsupported query: {0}")]
+    Unsupported(&'static str),
+    /// The database failed.
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
 /// Deep-merge `patch` into `target` using JSON Merge Patch (RFC 7396).
 /// This is the Rust equivalent of what `PostgreSQL`'s `||` should do but
 /// doesn't — `||` is a shallow merge that replaces top-level keys entirely.
@@ -20,10 +62,86 @@ fn deep_merge(target: &mut Value, patch: &Value) {
     json_patch::merge(target, patch);
 }
 
+/// Types of the events that change a journey's `shared_data`, replayed by
+/// [`StructuredJourneyViewRepository::data_at_version`].
+const DATA_EVENT_TYPES: [&str; 4] = [
+    "JourneyModified",
+    "JourneyCorrected",
+    "AttributesSet",
+    "JourneyForgotten",
+];
+
+/// The operations the service needs from a journey view store.
+///
+/// [`StructuredJourneyViewRepository`] is the Postgres implementation and the
+/// default. [`InMemoryJourneyViewRepository`](crate::memory_view_repository::InMemoryJourneyViewRepository)
+/// keeps views in process memory, and the `sqlite` feature adds
+/// `SqliteJourneyViewRepository`; neither needs a database server, for local
+/// development and tests.
+#[async_trait::async_trait]
+pub trait JourneyViewStore: Send + Sync {
+    /// Load a journey view by ID.
+    async fn load(&self, journey_id: &Uuid) -> Result<Option<JourneyView>, ViewRepositoryError>;
+
+    /// Load every journey view, most recently created first.
+    async fn load_all(&self) -> Result<Vec<JourneyView>, ViewRepositoryError>;
+
+    /// Journeys with a non-forgotten person whose email matches `email`,
+    /// ignoring case.
+    async fn find_by_email(&self, email: &str) -> Result<Vec<JourneyView>, ViewRepositoryError>;
+
+    /// Journeys whose `shared_data` structurally contains `fragment`, ordered
+    /// by journey ID.
+    async fn find_where_data_contains(
+        &self,
+        fragment: &Value,
+    ) -> Result<Vec<JourneyView>, ViewRepositoryError>;
+
+    /// Project `events` onto the view of `journey_id`, atomically.
+    async fn apply_events(
+        &self,
+        journey_id: Uuid,
+        events: &[EventEnvelope<Journey>],
+    ) -> Result<(), ViewRepositoryError>;
+}
+
+/// Parse the `journey_view.state` column.
+fn parse_state(state: &str) -> JourneyState {
+    match state {
+        "Complete" => JourneyState::Complete,
+        "Paused" => JourneyState::Paused,
+        _ => JourneyState::InProgress,
+    }
+}
+
+/// The `journey_view.state` column value for `state`.
+const fn state_column(state: JourneyState) -> &'static str {
+    match state {
+        JourneyState::InProgress => "InProgress",
+        JourneyState::Paused => "Paused",
+        JourneyState::Complete => "Complete",
+    }
+}
+
+/// Escape `LIKE` wildcards so `text` matches literally.
+fn escape_like(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
 /// A structured database view repository for journeys.
 #[derive(Clone)]
 pub struct StructuredJourneyViewRepository {
     pool: Pool<Postgres>,
+    updates: Option<JourneyUpdates>,
+    embed_persons: bool,
+    pii_cipher: Option<Arc<dyn PiiCipher>>,
 }
 
 struct LoadAllState<'a> {
@@ -37,7 +155,76 @@ struct LoadAllState<'a> {
 impl StructuredJourneyViewRepository {
     #[must_use]
     pub const fn new(pool: Pool<Postgres>) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            updates: None,
+            embed_persons: false,
+            pii_cipher: None,
+        }
+    }
+
+    /// Publish the reloaded view to `updates` after every committed dispatch.
+    ///
+    /// The view is only reloaded when the journey has at least one subscriber.
+    #[must_use]
+    pub fn with_updates(mut self, updates: JourneyUpdates) -> Self {
+        self.updates = Some(updates);
+        self
+    }
+
+    /// Also copy each captured person's name, email and phone into
+    /// `shared_data` under `person.<person_ref>`, so a single view shows them.
+    ///
+    /// `journey_person` stays the source of truth. The copy is removed again
+    /// when the subject or the journey is forgotten, whether or not this is
+    /// enabled.
+    ///
+    /// The copy would be plaintext, so nothing is embedded while a
+    /// [PII cipher](Self::with_pii_cipher) is set.
+    #[must_use]
+    pub const fn with_embedded_persons(mut self, embed_persons: bool) -> Self {
+        self.embed_persons = embed_persons;
+        self
+    }
+
+    /// Encrypt each person's name, email and phone in `journey_person` with
+    /// `cipher`, decrypting them again on read; see [`crate::pii_cipher`].
+    ///
+    /// Only those columns are encrypted: `details` stays readable, and
+    /// identities are no longer copied into `shared_data` even with
+    /// [`Self::with_embedded_persons`]. Names can no longer be searched; see
+    /// [`Self::find_persons_by_name`].
+    #[must_use]
+    pub fn with_pii_cipher(mut self, cipher: Arc<dyn PiiCipher>) -> Self {
+        self.pii_cipher = Some(cipher);
+        self
+    }
+
+    /// `value` as stored in `journey_person`: sealed when a cipher is set.
+    fn seal(&self, value: &str) -> String {
+        self.pii_cipher.as_deref().map_or_else(
+            || value.to_string(),
+            |cipher| pii_cipher::seal(cipher, value),
+        )
+    }
+
+    /// Decrypt the identity fields of `persons` read from `journey_person`.
+    fn reveal(&self, mut persons: Vec<PersonView>) -> Result<Vec<PersonView>, ViewRepositoryError> {
+        let Some(cipher) = self.pii_cipher.as_deref() else {
+            return Ok(persons);
+        };
+        let open = |value: Option<String>| {
+            value
+                .map(|value| pii_cipher::open(cipher, value))
+                .transpose()
+                .map_err(|e| ViewRepositoryError::InconsistentRow(format!("person field: {e}")))
+        };
+        for person in &mut persons {
+            person.name = open(person.name.take())?;
+            person.email = open(person.email.take())?;
+            person.phone = open(person.phone.take())?;
+        }
+        Ok(persons)
     }
 
     /// Load a journey view by ID.
@@ -45,7 +232,10 @@ impl StructuredJourneyViewRepository {
     /// # Errors
     ///
     /// Returns an error if the database query fails.
-    pub async fn load(&self, journey_id: &Uuid) -> Result<Option<JourneyView>, sqlx::Error> {
+    pub async fn load(
+        &self,
+        journey_id: &Uuid,
+    ) -> Result<Option<JourneyView>, ViewRepositoryError> {
         let mut tx = self.begin_repeatable_read().await?;
         self.load_in_tx(&mut tx, journey_id).await
     }
@@ -58,10 +248,12 @@ impl StructuredJourneyViewRepository {
         &self,
         tx: &mut sqlx::Transaction<'_, Postgres>,
         journey_id: &Uuid,
-    ) -> Result<Option<JourneyView>, sqlx::Error> {
+    ) -> Result<Option<JourneyView>, ViewRepositoryError> {
         let journey_row = sqlx::query(
             r"
-            SELECT id, state, shared_data, current_step, version
+            SELECT id, journey_type, state, shared_data, draft_data, current_step, version,
+                   paused_at, resumed_at, paused_duration_ms, forgotten,
+                   created_at, updated_at
             FROM journey_view
             WHERE id = $1
             ",
@@ -75,40 +267,32 @@ impl StructuredJourneyViewRepository {
         };
 
         let id: Uuid = row.get("id");
-        let state = match row.get::<String, _>("state").as_str() {
-            "Complete" => JourneyState::Complete,
-            _ => JourneyState::InProgress,
-        };
+        let state = parse_state(&row.get::<String, _>("state"));
         let current_step: Option<String> = row.get("current_step");
         let shared_data: Value = row.get("shared_data");
 
-        let workflow_row = sqlx::query(
-            r"
-            SELECT suggested_actions, phase
-            FROM journey_workflow_decision
-            WHERE journey_id = $1 AND is_latest = TRUE
-            ORDER BY created_at DESC
-            LIMIT 1
-            ",
-        )
-        .bind(journey_id)
-        .fetch_optional(&mut **tx)
-        .await?;
-
-        let latest_workflow_decision = workflow_row.map(|r| WorkflowDecisionView {
-            suggested_actions: r.get("suggested_actions"),
-            phase: r.get("phase"),
-        });
-
+        let latest_workflow_decision =
+            Self::load_latest_decision_with(&mut **tx, journey_id).await?;
         let persons = self.load_persons_with(&mut **tx, journey_id).await?;
+        let tags = Self::load_tags_with(&mut **tx, journey_id).await?;
 
         Ok(Some(JourneyView {
             id,
+            journey_type: row.get("journey_type"),
             state,
             shared_data,
+            draft_data: row.get("draft_data"),
             current_step,
             latest_workflow_decision,
             persons,
+            paused_at: row.get("paused_at"),
+            resumed_at: row.get("resumed_at"),
+            paused_duration_ms: row.get("paused_duration_ms"),
+            forgotten: row.get("forgotten"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+            tags,
+            version: row.get("version"),
         }))
     }
 
@@ -117,7 +301,7 @@ impl StructuredJourneyViewRepository {
     /// # Errors
     ///
     /// Returns an error if the database query fails.
-    pub async fn load_all(&self) -> Result<Vec<JourneyView>, sqlx::Error> {
+    pub async fn load_all(&self) -> Result<Vec<JourneyView>, ViewRepositoryError> {
         self.stream_all().await?.try_collect().await
     }
 
@@ -131,7 +315,8 @@ impl StructuredJourneyViewRepository {
     /// Returns an error if the transaction cannot be opened.
     pub async fn stream_all(
         &self,
-    ) -> Result<impl Stream<Item = Result<JourneyView, sqlx::Error>>, sqlx::Error> {
+    ) -> Result<impl Stream<Item = Result<JourneyView, ViewRepositoryError>>, ViewRepositoryError>
+    {
         let tx = self.begin_repeatable_read().await?;
         let state = LoadAllState {
             repo: self.clone(),
@@ -156,8 +341,9 @@ impl StructuredJourneyViewRepository {
                     return Ok(None);
                 }
 
-                let batch_len = i64::try_from(views.len())
-                    .map_err(|_| sqlx::Error::Protocol("journey batch length overflow".into()))?;
+                let batch_len = i64::try_from(views.len()).map_err(|_| {
+                    ViewRepositoryError::InconsistentRow("journey batch length overflow".into())
+                })?;
                 state.offset += batch_len;
                 state.pending_views = views.into();
             }
@@ -169,7 +355,10 @@ impl StructuredJourneyViewRepository {
     /// # Errors
     ///
     /// Returns an error if the database query fails.
-    pub async fn load_persons(&self, journey_id: &Uuid) -> Result<Vec<PersonView>, sqlx::Error> {
+    pub async fn load_persons(
+        &self,
+        journey_id: &Uuid,
+    ) -> Result<Vec<PersonView>, ViewRepositoryError> {
         self.load_persons_with(&self.pool, journey_id).await
     }
 
@@ -182,7 +371,9 @@ impl StructuredJourneyViewRepository {
     /// Callers that only read data do not need to commit; dropping the returned
     /// transaction rolls it back, which is equivalent to a commit for a
     /// read-only transaction in Postgres.
-    async fn begin_repeatable_read(&self) -> Result<sqlx::Transaction<'_, Postgres>, sqlx::Error> {
+    async fn begin_repeatable_read(
+        &self,
+    ) -> Result<sqlx::Transaction<'_, Postgres>, ViewRepositoryError> {
         let mut tx = self.pool.begin().await?;
         sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ")
             .execute(&mut *tx)
@@ -194,11 +385,11 @@ impl StructuredJourneyViewRepository {
         &self,
         executor: E,
         journey_id: &Uuid,
-    ) -> Result<Vec<PersonView>, sqlx::Error>
+    ) -> Result<Vec<PersonView>, ViewRepositoryError>
     where
         E: sqlx::Executor<'e, Database = Postgres>,
     {
-        sqlx::query_as::<_, PersonView>(
+        let persons = sqlx::query_as::<_, PersonView>(
             r"
             SELECT journey_id, person_ref, subject_id,
                    name, email, phone, details, forgotten
@@ -209,7 +400,8 @@ impl StructuredJourneyViewRepository {
         )
         .bind(journey_id)
         .fetch_all(executor)
-        .await
+        .await?;
+        self.reveal(persons)
     }
 
     #[allow(deprecated)]
@@ -218,16 +410,25 @@ impl StructuredJourneyViewRepository {
         tx: &mut sqlx::Transaction<'_, Postgres>,
         limit: i64,
         offset: i64,
-    ) -> Result<Vec<JourneyView>, sqlx::Error> {
+    ) -> Result<Vec<JourneyView>, ViewRepositoryError> {
         let rows = sqlx::query(
             r"
             SELECT j.id,
+                   j.journey_type,
                    j.state,
                    j.shared_data,
+                   j.draft_data,
                    j.current_step,
                    j.version,
+                   j.paused_at,
+                   j.resumed_at,
+                   j.paused_duration_ms,
+                   j.forgotten,
+                   j.created_at,
+                   j.updated_at,
                    w.suggested_actions,
-                   w.phase
+                   w.phase,
+                   w.ranked_actions
             FROM journey_view AS j
             LEFT JOIN journey_workflow_decision AS w
               ON w.journey_id = j.id
@@ -251,27 +452,36 @@ impl StructuredJourneyViewRepository {
 
         for row in rows {
             let id: Uuid = row.get("id");
-            let state = match row.get::<String, _>("state").as_str() {
-                "Complete" => JourneyState::Complete,
-                _ => JourneyState::InProgress,
-            };
+            let state = parse_state(&row.get::<String, _>("state"));
             let suggested_actions: Option<Vec<String>> = row.get("suggested_actions");
 
             view_index.insert(id, views.len());
             journey_ids.push(id);
             let phase: Option<String> = row.get("phase");
+            let ranked_actions: Option<Json<Vec<RankedAction>>> = row.get("ranked_actions");
             views.push(JourneyView {
                 id,
+                journey_type: row.get("journey_type"),
                 state,
                 shared_data: row.get("shared_data"),
+                draft_data: row.get("draft_data"),
                 current_step: row.get("current_step"),
                 latest_workflow_decision: suggested_actions.map(|suggested_actions| {
                     WorkflowDecisionView {
                         suggested_actions,
                         phase,
+                        ranked_actions: ranked_actions.map(|r| r.0).unwrap_or_default(),
                     }
                 }),
                 persons: Vec::new(),
+                paused_at: row.get("paused_at"),
+                resumed_at: row.get("resumed_at"),
+                paused_duration_ms: row.get("paused_duration_ms"),
+                forgotten: row.get("forgotten"),
+                created_at: row.get("created_at"),
+                updated_at: row.get("updated_at"),
+                tags: BTreeMap::new(),
+                version: row.get("version"),
             });
         }
 
@@ -288,33 +498,71 @@ impl StructuredJourneyViewRepository {
         .fetch_all(&mut **tx)
         .await?;
 
-        for person in persons {
+        for person in self.reveal(persons)? {
             if let Some(index) = view_index.get(&person.journey_id) {
                 views[*index].persons.push(person);
             }
         }
 
+        let tags: Vec<(Uuid, String, String)> = sqlx::query_as(
+            "SELECT journey_id, key, value FROM journey_tags WHERE journey_id = ANY($1)",
+        )
+        .bind(&journey_ids)
+        .fetch_all(&mut **tx)
+        .await?;
+
+        for (journey_id, key, value) in tags {
+            if let Some(index) = view_index.get(&journey_id) {
+                views[*index].tags.insert(key, value);
+            }
+        }
+
         Ok(views)
     }
 
+    async fn load_tags_with<'e, E>(
+        executor: E,
+        journey_id: &Uuid,
+    ) -> Result<BTreeMap<String, String>, ViewRepositoryError>
+    where
+        E: sqlx::Executor<'e, Database = Postgres>,
+    {
+        let tags: Vec<(String, String)> =
+            sqlx::query_as("SELECT key, value FROM journey_tags WHERE journey_id = $1")
+                .bind(journey_id)
+                .fetch_all(executor)
+                .await?;
+        Ok(tags.into_iter().collect())
+    }
+
     /// Find journeys that have a non-forgotten person with the given email address.
     ///
-    /// The comparison is case-insensitive.
+    /// The comparison is case-insensitive. With a cipher set, encrypted rows
+    /// are matched on their `email_index` blind index.
     ///
     /// # Errors
     ///
     /// Returns an error if the database query fails.
-    pub async fn find_by_email(&self, email: &str) -> Result<Vec<JourneyView>, sqlx::Error> {
+    pub async fn find_by_email(
+        &self,
+        email: &str,
+    ) -> Result<Vec<JourneyView>, ViewRepositoryError> {
         let mut tx = self.begin_repeatable_read().await?;
 
         let rows = sqlx::query(
             r"
             SELECT DISTINCT journey_id
             FROM journey_person
-            WHERE lower(email) = lower($1) AND forgotten = FALSE
+            WHERE (lower(email) = lower($1) OR email_index = $2)
+              AND forgotten = FALSE
             ",
         )
         .bind(email)
+        .bind(
+            self.pii_cipher
+                .as_deref()
+                .map(|cipher| pii_cipher::email_index(cipher, email)),
+        )
         .fetch_all(&mut *tx)
         .await?;
 
@@ -328,166 +576,1007 @@ impl StructuredJourneyViewRepository {
         Ok(views)
     }
 
-    /// Load all person slots across all journeys, ordered by `(journey_id, person_ref)`.
+    /// Find journeys started with the given `journey_type`, newest first.
+    ///
+    /// Backed by the `idx_journey_view_journey_type` index.
     ///
     /// # Errors
     ///
     /// Returns an error if the database query fails.
-    pub async fn load_all_persons(&self) -> Result<Vec<PersonView>, sqlx::Error> {
-        sqlx::query_as::<_, PersonView>(
+    pub async fn find_by_type(
+        &self,
+        journey_type: &str,
+    ) -> Result<Vec<JourneyView>, ViewRepositoryError> {
+        let mut tx = self.begin_repeatable_read().await?;
+
+        let ids: Vec<Uuid> = sqlx::query_scalar(
             r"
-            SELECT journey_id, person_ref, subject_id,
-                   name, email, phone, details, forgotten
-            FROM journey_person
-            ORDER BY journey_id, person_ref
+            SELECT id
+            FROM journey_view
+            WHERE journey_type = $1
+            ORDER BY created_at DESC, id
             ",
         )
-        .fetch_all(&self.pool)
-        .await
+        .bind(journey_type)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut views = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(view) = self.load_in_tx(&mut tx, &id).await? {
+                views.push(view);
+            }
+        }
+        Ok(views)
     }
 
-    /// Find all journey aggregate IDs that have referenced the given subject.
-    ///
-    /// Searches three event types in the event store — all carry `subject_id`
-    /// in plaintext or in an unencrypted index array, so no decryption is
-    /// needed:
+    /// Find journeys tagged `key` = `value`, newest first.
     ///
-    /// - `PersonCaptured` / `PersonDetailsUpdated` — legacy and current
-    ///   identity-capture events; subject UUID is a top-level string field.
-    /// - `AttributesSet` — new path-keyed command; the crypto layer writes a
-    ///   `subjects` array of UUID strings alongside the encrypted partitions.
-    ///   Queried with a GIN array-containment predicate backed by
-    ///   `idx_events_attributes_set_subjects`.
+    /// Backed by the `idx_journey_tags_key_value` index.
     ///
     /// # Errors
     ///
     /// Returns an error if the database query fails.
-    pub async fn find_journeys_by_subject(
+    pub async fn find_by_tag(
         &self,
-        subject_id: &Uuid,
-    ) -> Result<Vec<String>, sqlx::Error> {
-        let rows = sqlx::query(
+        key: &str,
+        value: &str,
+    ) -> Result<Vec<JourneyView>, ViewRepositoryError> {
+        let mut tx = self.begin_repeatable_read().await?;
+
+        let ids: Vec<Uuid> = sqlx::query_scalar(
             r"
-            SELECT DISTINCT aggregate_id
-            FROM events
-            WHERE aggregate_type = 'Journey'
-              AND (
-                (event_type = 'PersonCaptured'
-                 AND payload -> 'PersonCaptured' ->> 'subject_id' = $1)
-                OR
-                (event_type = 'PersonDetailsUpdated'
-                 AND payload -> 'PersonDetailsUpdated' ->> 'subject_id' = $1)
-                OR
-                (event_type = 'AttributesSet'
-                 AND payload -> 'AttributesSet' -> 'subjects'
-                     @> jsonb_build_array($1))
-              )
+            SELECT v.id
+            FROM journey_tags AS t
+            JOIN journey_view AS v ON v.id = t.journey_id
+            WHERE t.key = $1 AND t.value = $2
+            ORDER BY v.created_at DESC, v.id
             ",
         )
-        .bind(subject_id.to_string())
-        .fetch_all(&self.pool)
+        .bind(key)
+        .bind(value)
+        .fetch_all(&mut *tx)
         .await?;
 
-        Ok(rows
-            .into_iter()
-            .map(|r| r.get::<String, _>("aggregate_id"))
-            .collect())
+        let mut views = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(view) = self.load_in_tx(&mut tx, &id).await? {
+                views.push(view);
+            }
+        }
+        Ok(views)
     }
 
-    /// Find all non-forgotten subject IDs associated with the given email address.
+    /// One page of the journeys matching `filter`, newest first, with the
+    /// number of matches across all pages.
     ///
-    /// The comparison is case-insensitive. Subjects that have already been forgotten
-    /// are excluded, so a duplicate erasure request is a safe no-op.
+    /// The page and the total are read from the same snapshot, so they agree
+    /// even while journeys are being written.
     ///
     /// # Errors
     ///
     /// Returns an error if the database query fails.
-    pub async fn find_subjects_by_email(&self, email: &str) -> Result<Vec<Uuid>, sqlx::Error> {
-        // subject_lookup is the authoritative email → subject_id index.
-        // Rows are deleted on shredding, so no forgotten-filter is needed.
-        let rows = sqlx::query(
+    pub async fn find_page(
+        &self,
+        filter: &JourneyFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<JourneyPage, ViewRepositoryError> {
+        let mut tx = self.begin_repeatable_read().await?;
+        let state = filter.state.map(state_column);
+
+        let total: i64 = sqlx::query_scalar(
             r"
-            SELECT subject_id
-            FROM subject_lookup
-            WHERE email_lower = lower($1)
+            SELECT COUNT(*)
+            FROM journey_view
+            WHERE ($1::text IS NULL OR state = $1)
+              AND ($2::text IS NULL OR current_step = $2)
             ",
         )
-        .bind(email)
-        .fetch_all(&self.pool)
+        .bind(state)
+        .bind(filter.current_step.as_deref())
+        .fetch_one(&mut *tx)
         .await?;
 
-        Ok(rows
-            .into_iter()
-            .map(|r| r.get::<Uuid, _>("subject_id"))
-            .collect())
+        let ids: Vec<Uuid> = sqlx::query_scalar(
+            r"
+            SELECT id
+            FROM journey_view
+            WHERE ($1::text IS NULL OR state = $1)
+              AND ($2::text IS NULL OR current_step = $2)
+            ORDER BY created_at DESC, id
+            LIMIT $3 OFFSET $4
+            ",
+        )
+        .bind(state)
+        .bind(filter.current_step.as_deref())
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut items = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(view) = self.load_in_tx(&mut tx, &id).await? {
+                items.push(view);
+            }
+        }
+        Ok(JourneyPage {
+            items,
+            total,
+            limit,
+            offset,
+        })
     }
 
-    /// Remove the `subject_lookup` row for `subject_id`.
-    ///
-    /// Convenience wrapper for use outside a transaction. Production shredding
-    /// uses [`Self::delete_subject_lookup_in_tx`] to commit this deletion
-    /// atomically with the DEK deletion.
+    /// The latest workflow decision of `journey_id`, read from
+    /// `journey_workflow_decision` alone — without the journey's
+    /// `shared_data` or persons. `None` for an unknown journey or one not yet
+    /// evaluated.
     ///
     /// # Errors
     ///
     /// Returns an error if the database query fails.
-    pub async fn delete_subject_lookup(&self, subject_id: &Uuid) -> Result<(), sqlx::Error> {
-        Self::do_delete_subject_lookup(&self.pool, subject_id).await
+    pub async fn load_latest_decision(
+        &self,
+        journey_id: &Uuid,
+    ) -> Result<Option<WorkflowDecisionView>, ViewRepositoryError> {
+        Self::load_latest_decision_with(&self.pool, journey_id).await
     }
 
-    /// Remove the `subject_lookup` row for `subject_id` within an already-open transaction.
+    async fn load_latest_decision_with<'e, E>(
+        executor: E,
+        journey_id: &Uuid,
+    ) -> Result<Option<WorkflowDecisionView>, ViewRepositoryError>
+    where
+        E: sqlx::Executor<'e, Database = Postgres>,
+    {
+        let row = sqlx::query(
+            r"
+            SELECT suggested_actions, phase, ranked_actions
+            FROM journey_workflow_decision
+            WHERE journey_id = $1 AND is_latest = TRUE
+            ORDER BY created_at DESC
+            LIMIT 1
+            ",
+        )
+        .bind(journey_id)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(row.map(|r| WorkflowDecisionView {
+            suggested_actions: r.get("suggested_actions"),
+            phase: r.get("phase"),
+            ranked_actions: r.get::<Json<_>, _>("ranked_actions").0,
+        }))
+    }
+
+    /// Where to resume `journey_id`: its current step, completion and the
+    /// next actions of its latest decision, in a single query that skips
+    /// `shared_data` and persons. `None` for an unknown journey.
     ///
-    /// Used by the shredding route handler to commit the email-address deletion
-    /// atomically with the DEK deletion in a single transaction. This is the
-    /// GDPR erasure of the email address from this table.
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn resume_state(
+        &self,
+        journey_id: &Uuid,
+    ) -> Result<Option<ResumeState>, ViewRepositoryError> {
+        let row = sqlx::query(
+            r"
+            SELECT j.state, j.current_step, w.suggested_actions, w.ranked_actions
+            FROM journey_view AS j
+            LEFT JOIN journey_workflow_decision AS w
+                ON w.journey_id = j.id AND w.is_latest = TRUE
+            WHERE j.id = $1
+            ORDER BY w.created_at DESC
+            LIMIT 1
+            ",
+        )
+        .bind(journey_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|r| {
+            let suggested_actions: Vec<String> = r
+                .get::<Option<_>, _>("suggested_actions")
+                .unwrap_or_default();
+            let ranked_actions: Vec<RankedAction> = r
+                .get::<Option<Json<_>>, _>("ranked_actions")
+                .map(|ranked| ranked.0)
+                .unwrap_or_default();
+            let primary_next_step = ranked_actions
+                .into_iter()
+                .next()
+                .map(|ranked| ranked.action)
+                .or_else(|| suggested_actions.first().cloned());
+            ResumeState {
+                current_step: r.get("current_step"),
+                primary_next_step,
+                suggested_actions,
+                is_complete: parse_state(&r.get::<String, _>("state")) == JourneyState::Complete,
+            }
+        }))
+    }
+
+    /// Every step transition of `journey_id`, in event order, including
+    /// moves back to an earlier step. Empty for an unknown journey.
     ///
     /// # Errors
     ///
     /// Returns an error if the database query fails.
-    pub async fn delete_subject_lookup_in_tx(
+    pub async fn load_step_history(
         &self,
-        tx: &mut sqlx::Transaction<'_, Postgres>,
-        subject_id: &Uuid,
-    ) -> Result<(), sqlx::Error> {
-        Self::do_delete_subject_lookup(&mut **tx, subject_id).await
+        journey_id: &Uuid,
+    ) -> Result<Vec<StepTransition>, ViewRepositoryError> {
+        Self::load_step_history_with(&self.pool, journey_id).await
     }
 
-    async fn do_delete_subject_lookup<'e, E>(
+    async fn load_step_history_with<'e, E>(
         executor: E,
-        subject_id: &Uuid,
-    ) -> Result<(), sqlx::Error>
+        journey_id: &Uuid,
+    ) -> Result<Vec<StepTransition>, ViewRepositoryError>
     where
         E: sqlx::Executor<'e, Database = Postgres>,
     {
-        sqlx::query("DELETE FROM subject_lookup WHERE subject_id = $1")
-            .bind(subject_id)
-            .execute(executor)
-            .await?;
-        Ok(())
-    }
-
-    fn parse_journey_id(view_id: &str) -> Result<Uuid, sqlx::Error> {
-        Uuid::parse_str(view_id).map_err(|e| {
-            sqlx::Error::Decode(Box::new(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                format!("Invalid UUID: {e}"),
-            )))
-        })
+        Ok(sqlx::query_as::<_, StepTransition>(
+            r"
+            SELECT from_step, to_step, sequence, entered_at
+            FROM journey_step_history
+            WHERE journey_id = $1
+            ORDER BY sequence
+            ",
+        )
+        .bind(journey_id)
+        .fetch_all(executor)
+        .await?)
     }
 
-    #[allow(clippy::too_many_lines, clippy::cast_possible_wrap, deprecated)]
+    /// Everything recorded about `journey_id` as one [`JourneyExport`]: the
+    /// view with its persons, the step history and every workflow decision.
+    ///
+    /// All sections are read from the same snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ViewRepositoryError::NotFound`] for an unknown journey, or
+    /// an error if a database query fails.
+    pub async fn export(&self, journey_id: &Uuid) -> Result<JourneyExport, ViewRepositoryError> {
+        let mut tx = self.begin_repeatable_read().await?;
+        let journey = self
+            .load_in_tx(&mut tx, journey_id)
+            .await?
+            .ok_or(ViewRepositoryError::NotFound(*journey_id))?;
+        let step_history = Self::load_step_history_with(&mut *tx, journey_id).await?;
+
+        let decisions = sqlx::query(
+            r"
+            SELECT suggested_actions, phase, ranked_actions, created_at
+            FROM journey_workflow_decision
+            WHERE journey_id = $1
+            ORDER BY created_at, id
+            ",
+        )
+        .bind(journey_id)
+        .fetch_all(&mut *tx)
+        .await?
+        .into_iter()
+        .map(|row| DecisionRecord {
+            suggested_actions: row.get("suggested_actions"),
+            phase: row.get("phase"),
+            ranked_actions: row.get::<Json<_>, _>("ranked_actions").0,
+            decided_at: row.get("created_at"),
+        })
+        .collect();
+
+        Ok(JourneyExport {
+            format_version: JOURNEY_EXPORT_FORMAT_VERSION,
+            journey,
+            step_history,
+            decisions,
+        })
+    }
+
+    /// How long `journey_id` stayed on each step it entered, in event order.
+    ///
+    /// A visit lasts until the next step was entered or, for the last step,
+    /// until the journey completed. The step an incomplete journey is still on
+    /// has no duration. Empty for an unknown journey.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn dwell_times(
+        &self,
+        journey_id: &Uuid,
+    ) -> Result<Vec<StepDwell>, ViewRepositoryError> {
+        Ok(sqlx::query_as::<_, StepDwell>(
+            r"
+            SELECT h.to_step AS step,
+                   h.entered_at,
+                   (EXTRACT(EPOCH FROM COALESCE(
+                       LEAD(h.entered_at) OVER (ORDER BY h.sequence),
+                       v.completed_at
+                   ) - h.entered_at) * 1000)::BIGINT AS duration_ms
+            FROM journey_step_history h
+            JOIN journey_view v ON v.id = h.journey_id
+            WHERE h.journey_id = $1
+            ORDER BY h.sequence
+            ",
+        )
+        .bind(journey_id)
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Mean time from start to completion across completed journeys, in
+    /// milliseconds, not counting time spent paused. With `journey_type`,
+    /// only journeys of that type count. `None` if none have completed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn average_completion_time(
+        &self,
+        journey_type: Option<&str>,
+    ) -> Result<Option<i64>, ViewRepositoryError> {
+        Ok(sqlx::query_scalar(
+            r"
+            SELECT AVG(
+                       EXTRACT(EPOCH FROM completed_at - created_at) * 1000
+                       - paused_duration_ms
+                   )::BIGINT
+            FROM journey_view
+            WHERE completed_at IS NOT NULL
+              AND ($1::text IS NULL OR journey_type = $1)
+            ",
+        )
+        .bind(journey_type)
+        .fetch_one(&self.pool)
+        .await?)
+    }
+
+    /// Conversion counts per step: how many journeys ever entered each step
+    /// and how many of those went on to complete, most-reached step first.
+    ///
+    /// Built from `journey_step_history`, so a journey that moved past a step
+    /// still counts towards it. With `journey_type`, only journeys started
+    /// with that type are counted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn funnel(
+        &self,
+        journey_type: Option<&str>,
+    ) -> Result<Vec<FunnelStep>, ViewRepositoryError> {
+        Ok(sqlx::query_as::<_, FunnelStep>(
+            r"
+            SELECT h.to_step                                              AS step,
+                   COUNT(DISTINCT h.journey_id)                           AS reached,
+                   COUNT(DISTINCT h.journey_id) FILTER (
+                       WHERE v.state = 'Complete'
+                   )                                                      AS completed
+            FROM journey_step_history h
+            JOIN journey_view v ON v.id = h.journey_id
+            WHERE $1::text IS NULL OR v.journey_type = $1
+            GROUP BY h.to_step
+            ORDER BY reached DESC, step
+            ",
+        )
+        .bind(journey_type)
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Find non-forgotten persons whose name starts with `query`, most
+    /// recently updated first.
+    ///
+    /// The match is a case-insensitive prefix match, so `"ali"` finds
+    /// "Alice Smith" but not "Natalie"; `%` and `_` in `query` match
+    /// literally. A blank `query` matches nobody. Backed by the
+    /// `idx_journey_person_name_prefix` index. Substring search would need a
+    /// `pg_trgm` GIN index on `name` instead.
+    ///
+    /// With a cipher set, names cannot be matched in SQL and there is no
+    /// blind index for prefixes, so the search is refused rather than
+    /// decrypting every person.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ViewRepositoryError::Unsupported`] while a cipher is set, or
+    /// an error if the database query fails.
+    pub async fn find_persons_by_name(
+        &self,
+        query: &str,
+    ) -> Result<Vec<PersonView>, ViewRepositoryError> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        if self.pii_cipher.is_some() {
+            return Err(ViewRepositoryError::Unsupported(
+                "names are encrypted, so they cannot be searched",
+            ));
+        }
+
+        Ok(sqlx::query_as::<_, PersonView>(
+            r"
+            SELECT journey_id, person_ref, subject_id,
+                   name, email, phone, details, forgotten
+            FROM journey_person
+            WHERE lower(name) LIKE lower($1) || '%' ESCAPE '\'
+              AND forgotten = FALSE
+            ORDER BY updated_at DESC, journey_id, person_ref
+            ",
+        )
+        .bind(escape_like(query))
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Find journeys whose `shared_data` contains `fragment`, using JSONB
+    /// containment (`@>`) backed by the `idx_journey_shared_data` GIN index.
+    ///
+    /// `fragment` is matched structurally: `{"search": {"destination": "JFK"}}`
+    /// finds every journey searching for JFK regardless of its other data.
+    /// Results are ordered by journey id.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn find_where_data_contains(
+        &self,
+        fragment: &Value,
+    ) -> Result<Vec<JourneyView>, ViewRepositoryError> {
+        let mut tx = self.begin_repeatable_read().await?;
+
+        let ids: Vec<Uuid> = sqlx::query_scalar(
+            r"
+            SELECT id
+            FROM journey_view
+            WHERE shared_data @> $1
+            ORDER BY id
+            ",
+        )
+        .bind(fragment)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut views = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(view) = self.load_in_tx(&mut tx, &id).await? {
+                views.push(view);
+            }
+        }
+        Ok(views)
+    }
+
+    /// Load all person slots across all journeys, ordered by `(journey_id, person_ref)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn load_all_persons(&self) -> Result<Vec<PersonView>, ViewRepositoryError> {
+        let persons = sqlx::query_as::<_, PersonView>(
+            r"
+            SELECT journey_id, person_ref, subject_id,
+                   name, email, phone, details, forgotten
+            FROM journey_person
+            ORDER BY journey_id, person_ref
+            ",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        self.reveal(persons)
+    }
+
+    /// One page of person slots across all journeys, ordered by
+    /// `(journey_id, person_ref)`, each with its journey's state, current
+    /// step and completion time, read in a single query.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn persons_with_status(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<PersonWithStatus>, ViewRepositoryError> {
+        let rows = sqlx::query(
+            r"
+            SELECT p.journey_id, p.person_ref, p.subject_id,
+                   p.name, p.email, p.phone, p.details, p.forgotten,
+                   j.state, j.current_step, j.completed_at
+            FROM journey_person AS p
+            JOIN journey_view AS j ON j.id = p.journey_id
+            ORDER BY p.journey_id, p.person_ref
+            LIMIT $1 OFFSET $2
+            ",
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let persons = rows
+            .iter()
+            .map(PersonView::from_row)
+            .collect::<Result<Vec<_>, _>>()?;
+        let persons = self.reveal(persons)?;
+        Ok(persons
+            .into_iter()
+            .zip(&rows)
+            .map(|(person, row)| PersonWithStatus {
+                person,
+                state: parse_state(&row.get::<String, _>("state")),
+                current_step: row.get("current_step"),
+                completed_at: row.get("completed_at"),
+            })
+            .collect())
+    }
+
+    /// Find all journey aggregate IDs that have referenced the given subject.
+    ///
+    /// Searches three event types in the event store — all carry `subject_id`
+    /// in plaintext or in an unencrypted index array, so no decryption is
+    /// needed:
+    ///
+    /// - `PersonCaptured` / `PersonDetailsUpdated` — legacy and current
+    ///   identity-capture events; subject UUID is a top-level string field.
+    /// - `AttributesSet` — new path-keyed command; the crypto layer writes a
+    ///   `subjects` array of UUID strings alongside the encrypted partitions.
+    ///   Queried with a GIN array-containment predicate backed by
+    ///   `idx_events_attributes_set_subjects`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn find_journeys_by_subject(
+        &self,
+        subject_id: &Uuid,
+    ) -> Result<Vec<String>, ViewRepositoryError> {
+        let rows = sqlx::query(
+            r"
+            SELECT DISTINCT aggregate_id
+            FROM events
+            WHERE aggregate_type = 'Journey'
+              AND (
+                (event_type = 'PersonCaptured'
+                 AND payload -> 'PersonCaptured' ->> 'subject_id' = $1)
+                OR
+                (event_type = 'PersonDetailsUpdated'
+                 AND payload -> 'PersonDetailsUpdated' ->> 'subject_id' = $1)
+                OR
+                (event_type = 'AttributesSet'
+                 AND payload -> 'AttributesSet' -> 'subjects'
+                     @> jsonb_build_array($1))
+              )
+            ",
+        )
+        .bind(subject_id.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| r.get::<String, _>("aggregate_id"))
+            .collect())
+    }
+
+    /// Find all non-forgotten subject IDs associated with the given email address.
+    ///
+    /// The comparison is case-insensitive. Subjects that have already been forgotten
+    /// are excluded, so a duplicate erasure request is a safe no-op.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn find_subjects_by_email(
+        &self,
+        email: &str,
+    ) -> Result<Vec<Uuid>, ViewRepositoryError> {
+        // subject_lookup is the authoritative email → subject_id index.
+        // Rows are deleted on shredding, so no forgotten-filter is needed.
+        let rows = sqlx::query(
+            r"
+            SELECT subject_id
+            FROM subject_lookup
+            WHERE email_lower = lower($1)
+            ",
+        )
+        .bind(email)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| r.get::<Uuid, _>("subject_id"))
+            .collect())
+    }
+
+    /// Remove the `subject_lookup` row for `subject_id`.
+    ///
+    /// Convenience wrapper for use outside a transaction. Production shredding
+    /// uses [`Self::delete_subject_lookup_in_tx`] to commit this deletion
+    /// atomically with the DEK deletion.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn delete_subject_lookup(
+        &self,
+        subject_id: &Uuid,
+    ) -> Result<(), ViewRepositoryError> {
+        Self::do_delete_subject_lookup(&self.pool, subject_id).await
+    }
+
+    /// Remove the `subject_lookup` row for `subject_id` within an already-open transaction.
+    ///
+    /// Used by the shredding route handler to commit the email-address deletion
+    /// atomically with the DEK deletion in a single transaction. This is the
+    /// GDPR erasure of the email address from this table.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn delete_subject_lookup_in_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, Postgres>,
+        subject_id: &Uuid,
+    ) -> Result<(), ViewRepositoryError> {
+        Self::do_delete_subject_lookup(&mut **tx, subject_id).await
+    }
+
+    /// Delete the snapshots of `journey_ids` inside an existing transaction.
+    ///
+    /// Snapshots hold the journey's decrypted state, so the shredding route
+    /// handler drops them with the subject's DEK. The next load replays the
+    /// events, whose PII no longer decrypts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn delete_snapshots_in_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, Postgres>,
+        journey_ids: &[String],
+    ) -> Result<(), ViewRepositoryError> {
+        sqlx::query("DELETE FROM snapshots WHERE aggregate_type = $1 AND aggregate_id = ANY($2)")
+            .bind(Journey::TYPE)
+            .bind(journey_ids)
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+
+    async fn do_delete_subject_lookup<'e, E>(
+        executor: E,
+        subject_id: &Uuid,
+    ) -> Result<(), ViewRepositoryError>
+    where
+        E: sqlx::Executor<'e, Database = Postgres>,
+    {
+        sqlx::query("DELETE FROM subject_lookup WHERE subject_id = $1")
+            .bind(subject_id)
+            .execute(executor)
+            .await?;
+        Ok(())
+    }
+
+    /// Journey aggregate IDs in the event store, in ascending order, starting
+    /// after `after` and returning at most `limit`.
+    ///
+    /// Used to page through every journey when rebuilding the projection.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn journey_aggregate_ids(
+        &self,
+        after: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<String>, ViewRepositoryError> {
+        Ok(sqlx::query_scalar(
+            "SELECT DISTINCT aggregate_id FROM events \
+             WHERE aggregate_type = 'Journey' AND ($1::text IS NULL OR aggregate_id > $1) \
+             ORDER BY aggregate_id \
+             LIMIT $2",
+        )
+        .bind(after)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// IDs of in-progress journeys, of `journey_type` when given, in
+    /// ascending order, starting after `after` and returning at most `limit`.
+    ///
+    /// Used to page through the journeys to re-evaluate after a decision
+    /// model change.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn in_progress_journey_ids(
+        &self,
+        journey_type: Option<&str>,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<Uuid>, ViewRepositoryError> {
+        Ok(sqlx::query_scalar(
+            "SELECT id FROM journey_view \
+             WHERE state = $1 \
+               AND ($2::text IS NULL OR journey_type = $2) \
+               AND ($3::uuid IS NULL OR id > $3) \
+             ORDER BY id \
+             LIMIT $4",
+        )
+        .bind(state_column(JourneyState::InProgress))
+        .bind(journey_type)
+        .bind(after)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// The journey's `shared_data` as it stood after event `sequence`,
+    /// replayed from the event store; `{}` before the journey's first capture
+    /// or for an unknown journey.
+    ///
+    /// Secret attributes are encrypted in the store and are left out, so
+    /// `persons` holds only what was set in plaintext.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails or an event cannot be
+    /// decoded.
+    pub async fn data_at_version(
+        &self,
+        journey_id: &Uuid,
+        sequence: i64,
+    ) -> Result<Value, ViewRepositoryError> {
+        let payloads: Vec<Value> = sqlx::query_scalar(
+            "SELECT payload FROM events \
+             WHERE aggregate_type = 'Journey' AND aggregate_id = $1 AND sequence <= $2 \
+               AND event_type = ANY($3) \
+             ORDER BY sequence",
+        )
+        .bind(journey_id.to_string())
+        .bind(sequence)
+        .bind(&DATA_EVENT_TYPES[..])
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut journey = Journey::default();
+        for payload in payloads {
+            let event = serde_json::from_value(payload)
+                .map_err(|e| ViewRepositoryError::InconsistentRow(format!("event payload: {e}")))?;
+            journey.apply(event);
+        }
+        Ok(journey.shared_data().clone())
+    }
+
+    /// The merge patch turning the journey's `shared_data` after event
+    /// `from_seq` into what it was after `to_seq`; see [`merge_diff`].
+    ///
+    /// # Errors
+    ///
+    /// As [`Self::data_at_version`].
+    pub async fn diff_versions(
+        &self,
+        journey_id: &Uuid,
+        from_seq: i64,
+        to_seq: i64,
+    ) -> Result<Value, ViewRepositoryError> {
+        let from = self.data_at_version(journey_id, from_seq).await?;
+        let to = self.data_at_version(journey_id, to_seq).await?;
+        Ok(merge_diff(&from, &to))
+    }
+
+    /// Replace the projection of `journey_id` with one built from `events`.
+    ///
+    /// Deletes the journey's view rows and applies `events` in a single
+    /// transaction, so readers see either the old view or the rebuilt one.
+    /// `events` must be the journey's complete history.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any statement fails; nothing is changed in that case.
+    pub async fn replace_journey(
+        &self,
+        journey_id: Uuid,
+        events: &[EventEnvelope<Journey>],
+    ) -> Result<(), ViewRepositoryError> {
+        let mut tx = self.pool.begin().await?;
+
+        // journey_person, journey_workflow_decision, journey_step_history and
+        // journey_tags cascade.
+        sqlx::query("DELETE FROM journey_view WHERE id = $1")
+            .bind(journey_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for event in events {
+            self.apply_event_in_tx(&mut tx, journey_id, event).await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Project `events` onto the view of `journey_id` in a single transaction.
+    ///
+    /// Events at or below the view's version have already been projected, or
+    /// arrived after a later one, and are skipped, so a replayed, duplicated
+    /// or out-of-order dispatch cannot regress the view.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any statement fails; none of `events` is applied
+    /// in that case.
+    #[allow(clippy::cast_possible_wrap)]
+    pub async fn apply_events(
+        &self,
+        journey_id: Uuid,
+        events: &[EventEnvelope<Journey>],
+    ) -> Result<(), ViewRepositoryError> {
+        let mut tx = self.pool.begin().await?;
+        // Lock the row so a concurrent dispatch for the same journey waits
+        // for this one and then sees its version.
+        let mut version: Option<i64> =
+            sqlx::query_scalar("SELECT version FROM journey_view WHERE id = $1 FOR UPDATE")
+                .bind(journey_id)
+                .fetch_optional(&mut *tx)
+                .await?;
+        for event in events {
+            let sequence = event.sequence as i64;
+            if let Some(version) = version.filter(|version| sequence <= *version) {
+                eprintln!(
+                    "Skipping event {sequence} for journey view '{journey_id}': already at version {version}"
+                );
+                continue;
+            }
+            self.apply_event_in_tx(&mut tx, journey_id, event).await?;
+            version = Some(sequence);
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Redact the personal data of `journey_id` while keeping its skeletal
+    /// view row: every `journey_person` row has its identity fields nulled and
+    /// its details cleared, the `persons` subtree is dropped from
+    /// `shared_data`, and the journey is flagged `forgotten`.
+    ///
+    /// This is what projecting a `Forgotten` event does; call it directly to
+    /// redact a view without going through the aggregate. It deletes no keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn redact(&self, journey_id: Uuid) -> Result<(), ViewRepositoryError> {
+        let mut tx = self.pool.begin().await?;
+        Self::redact_in_tx(&mut tx, journey_id, None, None).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Mark the views of journeys in one of `states` whose last event is
+    /// more than `older_than` old forgotten, as [`Self::redact`] does,
+    /// returning how many were swept. Views already forgotten are left alone.
+    ///
+    /// The rows are kept, with their `updated_at`, so an event the journey
+    /// records later still projects onto its view. This prunes the read
+    /// models only: the journeys' events are kept, so a view rebuild brings
+    /// the swept data back.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn sweep_stale(
+        &self,
+        older_than: Duration,
+        states: &[JourneyState],
+    ) -> Result<u64, ViewRepositoryError> {
+        let states: Vec<&str> = states.iter().copied().map(state_column).collect();
+        let mut tx = self.pool.begin().await?;
+        let stale: Vec<(Uuid, DateTime<Utc>)> = sqlx::query_as(
+            r"
+            SELECT id, updated_at
+            FROM journey_view
+            WHERE state = ANY($1)
+              AND forgotten = FALSE
+              AND updated_at < CURRENT_TIMESTAMP - make_interval(secs => $2)
+            FOR UPDATE
+            ",
+        )
+        .bind(&states)
+        .bind(older_than.as_secs_f64())
+        .fetch_all(&mut *tx)
+        .await?;
+        for (journey_id, updated_at) in &stale {
+            Self::redact_in_tx(&mut tx, *journey_id, None, Some(*updated_at)).await?;
+        }
+        tx.commit().await?;
+        Ok(stale.len() as u64)
+    }
+
+    /// Shared by [`Self::redact`] and the `Forgotten` projection. `version` is
+    /// left unchanged when `None`.
+    async fn redact_in_tx(
+        tx: &mut sqlx::Transaction<'_, Postgres>,
+        journey_id: Uuid,
+        version: Option<i64>,
+        occurred_at: Option<DateTime<Utc>>,
+    ) -> Result<(), ViewRepositoryError> {
+        sqlx::query(
+            r"
+            UPDATE journey_person
+            SET name        = NULL,
+                email       = NULL,
+                email_index = NULL,
+                phone       = NULL,
+                details     = '{}',
+                forgotten   = TRUE,
+                updated_at  = COALESCE($2, CURRENT_TIMESTAMP)
+            WHERE journey_id = $1
+            ",
+        )
+        .bind(journey_id)
+        .bind(occurred_at)
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query(
+            r"
+            UPDATE journey_view
+            SET shared_data = shared_data - 'persons' - 'person',
+                draft_data  = '{}'::jsonb,
+                forgotten   = TRUE,
+                version     = COALESCE($2, version),
+                updated_at  = COALESCE($3, CURRENT_TIMESTAMP)
+            WHERE id = $1
+            ",
+        )
+        .bind(journey_id)
+        .bind(version)
+        .bind(occurred_at)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    fn parse_journey_id(view_id: &str) -> Result<Uuid, ViewRepositoryError> {
+        Uuid::parse_str(view_id).map_err(|source| ViewRepositoryError::InvalidJourneyId {
+            id: view_id.to_string(),
+            source,
+        })
+    }
+
+    #[allow(clippy::too_many_lines, clippy::cast_possible_wrap, deprecated)]
     async fn apply_event_in_tx(
         &self,
         tx: &mut sqlx::Transaction<'_, Postgres>,
         journey_id: Uuid,
         event: &EventEnvelope<Journey>,
-    ) -> Result<(), sqlx::Error> {
+    ) -> Result<(), ViewRepositoryError> {
+        // Bind the event's own time rather than the clock so a replay writes
+        // the same timestamps. Events persisted without one fall back to now.
+        let occurred_at = event.occurred_at();
+
         match &event.payload {
-            JourneyEvent::Started { id } => {
+            JourneyEvent::Started { id, journey_type } => {
                 sqlx::query(
                     r"
-                    INSERT INTO journey_view (id, state, current_step, version)
-                    VALUES ($1, $2, $3, $4)
+                    INSERT INTO journey_view
+                        (id, journey_type, state, current_step, shared_data, version,
+                         created_at, updated_at)
+                    VALUES (
+                        $1, $6, $2, $3, '{}'::jsonb, $4,
+                        COALESCE($5, CURRENT_TIMESTAMP), COALESCE($5, CURRENT_TIMESTAMP)
+                    )
                     ON CONFLICT (id) DO NOTHING
                     ",
                 )
@@ -495,11 +1584,44 @@ impl StructuredJourneyViewRepository {
                 .bind("InProgress")
                 .bind::<Option<String>>(None)
                 .bind(event.sequence as i64)
+                .bind(occurred_at)
+                .bind(journey_type)
+                .execute(&mut **tx)
+                .await?;
+            }
+
+            JourneyEvent::DraftSaved { step, data } => {
+                // Merged in Rust like `Modified`, but into the step's draft.
+                let current: Value =
+                    sqlx::query_scalar("SELECT draft_data -> $2 FROM journey_view WHERE id = $1")
+                        .bind(journey_id)
+                        .bind(step)
+                        .fetch_one(&mut **tx)
+                        .await
+                        .map(|draft: Option<Value>| draft.unwrap_or_else(|| json!({})))?;
+
+                let mut merged = current;
+                deep_merge(&mut merged, data);
+
+                sqlx::query(
+                    r"
+                    UPDATE journey_view
+                    SET draft_data = jsonb_set(draft_data, ARRAY[$2], $3),
+                        version    = $4,
+                        updated_at = COALESCE($5, CURRENT_TIMESTAMP)
+                    WHERE id = $1
+                    ",
+                )
+                .bind(journey_id)
+                .bind(step)
+                .bind(&merged)
+                .bind(event.sequence as i64)
+                .bind(occurred_at)
                 .execute(&mut **tx)
                 .await?;
             }
 
-            JourneyEvent::Modified { step: _, data } => {
+            JourneyEvent::Modified { step, data } => {
                 // Deep-merge new data into shared_data.
                 // shared_data never contains PII and is never cleared by shredding.
                 // We load, merge in Rust, and write back rather than using
@@ -512,21 +1634,27 @@ impl StructuredJourneyViewRepository {
                         .fetch_one(&mut **tx)
                         .await?;
 
+                // Data that is not an object is filed under the step, as
+                // the aggregate does, rather than replacing shared_data.
                 let mut merged = current;
-                deep_merge(&mut merged, data);
+                deep_merge(&mut merged, &step_patch(step, data));
 
+                // Capturing the step commits it, so its draft is dropped.
                 sqlx::query(
                     r"
                     UPDATE journey_view
                     SET shared_data = $2,
+                        draft_data  = draft_data - $5,
                         version     = $3,
-                        updated_at  = CURRENT_TIMESTAMP
+                        updated_at  = COALESCE($4, CURRENT_TIMESTAMP)
                     WHERE id = $1
                     ",
                 )
                 .bind(journey_id)
                 .bind(&merged)
                 .bind(event.sequence as i64)
+                .bind(occurred_at)
+                .bind(step)
                 .execute(&mut **tx)
                 .await?;
             }
@@ -544,34 +1672,63 @@ impl StructuredJourneyViewRepository {
                 sqlx::query(
                     r"
                     INSERT INTO journey_person
-                        (journey_id, person_ref, subject_id, name, email, phone)
-                    VALUES ($1, $2, $3, $4, $5, $6)
+                        (journey_id, person_ref, subject_id, name, email, phone,
+                         email_index, created_at, updated_at)
+                    VALUES ($1, $2, $3, $4, $5, $6, $8,
+                            COALESCE($7, CURRENT_TIMESTAMP), COALESCE($7, CURRENT_TIMESTAMP))
                     ON CONFLICT (journey_id, person_ref) DO UPDATE
-                    SET subject_id = $3,
-                        name       = $4,
-                        email      = $5,
-                        phone      = $6,
-                        updated_at = CURRENT_TIMESTAMP
+                    SET subject_id  = $3,
+                        name        = $4,
+                        email       = $5,
+                        phone       = $6,
+                        email_index = $8,
+                        updated_at  = COALESCE($7, CURRENT_TIMESTAMP)
                     ",
                 )
                 .bind(journey_id)
                 .bind(person_ref)
                 .bind(subject_id)
-                .bind(name)
-                .bind(email)
-                .bind(phone)
+                .bind(self.seal(name))
+                .bind(self.seal(email))
+                .bind(phone.as_deref().map(|phone| self.seal(phone)))
+                .bind(occurred_at)
+                .bind(
+                    self.pii_cipher
+                        .as_deref()
+                        .map(|cipher| pii_cipher::email_index(cipher, email)),
+                )
                 .execute(&mut **tx)
                 .await?;
 
+                if self.embed_persons && self.pii_cipher.is_none() {
+                    sqlx::query(
+                        r"
+                        UPDATE journey_view
+                        SET shared_data = shared_data || jsonb_build_object(
+                                'person',
+                                COALESCE(shared_data -> 'person', '{}')
+                                    || jsonb_build_object($2::text, $3::jsonb)
+                            )
+                        WHERE id = $1
+                        ",
+                    )
+                    .bind(journey_id)
+                    .bind(person_ref)
+                    .bind(json!({ "name": name, "email": email, "phone": phone }))
+                    .execute(&mut **tx)
+                    .await?;
+                }
+
                 sqlx::query(
                     r"
                     UPDATE journey_view
-                    SET version = $1, updated_at = CURRENT_TIMESTAMP
+                    SET version = $1, updated_at = COALESCE($3, CURRENT_TIMESTAMP)
                     WHERE id = $2
                     ",
                 )
                 .bind(event.sequence as i64)
                 .bind(journey_id)
+                .bind(occurred_at)
                 .execute(&mut **tx)
                 .await?;
             }
@@ -584,66 +1741,98 @@ impl StructuredJourneyViewRepository {
                     r"
                     UPDATE journey_person
                     SET details    = details || $3,
-                        updated_at = CURRENT_TIMESTAMP
+                        updated_at = COALESCE($4, CURRENT_TIMESTAMP)
                     WHERE journey_id = $1 AND person_ref = $2
                     ",
                 )
                 .bind(journey_id)
                 .bind(person_ref)
                 .bind(data)
+                .bind(occurred_at)
                 .execute(&mut **tx)
                 .await?;
 
                 sqlx::query(
                     r"
                     UPDATE journey_view
-                    SET version = $1, updated_at = CURRENT_TIMESTAMP
+                    SET version = $1, updated_at = COALESCE($3, CURRENT_TIMESTAMP)
                     WHERE id = $2
                     ",
                 )
                 .bind(event.sequence as i64)
                 .bind(journey_id)
+                .bind(occurred_at)
                 .execute(&mut **tx)
                 .await?;
             }
 
             JourneyEvent::SubjectForgotten { subject_id } => {
                 // Null out PII for the specific subject in this journey only.
-                // shared_data in journey_view is NOT touched — it never contained PII.
-                // Other persons in the same journey are NOT affected.
+                // Other persons in the same journey are NOT affected. shared_data
+                // only holds PII if persons were embedded; drop those copies.
+                sqlx::query(
+                    r"
+                    UPDATE journey_view
+                    SET shared_data = jsonb_set(
+                            shared_data,
+                            '{person}',
+                            (shared_data -> 'person') - ARRAY(
+                                SELECT person_ref
+                                FROM journey_person
+                                WHERE journey_id = $1 AND subject_id = $2
+                            )
+                        )
+                    WHERE id = $1 AND shared_data ? 'person'
+                    ",
+                )
+                .bind(journey_id)
+                .bind(subject_id)
+                .execute(&mut **tx)
+                .await?;
+
                 sqlx::query(
                     r"
                     UPDATE journey_person
-                    SET name       = NULL,
-                        email      = NULL,
-                        phone      = NULL,
-                        details    = '{}',
-                        forgotten  = TRUE,
-                        updated_at = CURRENT_TIMESTAMP
+                    SET name        = NULL,
+                        email       = NULL,
+                        email_index = NULL,
+                        phone       = NULL,
+                        details     = '{}',
+                        forgotten   = TRUE,
+                        updated_at  = COALESCE($3, CURRENT_TIMESTAMP)
                     WHERE journey_id = $1 AND subject_id = $2
                     ",
                 )
                 .bind(journey_id)
                 .bind(subject_id)
+                .bind(occurred_at)
                 .execute(&mut **tx)
                 .await?;
 
                 sqlx::query(
                     r"
                     UPDATE journey_view
-                    SET version = $1, updated_at = CURRENT_TIMESTAMP
+                    SET version = $1, updated_at = COALESCE($3, CURRENT_TIMESTAMP)
                     WHERE id = $2
                     ",
                 )
                 .bind(event.sequence as i64)
                 .bind(journey_id)
+                .bind(occurred_at)
                 .execute(&mut **tx)
                 .await?;
             }
 
+            JourneyEvent::Forgotten => {
+                Self::redact_in_tx(tx, journey_id, Some(event.sequence as i64), occurred_at)
+                    .await?;
+            }
+
             JourneyEvent::WorkflowEvaluated {
                 suggested_actions,
                 phase,
+                ranked_actions,
+                ..
             } => {
                 sqlx::query(
                     r"
@@ -659,42 +1848,79 @@ impl StructuredJourneyViewRepository {
                 sqlx::query(
                     r"
                     INSERT INTO journey_workflow_decision
-                        (journey_id, suggested_actions, is_latest, phase)
-                    VALUES ($1, $2, TRUE, $3)
+                        (journey_id, suggested_actions, is_latest, phase, ranked_actions, created_at)
+                    VALUES ($1, $2, TRUE, $3, $4, COALESCE($5, CURRENT_TIMESTAMP))
                     ",
                 )
                 .bind(journey_id)
                 .bind(suggested_actions)
                 .bind(phase)
+                .bind(Json(ranked_actions))
+                .bind(occurred_at)
                 .execute(&mut **tx)
                 .await?;
 
                 sqlx::query(
                     r"
                     UPDATE journey_view
-                    SET version = $1, updated_at = CURRENT_TIMESTAMP
+                    SET version = $1, updated_at = COALESCE($3, CURRENT_TIMESTAMP)
                     WHERE id = $2
                     ",
                 )
                 .bind(event.sequence as i64)
                 .bind(journey_id)
+                .bind(occurred_at)
                 .execute(&mut **tx)
                 .await?;
             }
 
-            JourneyEvent::StepProgressed { to_step, .. } => {
+            JourneyEvent::StepProgressed { from_step, to_step } => {
                 sqlx::query(
                     r"
                     UPDATE journey_view
                     SET current_step = $1,
                         version      = $2,
-                        updated_at   = CURRENT_TIMESTAMP
+                        updated_at   = COALESCE($4, CURRENT_TIMESTAMP)
                     WHERE id = $3
                     ",
                 )
                 .bind(to_step)
                 .bind(event.sequence as i64)
                 .bind(journey_id)
+                .bind(occurred_at)
+                .execute(&mut **tx)
+                .await?;
+
+                // Keep every visit, not just the latest step, for funnels.
+                sqlx::query(
+                    r"
+                    INSERT INTO journey_step_history
+                        (journey_id, sequence, from_step, to_step, entered_at)
+                    VALUES ($1, $2, $3, $4, COALESCE($5, CURRENT_TIMESTAMP))
+                    ON CONFLICT (journey_id, sequence) DO NOTHING
+                    ",
+                )
+                .bind(journey_id)
+                .bind(event.sequence as i64)
+                .bind(from_step)
+                .bind(to_step)
+                .bind(occurred_at)
+                .execute(&mut **tx)
+                .await?;
+            }
+
+            JourneyEvent::StepSkipped { .. } | JourneyEvent::WorkflowSatisfied => {
+                sqlx::query(
+                    r"
+                    UPDATE journey_view
+                    SET version    = $1,
+                        updated_at = COALESCE($3, CURRENT_TIMESTAMP)
+                    WHERE id = $2
+                    ",
+                )
+                .bind(event.sequence as i64)
+                .bind(journey_id)
+                .bind(occurred_at)
                 .execute(&mut **tx)
                 .await?;
             }
@@ -703,15 +1929,134 @@ impl StructuredJourneyViewRepository {
                 sqlx::query(
                     r"
                     UPDATE journey_view
-                    SET state      = $1,
-                        version    = $2,
-                        updated_at = CURRENT_TIMESTAMP
+                    SET state        = $1,
+                        version      = $2,
+                        completed_at = COALESCE($4, CURRENT_TIMESTAMP),
+                        updated_at   = COALESCE($4, CURRENT_TIMESTAMP)
                     WHERE id = $3
                     ",
                 )
                 .bind("Complete")
                 .bind(event.sequence as i64)
                 .bind(journey_id)
+                .bind(occurred_at)
+                .execute(&mut **tx)
+                .await?;
+            }
+
+            JourneyEvent::Reopened => {
+                // No longer complete, so no completion time until it closes again.
+                sqlx::query(
+                    r"
+                    UPDATE journey_view
+                    SET state        = $1,
+                        version      = $2,
+                        completed_at = NULL,
+                        updated_at   = COALESCE($4, CURRENT_TIMESTAMP)
+                    WHERE id = $3
+                    ",
+                )
+                .bind("InProgress")
+                .bind(event.sequence as i64)
+                .bind(journey_id)
+                .bind(occurred_at)
+                .execute(&mut **tx)
+                .await?;
+            }
+
+            JourneyEvent::Paused { at } => {
+                sqlx::query(
+                    r"
+                    UPDATE journey_view
+                    SET state      = $1,
+                        paused_at  = $2,
+                        version    = $3,
+                        updated_at = COALESCE($5, CURRENT_TIMESTAMP)
+                    WHERE id = $4
+                    ",
+                )
+                .bind("Paused")
+                .bind(at)
+                .bind(event.sequence as i64)
+                .bind(journey_id)
+                .bind(occurred_at)
+                .execute(&mut **tx)
+                .await?;
+            }
+
+            JourneyEvent::Resumed { at } => {
+                // Fold the finished pause into the running total; a clock that
+                // stepped backwards counts as no time paused.
+                sqlx::query(
+                    r"
+                    UPDATE journey_view
+                    SET state              = $1,
+                        paused_duration_ms = paused_duration_ms + GREATEST(
+                            0,
+                            (EXTRACT(EPOCH FROM ($2 - COALESCE(paused_at, $2))) * 1000)::BIGINT
+                        ),
+                        paused_at          = NULL,
+                        resumed_at         = $2,
+                        version            = $3,
+                        updated_at         = COALESCE($5, CURRENT_TIMESTAMP)
+                    WHERE id = $4
+                    ",
+                )
+                .bind("InProgress")
+                .bind(at)
+                .bind(event.sequence as i64)
+                .bind(journey_id)
+                .bind(occurred_at)
+                .execute(&mut **tx)
+                .await?;
+            }
+
+            JourneyEvent::Tagged { key, value } => {
+                sqlx::query(
+                    r"
+                    INSERT INTO journey_tags (journey_id, key, value)
+                    VALUES ($1, $2, $3)
+                    ON CONFLICT (journey_id, key) DO UPDATE SET value = EXCLUDED.value
+                    ",
+                )
+                .bind(journey_id)
+                .bind(key)
+                .bind(value)
+                .execute(&mut **tx)
+                .await?;
+
+                sqlx::query(
+                    r"
+                    UPDATE journey_view
+                    SET version    = $1,
+                        updated_at = COALESCE($3, CURRENT_TIMESTAMP)
+                    WHERE id = $2
+                    ",
+                )
+                .bind(event.sequence as i64)
+                .bind(journey_id)
+                .bind(occurred_at)
+                .execute(&mut **tx)
+                .await?;
+            }
+
+            JourneyEvent::Corrected { path, value } => {
+                // The path exists, so jsonb_set replaces the value in place.
+                let path: Vec<String> = path.tokens().map(|t| t.decoded().to_string()).collect();
+                sqlx::query(
+                    r"
+                    UPDATE journey_view
+                    SET shared_data = jsonb_set(shared_data, $2, $3),
+                        version     = $4,
+                        updated_at  = COALESCE($5, CURRENT_TIMESTAMP)
+                    WHERE id = $1
+                    ",
+                )
+                .bind(journey_id)
+                .bind(&path)
+                .bind(value)
+                .bind(event.sequence as i64)
+                .bind(occurred_at)
                 .execute(&mut **tx)
                 .await?;
             }
@@ -744,13 +2089,14 @@ impl StructuredJourneyViewRepository {
                     UPDATE journey_view
                     SET shared_data = $2,
                         version     = $3,
-                        updated_at  = CURRENT_TIMESTAMP
+                        updated_at  = COALESCE($4, CURRENT_TIMESTAMP)
                     WHERE id = $1
                     ",
                 )
                 .bind(journey_id)
                 .bind(&merged)
                 .bind(event.sequence as i64)
+                .bind(occurred_at)
                 .execute(&mut **tx)
                 .await?;
 
@@ -769,13 +2115,14 @@ impl StructuredJourneyViewRepository {
                         r"
                         UPDATE journey_person
                         SET details    = details || $3,
-                            updated_at = CURRENT_TIMESTAMP
+                            updated_at = COALESCE($4, CURRENT_TIMESTAMP)
                         WHERE journey_id = $1 AND person_ref = $2
                         ",
                     )
                     .bind(journey_id)
                     .bind(&partition.person_ref)
                     .bind(&details_update)
+                    .bind(occurred_at)
                     .execute(&mut **tx)
                     .await?;
                 }
@@ -786,6 +2133,36 @@ impl StructuredJourneyViewRepository {
     }
 }
 
+#[async_trait::async_trait]
+impl JourneyViewStore for StructuredJourneyViewRepository {
+    async fn load(&self, journey_id: &Uuid) -> Result<Option<JourneyView>, ViewRepositoryError> {
+        Self::load(self, journey_id).await
+    }
+
+    async fn load_all(&self) -> Result<Vec<JourneyView>, ViewRepositoryError> {
+        Self::load_all(self).await
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Vec<JourneyView>, ViewRepositoryError> {
+        Self::find_by_email(self, email).await
+    }
+
+    async fn find_where_data_contains(
+        &self,
+        fragment: &Value,
+    ) -> Result<Vec<JourneyView>, ViewRepositoryError> {
+        Self::find_where_data_contains(self, fragment).await
+    }
+
+    async fn apply_events(
+        &self,
+        journey_id: Uuid,
+        events: &[EventEnvelope<Journey>],
+    ) -> Result<(), ViewRepositoryError> {
+        Self::apply_events(self, journey_id, events).await
+    }
+}
+
 #[async_trait::async_trait]
 impl Query<Journey> for StructuredJourneyViewRepository {
     async fn dispatch(&self, view_id: &str, events: &[EventEnvelope<Journey>]) {
@@ -801,24 +2178,69 @@ impl Query<Journey> for StructuredJourneyViewRepository {
             }
         };
 
-        let mut tx = match self.pool.begin().await {
-            Ok(tx) => tx,
-            Err(e) => {
-                eprintln!("Error starting transaction for journey '{view_id}': {e:?}");
-                return;
-            }
-        };
+        if let Err(e) = self.apply_events(journey_id, events).await {
+            // apply_events rolls back every event in this batch on failure.
+            eprintln!("Error updating journey view '{view_id}': {e:?}");
+            return;
+        }
 
-        for event in events {
-            if let Err(e) = self.apply_event_in_tx(&mut tx, journey_id, event).await {
-                eprintln!("Error applying event to journey view '{view_id}': {e:?}");
-                // Returning here drops tx, which rolls back all events in this batch.
-                return;
+        if let Some(updates) = &self.updates
+            && updates.has_subscribers(&journey_id)
+        {
+            match self.load(&journey_id).await {
+                Ok(Some(view)) => updates.publish(journey_id, view),
+                Ok(None) => {}
+                Err(e) => {
+                    eprintln!("Error reloading journey view '{view_id}' for subscribers: {e:?}");
+                }
             }
         }
+    }
+}
 
-        if let Err(e) = tx.commit().await {
-            eprintln!("Error committing journey view update for '{view_id}': {e:?}");
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ── ViewRepositoryError ──────────────────────────────────────────────
+
+    #[test]
+    fn parse_journey_id_accepts_a_uuid() {
+        let journey_id = Uuid::new_v4();
+        let parsed =
+            StructuredJourneyViewRepository::parse_journey_id(&journey_id.to_string()).unwrap();
+        assert_eq!(parsed, journey_id);
+    }
+
+    #[test]
+    fn parse_journey_id_rejects_anything_else() {
+        let err = StructuredJourneyViewRepository::parse_journey_id("not-a-uuid").unwrap_err();
+        assert!(
+            matches!(&err, ViewRepositoryError::InvalidJourneyId { id, .. } if id == "not-a-uuid")
+        );
+        assert!(
+            err.to_string()
+                .starts_with("invalid journey id 'not-a-uuid': ")
+        );
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
+    #[test]
+    fn database_errors_convert_and_display_transparently() {
+        let err = ViewRepositoryError::from(sqlx::Error::RowNotFound);
+        assert!(matches!(
+            err,
+            ViewRepositoryError::Database(sqlx::Error::RowNotFound)
+        ));
+        assert_eq!(err.to_string(), sqlx::Error::RowNotFound.to_string());
+    }
+
+    #[test]
+    fn not_found_names_the_journey() {
+        let journey_id = Uuid::new_v4();
+        assert_eq!(
+            ViewRepositoryError::NotFound(journey_id).to_string(),
+            format!("no view for journey {journey_id}")
+        );
     }
 }
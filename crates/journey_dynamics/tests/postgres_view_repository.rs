@@ -11,15 +11,43 @@
 //! They are deliberately kept out of `--lib` runs so that
 //! `cargo nextest run --lib` succeeds without a database being present.
 
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::Duration,
+};
 
-use cqrs_es::{EventEnvelope, Query};
+use chrono::{DateTime, Utc};
+use cqrs_es::{
+    CqrsFramework, DomainEvent, EventEnvelope, EventStore, Query, mem_store::MemStore,
+    persist::PersistedEventStore,
+};
+use cqrs_es_crypto::{FieldCipher, KekProvider, PostgresKeyStore, StaticKekProvider};
 use hegel::{TestCase, generators as gs};
 use journey_dynamics::{
-    domain::events::JourneyEvent, queries::JourneyState,
-    view_repository::StructuredJourneyViewRepository,
+    batching_query::BatchingOptions,
+    command_log::CommandLog,
+    command_rejections::{CommandRejection, PostgresRejectionLog, RejectionLog},
+    config::cqrs_framework,
+    domain::{
+        commands::JourneyCommand,
+        events::JourneyEvent,
+        journey::{Journey, JourneyError, JourneyServices},
+    },
+    event_feed::EventFeed,
+    idempotency::{Claim, IdempotencyStore, PostgresIdempotencyStore, RecordedResponse},
+    journey_updates::JourneyUpdates,
+    queries::{
+        FunnelStep, JOURNEY_EXPORT_FORMAT_VERSION, JourneyFilter, JourneyState, JourneyView,
+        PersonView, PersonWithStatus, ResumeState, StepDwell, StepTransition, WorkflowDecisionView,
+    },
+    rebuild::{RebuildStats, rebuild_views},
+    reevaluate::{ReevaluateStats, reevaluate_all},
+    services::decision_engine::{RankedAction, SimpleDecisionEngine, TableDecisionEngine},
+    view_repository::{StructuredJourneyViewRepository, ViewRepositoryError},
 };
 use jsonptr::PointerBuf;
+use postgres_es::PostgresEventRepository;
 use serde_json::json;
 use sqlx::{Pool, Postgres, postgres::PgPoolOptions};
 use test_context::{AsyncTestContext, test_context};
@@ -64,6 +92,10 @@ impl AsyncTestContext for PostgresViewRepositoryContext {
                 .bind(journey_id)
                 .execute(&self.pool)
                 .await;
+            let _ = sqlx::query("DELETE FROM journey_command_log WHERE journey_id = $1")
+                .bind(journey_id)
+                .execute(&self.pool)
+                .await;
         }
 
         for subject_id in self.subject_ids {
@@ -153,7 +185,10 @@ async fn test_journey_started_event(ctx: &mut PostgresViewRepositoryContext) {
         &[EventEnvelope {
             aggregate_id: journey_id.to_string(),
             sequence: 1,
-            payload: JourneyEvent::Started { id: journey_id },
+            payload: JourneyEvent::Started {
+                id: journey_id,
+                journey_type: None,
+            },
             metadata: std::collections::HashMap::default(),
         }],
     )
@@ -178,7 +213,10 @@ async fn test_journey_full_lifecycle(ctx: &mut PostgresViewRepositoryContext) {
             EventEnvelope {
                 aggregate_id: journey_id.to_string(),
                 sequence: 1,
-                payload: JourneyEvent::Started { id: journey_id },
+                payload: JourneyEvent::Started {
+                    id: journey_id,
+                    journey_type: None,
+                },
                 metadata: std::collections::HashMap::default(),
             },
             EventEnvelope {
@@ -196,6 +234,8 @@ async fn test_journey_full_lifecycle(ctx: &mut PostgresViewRepositoryContext) {
                 payload: JourneyEvent::WorkflowEvaluated {
                     suggested_actions: vec!["passenger_details".to_string()],
                     phase: None,
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec![],
                 },
                 metadata: std::collections::HashMap::default(),
             },
@@ -242,7 +282,10 @@ async fn test_workflow_decision_phase_null_roundtrip(ctx: &mut PostgresViewRepos
             EventEnvelope {
                 aggregate_id: journey_id.to_string(),
                 sequence: 1,
-                payload: JourneyEvent::Started { id: journey_id },
+                payload: JourneyEvent::Started {
+                    id: journey_id,
+                    journey_type: None,
+                },
                 metadata: HashMap::default(),
             },
             EventEnvelope {
@@ -251,6 +294,8 @@ async fn test_workflow_decision_phase_null_roundtrip(ctx: &mut PostgresViewRepos
                 payload: JourneyEvent::WorkflowEvaluated {
                     suggested_actions: vec!["next_step".to_string()],
                     phase: None,
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec![],
                 },
                 metadata: HashMap::default(),
             },
@@ -269,6 +314,65 @@ async fn test_workflow_decision_phase_null_roundtrip(ctx: &mut PostgresViewRepos
     );
 }
 
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_workflow_decision_ranked_actions_roundtrip(ctx: &mut PostgresViewRepositoryContext) {
+    let repo = ctx.repo();
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    let ranked_actions = vec![
+        RankedAction {
+            action: "search".to_string(),
+            weight: 0.7,
+        },
+        RankedAction {
+            action: "help".to_string(),
+            weight: 0.3,
+        },
+    ];
+
+    repo.dispatch(
+        &journey_id.to_string(),
+        &[
+            EventEnvelope {
+                aggregate_id: journey_id.to_string(),
+                sequence: 1,
+                payload: JourneyEvent::Started {
+                    id: journey_id,
+                    journey_type: None,
+                },
+                metadata: HashMap::default(),
+            },
+            EventEnvelope {
+                aggregate_id: journey_id.to_string(),
+                sequence: 2,
+                payload: JourneyEvent::WorkflowEvaluated {
+                    suggested_actions: vec!["search".to_string(), "help".to_string()],
+                    phase: None,
+                    ranked_actions: ranked_actions.clone(),
+                    completion_blocked_by: vec![],
+                },
+                metadata: HashMap::default(),
+            },
+        ],
+    )
+    .await;
+
+    let view = repo.load(&journey_id).await.unwrap().unwrap();
+    let decision = view.latest_workflow_decision.unwrap();
+    assert_eq!(decision.ranked_actions, ranked_actions);
+
+    let listed = repo.load_all().await.unwrap();
+    let listed = listed.iter().find(|v| v.id == journey_id).unwrap();
+    assert_eq!(
+        listed
+            .latest_workflow_decision
+            .as_ref()
+            .unwrap()
+            .ranked_actions,
+        ranked_actions
+    );
+}
+
 // ── load_all ─────────────────────────────────────────────────────────────
 
 #[test_context(PostgresViewRepositoryContext)]
@@ -286,7 +390,10 @@ async fn test_load_all_returns_inserted_journeys_with_nested_data(
         &[EventEnvelope {
             aggregate_id: journey_id_1.to_string(),
             sequence: 1,
-            payload: JourneyEvent::Started { id: journey_id_1 },
+            payload: JourneyEvent::Started {
+                id: journey_id_1,
+                journey_type: None,
+            },
             metadata: HashMap::default(),
         }],
     )
@@ -298,7 +405,10 @@ async fn test_load_all_returns_inserted_journeys_with_nested_data(
             EventEnvelope {
                 aggregate_id: journey_id_2.to_string(),
                 sequence: 1,
-                payload: JourneyEvent::Started { id: journey_id_2 },
+                payload: JourneyEvent::Started {
+                    id: journey_id_2,
+                    journey_type: None,
+                },
                 metadata: HashMap::default(),
             },
             EventEnvelope {
@@ -319,6 +429,8 @@ async fn test_load_all_returns_inserted_journeys_with_nested_data(
                 payload: JourneyEvent::WorkflowEvaluated {
                     suggested_actions: vec!["passenger_details".to_string()],
                     phase: None,
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec![],
                 },
                 metadata: HashMap::default(),
             },
@@ -361,7 +473,10 @@ async fn test_person_captured_event(ctx: &mut PostgresViewRepositoryContext) {
             EventEnvelope {
                 aggregate_id: journey_id.to_string(),
                 sequence: 1,
-                payload: JourneyEvent::Started { id: journey_id },
+                payload: JourneyEvent::Started {
+                    id: journey_id,
+                    journey_type: None,
+                },
                 metadata: std::collections::HashMap::default(),
             },
             EventEnvelope {
@@ -394,6 +509,161 @@ async fn test_person_captured_event(ctx: &mut PostgresViewRepositoryContext) {
     assert!(!p.forgotten);
 }
 
+/// Start a journey and capture two people, Alice as `passenger_0` and Bob as
+/// `passenger_1`, through `repo`.
+async fn capture_alice_and_bob(
+    repo: &StructuredJourneyViewRepository,
+    journey_id: Uuid,
+    alice: Uuid,
+    bob: Uuid,
+) {
+    let people = [
+        ("passenger_0", alice, "Alice Smith", "alice@example.com"),
+        ("passenger_1", bob, "Bob Jones", "bob@example.com"),
+    ];
+    let mut events = vec![EventEnvelope {
+        aggregate_id: journey_id.to_string(),
+        sequence: 1,
+        payload: JourneyEvent::Started {
+            id: journey_id,
+            journey_type: None,
+        },
+        metadata: HashMap::default(),
+    }];
+    for (person_ref, subject_id, name, email) in people {
+        events.push(EventEnvelope {
+            aggregate_id: journey_id.to_string(),
+            sequence: events.len() + 1,
+            payload: JourneyEvent::PersonCaptured {
+                person_ref: person_ref.to_string(),
+                subject_id,
+                name: name.to_string(),
+                email: email.to_string(),
+                phone: None,
+            },
+            metadata: HashMap::default(),
+        });
+    }
+    repo.dispatch(&journey_id.to_string(), &events).await;
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_person_captured_is_embedded_in_shared_data_when_enabled(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    let repo = ctx.repo().with_embedded_persons(true);
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    capture_alice_and_bob(&repo, journey_id, Uuid::new_v4(), Uuid::new_v4()).await;
+
+    let view = repo.load(&journey_id).await.unwrap().unwrap();
+    assert_eq!(
+        view.get_field("person.passenger_0.email"),
+        Some(&json!("alice@example.com"))
+    );
+    assert_eq!(
+        view.shared_data["person"]["passenger_1"],
+        json!({ "name": "Bob Jones", "email": "bob@example.com", "phone": null })
+    );
+    // The structured table is still written.
+    assert_eq!(view.persons.len(), 2);
+}
+
+/// Starting a journey from a view with embedded identities copies the data
+/// but none of the identities, which would otherwise land in a plaintext
+/// `Modified` that shredding cannot reach.
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_start_from_an_embedded_view_keeps_identities_out_of_the_seed(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    let repo = ctx.repo().with_embedded_persons(true);
+    let source_id = ctx.track_journey(Uuid::new_v4());
+    start_with_person(&repo, source_id).await;
+    let source = repo.load(&source_id).await.unwrap().unwrap();
+    assert!(source.has_field("person.passenger_0.email"));
+
+    let store = MemStore::<Journey>::default();
+    let cqrs = CqrsFramework::new(
+        store.clone(),
+        vec![],
+        JourneyServices::without_validation(Arc::new(SimpleDecisionEngine)),
+    );
+    let journey_id = Uuid::new_v4();
+    cqrs.execute(
+        &journey_id.to_string(),
+        JourneyCommand::StartFrom {
+            id: journey_id,
+            source_id,
+            include_person: false,
+            source: source.into(),
+        },
+    )
+    .await
+    .unwrap();
+
+    let events = store.load_events(&journey_id.to_string()).await.unwrap();
+    let modified = events
+        .iter()
+        .find_map(|event| match &event.payload {
+            #[allow(deprecated)]
+            JourneyEvent::Modified { data, .. } => Some(data.clone()),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(modified, json!({ "origin": "LHR" }));
+    let recorded =
+        serde_json::to_string(&events.iter().map(|e| &e.payload).collect::<Vec<_>>()).unwrap();
+    for identity in ["Alice Smith", "alice@example.com", "+44 20 7946 0000"] {
+        assert!(!recorded.contains(identity), "{identity} was recorded");
+    }
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_person_captured_is_not_embedded_by_default(ctx: &mut PostgresViewRepositoryContext) {
+    let repo = ctx.repo();
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    capture_alice_and_bob(&repo, journey_id, Uuid::new_v4(), Uuid::new_v4()).await;
+
+    let view = repo.load(&journey_id).await.unwrap().unwrap();
+    assert!(!view.has_field("person"));
+    assert_eq!(view.persons.len(), 2);
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_forgetting_a_subject_removes_its_embedded_copy(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    let repo = ctx.repo().with_embedded_persons(true);
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    let alice = Uuid::new_v4();
+    capture_alice_and_bob(&repo, journey_id, alice, Uuid::new_v4()).await;
+
+    repo.dispatch(
+        &journey_id.to_string(),
+        &[EventEnvelope {
+            aggregate_id: journey_id.to_string(),
+            sequence: 4,
+            payload: JourneyEvent::SubjectForgotten { subject_id: alice },
+            metadata: HashMap::default(),
+        }],
+    )
+    .await;
+
+    let view = repo.load(&journey_id).await.unwrap().unwrap();
+    assert!(!view.has_field("person.passenger_0"));
+    assert_eq!(
+        view.get_field("person.passenger_1.email"),
+        Some(&json!("bob@example.com"))
+    );
+
+    repo.redact(journey_id).await.unwrap();
+    let view = repo.load(&journey_id).await.unwrap().unwrap();
+    assert!(!view.has_field("person"));
+}
+
 #[test_context(PostgresViewRepositoryContext)]
 #[tokio::test]
 async fn test_multiple_persons_captured(ctx: &mut PostgresViewRepositoryContext) {
@@ -408,7 +678,10 @@ async fn test_multiple_persons_captured(ctx: &mut PostgresViewRepositoryContext)
             EventEnvelope {
                 aggregate_id: journey_id.to_string(),
                 sequence: 1,
-                payload: JourneyEvent::Started { id: journey_id },
+                payload: JourneyEvent::Started {
+                    id: journey_id,
+                    journey_type: None,
+                },
                 metadata: std::collections::HashMap::default(),
             },
             EventEnvelope {
@@ -462,7 +735,10 @@ async fn test_person_captured_updates_identity_fields(ctx: &mut PostgresViewRepo
             EventEnvelope {
                 aggregate_id: journey_id.to_string(),
                 sequence: 1,
-                payload: JourneyEvent::Started { id: journey_id },
+                payload: JourneyEvent::Started {
+                    id: journey_id,
+                    journey_type: None,
+                },
                 metadata: std::collections::HashMap::default(),
             },
             EventEnvelope {
@@ -519,7 +795,10 @@ async fn test_person_details_updated(ctx: &mut PostgresViewRepositoryContext) {
             EventEnvelope {
                 aggregate_id: journey_id.to_string(),
                 sequence: 1,
-                payload: JourneyEvent::Started { id: journey_id },
+                payload: JourneyEvent::Started {
+                    id: journey_id,
+                    journey_type: None,
+                },
                 metadata: std::collections::HashMap::default(),
             },
             EventEnvelope {
@@ -587,7 +866,10 @@ async fn test_subject_forgotten_only_affects_target_person(
             EventEnvelope {
                 aggregate_id: journey_id.to_string(),
                 sequence: 1,
-                payload: JourneyEvent::Started { id: journey_id },
+                payload: JourneyEvent::Started {
+                    id: journey_id,
+                    journey_type: None,
+                },
                 metadata: std::collections::HashMap::default(),
             },
             EventEnvelope {
@@ -662,6 +944,108 @@ async fn test_subject_forgotten_only_affects_target_person(
     assert_eq!(pb.email.as_deref(), Some("bob@example.com"));
 }
 
+// ── Forgotten / redact ───────────────────────────────────────────────────
+
+/// Start a journey holding one captured person and a `persons` subtree in
+/// `shared_data`.
+async fn start_with_person(repo: &StructuredJourneyViewRepository, journey_id: Uuid) {
+    repo.dispatch(
+        &journey_id.to_string(),
+        &[
+            EventEnvelope {
+                aggregate_id: journey_id.to_string(),
+                sequence: 1,
+                payload: JourneyEvent::Started {
+                    id: journey_id,
+                    journey_type: None,
+                },
+                metadata: std::collections::HashMap::default(),
+            },
+            EventEnvelope {
+                aggregate_id: journey_id.to_string(),
+                sequence: 2,
+                payload: JourneyEvent::Modified {
+                    step: "search".to_string(),
+                    data: json!({
+                        "origin": "LHR",
+                        "persons": { "passenger_0": { "dob": "1990-01-01" } }
+                    }),
+                },
+                metadata: std::collections::HashMap::default(),
+            },
+            EventEnvelope {
+                aggregate_id: journey_id.to_string(),
+                sequence: 3,
+                payload: JourneyEvent::PersonCaptured {
+                    person_ref: "passenger_0".to_string(),
+                    subject_id: Uuid::new_v4(),
+                    name: "Alice Smith".to_string(),
+                    email: "alice@example.com".to_string(),
+                    phone: Some("+44 20 7946 0000".to_string()),
+                },
+                metadata: std::collections::HashMap::default(),
+            },
+        ],
+    )
+    .await;
+}
+
+fn assert_redacted(view: &JourneyView) {
+    assert!(view.forgotten, "journey must be marked forgotten");
+    assert_eq!(view.shared_data, json!({"origin": "LHR"}));
+    assert_eq!(view.persons.len(), 1, "person row is kept for audit counts");
+    let person = &view.persons[0];
+    assert!(person.forgotten);
+    assert!(person.name.is_none());
+    assert!(person.email.is_none());
+    assert!(person.phone.is_none());
+    assert_eq!(person.details, json!({}));
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_journey_forgotten_clears_persons_but_view_still_loads(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    let repo = ctx.repo();
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    start_with_person(&repo, journey_id).await;
+
+    repo.dispatch(
+        &journey_id.to_string(),
+        &[EventEnvelope {
+            aggregate_id: journey_id.to_string(),
+            sequence: 4,
+            payload: JourneyEvent::Forgotten,
+            metadata: std::collections::HashMap::default(),
+        }],
+    )
+    .await;
+
+    let view = repo.load(&journey_id).await.unwrap().unwrap();
+    assert_redacted(&view);
+    assert!(
+        repo.find_by_email("alice@example.com")
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_redact_clears_persons_but_view_still_loads(ctx: &mut PostgresViewRepositoryContext) {
+    let repo = ctx.repo();
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    start_with_person(&repo, journey_id).await;
+
+    repo.redact(journey_id).await.unwrap();
+
+    let view = repo.load(&journey_id).await.unwrap().unwrap();
+    assert_redacted(&view);
+    assert_eq!(view.state, JourneyState::InProgress);
+}
+
 // ── find_by_email ────────────────────────────────────────────────────────
 
 #[test_context(PostgresViewRepositoryContext)]
@@ -680,7 +1064,10 @@ async fn test_find_by_email(ctx: &mut PostgresViewRepositoryContext) {
                 EventEnvelope {
                     aggregate_id: journey_id.to_string(),
                     sequence: 1,
-                    payload: JourneyEvent::Started { id: journey_id },
+                    payload: JourneyEvent::Started {
+                        id: journey_id,
+                        journey_type: None,
+                    },
                     metadata: std::collections::HashMap::default(),
                 },
                 EventEnvelope {
@@ -718,7 +1105,10 @@ async fn test_find_by_email_excludes_forgotten_persons(ctx: &mut PostgresViewRep
             EventEnvelope {
                 aggregate_id: journey_id.to_string(),
                 sequence: 1,
-                payload: JourneyEvent::Started { id: journey_id },
+                payload: JourneyEvent::Started {
+                    id: journey_id,
+                    journey_type: None,
+                },
                 metadata: std::collections::HashMap::default(),
             },
             EventEnvelope {
@@ -750,69 +1140,295 @@ async fn test_find_by_email_excludes_forgotten_persons(ctx: &mut PostgresViewRep
     );
 }
 
-// ── find_subjects_by_email ───────────────────────────────────────────────
+// ── find_persons_by_name ─────────────────────────────────────────────────
 
 #[test_context(PostgresViewRepositoryContext)]
 #[tokio::test]
-#[hegel::test(test_cases = 20)]
-async fn test_find_subjects_by_email_case_insensitive(
-    tc: TestCase,
-    ctx: &mut PostgresViewRepositoryContext,
-) {
+async fn test_find_persons_by_name_prefix(ctx: &mut PostgresViewRepositoryContext) {
     let repo = ctx.repo();
-    let subject_id = Uuid::new_v4();
-    let canonical_email = tc.draw(gs::emails());
-    let stored_email = if tc.draw(gs::booleans()) {
-        canonical_email.to_ascii_uppercase()
-    } else {
-        canonical_email.to_ascii_lowercase()
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    // Names are unique to this run so rows from other tests never match.
+    let prefix = format!("Zz{}", &Uuid::new_v4().simple().to_string()[..8]);
+    let event = |sequence, payload| EventEnvelope {
+        aggregate_id: journey_id.to_string(),
+        sequence,
+        payload,
+        metadata: HashMap::default(),
     };
-    let query_email = if tc.draw(gs::booleans()) {
-        canonical_email.to_ascii_uppercase()
-    } else {
-        canonical_email.to_ascii_lowercase()
+    let captured = |person_ref: &str, subject_id, name: String| JourneyEvent::PersonCaptured {
+        person_ref: person_ref.to_string(),
+        subject_id,
+        name,
+        email: "person@example.com".to_string(),
+        phone: None,
     };
+    let forgotten_subject = Uuid::new_v4();
 
-    ctx.insert_subject_lookup(subject_id, &stored_email).await;
+    repo.dispatch(
+        &journey_id.to_string(),
+        &[event(
+            1,
+            JourneyEvent::Started {
+                id: journey_id,
+                journey_type: None,
+            },
+        )],
+    )
+    .await;
+    // One dispatch per person, so each gets a later updated_at.
+    for (sequence, payload) in [
+        (
+            2,
+            captured("p0", Uuid::new_v4(), format!("{prefix}ann Smith")),
+        ),
+        (
+            3,
+            captured("p1", Uuid::new_v4(), format!("{prefix}bob Jones")),
+        ),
+        (
+            4,
+            captured(
+                "p2",
+                Uuid::new_v4(),
+                format!("{}ANNA Lee", prefix.to_uppercase()),
+            ),
+        ),
+        (
+            5,
+            captured("p3", forgotten_subject, format!("{prefix}anne Forgotten")),
+        ),
+        (
+            6,
+            JourneyEvent::SubjectForgotten {
+                subject_id: forgotten_subject,
+            },
+        ),
+    ] {
+        repo.dispatch(&journey_id.to_string(), &[event(sequence, payload)])
+            .await;
+    }
 
-    let subjects = repo.find_subjects_by_email(&query_email).await.unwrap();
-    assert_eq!(subjects, vec![subject_id]);
-}
+    let refs = |persons: Vec<PersonView>| {
+        persons
+            .into_iter()
+            .map(|p| p.person_ref)
+            .collect::<Vec<_>>()
+    };
 
-#[test_context(PostgresViewRepositoryContext)]
-#[tokio::test]
-async fn test_find_subjects_by_email_deduplicates_across_journeys(
-    ctx: &mut PostgresViewRepositoryContext,
-) {
-    // subject_lookup has subject_id as PK — one row regardless of how many
-    // journeys a subject appears in.  The query must return exactly one result.
-    let repo = ctx.repo();
-    let subject_id = Uuid::new_v4();
-    let unique_email = format!("repeat+{}@example.com", Uuid::new_v4());
+    // Case-insensitive prefix match, most recently updated first; the
+    // forgotten person is excluded.
+    let found = repo
+        .find_persons_by_name(&format!("{prefix}ANN"))
+        .await
+        .unwrap();
+    assert_eq!(refs(found), vec!["p2", "p0"]);
 
-    ctx.insert_subject_lookup(subject_id, &unique_email).await;
+    let found = repo.find_persons_by_name(&prefix).await.unwrap();
+    assert_eq!(refs(found), vec!["p2", "p1", "p0"]);
 
-    let subjects = repo.find_subjects_by_email(&unique_email).await.unwrap();
-    assert_eq!(
-        subjects.len(),
-        1,
-        "same subject across two journeys must be deduplicated"
+    // Not a prefix of any name; wildcards match literally.
+    assert!(
+        repo.find_persons_by_name("ann")
+            .await
+            .unwrap()
+            .iter()
+            .all(|p| p.journey_id != journey_id)
     );
-    assert_eq!(subjects[0], subject_id);
+    assert!(
+        repo.find_persons_by_name(&format!("{prefix}%"))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        repo.find_persons_by_name(&format!("{prefix}_nn"))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(repo.find_persons_by_name("  ").await.unwrap().is_empty());
 }
 
+// ── PII encryption ───────────────────────────────────────────────────────
+
+/// With a cipher, identities are stored encrypted, read back in plaintext, and
+/// still found by email through the blind index.
+#[cfg(feature = "encryption")]
 #[test_context(PostgresViewRepositoryContext)]
 #[tokio::test]
-async fn test_find_subjects_by_email_excludes_shredded(ctx: &mut PostgresViewRepositoryContext) {
-    // After shredding, the subject_lookup row is deleted by the route handler.
-    // Verify the email lookup returns nothing once that deletion has occurred.
-    let repo = ctx.repo();
-    let subject_id = Uuid::new_v4();
-    let unique_email = format!("gone+{}@example.com", Uuid::new_v4());
+async fn test_pii_cipher_round_trips_person_identities(ctx: &mut PostgresViewRepositoryContext) {
+    use journey_dynamics::pii_cipher::{AesGcmPiiCipher, CIPHERTEXT_PREFIX};
 
-    ctx.insert_subject_lookup(subject_id, &unique_email).await;
+    let repo = ctx
+        .repo()
+        .with_pii_cipher(Arc::new(AesGcmPiiCipher::new(&[0x42; 32]).unwrap()));
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    start_with_person(&repo, journey_id).await;
 
-    // Simulate shredding via the actual method the route handler calls.
+    let (name, email, phone, email_index): (String, String, String, Option<Vec<u8>>) =
+        sqlx::query_as(
+            "SELECT name, email, phone, email_index FROM journey_person WHERE journey_id = $1",
+        )
+        .bind(journey_id)
+        .fetch_one(&ctx.pool)
+        .await
+        .unwrap();
+    for stored in [&name, &email, &phone] {
+        assert!(stored.starts_with(CIPHERTEXT_PREFIX), "{stored}");
+    }
+    assert!(!email.contains("alice"));
+    assert!(email_index.is_some());
+
+    let view = repo.load(&journey_id).await.unwrap().unwrap();
+    let person = &view.persons[0];
+    assert_eq!(person.name.as_deref(), Some("Alice Smith"));
+    assert_eq!(person.email.as_deref(), Some("alice@example.com"));
+    assert_eq!(person.phone.as_deref(), Some("+44 20 7946 0000"));
+
+    let found = repo.find_by_email("Alice@Example.com").await.unwrap();
+    assert!(found.iter().any(|view| view.id == journey_id));
+    assert!(matches!(
+        repo.find_persons_by_name("alice s").await,
+        Err(ViewRepositoryError::Unsupported(_))
+    ));
+
+    repo.dispatch(
+        &journey_id.to_string(),
+        &[EventEnvelope {
+            aggregate_id: journey_id.to_string(),
+            sequence: 4,
+            payload: JourneyEvent::Forgotten,
+            metadata: HashMap::default(),
+        }],
+    )
+    .await;
+    let email_index: Option<Vec<u8>> =
+        sqlx::query_scalar("SELECT email_index FROM journey_person WHERE journey_id = $1")
+            .bind(journey_id)
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert!(email_index.is_none());
+}
+
+/// With a cipher, identities stay out of `shared_data` even when embedding is
+/// asked for: the copy would be plaintext.
+#[cfg(feature = "encryption")]
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_pii_cipher_keeps_identities_out_of_shared_data(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    use journey_dynamics::pii_cipher::AesGcmPiiCipher;
+
+    let repo = ctx
+        .repo()
+        .with_embedded_persons(true)
+        .with_pii_cipher(Arc::new(AesGcmPiiCipher::new(&[0x42; 32]).unwrap()));
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    start_with_person(&repo, journey_id).await;
+
+    let shared_data: serde_json::Value =
+        sqlx::query_scalar("SELECT shared_data FROM journey_view WHERE id = $1")
+            .bind(journey_id)
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert!(shared_data.get("person").is_none(), "{shared_data}");
+    assert!(!shared_data.to_string().contains("alice"));
+
+    let view = repo.load(&journey_id).await.unwrap().unwrap();
+    assert_eq!(view.persons[0].email.as_deref(), Some("alice@example.com"));
+}
+
+/// A person field that no longer decrypts, such as one sealed under a rotated
+/// key, is reported as an inconsistent row rather than returned garbled.
+#[cfg(feature = "encryption")]
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_undecryptable_person_field_is_an_inconsistent_row(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    use journey_dynamics::pii_cipher::AesGcmPiiCipher;
+
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    let writer = ctx
+        .repo()
+        .with_pii_cipher(Arc::new(AesGcmPiiCipher::new(&[0x42; 32]).unwrap()));
+    start_with_person(&writer, journey_id).await;
+
+    let reader = ctx
+        .repo()
+        .with_pii_cipher(Arc::new(AesGcmPiiCipher::new(&[0x24; 32]).unwrap()));
+    assert!(matches!(
+        reader.load(&journey_id).await,
+        Err(ViewRepositoryError::InconsistentRow(_))
+    ));
+}
+
+// ── find_subjects_by_email ───────────────────────────────────────────────
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+#[hegel::test(test_cases = 20)]
+async fn test_find_subjects_by_email_case_insensitive(
+    tc: TestCase,
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    let repo = ctx.repo();
+    let subject_id = Uuid::new_v4();
+    let canonical_email = tc.draw(gs::emails());
+    let stored_email = if tc.draw(gs::booleans()) {
+        canonical_email.to_ascii_uppercase()
+    } else {
+        canonical_email.to_ascii_lowercase()
+    };
+    let query_email = if tc.draw(gs::booleans()) {
+        canonical_email.to_ascii_uppercase()
+    } else {
+        canonical_email.to_ascii_lowercase()
+    };
+
+    ctx.insert_subject_lookup(subject_id, &stored_email).await;
+
+    let subjects = repo.find_subjects_by_email(&query_email).await.unwrap();
+    assert_eq!(subjects, vec![subject_id]);
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_find_subjects_by_email_deduplicates_across_journeys(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    // subject_lookup has subject_id as PK — one row regardless of how many
+    // journeys a subject appears in.  The query must return exactly one result.
+    let repo = ctx.repo();
+    let subject_id = Uuid::new_v4();
+    let unique_email = format!("repeat+{}@example.com", Uuid::new_v4());
+
+    ctx.insert_subject_lookup(subject_id, &unique_email).await;
+
+    let subjects = repo.find_subjects_by_email(&unique_email).await.unwrap();
+    assert_eq!(
+        subjects.len(),
+        1,
+        "same subject across two journeys must be deduplicated"
+    );
+    assert_eq!(subjects[0], subject_id);
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_find_subjects_by_email_excludes_shredded(ctx: &mut PostgresViewRepositoryContext) {
+    // After shredding, the subject_lookup row is deleted by the route handler.
+    // Verify the email lookup returns nothing once that deletion has occurred.
+    let repo = ctx.repo();
+    let subject_id = Uuid::new_v4();
+    let unique_email = format!("gone+{}@example.com", Uuid::new_v4());
+
+    ctx.insert_subject_lookup(subject_id, &unique_email).await;
+
+    // Simulate shredding via the actual method the route handler calls.
     repo.delete_subject_lookup(&subject_id).await.unwrap();
 
     let subjects = repo.find_subjects_by_email(&unique_email).await.unwrap();
@@ -1071,7 +1687,10 @@ async fn test_attributes_set_plaintext_updates_shared_data(
         &[EventEnvelope {
             aggregate_id: journey_id.to_string(),
             sequence: 1,
-            payload: JourneyEvent::Started { id: journey_id },
+            payload: JourneyEvent::Started {
+                id: journey_id,
+                journey_type: None,
+            },
             metadata: HashMap::default(),
         }],
     )
@@ -1114,3 +1733,2177 @@ async fn test_attributes_set_plaintext_updates_shared_data(
         "destination must be stored at path search/destination"
     );
 }
+
+// ── Corrected ────────────────────────────────────────────────────────────
+
+/// A `Corrected` event replaces one nested value in `shared_data`, array
+/// indices included, and leaves its siblings alone.
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_corrected_replaces_one_value_in_shared_data(ctx: &mut PostgresViewRepositoryContext) {
+    let journey_id = project_timeline(
+        ctx,
+        vec![
+            (0, started("flight-booking")),
+            (
+                1,
+                JourneyEvent::Modified {
+                    step: "passengers".to_string(),
+                    data: json!({ "passengers": [{ "first": "Jhon", "last": "Smith" }] }),
+                },
+            ),
+            (
+                2,
+                JourneyEvent::Corrected {
+                    path: "/passengers/0/first".parse().unwrap(),
+                    value: json!("John"),
+                },
+            ),
+        ],
+    )
+    .await;
+
+    let view = ctx.repo().load(&journey_id).await.unwrap().unwrap();
+    assert_eq!(
+        view.shared_data,
+        json!({ "passengers": [{ "first": "John", "last": "Smith" }] })
+    );
+}
+
+// ── DraftSaved ───────────────────────────────────────────────────────────
+
+/// Drafts merge per step outside `shared_data`, and capturing a step drops
+/// only that step's draft.
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_draft_saved_is_kept_apart_until_the_step_is_captured(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    let draft = |step: &str, data| JourneyEvent::DraftSaved {
+        step: step.to_string(),
+        data,
+    };
+    let journey_id = project_timeline(
+        ctx,
+        vec![
+            (0, started("flight-booking")),
+            (1, draft("search", json!({ "search": { "origin": "LHR" } }))),
+            (
+                2,
+                draft("search", json!({ "search": { "destination": "JFK" } })),
+            ),
+            (
+                3,
+                draft("passengers", json!({ "passengers": [{ "first": "Jo" }] })),
+            ),
+        ],
+    )
+    .await;
+
+    let view = ctx.repo().load(&journey_id).await.unwrap().unwrap();
+    assert_eq!(view.shared_data, json!({}));
+    assert_eq!(
+        view.draft_data,
+        json!({
+            "search": { "search": { "origin": "LHR", "destination": "JFK" } },
+            "passengers": { "passengers": [{ "first": "Jo" }] }
+        })
+    );
+
+    ctx.repo()
+        .dispatch(
+            &journey_id.to_string(),
+            &[EventEnvelope {
+                aggregate_id: journey_id.to_string(),
+                sequence: 5,
+                payload: JourneyEvent::Modified {
+                    step: "search".to_string(),
+                    data: json!({ "search": { "origin": "LHR", "destination": "JFK" } }),
+                },
+                metadata: HashMap::default(),
+            }],
+        )
+        .await;
+
+    let view = ctx.repo().load(&journey_id).await.unwrap().unwrap();
+    assert_eq!(
+        view.draft_data,
+        json!({ "passengers": { "passengers": [{ "first": "Jo" }] } })
+    );
+    assert_eq!(
+        view.shared_data,
+        json!({ "search": { "origin": "LHR", "destination": "JFK" } })
+    );
+}
+
+// ── Paused / Resumed ─────────────────────────────────────────────────────
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_pause_and_resume_persist_state_and_timestamps(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    let repo = ctx.repo();
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    let paused_at: DateTime<Utc> = "2025-08-15T10:00:00Z".parse().unwrap();
+    let resumed_at: DateTime<Utc> = "2025-08-15T10:01:30Z".parse().unwrap();
+
+    repo.dispatch(
+        &journey_id.to_string(),
+        &[
+            EventEnvelope {
+                aggregate_id: journey_id.to_string(),
+                sequence: 1,
+                payload: JourneyEvent::Started {
+                    id: journey_id,
+                    journey_type: None,
+                },
+                metadata: HashMap::default(),
+            },
+            EventEnvelope {
+                aggregate_id: journey_id.to_string(),
+                sequence: 2,
+                payload: JourneyEvent::Paused { at: paused_at },
+                metadata: HashMap::default(),
+            },
+        ],
+    )
+    .await;
+
+    let view = repo.load(&journey_id).await.unwrap().unwrap();
+    assert_eq!(view.state, JourneyState::Paused);
+    assert_eq!(view.paused_at, Some(paused_at));
+    assert_eq!(view.resumed_at, None);
+    assert_eq!(view.paused_duration_ms, 0);
+
+    repo.dispatch(
+        &journey_id.to_string(),
+        &[EventEnvelope {
+            aggregate_id: journey_id.to_string(),
+            sequence: 3,
+            payload: JourneyEvent::Resumed { at: resumed_at },
+            metadata: HashMap::default(),
+        }],
+    )
+    .await;
+
+    let view = repo.load(&journey_id).await.unwrap().unwrap();
+    assert_eq!(view.state, JourneyState::InProgress);
+    assert_eq!(view.paused_at, None);
+    assert_eq!(view.resumed_at, Some(resumed_at));
+    assert_eq!(view.paused_duration_ms, 90_000);
+}
+
+// ── Event time ───────────────────────────────────────────────────────────
+
+/// Projection timestamps come from the envelope's `time` metadata, so
+/// replaying the same events into a fresh row reproduces them exactly.
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_replay_reproduces_event_timestamps(ctx: &mut PostgresViewRepositoryContext) {
+    let repo = ctx.repo();
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    let at = |time: &str| HashMap::from([("time".to_string(), time.to_string())]);
+    let events = [
+        EventEnvelope {
+            aggregate_id: journey_id.to_string(),
+            sequence: 1,
+            payload: JourneyEvent::Started {
+                id: journey_id,
+                journey_type: None,
+            },
+            metadata: at("2025-08-15T10:00:00Z"),
+        },
+        EventEnvelope {
+            aggregate_id: journey_id.to_string(),
+            sequence: 2,
+            payload: JourneyEvent::WorkflowEvaluated {
+                suggested_actions: vec!["search".to_string()],
+                phase: None,
+                ranked_actions: vec![],
+                completion_blocked_by: vec![],
+            },
+            metadata: at("2025-08-15T10:05:00Z"),
+        },
+    ];
+
+    repo.dispatch(&journey_id.to_string(), &events).await;
+    let first = repo.load(&journey_id).await.unwrap().unwrap();
+
+    // Drop the projection and replay the same events.
+    sqlx::query("DELETE FROM journey_view WHERE id = $1")
+        .bind(journey_id)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    repo.dispatch(&journey_id.to_string(), &events).await;
+    let replayed = repo.load(&journey_id).await.unwrap().unwrap();
+
+    let created_at: DateTime<Utc> = "2025-08-15T10:00:00Z".parse().unwrap();
+    let updated_at: DateTime<Utc> = "2025-08-15T10:05:00Z".parse().unwrap();
+    assert_eq!(first.created_at, Some(created_at));
+    assert_eq!(first.updated_at, Some(updated_at));
+    assert_eq!(replayed.created_at, first.created_at);
+    assert_eq!(replayed.updated_at, first.updated_at);
+}
+
+// ── find_where_data_contains ─────────────────────────────────────────────
+
+/// Dispatch `Started` then an `AttributesSet` with the given plaintext search
+/// destination.
+async fn start_with_destination(
+    ctx: &mut PostgresViewRepositoryContext,
+    destination: &str,
+) -> Uuid {
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    let repo = ctx.repo();
+
+    let mut plaintext = std::collections::BTreeMap::new();
+    plaintext.insert(
+        "/search/origin".parse::<PointerBuf>().unwrap(),
+        json!("LHR"),
+    );
+    plaintext.insert(
+        "/search/destination".parse::<PointerBuf>().unwrap(),
+        json!(destination),
+    );
+
+    repo.dispatch(
+        &journey_id.to_string(),
+        &[
+            EventEnvelope {
+                aggregate_id: journey_id.to_string(),
+                sequence: 1,
+                payload: JourneyEvent::Started {
+                    id: journey_id,
+                    journey_type: None,
+                },
+                metadata: HashMap::default(),
+            },
+            EventEnvelope {
+                aggregate_id: journey_id.to_string(),
+                sequence: 2,
+                payload: JourneyEvent::AttributesSet {
+                    plaintext,
+                    secret_partitions: vec![],
+                },
+                metadata: HashMap::default(),
+            },
+        ],
+    )
+    .await;
+
+    journey_id
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_find_where_data_contains_matches_nested_fragment(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    // A destination unique to this test run so parallel tests don't match.
+    let destination = format!("JFK-{}", Uuid::new_v4());
+    let jfk = start_with_destination(ctx, &destination).await;
+    let cdg = start_with_destination(ctx, "CDG").await;
+
+    let views = ctx
+        .repo()
+        .find_where_data_contains(&json!({ "search": { "destination": destination } }))
+        .await
+        .unwrap();
+
+    let ids: Vec<Uuid> = views.iter().map(|v| v.id).collect();
+    assert_eq!(ids, vec![jfk]);
+    assert!(!ids.contains(&cdg));
+    assert_eq!(views[0].shared_data["search"]["origin"], json!("LHR"));
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_find_where_data_contains_started_journey_has_empty_object(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    let repo = ctx.repo();
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    repo.dispatch(
+        &journey_id.to_string(),
+        &[EventEnvelope {
+            aggregate_id: journey_id.to_string(),
+            sequence: 1,
+            payload: JourneyEvent::Started {
+                id: journey_id,
+                journey_type: None,
+            },
+            metadata: HashMap::default(),
+        }],
+    )
+    .await;
+
+    // Every row's shared_data is an object, so `{}` matches the new journey.
+    let views = repo.find_where_data_contains(&json!({})).await.unwrap();
+    assert!(views.iter().any(|v| v.id == journey_id));
+}
+
+/// A capture of a bare scalar is filed under its step rather than replacing
+/// `shared_data`, and can be queried like any other field.
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_scalar_capture_is_stored_under_its_step(ctx: &mut PostgresViewRepositoryContext) {
+    let repo = ctx.repo();
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    // A name unique to this test run so parallel tests don't match.
+    let name = format!("Joe-{journey_id}");
+    repo.dispatch(
+        &journey_id.to_string(),
+        &[
+            EventEnvelope {
+                aggregate_id: journey_id.to_string(),
+                sequence: 1,
+                payload: JourneyEvent::Started {
+                    id: journey_id,
+                    journey_type: None,
+                },
+                metadata: HashMap::default(),
+            },
+            EventEnvelope {
+                aggregate_id: journey_id.to_string(),
+                sequence: 2,
+                payload: JourneyEvent::Modified {
+                    step: "first_name".to_string(),
+                    data: json!(name),
+                },
+                metadata: HashMap::default(),
+            },
+        ],
+    )
+    .await;
+
+    let view = repo.load(&journey_id).await.unwrap().unwrap();
+    assert_eq!(view.shared_data, json!({ "first_name": name }));
+    let views = repo
+        .find_where_data_contains(&json!({ "first_name": name }))
+        .await
+        .unwrap();
+    assert_eq!(
+        views.iter().map(|v| v.id).collect::<Vec<_>>(),
+        vec![journey_id]
+    );
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_replayed_and_out_of_order_events_are_skipped(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    let repo = ctx.repo();
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    let capture = |sequence, origin| EventEnvelope {
+        aggregate_id: journey_id.to_string(),
+        sequence,
+        payload: JourneyEvent::Modified {
+            step: "search".to_string(),
+            data: json!({ "origin": origin }),
+        },
+        metadata: HashMap::default(),
+    };
+    let started = EventEnvelope {
+        aggregate_id: journey_id.to_string(),
+        sequence: 1,
+        payload: JourneyEvent::Started {
+            id: journey_id,
+            journey_type: None,
+        },
+        metadata: HashMap::default(),
+    };
+    let version = || async {
+        sqlx::query_scalar::<_, i64>("SELECT version FROM journey_view WHERE id = $1")
+            .bind(journey_id)
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap()
+    };
+
+    repo.dispatch(
+        &journey_id.to_string(),
+        &[started.clone(), capture(2, "LHR"), capture(3, "LGW")],
+    )
+    .await;
+    // At-least-once delivery: the same batch again.
+    repo.dispatch(
+        &journey_id.to_string(),
+        &[started, capture(2, "LHR"), capture(3, "LGW")],
+    )
+    .await;
+    // A stale event, arriving after the one that superseded it.
+    repo.dispatch(&journey_id.to_string(), &[capture(2, "STN")])
+        .await;
+
+    let view = repo.load(&journey_id).await.unwrap().unwrap();
+    assert_eq!(view.shared_data, json!({ "origin": "LGW" }));
+    assert_eq!(version().await, 3);
+
+    // Later events still apply, including in a batch that repeats earlier ones.
+    repo.dispatch(
+        &journey_id.to_string(),
+        &[capture(3, "LGW"), capture(4, "LTN")],
+    )
+    .await;
+
+    let view = repo.load(&journey_id).await.unwrap().unwrap();
+    assert_eq!(view.shared_data, json!({ "origin": "LTN" }));
+    assert_eq!(version().await, 4);
+}
+
+async fn start_typed(ctx: &mut PostgresViewRepositoryContext, journey_type: Option<&str>) -> Uuid {
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    ctx.repo()
+        .dispatch(
+            &journey_id.to_string(),
+            &[EventEnvelope {
+                aggregate_id: journey_id.to_string(),
+                sequence: 1,
+                payload: JourneyEvent::Started {
+                    id: journey_id,
+                    journey_type: journey_type.map(str::to_string),
+                },
+                metadata: HashMap::default(),
+            }],
+        )
+        .await;
+    journey_id
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_find_by_type_returns_only_journeys_of_that_type(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    // A type unique to this test run so parallel tests don't match.
+    let journey_type = format!("insurance-{}", Uuid::new_v4());
+    let mut expected = vec![
+        start_typed(ctx, Some(&journey_type)).await,
+        start_typed(ctx, Some(&journey_type)).await,
+    ];
+    let other = start_typed(ctx, Some("flight-booking")).await;
+    let untyped = start_typed(ctx, None).await;
+
+    let views = ctx.repo().find_by_type(&journey_type).await.unwrap();
+
+    let mut ids: Vec<Uuid> = views.iter().map(|v| v.id).collect();
+    ids.sort();
+    expected.sort();
+    assert_eq!(ids, expected);
+    assert!(
+        views
+            .iter()
+            .all(|v| v.journey_type.as_deref() == Some(journey_type.as_str()))
+    );
+
+    let untyped_view = ctx.repo().load(&untyped).await.unwrap().unwrap();
+    assert_eq!(untyped_view.journey_type, None);
+    let other_view = ctx.repo().load(&other).await.unwrap().unwrap();
+    assert_eq!(other_view.journey_type.as_deref(), Some("flight-booking"));
+}
+
+// ── Tags ─────────────────────────────────────────────────────────────────
+
+async fn tag(
+    ctx: &PostgresViewRepositoryContext,
+    journey_id: Uuid,
+    sequence: usize,
+    key: &str,
+    value: &str,
+) {
+    ctx.repo()
+        .dispatch(
+            &journey_id.to_string(),
+            &[EventEnvelope {
+                aggregate_id: journey_id.to_string(),
+                sequence,
+                payload: JourneyEvent::Tagged {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                },
+                metadata: HashMap::default(),
+            }],
+        )
+        .await;
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_tagged_journeys_are_loaded_with_their_tags(ctx: &mut PostgresViewRepositoryContext) {
+    let journey_id = start_typed(ctx, None).await;
+    tag(ctx, journey_id, 2, "channel", "web").await;
+    tag(ctx, journey_id, 3, "campaign", "summer").await;
+    tag(ctx, journey_id, 4, "channel", "mobile").await;
+
+    let expected = BTreeMap::from([
+        ("campaign".to_string(), "summer".to_string()),
+        ("channel".to_string(), "mobile".to_string()),
+    ]);
+    let view = ctx.repo().load(&journey_id).await.unwrap().unwrap();
+    assert_eq!(view.tags, expected);
+    assert_eq!(view.shared_data, json!({}));
+
+    let listed = ctx.repo().load_all().await.unwrap();
+    let listed = listed.iter().find(|view| view.id == journey_id).unwrap();
+    assert_eq!(listed.tags, expected);
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_find_by_tag_returns_only_journeys_with_that_value(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    // A value unique to this test run so parallel tests don't match.
+    let campaign = format!("summer-{}", Uuid::new_v4());
+    let first = start_typed(ctx, None).await;
+    let second = start_typed(ctx, None).await;
+    let other_value = start_typed(ctx, None).await;
+    let retagged = start_typed(ctx, None).await;
+    tag(ctx, first, 2, "campaign", &campaign).await;
+    tag(ctx, second, 2, "campaign", &campaign).await;
+    tag(ctx, other_value, 2, "campaign", "winter").await;
+    tag(ctx, retagged, 2, "campaign", &campaign).await;
+    tag(ctx, retagged, 3, "campaign", "winter").await;
+
+    let views = ctx.repo().find_by_tag("campaign", &campaign).await.unwrap();
+
+    let mut ids: Vec<Uuid> = views.iter().map(|view| view.id).collect();
+    ids.sort();
+    let mut expected = vec![first, second];
+    expected.sort();
+    assert_eq!(ids, expected);
+    assert!(views.iter().all(|view| view.tags["campaign"] == campaign));
+    assert!(
+        ctx.repo()
+            .find_by_tag("channel", &campaign)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+// ── Step history and funnel ─────────────────────────────────────────────
+
+/// Dispatch a `StepProgressed` for each of `steps`, in order, starting at
+/// `sequence`, followed by `Completed` when `complete` is set.
+async fn progress_through(
+    ctx: &PostgresViewRepositoryContext,
+    journey_id: Uuid,
+    sequence: usize,
+    steps: &[&str],
+    complete: bool,
+) {
+    let mut events: Vec<EventEnvelope<Journey>> = Vec::new();
+    let mut from_step = None;
+    for step in steps {
+        events.push(EventEnvelope {
+            aggregate_id: journey_id.to_string(),
+            sequence: sequence + events.len(),
+            payload: JourneyEvent::StepProgressed {
+                from_step: from_step.replace((*step).to_string()),
+                to_step: (*step).to_string(),
+            },
+            metadata: HashMap::default(),
+        });
+    }
+    if complete {
+        events.push(EventEnvelope {
+            aggregate_id: journey_id.to_string(),
+            sequence: sequence + events.len(),
+            payload: JourneyEvent::Completed,
+            metadata: HashMap::default(),
+        });
+    }
+    ctx.repo().dispatch(&journey_id.to_string(), &events).await;
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_step_history_records_forward_then_back_transitions(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    let journey_id = start_typed(ctx, None).await;
+    let entered_at: DateTime<Utc> = "2025-08-15T10:00:00Z".parse().unwrap();
+    let metadata = HashMap::from([("time".to_string(), entered_at.to_rfc3339())]);
+    let transitions = [
+        (None, "search"),
+        (Some("search"), "passenger_details"),
+        (Some("passenger_details"), "search"),
+    ];
+    let events: Vec<EventEnvelope<Journey>> = transitions
+        .iter()
+        .zip(2..)
+        .map(|((from_step, to_step), sequence)| EventEnvelope {
+            aggregate_id: journey_id.to_string(),
+            sequence,
+            payload: JourneyEvent::StepProgressed {
+                from_step: from_step.map(str::to_string),
+                to_step: (*to_step).to_string(),
+            },
+            metadata: metadata.clone(),
+        })
+        .collect();
+    ctx.repo().dispatch(&journey_id.to_string(), &events).await;
+
+    let history = ctx.repo().load_step_history(&journey_id).await.unwrap();
+
+    assert_eq!(
+        history,
+        transitions
+            .iter()
+            .zip(2..)
+            .map(|((from_step, to_step), sequence)| StepTransition {
+                from_step: from_step.map(str::to_string),
+                to_step: (*to_step).to_string(),
+                sequence,
+                entered_at,
+            })
+            .collect::<Vec<_>>()
+    );
+    let view = ctx.repo().load(&journey_id).await.unwrap().unwrap();
+    assert_eq!(view.current_step.as_deref(), Some("search"));
+    assert!(
+        ctx.repo()
+            .load_step_history(&Uuid::new_v4())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_funnel_counts_reached_and_completed_per_step(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    // A type unique to this test run so parallel tests don't match.
+    let journey_type = format!("funnel-{}", Uuid::new_v4());
+    let full = ["search", "passenger_details", "payment"];
+
+    let completed = start_typed(ctx, Some(&journey_type)).await;
+    progress_through(ctx, completed, 2, &full, true).await;
+    let abandoned_at_payment = start_typed(ctx, Some(&journey_type)).await;
+    progress_through(ctx, abandoned_at_payment, 2, &full, false).await;
+    let abandoned_at_search = start_typed(ctx, Some(&journey_type)).await;
+    progress_through(ctx, abandoned_at_search, 2, &full[..1], false).await;
+    // Revisiting a step does not count the journey twice.
+    let revisited = start_typed(ctx, Some(&journey_type)).await;
+    progress_through(
+        ctx,
+        revisited,
+        2,
+        &["search", "passenger_details", "search"],
+        true,
+    )
+    .await;
+    let other_type = start_typed(ctx, Some("flight-booking")).await;
+    progress_through(ctx, other_type, 2, &full, true).await;
+
+    let funnel = ctx.repo().funnel(Some(&journey_type)).await.unwrap();
+
+    assert_eq!(
+        funnel,
+        vec![
+            FunnelStep {
+                step: "search".to_string(),
+                reached: 4,
+                completed: 2,
+            },
+            FunnelStep {
+                step: "passenger_details".to_string(),
+                reached: 3,
+                completed: 2,
+            },
+            FunnelStep {
+                step: "payment".to_string(),
+                reached: 2,
+                completed: 1,
+            },
+        ]
+    );
+
+    // Without a type every journey counts, including the other type's.
+    let all = ctx.repo().funnel(None).await.unwrap();
+    let payment = all.iter().find(|s| s.step == "payment").unwrap();
+    assert!(payment.reached >= 3);
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_find_page_filters_by_state_and_step_and_pages(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    // A step unique to this test run so parallel tests don't match.
+    let payment = format!("payment-{}", Uuid::new_v4());
+
+    let in_progress = start_typed(ctx, None).await;
+    progress_through(ctx, in_progress, 2, &["search", &payment], false).await;
+    let complete = start_typed(ctx, None).await;
+    progress_through(ctx, complete, 2, &["search", &payment], true).await;
+    let elsewhere = start_typed(ctx, None).await;
+    progress_through(ctx, elsewhere, 2, &["search"], false).await;
+
+    let at_payment = JourneyFilter {
+        state: None,
+        current_step: Some(payment.clone()),
+    };
+    let all = ctx.repo().find_page(&at_payment, 50, 0).await.unwrap();
+    assert_eq!(all.total, 2);
+    let mut ids: Vec<Uuid> = all.items.iter().map(|view| view.id).collect();
+    ids.sort();
+    let mut expected = vec![in_progress, complete];
+    expected.sort();
+    assert_eq!(ids, expected);
+
+    let filter = JourneyFilter {
+        state: Some(JourneyState::InProgress),
+        ..at_payment.clone()
+    };
+    let page = ctx.repo().find_page(&filter, 50, 0).await.unwrap();
+    assert_eq!(page.total, 1);
+    assert_eq!(page.items[0].id, in_progress);
+
+    // Pages split the matches without losing the total.
+    let first = ctx.repo().find_page(&at_payment, 1, 0).await.unwrap();
+    let second = ctx.repo().find_page(&at_payment, 1, 1).await.unwrap();
+    let past_end = ctx.repo().find_page(&at_payment, 1, 2).await.unwrap();
+    assert_eq!((first.total, first.limit, first.offset), (2, 1, 0));
+    assert_eq!(first.items.len(), 1);
+    assert_eq!(second.items.len(), 1);
+    assert_ne!(first.items[0].id, second.items[0].id);
+    assert!(past_end.items.is_empty());
+    assert_eq!(past_end.total, 2);
+}
+
+/// A `StepSkipped` touches only the view's timestamps; the transition after
+/// it is what the step history records.
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_step_skipped_is_not_a_transition(ctx: &mut PostgresViewRepositoryContext) {
+    let journey_id = project_timeline(
+        ctx,
+        vec![
+            (0, started("flight-booking")),
+            (1, entered(None, "search")),
+            (
+                5,
+                JourneyEvent::StepSkipped {
+                    step: "payment".to_string(),
+                    allowed: vec!["passengers".to_string()],
+                },
+            ),
+            (5, entered(Some("search"), "payment")),
+        ],
+    )
+    .await;
+
+    let view = ctx.repo().load(&journey_id).await.unwrap().unwrap();
+    assert_eq!(view.current_step.as_deref(), Some("payment"));
+    let history = ctx.repo().load_step_history(&journey_id).await.unwrap();
+    let steps: Vec<&str> = history.iter().map(|t| t.to_step.as_str()).collect();
+    assert_eq!(steps, ["search", "payment"]);
+}
+
+// ── JourneyUpdates publishing ───────────────────────────────────────────
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_dispatch_publishes_committed_view_to_subscribers(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    let updates = JourneyUpdates::default();
+    let repo = ctx.repo().with_updates(updates.clone());
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    let mut receiver = updates.subscribe(journey_id);
+
+    repo.dispatch(
+        &journey_id.to_string(),
+        &[
+            EventEnvelope {
+                aggregate_id: journey_id.to_string(),
+                sequence: 1,
+                payload: JourneyEvent::Started {
+                    id: journey_id,
+                    journey_type: None,
+                },
+                metadata: HashMap::default(),
+            },
+            EventEnvelope {
+                aggregate_id: journey_id.to_string(),
+                sequence: 2,
+                payload: JourneyEvent::Modified {
+                    step: "search".to_string(),
+                    data: json!({"origin": "LHR"}),
+                },
+                metadata: HashMap::default(),
+            },
+        ],
+    )
+    .await;
+
+    // One message per dispatched batch, carrying the view as committed.
+    let view = receiver.try_recv().unwrap();
+    assert_eq!(view.id, journey_id);
+    assert_eq!(view.shared_data, json!({"origin": "LHR"}));
+    assert!(receiver.try_recv().is_err());
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_dispatch_rolls_back_whole_batch_on_failure(ctx: &mut PostgresViewRepositoryContext) {
+    let updates = JourneyUpdates::default();
+    let repo = ctx.repo().with_updates(updates.clone());
+    let journey_id = start_with_destination(ctx, "JFK").await;
+    let before = repo.load(&journey_id).await.unwrap().unwrap();
+    let mut receiver = updates.subscribe(journey_id);
+
+    repo.dispatch(
+        &journey_id.to_string(),
+        &[
+            EventEnvelope {
+                aggregate_id: journey_id.to_string(),
+                sequence: 3,
+                payload: JourneyEvent::Modified {
+                    step: "search".to_string(),
+                    data: json!({"destination": "CDG"}),
+                },
+                metadata: HashMap::default(),
+            },
+            EventEnvelope {
+                aggregate_id: journey_id.to_string(),
+                sequence: 4,
+                payload: JourneyEvent::PersonCaptured {
+                    person_ref: "passenger_0".to_string(),
+                    subject_id: Uuid::new_v4(),
+                    name: "Alice Smith".to_string(),
+                    email: "alice@example.com".to_string(),
+                    phone: None,
+                },
+                metadata: HashMap::default(),
+            },
+            // PostgreSQL rejects NUL characters in jsonb, failing the batch
+            // after the first two events have been applied.
+            EventEnvelope {
+                aggregate_id: journey_id.to_string(),
+                sequence: 5,
+                payload: JourneyEvent::Modified {
+                    step: "search".to_string(),
+                    data: json!({"note": "bad\u{0}"}),
+                },
+                metadata: HashMap::default(),
+            },
+        ],
+    )
+    .await;
+
+    let after = repo.load(&journey_id).await.unwrap().unwrap();
+    assert_eq!(after.shared_data, before.shared_data);
+    assert!(after.persons.is_empty(), "person row must be rolled back");
+    assert_eq!(after.updated_at, before.updated_at);
+    assert!(
+        receiver.try_recv().is_err(),
+        "nothing is published on failure"
+    );
+}
+
+// ── rebuild_views ────────────────────────────────────────────────────────
+
+/// Persist `events` to the `events` table, as the event store would, and
+/// register the aggregate for cleanup.
+async fn persist_events(
+    ctx: &mut PostgresViewRepositoryContext,
+    events: &[EventEnvelope<Journey>],
+) {
+    for event in events {
+        if !ctx.event_aggregate_ids.contains(&event.aggregate_id) {
+            ctx.event_aggregate_ids.push(event.aggregate_id.clone());
+        }
+        sqlx::query(
+            "INSERT INTO events \
+             (aggregate_type, aggregate_id, sequence, event_type, event_version, payload, metadata) \
+             VALUES ('Journey', $1, $2, $3, $4, $5, $6)",
+        )
+        .bind(&event.aggregate_id)
+        .bind(i64::try_from(event.sequence).unwrap())
+        .bind(event.payload.event_type())
+        .bind(event.payload.event_version())
+        .bind(serde_json::to_value(&event.payload).unwrap())
+        .bind(serde_json::to_value(&event.metadata).unwrap())
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    }
+}
+
+/// A journey rebuilt from the event store matches the view live dispatch
+/// produced, even after the live view has been tampered with, and rebuilding
+/// twice changes nothing.
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_rebuild_views_reproduces_live_view(ctx: &mut PostgresViewRepositoryContext) {
+    let repo = ctx.repo();
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    let at = |time: &str| HashMap::from([("time".to_string(), time.to_string())]);
+    let mut plaintext = std::collections::BTreeMap::new();
+    plaintext.insert(
+        "/search/origin".parse::<PointerBuf>().unwrap(),
+        json!("LHR"),
+    );
+    let events = [
+        EventEnvelope {
+            aggregate_id: journey_id.to_string(),
+            sequence: 1,
+            payload: JourneyEvent::Started {
+                id: journey_id,
+                journey_type: None,
+            },
+            metadata: at("2025-08-15T10:00:00Z"),
+        },
+        EventEnvelope {
+            aggregate_id: journey_id.to_string(),
+            sequence: 2,
+            payload: JourneyEvent::AttributesSet {
+                plaintext,
+                secret_partitions: vec![],
+            },
+            metadata: at("2025-08-15T10:01:00Z"),
+        },
+        EventEnvelope {
+            aggregate_id: journey_id.to_string(),
+            sequence: 3,
+            payload: JourneyEvent::WorkflowEvaluated {
+                suggested_actions: vec!["select_flight".to_string()],
+                phase: Some("booking".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec![],
+            },
+            metadata: at("2025-08-15T10:01:00Z"),
+        },
+    ];
+    persist_events(ctx, &events).await;
+    repo.dispatch(&journey_id.to_string(), &events).await;
+    let live = serde_json::to_value(repo.load(&journey_id).await.unwrap().unwrap()).unwrap();
+
+    sqlx::query("UPDATE journey_view SET shared_data = '{}'::jsonb WHERE id = $1")
+        .bind(journey_id)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+
+    let event_store: PersistedEventStore<PostgresEventRepository, Journey> =
+        PersistedEventStore::new_event_store(PostgresEventRepository::new(ctx.pool.clone()));
+    for _ in 0..2 {
+        let stats = rebuild_views(&event_store, &repo, Some(vec![journey_id]))
+            .await
+            .unwrap();
+        assert_eq!(
+            stats,
+            RebuildStats {
+                journeys: 1,
+                events: 3
+            }
+        );
+        let rebuilt = serde_json::to_value(repo.load(&journey_id).await.unwrap().unwrap()).unwrap();
+        assert_eq!(rebuilt, live);
+    }
+}
+
+/// A journey with no events is left alone rather than counted or deleted.
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_rebuild_views_skips_journeys_without_events(ctx: &mut PostgresViewRepositoryContext) {
+    let repo = ctx.repo();
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    repo.dispatch(
+        &journey_id.to_string(),
+        &[EventEnvelope {
+            aggregate_id: journey_id.to_string(),
+            sequence: 1,
+            payload: JourneyEvent::Started {
+                id: journey_id,
+                journey_type: None,
+            },
+            metadata: HashMap::default(),
+        }],
+    )
+    .await;
+
+    let event_store: PersistedEventStore<PostgresEventRepository, Journey> =
+        PersistedEventStore::new_event_store(PostgresEventRepository::new(ctx.pool.clone()));
+    let stats = rebuild_views(&event_store, &repo, Some(vec![journey_id]))
+        .await
+        .unwrap();
+
+    assert_eq!(stats, RebuildStats::default());
+    assert!(repo.load(&journey_id).await.unwrap().is_some());
+}
+
+/// A framework over the Postgres event store and this repository, deciding
+/// with the transition table `model`.
+fn table_cqrs(
+    ctx: &PostgresViewRepositoryContext,
+    model: &str,
+) -> CqrsFramework<Journey, PersistedEventStore<PostgresEventRepository, Journey>> {
+    let engine = TableDecisionEngine::from_json(model).unwrap();
+    CqrsFramework::new(
+        PersistedEventStore::new_event_store(PostgresEventRepository::new(ctx.pool.clone())),
+        vec![Box::new(ctx.repo())],
+        JourneyServices::without_validation(Arc::new(engine)),
+    )
+}
+
+/// After the model changes, re-evaluating a journey type stores what the new
+/// model suggests for the data already captured, and leaves other types alone.
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_reevaluate_all_stores_the_new_models_suggestions(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    let old_model = r#"{
+        "search": { "requires": ["search.origin"], "next": ["passengers"] },
+        "passengers": {}
+    }"#;
+    let new_model = r#"{
+        "search": { "requires": ["search.origin"], "next": ["extras"] },
+        "extras": {}
+    }"#;
+    let journey_type = format!("reevaluate-{}", Uuid::new_v4());
+    let other_type = format!("reevaluate-{}", Uuid::new_v4());
+
+    let cqrs = table_cqrs(ctx, old_model);
+    let mut journeys = vec![];
+    for journey_type in [&journey_type, &other_type] {
+        let journey_id = ctx.track_journey(Uuid::new_v4());
+        ctx.event_aggregate_ids.push(journey_id.to_string());
+        let id = journey_id.to_string();
+        cqrs.execute(
+            &id,
+            JourneyCommand::Start {
+                id: journey_id,
+                journey_type: Some(journey_type.clone()),
+            },
+        )
+        .await
+        .unwrap();
+        cqrs.execute(
+            &id,
+            JourneyCommand::Capture {
+                step: "search".to_string(),
+                data: json!({ "search": { "origin": "LHR" } }),
+            },
+        )
+        .await
+        .unwrap();
+        journeys.push(journey_id);
+    }
+
+    let repo = ctx.repo();
+    let reloaded = table_cqrs(ctx, new_model);
+    let stats = reevaluate_all(&reloaded, &repo, Some(&journey_type))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        stats,
+        ReevaluateStats {
+            journeys: 1,
+            ..ReevaluateStats::default()
+        }
+    );
+    let suggested = |decision: Option<WorkflowDecisionView>| decision.unwrap().suggested_actions;
+    assert_eq!(
+        suggested(repo.load_latest_decision(&journeys[0]).await.unwrap()),
+        vec!["extras".to_string()]
+    );
+    assert_eq!(
+        suggested(repo.load_latest_decision(&journeys[1]).await.unwrap()),
+        vec!["passengers".to_string()]
+    );
+}
+
+/// Replaying the event store gives each version's data, and the diff between
+/// two versions is the merge patch of what changed.
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_diff_versions_between_captures(ctx: &mut PostgresViewRepositoryContext) {
+    let cqrs = table_cqrs(
+        ctx,
+        r#"{ "search": { "next": ["passengers"] }, "passengers": {} }"#,
+    );
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    ctx.event_aggregate_ids.push(journey_id.to_string());
+    let id = journey_id.to_string();
+    cqrs.execute(
+        &id,
+        JourneyCommand::Start {
+            id: journey_id,
+            journey_type: None,
+        },
+    )
+    .await
+    .unwrap();
+    let commands = [
+        JourneyCommand::Capture {
+            step: "search".to_string(),
+            data: json!({ "search": { "origin": "LHR", "destination": "JFK" } }),
+        },
+        JourneyCommand::Capture {
+            step: "passengers".to_string(),
+            data: json!({ "passengers": { "adults": 2 } }),
+        },
+        JourneyCommand::Correct {
+            path: "search.destination".to_string(),
+            value: json!("CDG"),
+        },
+    ];
+    let mut versions = vec![];
+    for command in commands {
+        cqrs.execute(&id, command).await.unwrap();
+        let version: i64 = sqlx::query_scalar(
+            "SELECT MAX(sequence) FROM events WHERE aggregate_type = 'Journey' AND aggregate_id = $1",
+        )
+        .bind(&id)
+        .fetch_one(&ctx.pool)
+        .await
+        .unwrap();
+        versions.push(version);
+    }
+    let repo = ctx.repo();
+
+    assert_eq!(
+        repo.data_at_version(&journey_id, 1).await.unwrap(),
+        json!({})
+    );
+    assert_eq!(
+        repo.data_at_version(&journey_id, versions[0])
+            .await
+            .unwrap(),
+        json!({ "search": { "origin": "LHR", "destination": "JFK" } })
+    );
+    let latest = repo
+        .data_at_version(&journey_id, versions[2])
+        .await
+        .unwrap();
+    assert_eq!(
+        latest,
+        repo.load(&journey_id).await.unwrap().unwrap().shared_data
+    );
+
+    assert_eq!(
+        repo.diff_versions(&journey_id, versions[0], versions[1])
+            .await
+            .unwrap(),
+        json!({ "passengers": { "adults": 2 } })
+    );
+    assert_eq!(
+        repo.diff_versions(&journey_id, versions[0], versions[2])
+            .await
+            .unwrap(),
+        json!({ "search": { "destination": "CDG" }, "passengers": { "adults": 2 } })
+    );
+    assert_eq!(
+        repo.diff_versions(&journey_id, versions[2], versions[0])
+            .await
+            .unwrap(),
+        json!({ "search": { "destination": "JFK" }, "passengers": null })
+    );
+    assert_eq!(
+        repo.diff_versions(&journey_id, versions[2], versions[2])
+            .await
+            .unwrap(),
+        json!({})
+    );
+}
+
+/// Journeys are never snapshotted: a snapshot would hold the decrypted
+/// aggregate, which shredding the subject's DEK cannot reach.
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_journeys_are_never_snapshotted(ctx: &mut PostgresViewRepositoryContext) {
+    let provider: Arc<dyn KekProvider> =
+        Arc::new(StaticKekProvider::single("test:v1", vec![0x42; 32]).unwrap());
+    let key_store = Arc::new(PostgresKeyStore::new(
+        ctx.pool.clone(),
+        Arc::clone(&provider),
+    ));
+    let engine = TableDecisionEngine::from_json(r#"{ "search": {} }"#).unwrap();
+    let (cqrs, repo, _) = cqrs_framework(
+        ctx.pool.clone(),
+        key_store,
+        FieldCipher::new(),
+        provider,
+        StructuredJourneyViewRepository::new(ctx.pool.clone()),
+        JourneyServices::without_validation(Arc::new(engine)),
+        None,
+    );
+
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    ctx.event_aggregate_ids.push(journey_id.to_string());
+    let id = journey_id.to_string();
+    cqrs.execute(
+        &id,
+        JourneyCommand::Start {
+            id: journey_id,
+            journey_type: None,
+        },
+    )
+    .await
+    .unwrap();
+    for origin in ["LHR", "LGW", "STN"] {
+        cqrs.execute(
+            &id,
+            JourneyCommand::Capture {
+                step: "search".to_string(),
+                data: json!({ "search": { "origin": origin } }),
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let snapshots: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM snapshots WHERE aggregate_type = 'Journey' AND aggregate_id = $1",
+    )
+    .bind(&id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(snapshots, 0);
+    let view = repo.load(&journey_id).await.unwrap().unwrap();
+    assert_eq!(view.shared_data["search"], json!({ "origin": "STN" }));
+}
+
+/// Paging through the feed from a cursor yields each journey's events once, in
+/// order, and a cursor past the tail yields nothing and itself.
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_event_feed_pages_by_cursor(ctx: &mut PostgresViewRepositoryContext) {
+    let provider: Arc<dyn KekProvider> =
+        Arc::new(StaticKekProvider::single("test:v1", vec![0x42; 32]).unwrap());
+    let key_store = Arc::new(PostgresKeyStore::new(
+        ctx.pool.clone(),
+        Arc::clone(&provider),
+    ));
+    let engine = TableDecisionEngine::from_json(r#"{ "search": {} }"#).unwrap();
+    let (cqrs, _, _) = cqrs_framework(
+        ctx.pool.clone(),
+        key_store.clone(),
+        FieldCipher::new(),
+        provider,
+        StructuredJourneyViewRepository::new(ctx.pool.clone()),
+        JourneyServices::without_validation(Arc::new(engine)),
+        None,
+    );
+    let feed = EventFeed::new(ctx.pool.clone(), key_store);
+
+    let start: i64 = sqlx::query_scalar("SELECT COALESCE(MAX(global_position), 0) FROM events")
+        .fetch_one(&ctx.pool)
+        .await
+        .unwrap();
+    let mut journeys = Vec::new();
+    for origin in ["LHR", "LGW"] {
+        let journey_id = ctx.track_journey(Uuid::new_v4());
+        ctx.event_aggregate_ids.push(journey_id.to_string());
+        cqrs.execute(
+            &journey_id.to_string(),
+            JourneyCommand::Start {
+                id: journey_id,
+                journey_type: None,
+            },
+        )
+        .await
+        .unwrap();
+        cqrs.execute(
+            &journey_id.to_string(),
+            JourneyCommand::Capture {
+                step: "search".to_string(),
+                data: json!({ "search": { "origin": origin } }),
+            },
+        )
+        .await
+        .unwrap();
+        journeys.push(journey_id.to_string());
+    }
+
+    // Other tests write events concurrently, so keep only this test's.
+    let mut cursor = start;
+    let mut seen = Vec::new();
+    loop {
+        let page = feed.read_after(cursor, 2).await.unwrap();
+        assert!(page.events.len() <= 2);
+        let Some(last) = page.events.last() else {
+            assert_eq!(page.next, cursor);
+            break;
+        };
+        assert_eq!(page.next, last.position);
+        assert!(page.events.iter().all(|event| event.position > cursor));
+        seen.extend(
+            page.events
+                .into_iter()
+                .filter(|event| journeys.contains(&event.journey_id))
+                .map(|event| {
+                    (
+                        event.journey_id,
+                        event.event.sequence,
+                        event.event.event_type,
+                    )
+                }),
+        );
+        cursor = page.next;
+    }
+
+    let expected: Vec<_> = journeys
+        .iter()
+        .flat_map(|journey_id| {
+            [
+                "JourneyOpened",
+                "JourneyModified",
+                "WorkflowEvaluated",
+                "StepProgressed",
+            ]
+            .into_iter()
+            .enumerate()
+            .map(move |(i, event_type)| (journey_id.clone(), i + 1, event_type.to_string()))
+        })
+        .collect();
+    assert_eq!(seen, expected);
+
+    let beyond = feed.read_after(cursor + 1_000_000, 10).await.unwrap();
+    assert!(beyond.events.is_empty());
+    assert_eq!(beyond.next, cursor + 1_000_000);
+}
+
+/// The framework records each accepted command in the command log, with the
+/// step and actor it was submitted with.
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_capture_is_recorded_in_the_command_log(ctx: &mut PostgresViewRepositoryContext) {
+    let provider: Arc<dyn KekProvider> =
+        Arc::new(StaticKekProvider::single("test:v1", vec![0x42; 32]).unwrap());
+    let key_store = Arc::new(PostgresKeyStore::new(
+        ctx.pool.clone(),
+        Arc::clone(&provider),
+    ));
+    let engine = TableDecisionEngine::from_json(r#"{ "search": {} }"#).unwrap();
+    let (cqrs, _, _) = cqrs_framework(
+        ctx.pool.clone(),
+        key_store,
+        FieldCipher::new(),
+        provider,
+        StructuredJourneyViewRepository::new(ctx.pool.clone()),
+        JourneyServices::without_validation(Arc::new(engine)),
+        None,
+    );
+
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    ctx.event_aggregate_ids.push(journey_id.to_string());
+    let id = journey_id.to_string();
+    cqrs.execute(
+        &id,
+        JourneyCommand::Start {
+            id: journey_id,
+            journey_type: None,
+        },
+    )
+    .await
+    .unwrap();
+    cqrs.execute_with_metadata(
+        &id,
+        JourneyCommand::Capture {
+            step: "search".to_string(),
+            data: json!({ "search": { "origin": "LHR" } }),
+        },
+        HashMap::from([
+            ("command".to_string(), "Capture".to_string()),
+            ("actor".to_string(), "agent-7".to_string()),
+        ]),
+    )
+    .await
+    .unwrap();
+
+    let log = CommandLog::new(ctx.pool.clone())
+        .load_command_log(&journey_id)
+        .await
+        .unwrap();
+
+    assert_eq!(log.len(), 2);
+    let capture = &log[1];
+    assert_eq!(capture.command_type.as_deref(), Some("Capture"));
+    assert_eq!(capture.step.as_deref(), Some("search"));
+    assert_eq!(capture.actor.as_deref(), Some("agent-7"));
+    assert!(capture.sequences.first() > log[0].sequences.last());
+}
+
+/// With view batching, a burst of commands reaches the view only once it is
+/// flushed, and then in full.
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_batched_view_writes_apply_on_flush(ctx: &mut PostgresViewRepositoryContext) {
+    let provider: Arc<dyn KekProvider> =
+        Arc::new(StaticKekProvider::single("test:v1", vec![0x42; 32]).unwrap());
+    let key_store = Arc::new(PostgresKeyStore::new(
+        ctx.pool.clone(),
+        Arc::clone(&provider),
+    ));
+    let engine = TableDecisionEngine::from_json(r#"{ "search": {} }"#).unwrap();
+    let (cqrs, repo, batcher) = cqrs_framework(
+        ctx.pool.clone(),
+        key_store,
+        FieldCipher::new(),
+        provider,
+        StructuredJourneyViewRepository::new(ctx.pool.clone()),
+        JourneyServices::without_validation(Arc::new(engine)),
+        Some(BatchingOptions {
+            window: Duration::from_mins(1),
+            max_events: 1000,
+        }),
+    );
+    let batcher = batcher.unwrap();
+
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    ctx.event_aggregate_ids.push(journey_id.to_string());
+    let id = journey_id.to_string();
+    cqrs.execute(
+        &id,
+        JourneyCommand::Start {
+            id: journey_id,
+            journey_type: None,
+        },
+    )
+    .await
+    .unwrap();
+    for origin in ["LHR", "LGW", "STN"] {
+        cqrs.execute(
+            &id,
+            JourneyCommand::Capture {
+                step: "search".to_string(),
+                data: json!({ "search": { "origin": origin } }),
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    assert!(repo.load(&journey_id).await.unwrap().is_none());
+    batcher.flush().await;
+
+    let view = repo.load(&journey_id).await.unwrap().unwrap();
+    assert_eq!(view.shared_data, json!({ "search": { "origin": "STN" } }));
+    assert_eq!(view.current_step.as_deref(), Some("search"));
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_export_contains_every_section(ctx: &mut PostgresViewRepositoryContext) {
+    let journey_id = start_typed(ctx, Some("flight-booking")).await;
+    let subject_id = Uuid::new_v4();
+    let decision = |actions: &[&str]| JourneyEvent::WorkflowEvaluated {
+        suggested_actions: actions.iter().map(ToString::to_string).collect(),
+        phase: Some("search".to_string()),
+        ranked_actions: vec![],
+        completion_blocked_by: vec![],
+    };
+    let payloads = vec![
+        JourneyEvent::Modified {
+            step: "search".to_string(),
+            data: json!({ "search": { "origin": "LHR" } }),
+        },
+        decision(&["passenger_details"]),
+        JourneyEvent::StepProgressed {
+            from_step: None,
+            to_step: "search".to_string(),
+        },
+        JourneyEvent::PersonCaptured {
+            person_ref: "passenger_0".to_string(),
+            subject_id,
+            name: "Alice Smith".to_string(),
+            email: "alice@example.com".to_string(),
+            phone: None,
+        },
+        decision(&["payment"]),
+        JourneyEvent::StepProgressed {
+            from_step: Some("search".to_string()),
+            to_step: "passenger_details".to_string(),
+        },
+    ];
+    let events: Vec<EventEnvelope<Journey>> = payloads
+        .into_iter()
+        .zip(2..)
+        .map(|(payload, sequence)| EventEnvelope {
+            aggregate_id: journey_id.to_string(),
+            sequence,
+            payload,
+            metadata: HashMap::default(),
+        })
+        .collect();
+    ctx.repo().dispatch(&journey_id.to_string(), &events).await;
+
+    let export = ctx.repo().export(&journey_id).await.unwrap();
+
+    assert_eq!(export.format_version, JOURNEY_EXPORT_FORMAT_VERSION);
+    assert_eq!(export.journey.id, journey_id);
+    assert_eq!(export.journey.state, JourneyState::InProgress);
+    assert_eq!(
+        export.journey.shared_data,
+        json!({ "search": { "origin": "LHR" } })
+    );
+    assert_eq!(export.journey.persons.len(), 1);
+    assert_eq!(
+        export.journey.persons[0].email.as_deref(),
+        Some("alice@example.com")
+    );
+    let steps: Vec<&str> = export
+        .step_history
+        .iter()
+        .map(|transition| transition.to_step.as_str())
+        .collect();
+    assert_eq!(steps, ["search", "passenger_details"]);
+    let decisions: Vec<&Vec<String>> = export
+        .decisions
+        .iter()
+        .map(|decision| &decision.suggested_actions)
+        .collect();
+    assert_eq!(
+        decisions,
+        [
+            &vec!["passenger_details".to_string()],
+            &vec!["payment".to_string()]
+        ]
+    );
+
+    // Every section is present in the serialized document.
+    let document = serde_json::to_value(&export).unwrap();
+    for section in ["format_version", "journey", "step_history", "decisions"] {
+        assert!(document.get(section).is_some(), "missing {section}");
+    }
+
+    assert!(matches!(
+        ctx.repo().export(&Uuid::new_v4()).await,
+        Err(ViewRepositoryError::NotFound(_))
+    ));
+}
+
+/// Project `timeline` — each event with its minute offset from
+/// `2025-08-15T10:00:00Z` — as a new journey, starting at sequence 1.
+async fn project_timeline(
+    ctx: &mut PostgresViewRepositoryContext,
+    timeline: Vec<(i64, JourneyEvent)>,
+) -> Uuid {
+    let journey_id = ctx.track_journey(Uuid::new_v4());
+    let start: DateTime<Utc> = "2025-08-15T10:00:00Z".parse().unwrap();
+    let events: Vec<EventEnvelope<Journey>> = timeline
+        .into_iter()
+        .zip(1..)
+        .map(|((minutes, payload), sequence)| EventEnvelope {
+            aggregate_id: journey_id.to_string(),
+            sequence,
+            payload: match payload {
+                JourneyEvent::Started { journey_type, .. } => JourneyEvent::Started {
+                    id: journey_id,
+                    journey_type,
+                },
+                payload => payload,
+            },
+            metadata: HashMap::from([(
+                "time".to_string(),
+                (start + chrono::Duration::minutes(minutes)).to_rfc3339(),
+            )]),
+        })
+        .collect();
+    ctx.repo().dispatch(&journey_id.to_string(), &events).await;
+    journey_id
+}
+
+fn started(journey_type: &str) -> JourneyEvent {
+    JourneyEvent::Started {
+        id: Uuid::nil(),
+        journey_type: Some(journey_type.to_string()),
+    }
+}
+
+fn entered(from_step: Option<&str>, to_step: &str) -> JourneyEvent {
+    JourneyEvent::StepProgressed {
+        from_step: from_step.map(str::to_string),
+        to_step: to_step.to_string(),
+    }
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_dwell_times_run_until_the_next_step_or_completion(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    let completed = project_timeline(
+        ctx,
+        vec![
+            (0, started("dwell")),
+            (0, entered(None, "search")),
+            (2, entered(Some("search"), "passenger_details")),
+            (5, entered(Some("passenger_details"), "search")),
+            (6, JourneyEvent::Completed),
+        ],
+    )
+    .await;
+    let ongoing = project_timeline(
+        ctx,
+        vec![
+            (0, started("dwell")),
+            (0, entered(None, "search")),
+            (3, entered(Some("search"), "payment")),
+        ],
+    )
+    .await;
+
+    let dwell = |steps: Vec<StepDwell>| -> Vec<(String, Option<i64>)> {
+        steps
+            .into_iter()
+            .map(|visit| (visit.step, visit.duration_ms))
+            .collect()
+    };
+    assert_eq!(
+        dwell(ctx.repo().dwell_times(&completed).await.unwrap()),
+        vec![
+            ("search".to_string(), Some(120_000)),
+            ("passenger_details".to_string(), Some(180_000)),
+            ("search".to_string(), Some(60_000)),
+        ]
+    );
+    assert_eq!(
+        dwell(ctx.repo().dwell_times(&ongoing).await.unwrap()),
+        vec![
+            ("search".to_string(), Some(180_000)),
+            ("payment".to_string(), None),
+        ]
+    );
+    assert!(
+        ctx.repo()
+            .dwell_times(&Uuid::new_v4())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+/// A reopened journey is in progress again and, until it closes a second
+/// time, has no completion time.
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_reopen_clears_completion(ctx: &mut PostgresViewRepositoryContext) {
+    let journey_type = format!("reopened-{}", Uuid::new_v4());
+
+    let journey_id = project_timeline(
+        ctx,
+        vec![
+            (0, started(&journey_type)),
+            (10, JourneyEvent::Completed),
+            (15, JourneyEvent::Reopened),
+        ],
+    )
+    .await;
+
+    let view = ctx.repo().load(&journey_id).await.unwrap().unwrap();
+    assert_eq!(view.state, JourneyState::InProgress);
+    assert_eq!(
+        ctx.repo()
+            .average_completion_time(Some(&journey_type))
+            .await
+            .unwrap(),
+        None
+    );
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_average_completion_time_excludes_pauses_and_incomplete_journeys(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    // A type unique to this test run so parallel tests don't match.
+    let journey_type = format!("timed-{}", Uuid::new_v4());
+    let start: DateTime<Utc> = "2025-08-15T10:00:00Z".parse().unwrap();
+    let minute = |m: i64| start + chrono::Duration::minutes(m);
+
+    project_timeline(
+        ctx,
+        vec![(0, started(&journey_type)), (10, JourneyEvent::Completed)],
+    )
+    .await;
+    // 20 minutes from start to finish, 4 of them paused.
+    project_timeline(
+        ctx,
+        vec![
+            (0, started(&journey_type)),
+            (5, JourneyEvent::Paused { at: minute(5) }),
+            (9, JourneyEvent::Resumed { at: minute(9) }),
+            (20, JourneyEvent::Completed),
+        ],
+    )
+    .await;
+    project_timeline(ctx, vec![(0, started(&journey_type))]).await;
+
+    assert_eq!(
+        ctx.repo()
+            .average_completion_time(Some(&journey_type))
+            .await
+            .unwrap(),
+        Some(13 * 60_000)
+    );
+    assert_eq!(
+        ctx.repo()
+            .average_completion_time(Some(&format!("untimed-{}", Uuid::new_v4())))
+            .await
+            .unwrap(),
+        None
+    );
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn idempotency_keys_replay_until_they_expire(ctx: &mut PostgresViewRepositoryContext) {
+    let store = PostgresIdempotencyStore::new(ctx.pool.clone());
+    let journey_id = Uuid::new_v4();
+    let created = RecordedResponse {
+        status: 201,
+        location: Some(format!("/journeys/{journey_id}")),
+    };
+
+    assert_eq!(
+        store.claim(journey_id, "k1", "f1").await.unwrap(),
+        Claim::Claimed
+    );
+    assert_eq!(
+        store.claim(journey_id, "k1", "f1").await.unwrap(),
+        Claim::InProgress
+    );
+    store.complete(journey_id, "k1", &created).await.unwrap();
+    assert_eq!(
+        store.claim(journey_id, "k1", "f1").await.unwrap(),
+        Claim::Completed(created)
+    );
+    assert_eq!(
+        store.claim(journey_id, "k1", "f2").await.unwrap(),
+        Claim::Mismatch
+    );
+
+    // Once expired, the key is claimed afresh.
+    let expiring = store.with_ttl(std::time::Duration::ZERO);
+    assert_eq!(
+        expiring.claim(journey_id, "k1", "f2").await.unwrap(),
+        Claim::Claimed
+    );
+
+    expiring.release(journey_id, "k1").await.unwrap();
+}
+
+/// A claim whose command never finished is taken over by the same request
+/// once its lease runs out, but never by another.
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn idempotency_claims_are_taken_over_after_their_lease(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    let store = PostgresIdempotencyStore::new(ctx.pool.clone());
+    let journey_id = Uuid::new_v4();
+    store.claim(journey_id, "k1", "f1").await.unwrap();
+
+    let expiring = store.with_lease(std::time::Duration::ZERO);
+    assert_eq!(
+        expiring.claim(journey_id, "k1", "f2").await.unwrap(),
+        Claim::Mismatch
+    );
+    assert_eq!(
+        expiring.claim(journey_id, "k1", "f1").await.unwrap(),
+        Claim::Claimed
+    );
+
+    let done = RecordedResponse {
+        status: 204,
+        location: None,
+    };
+    expiring.complete(journey_id, "k1", &done).await.unwrap();
+    assert_eq!(
+        expiring.claim(journey_id, "k1", "f1").await.unwrap(),
+        Claim::Completed(done)
+    );
+
+    expiring.release(journey_id, "k1").await.unwrap();
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn command_rejections_are_counted_by_code(ctx: &mut PostgresViewRepositoryContext) {
+    let log = PostgresRejectionLog::new(ctx.pool.clone());
+    let journey_id = Uuid::new_v4();
+    let before = log.count_rejections_by_code().await.unwrap();
+
+    for (command_type, err) in [
+        ("SetAttributes", JourneyError::InvalidData(vec![])),
+        ("CapturePerson", JourneyError::InvalidData(vec![])),
+        ("Complete", JourneyError::CannotComplete { missing: vec![] }),
+    ] {
+        log.record(&CommandRejection::new(journey_id, command_type, &err))
+            .await
+            .unwrap();
+    }
+
+    let after = log.count_rejections_by_code().await.unwrap();
+    let added = |code: &str| after[code] - before.get(code).copied().unwrap_or(0);
+    assert_eq!(added("invalid_data"), 2);
+    assert_eq!(added("cannot_complete"), 1);
+
+    let recorded: Vec<(String, String, String)> = sqlx::query_as(
+        r"
+        SELECT command_type, error_code, detail
+        FROM journey_command_rejections
+        WHERE journey_id = $1
+        ORDER BY id
+        ",
+    )
+    .bind(journey_id)
+    .fetch_all(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(recorded[0].0, "SetAttributes");
+    assert_eq!(recorded[2].1, "cannot_complete");
+    assert_eq!(
+        recorded[2].2,
+        JourneyError::CannotComplete { missing: vec![] }.to_string()
+    );
+
+    sqlx::query("DELETE FROM journey_command_rejections WHERE journey_id = $1")
+        .bind(journey_id)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_load_latest_decision_reads_only_the_newest_decision(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    let evaluated = |actions: &[&str], phase: &str| JourneyEvent::WorkflowEvaluated {
+        suggested_actions: actions.iter().map(ToString::to_string).collect(),
+        phase: Some(phase.to_string()),
+        ranked_actions: vec![],
+        completion_blocked_by: vec![],
+    };
+    let journey_id = project_timeline(
+        ctx,
+        vec![
+            (0, started("next-actions")),
+            (
+                1,
+                evaluated(&["flight_search_results"], "selecting_outbound"),
+            ),
+            (
+                2,
+                evaluated(
+                    &["return_flight_selection", "flight_search_results"],
+                    "selecting_return",
+                ),
+            ),
+        ],
+    )
+    .await;
+    let unevaluated = project_timeline(ctx, vec![(0, started("next-actions"))]).await;
+
+    let decision = ctx
+        .repo()
+        .load_latest_decision(&journey_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        decision.suggested_actions,
+        vec!["return_flight_selection", "flight_search_results"]
+    );
+    assert_eq!(decision.phase.as_deref(), Some("selecting_return"));
+
+    let repo = ctx.repo();
+    assert!(
+        repo.load_latest_decision(&unevaluated)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        repo.load_latest_decision(&Uuid::new_v4())
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_resume_state_offers_the_top_ranked_action(ctx: &mut PostgresViewRepositoryContext) {
+    let journey_id = project_timeline(
+        ctx,
+        vec![
+            (0, started("resume")),
+            (
+                1,
+                JourneyEvent::StepProgressed {
+                    from_step: None,
+                    to_step: "flight_search".to_string(),
+                },
+            ),
+            (
+                2,
+                JourneyEvent::WorkflowEvaluated {
+                    suggested_actions: vec!["flight_search".to_string()],
+                    phase: None,
+                    ranked_actions: vec![],
+                    completion_blocked_by: vec![],
+                },
+            ),
+            (
+                3,
+                JourneyEvent::WorkflowEvaluated {
+                    suggested_actions: vec!["seat_selection".to_string(), "payment".to_string()],
+                    phase: None,
+                    ranked_actions: vec![
+                        RankedAction {
+                            action: "payment".to_string(),
+                            weight: 0.9,
+                        },
+                        RankedAction {
+                            action: "seat_selection".to_string(),
+                            weight: 0.4,
+                        },
+                    ],
+                    completion_blocked_by: vec![],
+                },
+            ),
+        ],
+    )
+    .await;
+    let unevaluated = project_timeline(
+        ctx,
+        vec![(0, started("resume")), (1, JourneyEvent::Completed)],
+    )
+    .await;
+
+    let repo = ctx.repo();
+    assert_eq!(
+        repo.resume_state(&journey_id).await.unwrap(),
+        Some(ResumeState {
+            current_step: Some("flight_search".to_string()),
+            primary_next_step: Some("payment".to_string()),
+            suggested_actions: vec!["seat_selection".to_string(), "payment".to_string()],
+            is_complete: false,
+        })
+    );
+    assert_eq!(
+        repo.resume_state(&unevaluated).await.unwrap(),
+        Some(ResumeState {
+            current_step: None,
+            primary_next_step: None,
+            suggested_actions: vec![],
+            is_complete: true,
+        })
+    );
+    assert_eq!(repo.resume_state(&Uuid::new_v4()).await.unwrap(), None);
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_sweep_stale_forgets_only_old_views_in_the_given_states(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    let abandoned = project_timeline(ctx, vec![(0, started("retention"))]).await;
+    let paused = project_timeline(
+        ctx,
+        vec![
+            (0, started("retention")),
+            (
+                1,
+                JourneyEvent::Paused {
+                    at: "2025-08-15T10:01:00Z".parse().unwrap(),
+                },
+            ),
+        ],
+    )
+    .await;
+    let completed = project_timeline(
+        ctx,
+        vec![(0, started("retention")), (1, JourneyEvent::Completed)],
+    )
+    .await;
+    let recent = project_timeline(ctx, vec![(0, started("retention"))]).await;
+    // Other tests' journeys date from 2025, so backdate well before them to
+    // sweep nothing else.
+    sqlx::query("UPDATE journey_view SET updated_at = '2000-01-01T00:00:00Z' WHERE id = ANY($1)")
+        .bind(vec![abandoned, paused, completed])
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE journey_view SET updated_at = CURRENT_TIMESTAMP WHERE id = $1")
+        .bind(recent)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+
+    let swept = ctx
+        .repo()
+        .sweep_stale(
+            Duration::from_hours(10 * 365 * 24),
+            &[JourneyState::InProgress, JourneyState::Paused],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(swept, 2);
+    let repo = ctx.repo();
+    let forgotten = async |journey_id| repo.load(&journey_id).await.unwrap().unwrap().forgotten;
+    assert!(forgotten(abandoned).await);
+    assert!(forgotten(paused).await);
+    assert!(!forgotten(completed).await);
+    assert!(!forgotten(recent).await);
+
+    // Already forgotten, so a second sweep finds nothing.
+    let swept_again = repo
+        .sweep_stale(
+            Duration::from_hours(10 * 365 * 24),
+            &[JourneyState::InProgress, JourneyState::Paused],
+        )
+        .await
+        .unwrap();
+    assert_eq!(swept_again, 0);
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_a_swept_journey_keeps_projecting(ctx: &mut PostgresViewRepositoryContext) {
+    let journey_id = project_timeline(ctx, vec![(0, started("retention"))]).await;
+    sqlx::query("UPDATE journey_view SET updated_at = '2000-01-01T00:00:00Z' WHERE id = $1")
+        .bind(journey_id)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    let repo = ctx.repo();
+    repo.sweep_stale(
+        Duration::from_hours(10 * 365 * 24),
+        &[JourneyState::InProgress],
+    )
+    .await
+    .unwrap();
+
+    repo.dispatch(
+        &journey_id.to_string(),
+        &[EventEnvelope {
+            aggregate_id: journey_id.to_string(),
+            sequence: 2,
+            payload: JourneyEvent::Modified {
+                step: "search".to_string(),
+                data: json!({ "search": { "origin": "LHR" } }),
+            },
+            metadata: HashMap::default(),
+        }],
+    )
+    .await;
+
+    let view = repo.load(&journey_id).await.unwrap().unwrap();
+    assert_eq!(view.version, 2);
+    assert_eq!(view.shared_data, json!({ "search": { "origin": "LHR" } }));
+}
+
+#[test_context(PostgresViewRepositoryContext)]
+#[tokio::test]
+async fn test_persons_with_status_joins_each_person_to_their_journey(
+    ctx: &mut PostgresViewRepositoryContext,
+) {
+    // Ids with their top 64 bits clear sort before every random journey, so
+    // the first page is this test's.
+    let base = (Uuid::new_v4().as_u128() >> 64) & !0xff;
+    let completed = ctx.track_journey(Uuid::from_u128(base | 1));
+    let in_progress = ctx.track_journey(Uuid::from_u128(base | 2));
+    let captured = |person_ref: &str, name: &str| JourneyEvent::PersonCaptured {
+        person_ref: person_ref.to_string(),
+        subject_id: Uuid::new_v4(),
+        name: name.to_string(),
+        email: format!("{}@example.com", name.to_lowercase()),
+        phone: None,
+    };
+    for (journey_id, payloads) in [
+        (
+            completed,
+            vec![
+                captured("passenger_0", "Alice"),
+                captured("passenger_1", "Bob"),
+                JourneyEvent::Completed,
+            ],
+        ),
+        (
+            in_progress,
+            vec![
+                captured("passenger_0", "Carol"),
+                JourneyEvent::StepProgressed {
+                    from_step: None,
+                    to_step: "payment".to_string(),
+                },
+            ],
+        ),
+    ] {
+        let events: Vec<EventEnvelope<Journey>> = std::iter::once(JourneyEvent::Started {
+            id: journey_id,
+            journey_type: None,
+        })
+        .chain(payloads)
+        .zip(1..)
+        .map(|(payload, sequence)| EventEnvelope {
+            aggregate_id: journey_id.to_string(),
+            sequence,
+            payload,
+            metadata: HashMap::default(),
+        })
+        .collect();
+        ctx.repo().dispatch(&journey_id.to_string(), &events).await;
+    }
+
+    let repo = ctx.repo();
+    let first_page = repo.persons_with_status(2, 0).await.unwrap();
+    let second_page = repo.persons_with_status(2, 2).await.unwrap();
+
+    let summary = |p: &PersonWithStatus| {
+        (
+            p.person.journey_id,
+            p.person.name.clone().unwrap(),
+            p.state,
+            p.current_step.clone(),
+            p.completed_at.is_some(),
+        )
+    };
+    assert_eq!(
+        first_page.iter().map(summary).collect::<Vec<_>>(),
+        vec![
+            (
+                completed,
+                "Alice".to_string(),
+                JourneyState::Complete,
+                None,
+                true
+            ),
+            (
+                completed,
+                "Bob".to_string(),
+                JourneyState::Complete,
+                None,
+                true
+            ),
+        ]
+    );
+    assert_eq!(
+        summary(&second_page[0]),
+        (
+            in_progress,
+            "Carol".to_string(),
+            JourneyState::InProgress,
+            Some("payment".to_string()),
+            false
+        )
+    );
+}
@@ -0,0 +1,255 @@
+//! Typed constructors for flight-booking [`JourneyCommand`]s.
+//!
+//! Each constructor serialises one of the schema types from this crate into
+//! the attribute bag the orchestrator JDM reads, and wraps it in a
+//! `SetAttributes` command:
+//!
+//! | Constructor              | Attribute paths                          |
+//! |--------------------------|------------------------------------------|
+//! | `search_criteria`        | `search/…`                               |
+//! | `select_outbound_flight` | `booking/selectedOutboundFlight/…`       |
+//! | `select_return_flight`   | `booking/selectedReturnFlight/…`         |
+//! | `capture_passengers`     | `persons/passenger_<n>/…`                |
+//!
+//! Unset optional fields are omitted rather than written as `null`, which
+//! `SetAttributes` would otherwise store.
+
+use journey_dynamics::domain::{commands::JourneyCommand, flatten};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::{FlightSelection, PassengerDetail, SearchCriteria};
+
+/// Flight-booking constructors on [`JourneyCommand`].
+///
+/// ```rust,ignore
+/// use flight_booking::commands::FlightBookingCommands;
+///
+/// let command = JourneyCommand::search_criteria(search);
+/// ```
+pub trait FlightBookingCommands {
+    /// Set the search criteria under `search`.
+    fn search_criteria(search: SearchCriteria) -> JourneyCommand;
+
+    /// Select the outbound flight under `booking/selectedOutboundFlight`.
+    fn select_outbound_flight(flight: FlightSelection) -> JourneyCommand;
+
+    /// Select the return flight under `booking/selectedReturnFlight`.
+    fn select_return_flight(flight: FlightSelection) -> JourneyCommand;
+
+    /// Set each passenger's details under `persons/passenger_<n>`, numbered
+    /// from zero in the order given.
+    ///
+    /// Names, date of birth, passport number and nationality are secret
+    /// fields in [`attribute_schema`](crate::attribute_schema), so every slot
+    /// must first be bound to a subject with `CapturePerson`.
+    fn capture_passengers(passengers: Vec<PassengerDetail>) -> JourneyCommand;
+}
+
+impl FlightBookingCommands for JourneyCommand {
+    fn search_criteria(search: SearchCriteria) -> JourneyCommand {
+        set_attributes(&json!({ "search": to_attributes(&search) }))
+    }
+
+    fn select_outbound_flight(flight: FlightSelection) -> JourneyCommand {
+        set_attributes(&json!({
+            "booking": { "selectedOutboundFlight": to_attributes(&flight) }
+        }))
+    }
+
+    fn select_return_flight(flight: FlightSelection) -> JourneyCommand {
+        set_attributes(&json!({
+            "booking": { "selectedReturnFlight": to_attributes(&flight) }
+        }))
+    }
+
+    fn capture_passengers(passengers: Vec<PassengerDetail>) -> JourneyCommand {
+        let persons: serde_json::Map<String, Value> = passengers
+            .iter()
+            .enumerate()
+            .map(|(index, passenger)| (format!("passenger_{index}"), to_attributes(passenger)))
+            .collect();
+        set_attributes(&json!({ "persons": persons }))
+    }
+}
+
+fn set_attributes(data: &Value) -> JourneyCommand {
+    JourneyCommand::SetAttributes {
+        changes: flatten(data),
+    }
+}
+
+/// Serialise `value` and drop every `null` object member.
+fn to_attributes(value: &impl Serialize) -> Value {
+    let mut value =
+        serde_json::to_value(value).expect("flight-booking schema types always serialise");
+    strip_nulls(&mut value);
+    value
+}
+
+fn strip_nulls(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(strip_nulls);
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_nulls),
+        _ => {}
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use jsonptr::PointerBuf;
+
+    use super::*;
+    use crate::{AirportCode, PassengerCounts, PassengerType, TripType};
+
+    fn changes(command: JourneyCommand) -> BTreeMap<PointerBuf, Value> {
+        let JourneyCommand::SetAttributes { changes } = command else {
+            panic!("expected SetAttributes, got {command:?}");
+        };
+        changes
+    }
+
+    fn flight(flight_id: &str, price: f64, departure: &str, arrival: &str) -> FlightSelection {
+        FlightSelection {
+            flight_id: flight_id.to_string(),
+            airline: "British Airways".to_string(),
+            flight_number: None,
+            aircraft: None,
+            price,
+            departure: departure.to_string(),
+            arrival: arrival.to_string(),
+            duration: None,
+            stops: None,
+            cabin_class: None,
+        }
+    }
+
+    #[test]
+    fn search_criteria_matches_fixture() {
+        let command = JourneyCommand::search_criteria(SearchCriteria {
+            trip_type: TripType::RoundTrip,
+            origin: AirportCode("LHR".to_string()),
+            destination: AirportCode("JFK".to_string()),
+            departure_date: "2024-06-15".to_string(),
+            return_date: Some("2024-06-22".to_string()),
+            passengers: PassengerCounts {
+                adults: 2,
+                children: 0,
+                infants: 0,
+            },
+        });
+
+        let fixture = json!({
+            "search": {
+                "tripType": "round-trip",
+                "origin": "LHR",
+                "destination": "JFK",
+                "departureDate": "2024-06-15",
+                "returnDate": "2024-06-22",
+                "passengers": { "adults": 2, "children": 0, "infants": 0 }
+            }
+        });
+        assert_eq!(changes(command), flatten(&fixture));
+    }
+
+    #[test]
+    fn one_way_search_omits_return_date() {
+        let command = JourneyCommand::search_criteria(SearchCriteria {
+            trip_type: TripType::OneWay,
+            origin: AirportCode("LHR".to_string()),
+            destination: AirportCode("JFK".to_string()),
+            departure_date: "2024-06-15".to_string(),
+            return_date: None,
+            passengers: PassengerCounts {
+                adults: 1,
+                children: 0,
+                infants: 0,
+            },
+        });
+
+        let return_date: PointerBuf = "/search/returnDate".parse().unwrap();
+        assert!(!changes(command).contains_key(&return_date));
+    }
+
+    #[test]
+    fn flight_selections_match_fixtures() {
+        let outbound =
+            JourneyCommand::select_outbound_flight(flight("BA123", 450.00, "08:30", "11:45"));
+        let fixture = json!({
+            "booking": {
+                "selectedOutboundFlight": {
+                    "flightId": "BA123",
+                    "airline": "British Airways",
+                    "price": 450.00,
+                    "departure": "08:30",
+                    "arrival": "11:45"
+                }
+            }
+        });
+        assert_eq!(changes(outbound), flatten(&fixture));
+
+        let inbound =
+            JourneyCommand::select_return_flight(flight("BA456", 480.00, "14:20", "17:35"));
+        let fixture = json!({
+            "booking": {
+                "selectedReturnFlight": {
+                    "flightId": "BA456",
+                    "airline": "British Airways",
+                    "price": 480.00,
+                    "departure": "14:20",
+                    "arrival": "17:35"
+                }
+            }
+        });
+        assert_eq!(changes(inbound), flatten(&fixture));
+    }
+
+    #[test]
+    fn capture_passengers_numbers_slots_in_order() {
+        let command = JourneyCommand::capture_passengers(vec![
+            PassengerDetail {
+                first_name: "Alice".to_string(),
+                last_name: "Smith".to_string(),
+                date_of_birth: "1990-05-15".to_string(),
+                passport_number: Some("GB123456789".to_string()),
+                nationality: Some("GB".to_string()),
+                passenger_type: PassengerType::Adult,
+            },
+            PassengerDetail {
+                first_name: "Tom".to_string(),
+                last_name: "Smith".to_string(),
+                date_of_birth: "2018-02-01".to_string(),
+                passport_number: None,
+                nationality: None,
+                passenger_type: PassengerType::Child,
+            },
+        ]);
+
+        let fixture = json!({
+            "persons": {
+                "passenger_0": {
+                    "firstName": "Alice",
+                    "lastName": "Smith",
+                    "dateOfBirth": "1990-05-15",
+                    "passportNumber": "GB123456789",
+                    "nationality": "GB",
+                    "passengerType": "adult"
+                },
+                "passenger_1": {
+                    "firstName": "Tom",
+                    "lastName": "Smith",
+                    "dateOfBirth": "2018-02-01",
+                    "passengerType": "child"
+                }
+            }
+        });
+        assert_eq!(changes(command), flatten(&fixture));
+    }
+}
@@ -8,6 +8,10 @@ use journey_dynamics::queries::JourneyView;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+pub mod commands;
+pub mod steps;
+pub mod validation;
+
 // Main schema with optional top-level groups
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
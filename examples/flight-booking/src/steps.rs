@@ -0,0 +1,88 @@
+//! Typed view of the actions the flight-booking orchestrator suggests.
+//!
+//! The orchestrator JDM emits its next steps as plain strings in
+//! `suggestedActions`. Parsing them into [`FlightStep`] turns a renamed or
+//! newly added step in the model into an error rather than a silent mismatch.
+
+use std::fmt;
+use std::str::FromStr;
+
+use journey_dynamics::queries::WorkflowDecisionView;
+
+/// A step the flight-booking orchestrator can suggest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FlightStep {
+    FlightSearchResults,
+    ReturnFlightSelection,
+    PassengerDetails,
+    SeatSelection,
+    BookingConfirmation,
+}
+
+impl FlightStep {
+    /// Every step, in the order a round-trip booking reaches them.
+    pub const ALL: [Self; 5] = [
+        Self::FlightSearchResults,
+        Self::ReturnFlightSelection,
+        Self::PassengerDetails,
+        Self::SeatSelection,
+        Self::BookingConfirmation,
+    ];
+
+    /// The action name the orchestrator emits for this step.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::FlightSearchResults => "flight_search_results",
+            Self::ReturnFlightSelection => "return_flight_selection",
+            Self::PassengerDetails => "passenger_details",
+            Self::SeatSelection => "seat_selection",
+            Self::BookingConfirmation => "booking_confirmation",
+        }
+    }
+}
+
+impl fmt::Display for FlightStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An action name that is not a known [`FlightStep`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownFlightStep(pub String);
+
+impl fmt::Display for UnknownFlightStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown flight-booking step '{}'", self.0)
+    }
+}
+
+impl std::error::Error for UnknownFlightStep {}
+
+impl FromStr for FlightStep {
+    type Err = UnknownFlightStep;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|step| step.as_str() == s)
+            .ok_or_else(|| UnknownFlightStep(s.to_string()))
+    }
+}
+
+/// Parse a decision's suggested actions into [`FlightStep`]s, in order.
+///
+/// # Errors
+///
+/// Returns [`UnknownFlightStep`] for the first action that is not a known
+/// step.
+pub fn suggested_steps(
+    decision: &WorkflowDecisionView,
+) -> Result<Vec<FlightStep>, UnknownFlightStep> {
+    decision
+        .suggested_actions
+        .iter()
+        .map(|action| action.parse())
+        .collect()
+}
@@ -1,8 +1,9 @@
 #![allow(clippy::too_many_lines)]
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::sync::Arc;
 
 use cqrs_es::test::TestFramework;
+use cqrs_es::{Aggregate, EventEnvelope, View};
 use serde_json::json;
 use uuid::Uuid;
 
@@ -12,11 +13,20 @@ use journey_dynamics::{
         events::{JourneyEvent, SecretPartitionData},
         flatten,
         journey::{Journey, JourneyError, JourneyServices},
+        StepMergeMode,
+    },
+    queries::{JourneyView, WorkflowDecisionView},
+    services::{
+        decision_engine::GoRulesDecisionEngine,
+        schema_validator::{JsonSchemaValidator, ValidationIssue},
     },
-    services::{decision_engine::GoRulesDecisionEngine, schema_validator::JsonSchemaValidator},
 };
 use jsonptr::PointerBuf;
 
+use crate::steps::{suggested_steps, FlightStep, UnknownFlightStep};
+use crate::validation::{FlightBookingDataExt, FlightBookingValidator};
+use crate::{FlightBookingSchema, PaymentStatus, TripType};
+
 type JourneyTester = TestFramework<Journey>;
 
 fn create_journey_services() -> JourneyServices {
@@ -25,7 +35,9 @@ fn create_journey_services() -> JourneyServices {
     )));
     let schema: serde_json::Value =
         serde_json::from_str(include_str!("../schemas/flight-booking-schema.json")).unwrap();
-    let schema_validator = Arc::new(JsonSchemaValidator::new(&schema).unwrap());
+    let schema_validator = Arc::new(FlightBookingValidator::new(
+        JsonSchemaValidator::new(&schema).unwrap(),
+    ));
     JourneyServices::new(
         decision_engine,
         schema_validator,
@@ -65,13 +77,18 @@ fn flight_booking_search_criteria() {
     });
 
     JourneyTester::with(create_journey_services())
-        .given(vec![JourneyEvent::Started { id }])
+        .given(vec![JourneyEvent::Started {
+            id,
+            journey_type: None,
+        }])
         .when(set_attrs(&search))
         .then_expect_events(vec![
             attrs_set(&search),
             JourneyEvent::WorkflowEvaluated {
                 suggested_actions: vec!["flight_search_results".to_string()],
                 phase: Some("selecting_outbound".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec!["payment".to_string()],
             },
         ]);
 }
@@ -105,11 +122,16 @@ fn flight_booking_outbound_selection() {
 
     JourneyTester::with(create_journey_services())
         .given(vec![
-            JourneyEvent::Started { id },
+            JourneyEvent::Started {
+                id,
+                journey_type: None,
+            },
             attrs_set(&search),
             JourneyEvent::WorkflowEvaluated {
                 suggested_actions: vec!["flight_search_results".to_string()],
                 phase: Some("selecting_outbound".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec!["payment".to_string()],
             },
         ])
         .when(set_attrs(&outbound))
@@ -121,6 +143,8 @@ fn flight_booking_outbound_selection() {
                     "flight_search_results".to_string(),
                 ],
                 phase: Some("selecting_return".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec!["payment".to_string()],
             },
         ]);
 }
@@ -152,11 +176,16 @@ fn flight_booking_return_selection() {
 
     JourneyTester::with(create_journey_services())
         .given(vec![
-            JourneyEvent::Started { id },
+            JourneyEvent::Started {
+                id,
+                journey_type: None,
+            },
             attrs_set(&search),
             JourneyEvent::WorkflowEvaluated {
                 suggested_actions: vec!["flight_search_results".to_string()],
                 phase: Some("selecting_outbound".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec!["payment".to_string()],
             },
             attrs_set(&json!({ "booking": { "selectedOutboundFlight": outbound_flight } })),
             JourneyEvent::WorkflowEvaluated {
@@ -165,6 +194,8 @@ fn flight_booking_return_selection() {
                     "flight_search_results".to_string(),
                 ],
                 phase: Some("selecting_return".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec!["payment".to_string()],
             },
         ])
         .when(set_attrs(&return_data))
@@ -173,6 +204,8 @@ fn flight_booking_return_selection() {
             JourneyEvent::WorkflowEvaluated {
                 suggested_actions: vec!["passenger_details".to_string()],
                 phase: Some("collecting_passengers".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec!["payment".to_string()],
             },
         ]);
 }
@@ -187,7 +220,10 @@ fn flight_booking_capture_person() {
     let subject_id = Uuid::new_v4();
 
     JourneyTester::with(create_journey_services())
-        .given(vec![JourneyEvent::Started { id }])
+        .given(vec![JourneyEvent::Started {
+            id,
+            journey_type: None,
+        }])
         .when(JourneyCommand::CapturePerson {
             person_ref: "passenger_0".to_string(),
             subject_id,
@@ -236,7 +272,10 @@ fn flight_booking_capture_person_details() {
 
     JourneyTester::with(create_journey_services())
         .given(vec![
-            JourneyEvent::Started { id },
+            JourneyEvent::Started {
+                id,
+                journey_type: None,
+            },
             JourneyEvent::PersonCaptured {
                 person_ref: "passenger_0".to_string(),
                 subject_id,
@@ -275,6 +314,8 @@ fn flight_booking_capture_person_details() {
             JourneyEvent::WorkflowEvaluated {
                 suggested_actions: vec![],
                 phase: Some("collecting_search".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec!["payment".to_string()],
             },
         ]);
 }
@@ -287,7 +328,10 @@ fn flight_booking_capture_person_details_requires_prior_capture_person() {
     let path = |s: &str| -> PointerBuf { s.parse().unwrap() };
 
     JourneyTester::with(create_journey_services())
-        .given(vec![JourneyEvent::Started { id }])
+        .given(vec![JourneyEvent::Started {
+            id,
+            journey_type: None,
+        }])
         .when(JourneyCommand::SetAttributes {
             changes: {
                 let mut m = BTreeMap::new();
@@ -307,7 +351,10 @@ fn flight_booking_capture_two_passengers() {
 
     JourneyTester::with(create_journey_services())
         .given(vec![
-            JourneyEvent::Started { id },
+            JourneyEvent::Started {
+                id,
+                journey_type: None,
+            },
             JourneyEvent::PersonCaptured {
                 person_ref: "passenger_0".to_string(),
                 subject_id: subject_a,
@@ -369,11 +416,16 @@ fn flight_booking_passenger_details_ready_signal() {
 
     JourneyTester::with(create_journey_services())
         .given(vec![
-            JourneyEvent::Started { id },
+            JourneyEvent::Started {
+                id,
+                journey_type: None,
+            },
             attrs_set(&search),
             JourneyEvent::WorkflowEvaluated {
                 suggested_actions: vec!["flight_search_results".to_string()],
                 phase: Some("selecting_outbound".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec!["payment".to_string()],
             },
             attrs_set(&json!({ "booking": { "selectedOutboundFlight": outbound_flight } })),
             JourneyEvent::WorkflowEvaluated {
@@ -382,11 +434,15 @@ fn flight_booking_passenger_details_ready_signal() {
                     "flight_search_results".to_string(),
                 ],
                 phase: Some("selecting_return".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec!["payment".to_string()],
             },
             attrs_set(&json!({ "booking": { "selectedReturnFlight": return_flight } })),
             JourneyEvent::WorkflowEvaluated {
                 suggested_actions: vec!["passenger_details".to_string()],
                 phase: Some("collecting_passengers".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec!["payment".to_string()],
             },
             // PII captured for each passenger (encrypted at rest).
             JourneyEvent::PersonCaptured {
@@ -415,6 +471,8 @@ fn flight_booking_passenger_details_ready_signal() {
                     "passenger_details".to_string(),
                 ],
                 phase: Some("collecting_payment".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec!["payment".to_string()],
             },
         ]);
 }
@@ -451,11 +509,16 @@ fn flight_booking_three_passengers_ready_signal() {
 
     JourneyTester::with(create_journey_services())
         .given(vec![
-            JourneyEvent::Started { id },
+            JourneyEvent::Started {
+                id,
+                journey_type: None,
+            },
             attrs_set(&search),
             JourneyEvent::WorkflowEvaluated {
                 suggested_actions: vec!["flight_search_results".to_string()],
                 phase: Some("selecting_outbound".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec!["payment".to_string()],
             },
             attrs_set(&json!({ "booking": { "selectedOutboundFlight": outbound_flight } })),
             JourneyEvent::WorkflowEvaluated {
@@ -464,11 +527,15 @@ fn flight_booking_three_passengers_ready_signal() {
                     "flight_search_results".to_string(),
                 ],
                 phase: Some("selecting_return".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec!["payment".to_string()],
             },
             attrs_set(&json!({ "booking": { "selectedReturnFlight": return_flight } })),
             JourneyEvent::WorkflowEvaluated {
                 suggested_actions: vec!["passenger_details".to_string()],
                 phase: Some("collecting_passengers".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec!["payment".to_string()],
             },
         ])
         .when(set_attrs(&passenger_types))
@@ -480,6 +547,8 @@ fn flight_booking_three_passengers_ready_signal() {
                     "passenger_details".to_string(),
                 ],
                 phase: Some("collecting_payment".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec!["payment".to_string()],
             },
         ]);
 }
@@ -514,11 +583,16 @@ fn flight_booking_passenger_details_not_ready() {
 
     JourneyTester::with(create_journey_services())
         .given(vec![
-            JourneyEvent::Started { id },
+            JourneyEvent::Started {
+                id,
+                journey_type: None,
+            },
             attrs_set(&search),
             JourneyEvent::WorkflowEvaluated {
                 suggested_actions: vec!["flight_search_results".to_string()],
                 phase: Some("selecting_outbound".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec!["payment".to_string()],
             },
             attrs_set(&json!({ "booking": { "selectedOutboundFlight": outbound_flight } })),
             JourneyEvent::WorkflowEvaluated {
@@ -527,11 +601,15 @@ fn flight_booking_passenger_details_not_ready() {
                     "flight_search_results".to_string(),
                 ],
                 phase: Some("selecting_return".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec!["payment".to_string()],
             },
             attrs_set(&json!({ "booking": { "selectedReturnFlight": return_flight } })),
             JourneyEvent::WorkflowEvaluated {
                 suggested_actions: vec!["passenger_details".to_string()],
                 phase: Some("collecting_passengers".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec!["payment".to_string()],
             },
         ])
         .when(set_attrs(&partial_passengers))
@@ -540,6 +618,8 @@ fn flight_booking_passenger_details_not_ready() {
             JourneyEvent::WorkflowEvaluated {
                 suggested_actions: vec!["passenger_details".to_string()],
                 phase: Some("collecting_passengers".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec!["payment".to_string()],
             },
         ]);
 }
@@ -562,17 +642,78 @@ fn flight_booking_payment_capture() {
     let payment = json!({ "booking": { "paymentStatus": "completed" } });
 
     JourneyTester::with(create_journey_services())
-        .given(vec![JourneyEvent::Started { id }, attrs_set(&search)])
+        .given(vec![
+            JourneyEvent::Started {
+                id,
+                journey_type: None,
+            },
+            attrs_set(&search),
+        ])
         .when(set_attrs(&payment))
         .then_expect_events(vec![
             attrs_set(&payment),
             JourneyEvent::WorkflowEvaluated {
                 suggested_actions: vec!["booking_confirmation".to_string()],
                 phase: Some("booking_confirmed".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec![],
             },
         ]);
 }
 
+/// The orchestrator blocks completion on payment until the payment status
+/// reaches `completed`.
+#[test]
+fn flight_booking_completion_blocked_until_payment() {
+    let id = Uuid::new_v4();
+    let search = json!({
+        "search": {
+            "tripType": "one-way",
+            "origin": "LHR",
+            "destination": "JFK",
+            "departureDate": "2024-06-15",
+            "passengers": { "adults": 1, "children": 0, "infants": 0 }
+        }
+    });
+    let searched = vec![
+        JourneyEvent::Started {
+            id,
+            journey_type: None,
+        },
+        attrs_set(&search),
+        JourneyEvent::WorkflowEvaluated {
+            suggested_actions: vec!["flight_search_results".to_string()],
+            phase: Some("selecting_outbound".to_string()),
+            ranked_actions: vec![],
+            completion_blocked_by: vec!["payment".to_string()],
+        },
+    ];
+
+    JourneyTester::with(create_journey_services())
+        .given(searched.clone())
+        .when(JourneyCommand::Complete)
+        .then_expect_error(JourneyError::CannotComplete {
+            missing: vec!["payment".to_string()],
+        });
+
+    let payment = json!({ "booking": { "paymentStatus": "completed" } });
+    let mut paid = searched;
+    paid.extend([
+        attrs_set(&payment),
+        JourneyEvent::WorkflowEvaluated {
+            suggested_actions: vec!["booking_confirmation".to_string()],
+            phase: Some("booking_confirmed".to_string()),
+            ranked_actions: vec![],
+            completion_blocked_by: vec![],
+        },
+    ]);
+
+    JourneyTester::with(create_journey_services())
+        .given(paid)
+        .when(JourneyCommand::Complete)
+        .then_expect_events(vec![JourneyEvent::Completed]);
+}
+
 // ── Search modification ───────────────────────────────────────────────────────
 
 #[test]
@@ -594,17 +735,24 @@ fn flight_booking_modify_search_criteria() {
             "origin": "LAX",
             "destination": "NYC",
             "departureDate": "2024-07-01",
+            // A one-way trip has no return date; clear the original one.
+            "returnDate": null,
             "passengers": { "adults": 1, "children": 0, "infants": 0 }
         }
     });
 
     JourneyTester::with(create_journey_services())
         .given(vec![
-            JourneyEvent::Started { id },
+            JourneyEvent::Started {
+                id,
+                journey_type: None,
+            },
             attrs_set(&original_search),
             JourneyEvent::WorkflowEvaluated {
                 suggested_actions: vec!["flight_search_results".to_string()],
                 phase: Some("selecting_outbound".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec!["payment".to_string()],
             },
         ])
         .when(set_attrs(&updated_search))
@@ -613,10 +761,80 @@ fn flight_booking_modify_search_criteria() {
             JourneyEvent::WorkflowEvaluated {
                 suggested_actions: vec!["flight_search_results".to_string()],
                 phase: Some("selecting_outbound".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec!["payment".to_string()],
             },
         ]);
 }
 
+/// The `search` data after re-capturing a round-trip search as one-way with
+/// no `returnDate`, under `services`.
+#[allow(deprecated)]
+fn recapture_search_as_one_way(services: JourneyServices) -> serde_json::Value {
+    let round_trip = json!({
+        "search": {
+            "tripType": "round-trip",
+            "origin": "LHR",
+            "destination": "JFK",
+            "departureDate": "2024-06-15",
+            "returnDate": "2024-06-22",
+            "passengers": { "adults": 2, "children": 0, "infants": 0 }
+        }
+    });
+    let one_way = json!({
+        "search": {
+            "tripType": "one-way",
+            "origin": "LAX",
+            "destination": "NYC",
+            "departureDate": "2024-07-01",
+            "passengers": { "adults": 1, "children": 0, "infants": 0 }
+        }
+    });
+    let given = vec![
+        JourneyEvent::Started {
+            id: Uuid::new_v4(),
+            journey_type: None,
+        },
+        JourneyEvent::Modified {
+            step: "search_criteria".to_string(),
+            data: round_trip,
+        },
+    ];
+    let events = JourneyTester::with(services)
+        .given(given.clone())
+        .when(JourneyCommand::Capture {
+            step: "search_criteria".to_string(),
+            data: one_way,
+        })
+        .inspect_result()
+        .unwrap();
+
+    let mut journey = Journey::default();
+    for event in given.into_iter().chain(events) {
+        journey.apply(event);
+    }
+    journey.shared_data()["search"].clone()
+}
+
+#[test]
+fn flight_booking_recapture_search_keeps_return_date_under_merge() {
+    let search = recapture_search_as_one_way(create_journey_services());
+
+    assert_eq!(search["tripType"], json!("one-way"));
+    assert_eq!(search["returnDate"], json!("2024-06-22"));
+}
+
+#[test]
+fn flight_booking_recapture_search_drops_return_date_under_replace() {
+    let services =
+        create_journey_services().with_step_merge_mode("search_criteria", StepMergeMode::Replace);
+
+    let search = recapture_search_as_one_way(services);
+
+    assert_eq!(search["tripType"], json!("one-way"));
+    assert_eq!(search.get("returnDate"), None);
+}
+
 // ── Secret partitions — multi-subject demonstration ───────────────────────────
 
 /// Demonstrates that setting Secret person attributes (firstName, passportNumber)
@@ -643,7 +861,10 @@ fn flight_booking_set_person_secret_attributes() {
 
     JourneyTester::with(create_journey_services())
         .given(vec![
-            JourneyEvent::Started { id },
+            JourneyEvent::Started {
+                id,
+                journey_type: None,
+            },
             JourneyEvent::PersonCaptured {
                 person_ref: "passenger_0".to_string(),
                 subject_id: subject_a,
@@ -692,6 +913,8 @@ fn flight_booking_set_person_secret_attributes() {
             JourneyEvent::WorkflowEvaluated {
                 suggested_actions: vec![],
                 phase: Some("collecting_search".to_string()),
+                ranked_actions: vec![],
+                completion_blocked_by: vec!["payment".to_string()],
             },
         ]);
 }
@@ -706,10 +929,263 @@ fn test_invalid_booking_data_rejected_with_schema_validation() {
     // `paymentStatus` must be one of the PaymentStatus enum values; a free
     // string violates the schema.
     JourneyTester::with(create_journey_services())
-        .given(vec![JourneyEvent::Started { id }])
-        .when(set_attrs(&json!({ "booking": { "paymentStatus": "not_a_valid_status" } })))
-        .then_expect_error(JourneyError::InvalidData(
-            "Schema validation failed: {\"paymentStatus\":\"not_a_valid_status\"} is not valid under any of the schemas listed in the 'anyOf' keyword"
-                .to_string(),
-        ));
+        .given(vec![JourneyEvent::Started {
+            id,
+            journey_type: None,
+        }])
+        .when(set_attrs(
+            &json!({ "booking": { "paymentStatus": "not_a_valid_status" } }),
+        ))
+        .then_expect_error(JourneyError::InvalidData(vec![ValidationIssue::new(
+            "/booking/paymentStatus",
+            "\"not_a_valid_status\" is not one of \"pending\", \"processing\" or 3 other candidates",
+        )
+        .with_code("enum")
+        .with_param(
+            "expected",
+            json!(["pending", "processing", "completed", "failed", "refunded"]),
+        )]));
+}
+
+// ── Trip-type requirements ────────────────────────────────────────────────────
+
+/// A round-trip search without a return date passes the JSON Schema but not
+/// the trip-type rules.
+#[test]
+fn round_trip_without_return_date_rejected() {
+    let id = Uuid::new_v4();
+    let search = json!({
+        "search": {
+            "tripType": "round-trip",
+            "origin": "LHR",
+            "destination": "JFK",
+            "departureDate": "2024-06-15",
+            "passengers": { "adults": 1, "children": 0, "infants": 0 }
+        }
+    });
+
+    JourneyTester::with(create_journey_services())
+        .given(vec![JourneyEvent::Started {
+            id,
+            journey_type: None,
+        }])
+        .when(set_attrs(&search))
+        .then_expect_error(JourneyError::InvalidData(vec![ValidationIssue::new(
+            "/search/returnDate",
+            "a round-trip requires a return date",
+        )
+        .with_code("required")]));
+}
+
+/// Selecting a return flight for a one-way search is rejected.
+#[test]
+fn one_way_with_return_flight_rejected() {
+    let id = Uuid::new_v4();
+    let search = json!({
+        "search": {
+            "tripType": "one-way",
+            "origin": "LHR",
+            "destination": "JFK",
+            "departureDate": "2024-06-15",
+            "passengers": { "adults": 1, "children": 0, "infants": 0 }
+        }
+    });
+    let return_flight = json!({
+        "flightId": "BA456", "airline": "British Airways",
+        "price": 480.00, "departure": "14:20", "arrival": "17:35"
+    });
+
+    JourneyTester::with(create_journey_services())
+        .given(vec![
+            JourneyEvent::Started {
+                id,
+                journey_type: None,
+            },
+            attrs_set(&search),
+        ])
+        .when(set_attrs(
+            &json!({ "booking": { "selectedReturnFlight": return_flight } }),
+        ))
+        .then_expect_error(JourneyError::InvalidData(vec![ValidationIssue::new(
+            "/booking/selectedReturnFlight",
+            "a one-way trip has no return flight",
+        )
+        .with_code("not_allowed")]));
+}
+
+#[test]
+fn requirement_issues_of_inconsistent_trips() {
+    let booking =
+        |data: serde_json::Value| -> FlightBookingSchema { serde_json::from_value(data).unwrap() };
+    let search = |trip_type: &str, return_date: Option<&str>| {
+        json!({
+            "tripType": trip_type,
+            "origin": "LHR",
+            "destination": "JFK",
+            "departureDate": "2024-06-15",
+            "returnDate": return_date,
+            "passengers": { "adults": 1, "children": 0, "infants": 0 }
+        })
+    };
+    let paths = |data: serde_json::Value| -> Vec<String> {
+        booking(data)
+            .requirement_issues()
+            .into_iter()
+            .map(|issue| issue.path)
+            .collect()
+    };
+
+    // Consistent trips, and data with no search yet.
+    assert!(paths(json!({ "search": search("round-trip", Some("2024-06-22")) })).is_empty());
+    assert!(paths(json!({ "search": search("one-way", None) })).is_empty());
+    assert!(paths(json!({ "search": search("multi-city", Some("2024-06-01")) })).is_empty());
+    assert!(paths(json!({ "booking": { "bookingReference": "ABC123" } })).is_empty());
+
+    assert_eq!(
+        paths(json!({ "search": search("round-trip", Some("2024-06-01")) })),
+        ["/search/returnDate"]
+    );
+    assert_eq!(
+        paths(json!({
+            "search": search("round-trip", Some("2024-06-22")),
+            "booking": { "bookingReference": "ABC123" }
+        })),
+        ["/booking/selectedReturnFlight"]
+    );
+    assert_eq!(
+        paths(json!({
+            "search": search("one-way", Some("2024-06-22")),
+            "booking": { "selectedSeats": { "outbound": ["12A"], "return": ["14C"] } }
+        })),
+        ["/search/returnDate", "/booking/selectedSeats/return"]
+    );
+}
+
+// ── Typed view ────────────────────────────────────────────────────────────────
+
+/// Captured payloads projected into a [`JourneyView`] read back as a typed
+/// [`FlightBookingSchema`]; groups not yet captured stay `None`.
+#[test]
+fn journey_view_reads_back_as_typed_booking() {
+    let id = Uuid::new_v4();
+    let search = json!({
+        "search": {
+            "tripType": "round-trip",
+            "origin": "LHR",
+            "destination": "JFK",
+            "departureDate": "2024-06-15",
+            "returnDate": "2024-06-22",
+            "passengers": { "adults": 2, "children": 0, "infants": 0 }
+        }
+    });
+    let outbound = json!({
+        "booking": {
+            "selectedOutboundFlight": {
+                "flightId": "BA123", "airline": "British Airways",
+                "price": 450.00, "departure": "08:30", "arrival": "11:45"
+            },
+            "paymentStatus": "pending"
+        }
+    });
+    let passenger_type = json!({ "persons": { "passenger_0": { "passengerType": "adult" } } });
+
+    let mut view = JourneyView::default();
+    let events = [
+        JourneyEvent::Started {
+            id,
+            journey_type: Some("flight-booking".to_string()),
+        },
+        attrs_set(&search),
+        attrs_set(&outbound),
+        attrs_set(&passenger_type),
+    ];
+    for (sequence, payload) in (1..).zip(events) {
+        view.update(&EventEnvelope {
+            aggregate_id: id.to_string(),
+            sequence,
+            payload,
+            metadata: HashMap::default(),
+        });
+    }
+
+    let booking = FlightBookingSchema::try_from(&view).unwrap();
+
+    let criteria = booking.search.as_ref().unwrap();
+    assert!(matches!(criteria.trip_type, TripType::RoundTrip));
+    assert_eq!(criteria.origin.0, "LHR");
+    assert_eq!(criteria.return_date.as_deref(), Some("2024-06-22"));
+    assert_eq!(criteria.passengers.adults, 2);
+
+    let data = booking.booking.as_ref().unwrap();
+    let flight = data.selected_outbound_flight.as_ref().unwrap();
+    assert_eq!(flight.flight_id, "BA123");
+    assert!((flight.price - 450.00).abs() < f64::EPSILON);
+    assert!(matches!(data.payment_status, Some(PaymentStatus::Pending)));
+    assert!(data.selected_return_flight.is_none());
+    assert!(booking.search_results.is_none());
+
+    // The typed groups serialise back to the captured payloads.
+    let round_tripped = serde_json::to_value(&booking).unwrap();
+    assert_eq!(
+        round_tripped["search"]["returnDate"],
+        search["search"]["returnDate"]
+    );
+    assert_eq!(
+        round_tripped["booking"]["selectedOutboundFlight"]["flightId"],
+        outbound["booking"]["selectedOutboundFlight"]["flightId"]
+    );
+}
+
+// ── Step vocabulary ───────────────────────────────────────────────────────────
+
+/// Every action the orchestrator can suggest parses as a [`FlightStep`]; a
+/// step renamed or added in the JDM fails here until the enum catches up.
+#[test]
+fn orchestrator_actions_are_all_known_flight_steps() {
+    let engine = GoRulesDecisionEngine::new(include_str!(
+        "../jdm-models/flight-booking-orchestrator.jdm.json"
+    ));
+    let mut seen = BTreeSet::new();
+    for step in engine.list_steps() {
+        for action in &step.suggested_actions {
+            let parsed: FlightStep = action.parse().unwrap_or_else(|err| {
+                panic!("phase '{}' suggests {err}; add it to FlightStep", step.id)
+            });
+            assert_eq!(parsed.to_string(), *action);
+            seen.insert(parsed);
+        }
+    }
+    assert_eq!(seen, FlightStep::ALL.into_iter().collect());
+}
+
+#[test]
+fn suggested_steps_parses_a_workflow_decision() {
+    let decision = WorkflowDecisionView {
+        suggested_actions: vec![
+            "return_flight_selection".to_string(),
+            "flight_search_results".to_string(),
+        ],
+        phase: Some("selecting_return".to_string()),
+        ranked_actions: vec![],
+    };
+    assert_eq!(
+        suggested_steps(&decision).unwrap(),
+        vec![
+            FlightStep::ReturnFlightSelection,
+            FlightStep::FlightSearchResults
+        ]
+    );
+}
+
+#[test]
+fn suggested_steps_rejects_an_unknown_action() {
+    let decision = WorkflowDecisionView {
+        suggested_actions: vec!["passenger_details".to_string(), "upsell".to_string()],
+        phase: None,
+        ranked_actions: vec![],
+    };
+    assert_eq!(
+        suggested_steps(&decision),
+        Err(UnknownFlightStep("upsell".to_string()))
+    );
 }
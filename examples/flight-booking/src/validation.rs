@@ -0,0 +1,160 @@
+//! Trip-type rules the generated JSON Schema cannot express.
+//!
+//! The schema derived from [`FlightBookingSchema`] checks each group's shape,
+//! but not how the groups agree with each other: a round-trip search without
+//! a return date, or a one-way booking that carries a return flight, are both
+//! valid documents. [`FlightBookingValidator`] runs the schema first and then
+//! [`validate_flight_booking_requirements`] over the same data, so commands
+//! breaking these rules are rejected with the usual per-field issues.
+
+use journey_dynamics::services::schema_validator::{
+    CompletenessReport, JsonSchemaValidator, SchemaValidationError, SchemaValidator,
+    ValidationIssue,
+};
+use serde_json::Value;
+
+use crate::{FlightBookingSchema, TripType};
+
+/// Cross-field requirements of a flight booking.
+pub trait FlightBookingDataExt {
+    /// Every way the data contradicts its trip type, or `[]`.
+    ///
+    /// - A round-trip needs a `returnDate` no earlier than its
+    ///   `departureDate`, and a `selectedReturnFlight` once the booking has a
+    ///   `bookingReference`.
+    /// - A one-way trip has no `returnDate`, `selectedReturnFlight` or
+    ///   `return` seats.
+    /// - A multi-city trip is not checked.
+    fn requirement_issues(&self) -> Vec<ValidationIssue>;
+}
+
+impl FlightBookingDataExt for FlightBookingSchema {
+    fn requirement_issues(&self) -> Vec<ValidationIssue> {
+        let Some(search) = &self.search else {
+            return Vec::new();
+        };
+        let mut issues = Vec::new();
+
+        match search.trip_type {
+            TripType::RoundTrip => match &search.return_date {
+                None => issues.push(
+                    ValidationIssue::new(
+                        "/search/returnDate",
+                        "a round-trip requires a return date",
+                    )
+                    .with_code("required"),
+                ),
+                // ISO 8601 dates order as strings.
+                Some(return_date) if *return_date < search.departure_date => {
+                    issues.push(
+                        ValidationIssue::new(
+                            "/search/returnDate",
+                            "the return date is before the departure date",
+                        )
+                        .with_code("return_before_departure"),
+                    );
+                }
+                Some(_) => {}
+            },
+            TripType::OneWay => {
+                if search.return_date.is_some() {
+                    issues.push(
+                        ValidationIssue::new(
+                            "/search/returnDate",
+                            "a one-way trip has no return date",
+                        )
+                        .with_code("not_allowed"),
+                    );
+                }
+            }
+            TripType::MultiCity => {}
+        }
+
+        let Some(booking) = &self.booking else {
+            return issues;
+        };
+        match search.trip_type {
+            TripType::RoundTrip => {
+                if booking.booking_reference.is_some() && booking.selected_return_flight.is_none() {
+                    issues.push(
+                        ValidationIssue::new(
+                            "/booking/selectedReturnFlight",
+                            "a booked round-trip requires a return flight",
+                        )
+                        .with_code("required"),
+                    );
+                }
+            }
+            TripType::OneWay => {
+                if booking.selected_return_flight.is_some() {
+                    issues.push(
+                        ValidationIssue::new(
+                            "/booking/selectedReturnFlight",
+                            "a one-way trip has no return flight",
+                        )
+                        .with_code("not_allowed"),
+                    );
+                }
+                let return_seats = booking
+                    .selected_seats
+                    .as_ref()
+                    .and_then(|seats| seats.return_seats.as_ref());
+                if return_seats.is_some() {
+                    issues.push(
+                        ValidationIssue::new(
+                            "/booking/selectedSeats/return",
+                            "a one-way trip has no return seats",
+                        )
+                        .with_code("not_allowed"),
+                    );
+                }
+            }
+            TripType::MultiCity => {}
+        }
+        issues
+    }
+}
+
+/// Check `data` against the trip-type rules of [`FlightBookingDataExt`].
+///
+/// Data that does not deserialise into a [`FlightBookingSchema`] passes: its
+/// shape is the JSON Schema's to report.
+///
+/// # Errors
+///
+/// Returns [`SchemaValidationError::ValidationFailed`] with one issue per
+/// broken rule.
+pub fn validate_flight_booking_requirements(data: &Value) -> Result<(), SchemaValidationError> {
+    let Ok(booking) = serde_json::from_value::<FlightBookingSchema>(data.clone()) else {
+        return Ok(());
+    };
+    let issues = booking.requirement_issues();
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(SchemaValidationError::ValidationFailed(issues))
+    }
+}
+
+/// The flight-booking JSON Schema, followed by the trip-type rules.
+pub struct FlightBookingValidator {
+    schema: JsonSchemaValidator,
+}
+
+impl FlightBookingValidator {
+    #[must_use]
+    pub const fn new(schema: JsonSchemaValidator) -> Self {
+        Self { schema }
+    }
+}
+
+impl SchemaValidator for FlightBookingValidator {
+    fn validate(&self, data: &Value) -> Result<(), SchemaValidationError> {
+        self.schema.validate(data)?;
+        validate_flight_booking_requirements(data)
+    }
+
+    fn completeness(&self, step: &str, data: &Value) -> Option<CompletenessReport> {
+        self.schema.completeness(step, data)
+    }
+}
@@ -0,0 +1,10 @@
+ALTER TABLE journey_view
+    DROP COLUMN paused_duration_ms,
+    DROP COLUMN resumed_at,
+    DROP COLUMN paused_at;
+
+UPDATE journey_view SET state = 'InProgress' WHERE state = 'Paused';
+ALTER TABLE journey_view DROP CONSTRAINT journey_view_state_check;
+ALTER TABLE journey_view
+    ADD CONSTRAINT journey_view_state_check
+        CHECK (state IN ('InProgress', 'Complete'));
@@ -0,0 +1,10 @@
+-- Pause/Resume: allow the Paused state and record pause timestamps.
+ALTER TABLE journey_view DROP CONSTRAINT journey_view_state_check;
+ALTER TABLE journey_view
+    ADD CONSTRAINT journey_view_state_check
+        CHECK (state IN ('InProgress', 'Paused', 'Complete'));
+
+ALTER TABLE journey_view
+    ADD COLUMN paused_at          TIMESTAMPTZ,
+    ADD COLUMN resumed_at         TIMESTAMPTZ,
+    ADD COLUMN paused_duration_ms BIGINT NOT NULL DEFAULT 0 CHECK (paused_duration_ms >= 0);
@@ -0,0 +1,14 @@
+-- Projection timestamps now come from the event's own time (envelope
+-- metadata) rather than the time the projection ran, so store them with a
+-- time zone. Existing values were written with CURRENT_TIMESTAMP by a UTC
+-- session and are interpreted as UTC.
+ALTER TABLE journey_view
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC',
+    ALTER COLUMN updated_at TYPE TIMESTAMPTZ USING updated_at AT TIME ZONE 'UTC';
+
+ALTER TABLE journey_person
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC',
+    ALTER COLUMN updated_at TYPE TIMESTAMPTZ USING updated_at AT TIME ZONE 'UTC';
+
+ALTER TABLE journey_workflow_decision
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC';
@@ -0,0 +1,2 @@
+ALTER TABLE journey_view
+    DROP COLUMN forgotten;
@@ -0,0 +1,3 @@
+-- Journey-level Forget: keep the skeletal row, flag it as redacted.
+ALTER TABLE journey_view
+    ADD COLUMN forgotten BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1 @@
+ALTER TABLE journey_workflow_decision DROP COLUMN ranked_actions;
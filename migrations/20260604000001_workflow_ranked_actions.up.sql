@@ -0,0 +1,3 @@
+-- Weighted suggested actions, highest first: [{"action": ..., "weight": ...}].
+ALTER TABLE journey_workflow_decision
+    ADD COLUMN ranked_actions JSONB NOT NULL DEFAULT '[]'::jsonb;
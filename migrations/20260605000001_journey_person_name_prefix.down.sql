@@ -0,0 +1 @@
+DROP INDEX idx_journey_person_name_prefix;
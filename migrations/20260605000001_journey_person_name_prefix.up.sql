@@ -0,0 +1,3 @@
+-- Case-insensitive name prefix search (find_persons_by_name).
+CREATE INDEX idx_journey_person_name_prefix
+    ON journey_person (lower(name) text_pattern_ops);
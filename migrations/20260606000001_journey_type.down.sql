@@ -0,0 +1,4 @@
+DROP INDEX idx_journey_view_journey_type;
+
+ALTER TABLE journey_view
+    DROP COLUMN journey_type;
@@ -0,0 +1,6 @@
+-- The journey type given at Start, for filtering journeys by kind.
+ALTER TABLE journey_view
+    ADD COLUMN journey_type TEXT;
+
+CREATE INDEX idx_journey_view_journey_type
+    ON journey_view (journey_type);
@@ -0,0 +1,3 @@
+DROP INDEX idx_journey_step_history_to_step;
+
+DROP TABLE journey_step_history;
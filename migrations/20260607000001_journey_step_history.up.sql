@@ -0,0 +1,26 @@
+-- Every step a journey has entered, one row per StepProgressed event, so a
+-- visit survives the journey moving on. Feeds funnel analytics.
+CREATE TABLE journey_step_history
+(
+    journey_id UUID        NOT NULL REFERENCES journey_view (id) ON DELETE CASCADE,
+    sequence   BIGINT      NOT NULL CHECK (sequence >= 0),
+    from_step  TEXT,
+    to_step    TEXT        NOT NULL,
+    entered_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (journey_id, sequence)
+);
+
+CREATE INDEX idx_journey_step_history_to_step
+    ON journey_step_history (to_step);
+
+-- Backfill from the event store for journeys already projected.
+INSERT INTO journey_step_history (journey_id, sequence, from_step, to_step, entered_at)
+SELECT v.id,
+       e.sequence,
+       e.payload -> 'StepProgressed' ->> 'from_step',
+       e.payload -> 'StepProgressed' ->> 'to_step',
+       COALESCE(e.timestamp, CURRENT_TIMESTAMP)
+FROM   events e
+JOIN   journey_view v ON v.id::text = e.aggregate_id
+WHERE  e.aggregate_type = 'Journey'
+  AND  e.event_type = 'StepProgressed';
@@ -0,0 +1 @@
+ALTER TABLE journey_view DROP COLUMN completed_at;
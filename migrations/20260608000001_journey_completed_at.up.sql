@@ -0,0 +1,11 @@
+-- When a journey completed, so analytics can measure time to complete and
+-- the dwell on the final step.
+ALTER TABLE journey_view ADD COLUMN completed_at TIMESTAMPTZ;
+
+-- Backfill from the event store for journeys already projected.
+UPDATE journey_view v
+SET    completed_at = COALESCE(e.timestamp, v.updated_at)
+FROM   events e
+WHERE  e.aggregate_type = 'Journey'
+  AND  e.aggregate_id = v.id::text
+  AND  e.event_type = 'Completed';
@@ -0,0 +1,3 @@
+DROP INDEX idx_idempotency_key_claimed_at;
+
+DROP TABLE idempotency_key;
@@ -0,0 +1,15 @@
+-- Idempotency keys sent with commands, scoped per journey. A NULL status
+-- marks a command still executing; once it succeeds its response is kept so
+-- a repeat of the key replays it.
+CREATE TABLE idempotency_key
+(
+    journey_id UUID        NOT NULL,
+    key        TEXT        NOT NULL,
+    status     SMALLINT,
+    location   TEXT,
+    claimed_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (journey_id, key)
+);
+
+CREATE INDEX idx_idempotency_key_claimed_at
+    ON idempotency_key (claimed_at);
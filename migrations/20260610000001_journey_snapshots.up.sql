@@ -0,0 +1,13 @@
+-- Aggregate snapshots, written every JOURNEY_SNAPSHOT_INTERVAL events when it
+-- is set. The payload is the decrypted aggregate, so shredding a subject
+-- deletes the snapshots of their journeys. One row per aggregate: each
+-- snapshot replaces the last.
+CREATE TABLE snapshots
+(
+    aggregate_type   TEXT                                 NOT NULL,
+    aggregate_id     TEXT                                 NOT NULL,
+    last_sequence    BIGINT CHECK (last_sequence >= 0)    NOT NULL,
+    current_snapshot BIGINT CHECK (current_snapshot >= 0) NOT NULL,
+    payload          JSON                                 NOT NULL,
+    PRIMARY KEY (aggregate_type, aggregate_id)
+);
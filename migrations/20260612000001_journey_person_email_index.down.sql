@@ -0,0 +1,2 @@
+DROP INDEX idx_journey_person_email_index;
+ALTER TABLE journey_person DROP COLUMN email_index;
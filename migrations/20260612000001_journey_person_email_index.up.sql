@@ -0,0 +1,6 @@
+-- Blind index of each person's email, so find_by_email can match rows whose
+-- identity fields are encrypted. NULL for rows written without a cipher.
+ALTER TABLE journey_person ADD COLUMN email_index BYTEA;
+
+CREATE INDEX idx_journey_person_email_index
+    ON journey_person (email_index);
@@ -0,0 +1,5 @@
+DROP INDEX idx_journey_command_rejections_journey_id;
+
+DROP INDEX idx_journey_command_rejections_error_code;
+
+DROP TABLE journey_command_rejections;
@@ -0,0 +1,17 @@
+-- Commands the journey aggregate rejected, one row per rejection, with the
+-- error's stable code for counting and its message for context.
+CREATE TABLE journey_command_rejections
+(
+    id           BIGSERIAL PRIMARY KEY,
+    journey_id   UUID        NOT NULL,
+    command_type TEXT        NOT NULL,
+    error_code   TEXT        NOT NULL,
+    detail       TEXT        NOT NULL,
+    at           TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_journey_command_rejections_error_code
+    ON journey_command_rejections (error_code);
+
+CREATE INDEX idx_journey_command_rejections_journey_id
+    ON journey_command_rejections (journey_id);
@@ -0,0 +1 @@
+ALTER TABLE journey_view DROP COLUMN draft_data;
@@ -0,0 +1,3 @@
+-- Uncommitted data saved by SaveDraft, keyed by step. A step's draft is
+-- removed once the step is captured.
+ALTER TABLE journey_view ADD COLUMN draft_data JSONB NOT NULL DEFAULT '{}'::jsonb;
@@ -0,0 +1,3 @@
+DROP INDEX idx_journey_command_log_journey_id;
+
+DROP TABLE journey_command_log;
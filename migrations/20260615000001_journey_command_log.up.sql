@@ -0,0 +1,16 @@
+-- Commands the journey aggregate accepted, one row per command, recorded by
+-- the command log projection for auditing: who ran which command on which
+-- step, and the sequences of the events it produced.
+CREATE TABLE journey_command_log
+(
+    id           BIGSERIAL PRIMARY KEY,
+    journey_id   UUID        NOT NULL,
+    command_type TEXT,
+    step         TEXT,
+    actor        TEXT,
+    at           TIMESTAMPTZ NOT NULL,
+    sequences    BIGINT[]    NOT NULL
+);
+
+CREATE INDEX idx_journey_command_log_journey_id
+    ON journey_command_log (journey_id, id);
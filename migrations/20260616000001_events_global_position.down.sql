@@ -0,0 +1,3 @@
+DROP INDEX idx_events_global_position;
+
+ALTER TABLE events DROP COLUMN global_position;
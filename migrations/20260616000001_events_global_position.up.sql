@@ -0,0 +1,7 @@
+-- A cursor across every aggregate's events, increasing in insertion order,
+-- for consumers tailing the whole event log with `GET /events`. Existing
+-- events are numbered as the column is added.
+ALTER TABLE events ADD COLUMN global_position BIGSERIAL;
+
+CREATE UNIQUE INDEX idx_events_global_position
+    ON events (global_position);
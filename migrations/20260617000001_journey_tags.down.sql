@@ -0,0 +1,3 @@
+DROP INDEX idx_journey_tags_key_value;
+
+DROP TABLE journey_tags;
@@ -0,0 +1,12 @@
+-- Ops metadata set by the Tag command, one row per key, for segmenting
+-- journeys without touching their data.
+CREATE TABLE journey_tags
+(
+    journey_id UUID NOT NULL REFERENCES journey_view (id) ON DELETE CASCADE,
+    key        TEXT NOT NULL,
+    value      TEXT NOT NULL,
+    PRIMARY KEY (journey_id, key)
+);
+
+CREATE INDEX idx_journey_tags_key_value
+    ON journey_tags (key, value);
@@ -0,0 +1,2 @@
+-- The purged snapshots cannot be restored; journeys load from their events.
+SELECT 1;
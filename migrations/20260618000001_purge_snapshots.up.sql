@@ -0,0 +1,4 @@
+-- Snapshots held the decrypted aggregate, out of reach of crypto-shredding, so
+-- they are no longer written. Remove any left behind. The table stays so that
+-- shredding can keep deleting from it.
+DELETE FROM snapshots;
@@ -0,0 +1,5 @@
+-- A digest of the request that claimed each idempotency key, so a repeat of
+-- the key with another request is refused. Keys claimed before this column
+-- existed have none and match any request.
+ALTER TABLE idempotency_key
+    ADD COLUMN fingerprint TEXT;